@@ -27,6 +27,7 @@ fn setup_env_with_token() -> (Env, PaymentEscrowContractClient<'static>, Address
         issuer: admin.clone(),
     };
     client.add_supported_asset(&admin, &asset);
+    client.set_asset_token(&admin, &asset, &token_id);
 
     (env, client, admin, token_id)
 }
@@ -136,7 +137,7 @@ fn bench_kyc_checks(c: &mut Criterion) {
             || {
                 let (env, client, admin, _token) = setup_env_with_token();
                 let oracle = Address::generate(&env);
-                let _ = client.configure_kyc(&admin, &oracle, &true);
+                let _ = client.configure_kyc(&admin, &oracle, &true, &86400);
                 let sender = Address::generate(&env);
                 let recipient = Address::generate(&env);
                 let asset = Asset {
@@ -82,6 +82,7 @@ fn bench_batch_create_escrows(c: &mut Criterion) {
                             amount: 100,
                             asset: asset.clone(),
                             expiration_timestamp: 10000,
+                            client_ref: None,
                         });
                     }
                     env.ledger().with_mut(|li| li.timestamp = 5000);
@@ -118,6 +119,7 @@ fn bench_batch_deposit(c: &mut Criterion) {
                             amount: 100,
                             asset: asset.clone(),
                             expiration_timestamp: 10000,
+                            client_ref: None,
                         });
                     }
                     env.ledger().with_mut(|li| li.timestamp = 5000);
@@ -160,6 +162,7 @@ fn bench_batch_release(c: &mut Criterion) {
                             amount: 100,
                             asset: asset.clone(),
                             expiration_timestamp: 10000,
+                            client_ref: None,
                         });
                     }
                     env.ledger().with_mut(|li| li.timestamp = 5000);
@@ -28,6 +28,7 @@ pub enum AmlStatus {
     Flagged,
     Reviewing,
     Cleared,
+    Rejected,
 }
 
 #[derive(Clone)]
@@ -86,12 +87,15 @@ impl MockAmlOracleContract {
     }
 }
 
+/// Amounts above `amount_threshold` are escalated for review whatever their
+/// score; a zero threshold disables amount-based screening.
 pub fn screen_transaction(
     env: &Env,
     config: &AmlConfig,
     sender: &Address,
     recipient: &Address,
     amount: i128,
+    amount_threshold: i128,
 ) -> Result<AmlScreeningResult, AmlError> {
     if !config.enabled {
         return Ok(AmlScreeningResult {
@@ -106,8 +110,12 @@ pub fn screen_transaction(
 
     let risk_score = query_aml_oracle(env, &config.oracle_address, sender, recipient, amount)?;
 
-    let status = if risk_score > config.risk_threshold {
+    let high_risk = risk_score > config.risk_threshold;
+    let high_amount = amount_threshold > 0 && amount > amount_threshold;
+    let status = if high_risk {
         AmlStatus::Flagged
+    } else if high_amount {
+        AmlStatus::Reviewing
     } else {
         AmlStatus::Clear
     };
@@ -122,6 +130,21 @@ pub fn screen_transaction(
     })
 }
 
+/// Ask a remittance hub whether `sender` currently has an open AML flag.
+pub fn is_sender_flagged(
+    env: &Env,
+    hub_address: &Address,
+    sender: &Address,
+) -> Result<bool, AmlError> {
+    let func = Symbol::new(env, "is_sender_flagged");
+    let args: Vec<Val> = Vec::from_array(env, [sender.into_val(env)]);
+
+    match env.try_invoke_contract::<bool, InvokeError>(hub_address, &func, args) {
+        Ok(Ok(flagged)) => Ok(flagged),
+        _ => Err(AmlError::OracleUnavailable),
+    }
+}
+
 fn query_aml_oracle(
     env: &Env,
     oracle_address: &Address,
@@ -210,7 +233,7 @@ mod test {
             enabled: true,
         };
 
-        let result = screen_transaction(&env, &config, &sender, &recipient, 1000).unwrap();
+        let result = screen_transaction(&env, &config, &sender, &recipient, 1000, 0).unwrap();
         assert_eq!(result.risk_score, 20);
         assert_eq!(result.status, AmlStatus::Clear);
     }
@@ -240,7 +263,7 @@ mod test {
             enabled: true,
         };
 
-        let result = screen_transaction(&env, &config, &sender, &recipient, 5000).unwrap();
+        let result = screen_transaction(&env, &config, &sender, &recipient, 5000, 0).unwrap();
         assert_eq!(result.risk_score, 80);
         assert_eq!(result.status, AmlStatus::Flagged);
     }
@@ -264,7 +287,7 @@ mod test {
             enabled: false,
         };
 
-        let result = screen_transaction(&env, &config, &sender, &recipient, 1000).unwrap();
+        let result = screen_transaction(&env, &config, &sender, &recipient, 1000, 0).unwrap();
         assert_eq!(result.risk_score, 0);
         assert_eq!(result.status, AmlStatus::Clear);
     }
@@ -289,7 +312,7 @@ mod test {
             enabled: true,
         };
 
-        let result = screen_transaction(&env, &config, &sender, &recipient, 1000);
+        let result = screen_transaction(&env, &config, &sender, &recipient, 1000, 0);
         assert_eq!(result, Err(AmlError::OracleUnavailable));
     }
 
@@ -319,7 +342,7 @@ mod test {
             enabled: true,
         };
 
-        let result = screen_transaction(&env, &config, &sender, &recipient, 1000).unwrap();
+        let result = screen_transaction(&env, &config, &sender, &recipient, 1000, 0).unwrap();
         assert_eq!(result.risk_score, 90);
         assert_eq!(result.status, AmlStatus::Flagged);
     }
@@ -349,8 +372,50 @@ mod test {
             enabled: true,
         };
 
-        let result = screen_transaction(&env, &config, &sender, &recipient, 1000).unwrap();
+        let result = screen_transaction(&env, &config, &sender, &recipient, 1000, 0).unwrap();
         assert_eq!(result.risk_score, 50);
         assert_eq!(result.status, AmlStatus::Clear);
     }
+
+    fn screen_with_amount_threshold(score: u32, amount: i128) -> AmlStatus {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let oracle_id = env.register_contract(None, MockAmlOracleContract);
+        let oracle_client = MockAmlOracleContractClient::new(&env, &oracle_id);
+        let admin = Address::generate(&env);
+        oracle_client.initialize(&admin);
+
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        oracle_client.set_risk_score(&admin, &sender, &score);
+
+        let config = AmlConfig {
+            admin,
+            oracle_address: oracle_id,
+            risk_threshold: 50,
+            enabled: true,
+        };
+
+        screen_transaction(&env, &config, &sender, &recipient, amount, 10_000)
+            .unwrap()
+            .status
+    }
+
+    #[test]
+    fn test_screen_low_amount_high_score_is_flagged() {
+        assert_eq!(screen_with_amount_threshold(80, 500), AmlStatus::Flagged);
+    }
+
+    #[test]
+    fn test_screen_high_amount_low_score_is_reviewed() {
+        assert_eq!(screen_with_amount_threshold(20, 50_000), AmlStatus::Reviewing);
+        // At the threshold itself nothing is escalated
+        assert_eq!(screen_with_amount_threshold(20, 10_000), AmlStatus::Clear);
+    }
+
+    #[test]
+    fn test_screen_high_amount_high_score_is_flagged() {
+        assert_eq!(screen_with_amount_threshold(80, 50_000), AmlStatus::Flagged);
+    }
 }
@@ -0,0 +1,172 @@
+use soroban_sdk::{contracttype, Env, String};
+
+/// Error enum a numeric code belongs to; codes overlap between enums.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[contracttype]
+pub enum ErrorDomain {
+    /// `payment_escrow::Error`
+    Escrow,
+    /// `remittance_hub::RemittanceError`
+    Remittance,
+    /// `oracle::OracleError`
+    Oracle,
+    /// `aml::AmlError`
+    Aml,
+    /// `kyc::KycError`
+    Kyc,
+}
+
+const UNKNOWN: &str = "Unknown error code.";
+
+/// Human-readable description of an error code, so clients can report
+/// failures without keeping their own copy of each error enum.
+pub fn describe_error(env: &Env, domain: ErrorDomain, code: u32) -> String {
+    let description = match domain {
+        ErrorDomain::Escrow => describe_escrow(code),
+        ErrorDomain::Remittance => describe_remittance(code),
+        ErrorDomain::Oracle => describe_oracle(code),
+        ErrorDomain::Aml => describe_aml(code),
+        ErrorDomain::Kyc => describe_kyc(code),
+    };
+    String::from_str(env, description)
+}
+
+fn describe_escrow(code: u32) -> &'static str {
+    match code {
+        1 => "Amount must be greater than zero.",
+        2 => "Sender and recipient must be different addresses.",
+        3 => "Escrow counter overflowed `u64`.",
+        4 => "The requested escrow id does not exist.",
+        5 => "Operation is not valid for the escrow’s current status.",
+        6 => "Escrow must be approved before this action.",
+        7 => "Escrow is expired (current timestamp is past expiration).",
+        8 => "Caller is not authorized to perform this action.",
+        9 => "Escrow has already been released.",
+        10 => "Escrow is not yet expired.",
+        11 => "Caller is not the escrow sender.",
+        12 => "Escrow must be pending/fundable for this action.",
+        13 => "Asset is not supported or invalid.",
+        14 => "Provided amount is insufficient for the requested operation.",
+        15 => "Escrow is already funded.",
+        16 => "Deposited amount overflowed `i128`.",
+        17 => "Release/refund conditions are not satisfied.",
+        18 => "Caller is not allowed for this method.",
+        19 => "Not enough funds are available in escrow.",
+        20 => "Token does not match the one registered for the escrow's asset.",
+        21 => "Fee percentage must be within bounds (0–10000 bps).",
+        22 => "Partial release is disabled for this escrow.",
+        23 => "Arithmetic overflow/underflow occurred.",
+        24 => "Escrow has already been refunded.",
+        25 => "Refund is not authorized for the caller.",
+        26 => "No remaining funds are available for release/refund.",
+        27 => "Refund amount is invalid (<= 0 or exceeds available).",
+        28 => "Preimage does not hash to the escrow's hash lock.",
+        29 => "Condition index is past the end of the escrow's condition list.",
+        30 => "Current timestamp has not reached the required time.",
+        31 => "No condition template or category rule set is registered under the given name.",
+        32 => "Total fees exceed or equal the escrow amount.",
+        33 => "Approval already exists for this escrow/approver.",
+        34 => "Multi-party quorum has not been met.",
+        35 => "Approver is not whitelisted for multi-party approval.",
+        36 => "Approval window has expired.",
+        37 => "Escrow is finalized and cannot be modified.",
+        38 => "Approval record was not found.",
+        39 => "KYC checks failed for one or more parties.",
+        40 => "KYC has not been configured.",
+        41 => "Too many KYC proof checks for this account in the current window.",
+        42 => "Dispute already exists for this escrow.",
+        43 => "Caller is not an arbitrator for this dispute.",
+        44 => "Dispute record was not found.",
+        45 => "Voter has already voted on this dispute.",
+        46 => "Contract is paused (upgradeable pause flag set).",
+        47 => "Rate limit exceeded for this caller/function.",
+        48 => "Escrow is non-compliant with registered rules.",
+        49 => "No admin transfer has been proposed.",
+        50 => "Amount exceeds the limit of the sender's KYC tier.",
+        _ => UNKNOWN,
+    }
+}
+
+fn describe_remittance(code: u32) -> &'static str {
+    match code {
+        1 => "Amount must be greater than zero.",
+        2 => "The requested remittance/escrow record was not found.",
+        3 => "Operation is not valid for the current status.",
+        4 => "Due date must not be in the past.",
+        5 => "Expected an escrow mapping but none exists.",
+        6 => "Invoice id does not exist.",
+        7 => "Invoice status does not allow this operation.",
+        8 => "Caller is not authorized to perform this action.",
+        9 => "Oracle config has not been set.",
+        10 => "Oracle response timed out or exceeded staleness.",
+        11 => "Oracle returned an invalid rate.",
+        12 => "Asset is not supported.",
+        13 => "Oracle rate is stale.",
+        14 => "Currency conversion failed.",
+        15 => "Rate limit exceeded for this caller/function.",
+        16 => "Contract has already been initialized.",
+        17 => "AML screening flagged a high-risk result.",
+        18 => "AML oracle call failed.",
+        19 => "AML configuration is missing.",
+        20 => "AML flag record was not found.",
+        21 => "Batch size exceeds maximum allowed.",
+        22 => "Duplicate escrow id encountered.",
+        23 => "Remittance is still within its post-clearance cooling-off period.",
+        24 => "Remittance amount exceeds the configured per-transaction cap.",
+        25 => "Asset pair is already in the supported-pair registry.",
+        26 => "Invoice and remittance do not describe the same payment.",
+        32 => "Contract is paused (upgradeable pause flag set).",
+        33 => "Metric type is invalid or unsupported.",
+        34 => "Primary and secondary oracle rates diverge beyond the allowed deviation.",
+        35 => "Recurring invoice is not yet due for its next issue.",
+        36 => "Escrow asset or amount does not match the remittance it backs.",
+        _ => UNKNOWN,
+    }
+}
+
+fn describe_oracle(code: u32) -> &'static str {
+    match code {
+        1 => "Oracle has not been configured.",
+        2 => "Oracle response is too old or timed out.",
+        3 => "Oracle returned an invalid rate.",
+        4 => "Asset pair is not supported.",
+        5 => "Cached oracle rate is stale.",
+        6 => "Caller is not authorized.",
+        7 => "Rate limit exceeded for oracle queries.",
+        8 => "Conversion arithmetic overflowed.",
+        9 => "Amount must be greater than zero.",
+        10 => "Fallback oracle path failed.",
+        11 => "From/to asset are the same (no conversion needed).",
+        12 => "Primary and secondary oracle rates diverge beyond the allowed deviation.",
+        _ => UNKNOWN,
+    }
+}
+
+fn describe_aml(code: u32) -> &'static str {
+    match code {
+        1 => "AML module has not been configured.",
+        2 => "AML oracle is unavailable or invocation failed.",
+        3 => "Screening result exceeded configured risk threshold.",
+        4 => "Caller is not authorized.",
+        5 => "Risk threshold is invalid.",
+        6 => "Requested AML flag record was not found.",
+        _ => UNKNOWN,
+    }
+}
+
+fn describe_kyc(code: u32) -> &'static str {
+    match code {
+        1 => "KYC module has not been configured.",
+        2 => "Account is not verified under the configured KYC policy.",
+        3 => "Provided proof/signature is invalid.",
+        4 => "KYC oracle is unavailable or invocation failed.",
+        5 => "Caller is not authorized.",
+        6 => "Account is already verified.",
+        7 => "Proof has expired.",
+        8 => "Issuer is not trusted/valid.",
+        9 => "Rate limit exceeded.",
+        10 => "Account is suspended.",
+        11 => "KYC module is already configured.",
+        _ => UNKNOWN,
+    }
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, symbol_short, Address, Env, String, Symbol};
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, Env, String, Symbol};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[contracttype]
@@ -13,8 +13,8 @@ pub enum EventData {
     EscrowCreated(u64, Address, Address, AssetRef, i128),
     EscrowDeposited(u64, i128, i128),
     EscrowApproved(u64),
-    EscrowReleased(u64, i128),
-    EscrowRefunded(u64, i128),
+    EscrowReleased(u64, i128, Address),
+    EscrowRefunded(u64, i128, Address),
     EscrowExtended(u64, u64),
     InvoiceCreated(u64, u64, Address, Address, AssetRef, i128, i128),
     InvoicePaid(u64, u64, i128),
@@ -24,6 +24,9 @@ pub enum EventData {
     AdminAction(Symbol),
     AddressAction(Symbol, Address),
     PairAction(Symbol, Address, Address),
+    ValueChanged(Symbol, i128, i128),
+    FeeCollected(u64, i128, Address, Symbol),
+    PreimageRevealed(u64, Bytes),
 }
 
 #[cfg_attr(not(test), derive(Clone, Debug, PartialEq, Eq))]
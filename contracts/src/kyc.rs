@@ -1,6 +1,7 @@
+use ed25519_dalek::{Signature, VerifyingKey};
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, Address, BytesN, Env, IntoVal,
-    InvokeError, Symbol, Val, Vec,
+    contract, contracterror, contractimpl, contracttype, xdr::ToXdr, Address, Bytes, BytesN, Env,
+    IntoVal, InvokeError, Symbol, Val, Vec,
 };
 
 #[contracterror]
@@ -74,10 +75,14 @@ pub struct KycResult {
 pub enum KycDataKey {
     Config,
     Whitelist(Address),
+    /// ed25519 public key of a trusted proof issuer.
     TrustedIssuer(Address),
     IssuerKey(Address),
     RevokedProof(BytesN<64>),
+    /// `(window_start, count)` of proof checks made for an account.
     CheckCount(Address),
+    /// `(max_checks, window_seconds)` applied to `CheckCount`.
+    CheckLimit,
 }
 
 #[contract]
@@ -189,18 +194,37 @@ fn check_via_oracle(
     })
 }
 
+/// The message an issuer signs to attest `account`:
+/// `account xdr ++ issuer xdr ++ expiry (big-endian u64)`.
+pub fn proof_message(env: &Env, account: &Address, issuer: &Address, expiry: u64) -> Bytes {
+    let mut message = account.clone().to_xdr(env);
+    message.append(&issuer.clone().to_xdr(env));
+    message.extend_from_array(&expiry.to_be_bytes());
+    message
+}
+
+/// Upper bound on `proof_message` length: two address XDRs plus the expiry.
+const MAX_PROOF_MESSAGE_LEN: usize = 128;
+
+/// Checks an issuer-signed KYC proof for `account` valid until `expiry`
+/// (0 = no expiry). The signature is verified in-contract against the
+/// issuer's registered ed25519 key rather than with the host's
+/// `ed25519_verify`, which aborts the whole invocation on a bad signature;
+/// a mismatch comes back as `InvalidProof` so callers can keep state such
+/// as the attempt counter.
 pub fn verify_proof(
     env: &Env,
-    _account: &Address,
+    account: &Address,
     proof_signature: &BytesN<64>,
     trusted_issuer: &Address,
-    _proof_validity_period: u64,
-) -> Result<bool, KycError> {
+    expiry: u64,
+) -> Result<(), KycError> {
     let issuer_key = KycDataKey::TrustedIssuer(trusted_issuer.clone());
-    let is_trusted: bool = env.storage().persistent().get(&issuer_key).unwrap_or(false);
-    if !is_trusted {
-        return Err(KycError::InvalidIssuer);
-    }
+    let public_key: BytesN<32> = env
+        .storage()
+        .persistent()
+        .get(&issuer_key)
+        .ok_or(KycError::InvalidIssuer)?;
 
     let revoked_key = KycDataKey::RevokedProof(proof_signature.clone());
     let revoked: bool = env
@@ -212,30 +236,48 @@ pub fn verify_proof(
         return Err(KycError::InvalidProof);
     }
 
-    let all_zero = proof_signature.iter().all(|b| b == 0);
-    if all_zero {
-        return Err(KycError::InvalidProof);
+    if expiry != 0 && env.ledger().timestamp() > expiry {
+        return Err(KycError::ProofExpired);
     }
 
-    if _proof_validity_period > 0 {
-        let issued_at = proof_signature
-            .iter()
-            .take(8)
-            .enumerate()
-            .fold(0u64, |acc, (i, b)| acc | ((b as u64) << (8 * i)));
-
-        if issued_at != 0 {
-            let current_time = env.ledger().timestamp();
-            let expires_at = issued_at
-                .checked_add(_proof_validity_period)
-                .ok_or(KycError::InvalidProof)?;
-            if current_time > expires_at {
-                return Err(KycError::ProofExpired);
-            }
-        }
+    let message = proof_message(env, account, trusted_issuer, expiry);
+    let len = message.len() as usize;
+    if len > MAX_PROOF_MESSAGE_LEN {
+        return Err(KycError::InvalidProof);
     }
+    let mut buf = [0u8; MAX_PROOF_MESSAGE_LEN];
+    message.copy_into_slice(&mut buf[..len]);
+
+    let key = VerifyingKey::from_bytes(&public_key.to_array())
+        .map_err(|_| KycError::InvalidIssuer)?;
+    let signature = Signature::from_bytes(&proof_signature.to_array());
+    key.verify_strict(&buf[..len], &signature)
+        .map_err(|_| KycError::InvalidProof)
+}
 
-    Ok(true)
+/// Counts a proof check for `account`, failing with `RateLimited` once more
+/// than the configured number of checks fall in the current window. The
+/// window restarts after `window_seconds`. Unlimited when no limit is set.
+pub fn record_proof_check(env: &Env, account: &Address) -> Result<(), KycError> {
+    let limit: Option<(u32, u64)> = env.storage().persistent().get(&KycDataKey::CheckLimit);
+    let (max_checks, window) = match limit {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+
+    let key = KycDataKey::CheckCount(account.clone());
+    let now = env.ledger().timestamp();
+    let (window_start, count): (u64, u32) = match env.storage().persistent().get(&key) {
+        Some((start, count)) if now.saturating_sub(start) < window => (start, count),
+        _ => (now, 0),
+    };
+    if count >= max_checks {
+        return Err(KycError::RateLimited);
+    }
+    env.storage()
+        .persistent()
+        .set(&key, &(window_start, count + 1));
+    Ok(())
 }
 
 pub fn revoke_proof(
@@ -523,6 +565,22 @@ mod test {
         assert_eq!(result, Err(KycError::OracleUnavailable));
     }
 
+    fn sign_proof(
+        env: &Env,
+        signing_key: &ed25519_dalek::SigningKey,
+        account: &Address,
+        issuer: &Address,
+        expiry: u64,
+    ) -> BytesN<64> {
+        use ed25519_dalek::Signer;
+
+        let message = proof_message(env, account, issuer, expiry);
+        let mut buf = [0u8; 256];
+        let len = message.len() as usize;
+        message.copy_into_slice(&mut buf[..len]);
+        BytesN::from_array(env, &signing_key.sign(&buf[..len]).to_bytes())
+    }
+
     #[test]
     fn test_verify_proof_invalid_issuer() {
         let env = Env::default();
@@ -534,28 +592,33 @@ mod test {
         let sig = BytesN::from_array(&env, &[1u8; 64]);
 
         env.as_contract(&contract_id, || {
-            let result = verify_proof(&env, &account, &sig, &untrusted_issuer, 86400);
+            let result = verify_proof(&env, &account, &sig, &untrusted_issuer, 0);
             assert_eq!(result, Err(KycError::InvalidIssuer));
         });
     }
 
     #[test]
-    fn test_verify_proof_all_zeros() {
+    fn test_verify_proof_expired() {
         let env = Env::default();
         env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 5000;
+        });
 
         let contract_id = env.register_contract(None, MockKycOracleContract);
         let account = Address::generate(&env);
         let issuer = Address::generate(&env);
-        let sig = BytesN::from_array(&env, &[0u8; 64]);
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+        let sig = sign_proof(&env, &signing_key, &account, &issuer, 4000);
 
         env.as_contract(&contract_id, || {
             env.storage()
                 .persistent()
-                .set(&KycDataKey::TrustedIssuer(issuer.clone()), &true);
+                .set(&KycDataKey::TrustedIssuer(issuer.clone()), &public_key);
 
-            let result = verify_proof(&env, &account, &sig, &issuer, 86400);
-            assert_eq!(result, Err(KycError::InvalidProof));
+            let result = verify_proof(&env, &account, &sig, &issuer, 4000);
+            assert_eq!(result, Err(KycError::ProofExpired));
         });
     }
 
@@ -567,15 +630,41 @@ mod test {
         let contract_id = env.register_contract(None, MockKycOracleContract);
         let account = Address::generate(&env);
         let issuer = Address::generate(&env);
-        let sig = BytesN::from_array(&env, &[1u8; 64]);
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+        let sig = sign_proof(&env, &signing_key, &account, &issuer, 0);
+
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .persistent()
+                .set(&KycDataKey::TrustedIssuer(issuer.clone()), &public_key);
+
+            let result = verify_proof(&env, &account, &sig, &issuer, 0);
+            assert_eq!(result, Ok(()));
+        });
+    }
+
+    #[test]
+    fn test_verify_proof_tampered_signature() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, MockKycOracleContract);
+        let account = Address::generate(&env);
+        let issuer = Address::generate(&env);
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(&[7u8; 32]);
+        let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+        let mut sig = sign_proof(&env, &signing_key, &account, &issuer, 0).to_array();
+        sig[0] ^= 0xff;
+        let sig = BytesN::from_array(&env, &sig);
 
         env.as_contract(&contract_id, || {
             env.storage()
                 .persistent()
-                .set(&KycDataKey::TrustedIssuer(issuer.clone()), &true);
+                .set(&KycDataKey::TrustedIssuer(issuer.clone()), &public_key);
 
-            let result = verify_proof(&env, &account, &sig, &issuer, 86400);
-            assert_eq!(result, Ok(true));
+            let result = verify_proof(&env, &account, &sig, &issuer, 0);
+            assert_eq!(result, Err(KycError::InvalidProof));
         });
     }
 
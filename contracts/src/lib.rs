@@ -1,6 +1,7 @@
 #![no_std]
 
 pub mod aml;
+pub mod errors;
 pub mod events;
 pub mod kyc;
 pub mod oracle;
@@ -1,6 +1,6 @@
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, IntoVal,
-    InvokeError, String, Symbol, Val, Vec,
+    InvokeError, Map, String, Symbol, Val, Vec,
 };
 
 #[contracterror]
@@ -29,6 +29,8 @@ pub enum OracleError {
     FallbackFailed = 10,
     /// From/to asset are the same (no conversion needed).
     SameAsset = 11,
+    /// Primary and secondary oracle rates diverge beyond the allowed deviation.
+    RateDeviation = 12,
 }
 
 #[derive(Clone)]
@@ -42,6 +44,20 @@ pub struct OracleConfig {
     pub last_query_ledger: u64,
 }
 
+/// An oracle to query together with the precision its rates are quoted in.
+pub struct OracleFeed {
+    pub address: Address,
+    pub rate_decimals: Option<u32>,
+}
+
+/// The two feeds cross-checked by `get_conversion_rate_median`, and the
+/// largest gap, in basis points of their mean, tolerated between them.
+pub struct MedianSources {
+    pub primary: OracleFeed,
+    pub secondary: OracleFeed,
+    pub max_deviation_bps: i128,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[contracttype]
 pub struct ConversionResult {
@@ -51,6 +67,9 @@ pub struct ConversionResult {
     pub from_asset: String,
     pub to_asset: String,
     pub timestamp: u64,
+    /// How the rate was derived: `same`, `direct`, `inverse`, `triang`
+    /// (through USD), `cache`, or `median`.
+    pub path: Symbol,
 }
 
 #[derive(Clone)]
@@ -69,9 +88,18 @@ pub enum OracleDataKey {
     Config,
     CachedRate(String, String),
     SupportedPair(String, String),
+    RateHistory(String, String),
 }
 
 const RATE_PRECISION: i128 = 1_000_000_000_000_000_000; // 18 decimal places
+pub const RATE_DECIMALS: u32 = 18;
+/// Samples kept per pair in the rate history ring buffer.
+pub const RATE_HISTORY_SIZE: u32 = 16;
+/// Intermediate asset used to triangulate pairs without a direct or inverse rate.
+const TRIANGULATION_ASSET: &str = "USD";
+/// Rates further than this from the unweighted median are dropped before
+/// weighting, unless a band is configured.
+pub const DEFAULT_OUTLIER_BAND_BPS: i128 = 500;
 
 #[contract]
 pub struct MockOracleContract;
@@ -138,6 +166,32 @@ pub fn get_conversion_rate(
     amount: i128,
     max_staleness: u64,
     cached_rate: Option<CachedRate>,
+) -> Result<ConversionResult, OracleError> {
+    let feed = OracleFeed {
+        address: oracle_address.clone(),
+        rate_decimals: None,
+    };
+    convert_with_feed(
+        env,
+        &feed,
+        from_asset,
+        to_asset,
+        amount,
+        max_staleness,
+        cached_rate,
+    )
+}
+
+/// Like `get_conversion_rate`, but rescales the feed's rate to
+/// `RATE_PRECISION` when its decimal precision is known.
+pub fn convert_with_feed(
+    env: &Env,
+    feed: &OracleFeed,
+    from_asset: &String,
+    to_asset: &String,
+    amount: i128,
+    max_staleness: u64,
+    cached_rate: Option<CachedRate>,
 ) -> Result<ConversionResult, OracleError> {
     if amount <= 0 {
         return Err(OracleError::InvalidAmount);
@@ -151,14 +205,12 @@ pub fn get_conversion_rate(
             from_asset: from_asset.clone(),
             to_asset: to_asset.clone(),
             timestamp: env.ledger().timestamp(),
+            path: symbol_short!("same"),
         });
     }
 
-    let oracle_result = query_oracle(env, oracle_address, from_asset, to_asset);
-
-    match oracle_result {
-        Ok(rate_data) => {
-            validate_rate(&rate_data, env.ledger().timestamp(), max_staleness)?;
+    match resolve_rate(env, feed, from_asset, to_asset, max_staleness)? {
+        Some((rate_data, path)) => {
             let converted = apply_conversion(amount, rate_data.rate, rate_data.denominator)?;
 
             env.events().publish(
@@ -178,9 +230,10 @@ pub fn get_conversion_rate(
                 from_asset: from_asset.clone(),
                 to_asset: to_asset.clone(),
                 timestamp: env.ledger().timestamp(),
+                path,
             })
         }
-        Err(_) => match cached_rate {
+        None => match cached_rate {
             Some(ref cache) => {
                 validate_rate(cache, env.ledger().timestamp(), max_staleness)?;
                 let converted = apply_conversion(amount, cache.rate, cache.denominator)?;
@@ -197,6 +250,7 @@ pub fn get_conversion_rate(
                     from_asset: from_asset.clone(),
                     to_asset: to_asset.clone(),
                     timestamp: cache.timestamp,
+                    path: symbol_short!("cache"),
                 })
             }
             None => Err(OracleError::FallbackFailed),
@@ -204,6 +258,369 @@ pub fn get_conversion_rate(
     }
 }
 
+/// Cross-checks the primary and secondary oracles. When both return a fresh
+/// rate the conversion uses their mean, unless they sit more than
+/// `sources.max_deviation_bps` apart, which yields `RateDeviation`. A single
+/// healthy feed is used on its own, and the cache is the last resort.
+pub fn get_conversion_rate_median(
+    env: &Env,
+    sources: &MedianSources,
+    from_asset: &String,
+    to_asset: &String,
+    amount: i128,
+    max_staleness: u64,
+    cached_rate: Option<CachedRate>,
+) -> Result<ConversionResult, OracleError> {
+    if amount <= 0 {
+        return Err(OracleError::InvalidAmount);
+    }
+    if from_asset == to_asset {
+        return convert_with_feed(
+            env,
+            &sources.primary,
+            from_asset,
+            to_asset,
+            amount,
+            max_staleness,
+            None,
+        );
+    }
+
+    let primary = fetch_scaled_rate(env, &sources.primary, from_asset, to_asset, max_staleness);
+    let secondary = fetch_scaled_rate(env, &sources.secondary, from_asset, to_asset, max_staleness);
+
+    let rate = match (primary, secondary) {
+        (Some(primary), Some(secondary)) => {
+            let mean = (primary + secondary) / 2;
+            let deviation = (primary - secondary)
+                .abs()
+                .checked_mul(10_000)
+                .ok_or(OracleError::ConversionOverflow)?;
+            let limit = mean
+                .checked_mul(sources.max_deviation_bps)
+                .ok_or(OracleError::ConversionOverflow)?;
+            if deviation > limit {
+                return Err(OracleError::RateDeviation);
+            }
+            mean
+        }
+        (Some(rate), None) | (None, Some(rate)) => rate,
+        (None, None) => {
+            let cache = cached_rate.ok_or(OracleError::FallbackFailed)?;
+            validate_rate(&cache, env.ledger().timestamp(), max_staleness)?;
+            let converted = apply_conversion(amount, cache.rate, cache.denominator)?;
+            return Ok(ConversionResult {
+                converted_amount: converted,
+                rate: cache.rate,
+                denominator: cache.denominator,
+                from_asset: from_asset.clone(),
+                to_asset: to_asset.clone(),
+                timestamp: cache.timestamp,
+                path: symbol_short!("cache"),
+            });
+        }
+    };
+
+    let converted = apply_conversion(amount, rate, RATE_PRECISION)?;
+    env.events().publish(
+        (symbol_short!("conv"), symbol_short!("median")),
+        (from_asset.clone(), to_asset.clone(), rate, converted),
+    );
+
+    Ok(ConversionResult {
+        converted_amount: converted,
+        rate,
+        denominator: RATE_PRECISION,
+        from_asset: from_asset.clone(),
+        to_asset: to_asset.clone(),
+        timestamp: env.ledger().timestamp(),
+        path: symbol_short!("median"),
+    })
+}
+
+/// Finds a rate for `from_asset -> to_asset` on `feed`: the direct quote if
+/// the oracle has one, else the inverse of `to -> from`, else the product of
+/// `from -> USD` and `USD -> to`. Returns `None` when no path exists.
+fn resolve_rate(
+    env: &Env,
+    feed: &OracleFeed,
+    from_asset: &String,
+    to_asset: &String,
+    max_staleness: u64,
+) -> Result<Option<(CachedRate, Symbol)>, OracleError> {
+    if let Some(direct) = fetch_leg(env, feed, from_asset, to_asset, max_staleness)? {
+        return Ok(Some((direct, symbol_short!("direct"))));
+    }
+
+    if let Some(mut inverse) = fetch_leg(env, feed, to_asset, from_asset, max_staleness)? {
+        inverse.rate = inverse
+            .denominator
+            .checked_mul(RATE_PRECISION)
+            .ok_or(OracleError::ConversionOverflow)?
+            .checked_div(inverse.rate)
+            .ok_or(OracleError::ConversionOverflow)?;
+        inverse.denominator = RATE_PRECISION;
+        return Ok(Some((inverse, symbol_short!("inverse"))));
+    }
+
+    let usd = String::from_str(env, TRIANGULATION_ASSET);
+    if from_asset == &usd || to_asset == &usd {
+        return Ok(None);
+    }
+    let first = match fetch_leg(env, feed, from_asset, &usd, max_staleness)? {
+        Some(leg) => leg,
+        None => return Ok(None),
+    };
+    let second = match fetch_leg(env, feed, &usd, to_asset, max_staleness)? {
+        Some(leg) => leg,
+        None => return Ok(None),
+    };
+    let rate = first
+        .rate
+        .checked_mul(RATE_PRECISION)
+        .ok_or(OracleError::ConversionOverflow)?
+        .checked_div(first.denominator)
+        .ok_or(OracleError::ConversionOverflow)?
+        .checked_mul(second.rate)
+        .ok_or(OracleError::ConversionOverflow)?
+        .checked_div(second.denominator)
+        .ok_or(OracleError::ConversionOverflow)?;
+
+    Ok(Some((
+        CachedRate {
+            rate,
+            denominator: RATE_PRECISION,
+            timestamp: first.timestamp.min(second.timestamp),
+            from_asset: from_asset.clone(),
+            to_asset: to_asset.clone(),
+        },
+        symbol_short!("triang"),
+    )))
+}
+
+/// Queries a single pair from `feed`, validated and normalized. A feed that
+/// does not quote the pair yields `None`; a quote that is stale or invalid is
+/// an error, as it would be for a direct conversion.
+fn fetch_leg(
+    env: &Env,
+    feed: &OracleFeed,
+    from_asset: &String,
+    to_asset: &String,
+    max_staleness: u64,
+) -> Result<Option<CachedRate>, OracleError> {
+    let rate_data = match query_oracle(env, &feed.address, from_asset, to_asset) {
+        Ok(rate_data) => rate_data,
+        Err(_) => return Ok(None),
+    };
+    validate_rate(&rate_data, env.ledger().timestamp(), max_staleness)?;
+    match feed.rate_decimals {
+        Some(decimals) => normalize_rate(rate_data, decimals).map(Some),
+        None => Ok(Some(rate_data)),
+    }
+}
+
+/// Appends `cached` to its pair's rate history, dropping the oldest sample
+/// once `RATE_HISTORY_SIZE` is reached. Re-storing the latest sample is a no-op.
+pub fn record_rate_sample(env: &Env, cached: &CachedRate) {
+    let key = OracleDataKey::RateHistory(cached.from_asset.clone(), cached.to_asset.clone());
+    let mut history: Vec<(i128, i128, u64)> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+
+    let sample = (cached.rate, cached.denominator, cached.timestamp);
+    if history.last() == Some(sample) {
+        return;
+    }
+    if history.len() >= RATE_HISTORY_SIZE {
+        history.pop_front();
+    }
+    history.push_back(sample);
+    env.storage().persistent().set(&key, &history);
+}
+
+/// Recorded `(rate, denominator, timestamp)` samples for a pair, oldest first.
+pub fn get_rate_history(
+    env: &Env,
+    from_asset: &String,
+    to_asset: &String,
+) -> Vec<(i128, i128, u64)> {
+    env.storage()
+        .persistent()
+        .get(&OracleDataKey::RateHistory(
+            from_asset.clone(),
+            to_asset.clone(),
+        ))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Average of the pair's recorded rates from the last `window` seconds, scaled
+/// to `RATE_PRECISION`. Fails with `StaleRate` if no sample is recent enough.
+pub fn get_twap(
+    env: &Env,
+    from_asset: &String,
+    to_asset: &String,
+    window: u64,
+) -> Result<i128, OracleError> {
+    let cutoff = env.ledger().timestamp().saturating_sub(window);
+
+    let mut total: i128 = 0;
+    let mut count: i128 = 0;
+    for (rate, denominator, timestamp) in get_rate_history(env, from_asset, to_asset).iter() {
+        if timestamp < cutoff {
+            continue;
+        }
+        let scaled = rate
+            .checked_mul(RATE_PRECISION)
+            .ok_or(OracleError::ConversionOverflow)?
+            .checked_div(denominator)
+            .ok_or(OracleError::InvalidRate)?;
+        total = total
+            .checked_add(scaled)
+            .ok_or(OracleError::ConversionOverflow)?;
+        count += 1;
+    }
+
+    if count == 0 {
+        return Err(OracleError::StaleRate);
+    }
+    Ok(total / count)
+}
+
+/// Builds the feed descriptor for `oracle_address` from `rate_decimals`, the
+/// implied decimal places configured per oracle.
+pub fn feed_for(rate_decimals: &Map<Address, u32>, oracle_address: &Address) -> OracleFeed {
+    OracleFeed {
+        address: oracle_address.clone(),
+        rate_decimals: rate_decimals.get(oracle_address.clone()),
+    }
+}
+
+/// Rescales a rate carrying `decimals` implied places to `RATE_PRECISION`, so
+/// rates from feeds of different precision are directly comparable.
+fn normalize_rate(mut rate_data: CachedRate, decimals: u32) -> Result<CachedRate, OracleError> {
+    if decimals > RATE_DECIMALS {
+        return Err(OracleError::InvalidRate);
+    }
+    rate_data.rate = rate_data
+        .rate
+        .checked_mul(10i128.pow(RATE_DECIMALS - decimals))
+        .ok_or(OracleError::ConversionOverflow)?;
+    rate_data.denominator = RATE_PRECISION;
+    Ok(rate_data)
+}
+
+/// Queries every oracle in `trust_weights`, which maps each to its trust
+/// weight, and returns their consensus rate, scaled to `RATE_PRECISION`.
+/// Unreachable and stale feeds are skipped; see `weighted_median` for how the
+/// remaining rates are combined.
+pub fn aggregate_rate(
+    env: &Env,
+    rate_decimals: &Map<Address, u32>,
+    trust_weights: &Map<Address, u32>,
+    from_asset: &String,
+    to_asset: &String,
+    max_staleness: u64,
+    band_bps: i128,
+) -> Result<i128, OracleError> {
+    if trust_weights.is_empty() {
+        return Err(OracleError::OracleNotConfigured);
+    }
+
+    let mut samples: Vec<(i128, u32)> = Vec::new(env);
+    for (oracle, weight) in trust_weights.iter() {
+        if let Some(rate) = fetch_scaled_rate(
+            env,
+            &feed_for(rate_decimals, &oracle),
+            from_asset,
+            to_asset,
+            max_staleness,
+        ) {
+            samples.push_back((rate, weight));
+        }
+    }
+
+    weighted_median(env, &samples, band_bps)
+}
+
+/// Queries one oracle and returns its rate scaled to `RATE_PRECISION`, or
+/// `None` when the feed is unreachable, stale or invalid.
+fn fetch_scaled_rate(
+    env: &Env,
+    feed: &OracleFeed,
+    from_asset: &String,
+    to_asset: &String,
+    max_staleness: u64,
+) -> Option<i128> {
+    let rate_data = query_oracle(env, &feed.address, from_asset, to_asset).ok()?;
+    validate_rate(&rate_data, env.ledger().timestamp(), max_staleness).ok()?;
+    match feed.rate_decimals {
+        Some(decimals) => normalize_rate(rate_data, decimals).ok().map(|r| r.rate),
+        None => rate_data
+            .rate
+            .checked_mul(RATE_PRECISION)?
+            .checked_div(rate_data.denominator),
+    }
+}
+
+/// Weighted median of `(rate, weight)` samples. Rates deviating more than
+/// `band_bps` from the plain median are rejected first, so a single bad feed
+/// cannot drag the result however much it is trusted.
+pub fn weighted_median(
+    env: &Env,
+    samples: &Vec<(i128, u32)>,
+    band_bps: i128,
+) -> Result<i128, OracleError> {
+    // Insertion sort by rate; aggregation sets are a handful of feeds.
+    let mut sorted: Vec<(i128, u32)> = Vec::new(env);
+    for sample in samples.iter() {
+        if sample.1 == 0 {
+            continue;
+        }
+        let mut index = sorted.len();
+        while index > 0 && sorted.get(index - 1).unwrap().0 > sample.0 {
+            index -= 1;
+        }
+        sorted.insert(index, sample);
+    }
+    if sorted.is_empty() {
+        return Err(OracleError::FallbackFailed);
+    }
+
+    let len = sorted.len();
+    let center = if len % 2 == 1 {
+        sorted.get(len / 2).unwrap().0
+    } else {
+        (sorted.get(len / 2 - 1).unwrap().0 + sorted.get(len / 2).unwrap().0) / 2
+    };
+
+    let mut total_weight: u64 = 0;
+    let mut kept: Vec<(i128, u32)> = Vec::new(env);
+    for (rate, weight) in sorted.iter() {
+        let deviation = (rate - center)
+            .abs()
+            .checked_mul(10_000)
+            .ok_or(OracleError::ConversionOverflow)?;
+        let limit = center
+            .checked_mul(band_bps)
+            .ok_or(OracleError::ConversionOverflow)?;
+        if deviation <= limit {
+            total_weight += weight as u64;
+            kept.push_back((rate, weight));
+        }
+    }
+
+    let mut cumulative: u64 = 0;
+    for (rate, weight) in kept.iter() {
+        cumulative += weight as u64;
+        if cumulative * 2 >= total_weight {
+            return Ok(rate);
+        }
+    }
+    Err(OracleError::FallbackFailed)
+}
+
 fn query_oracle(
     env: &Env,
     oracle_address: &Address,
@@ -317,6 +734,27 @@ mod test {
         assert_eq!(result, Err(OracleError::InvalidAmount));
     }
 
+    #[test]
+    fn test_weighted_median_rejects_outlier() {
+        let env = Env::default();
+        let samples = Vec::from_array(&env, [(100, 1), (101, 1), (500, 10)]);
+        // 500 sits far outside the 5% band around the median of 101.
+        assert_eq!(weighted_median(&env, &samples, 500), Ok(100));
+    }
+
+    #[test]
+    fn test_weighted_median_follows_trusted_sources() {
+        let env = Env::default();
+        let samples = Vec::from_array(&env, [(100, 1), (102, 1), (103, 5)]);
+        assert_eq!(weighted_median(&env, &samples, 500), Ok(103));
+
+        let empty: Vec<(i128, u32)> = Vec::from_array(&env, [(100, 0)]);
+        assert_eq!(
+            weighted_median(&env, &empty, 500),
+            Err(OracleError::FallbackFailed)
+        );
+    }
+
     #[test]
     fn test_apply_conversion_math() {
         let result = apply_conversion(1000, 920000, 1000000).unwrap();
@@ -424,4 +862,140 @@ mod test {
         assert_eq!(conversion.converted_amount, 910);
         assert_eq!(conversion.rate, 910000);
     }
+
+    fn median_setup(env: &Env, primary_rate: i128, secondary_rate: i128) -> MedianSources {
+        let admin = Address::generate(env);
+        let from = String::from_str(env, "USDC");
+        let to = String::from_str(env, "EUR");
+
+        let mut oracles = [
+            (
+                env.register_contract(None, MockOracleContract),
+                primary_rate,
+            ),
+            (
+                env.register_contract(None, MockOracleContract),
+                secondary_rate,
+            ),
+        ];
+        for (oracle_id, rate) in oracles.iter_mut() {
+            let client = MockOracleContractClient::new(env, oracle_id);
+            client.init_oracle(&admin);
+            client.set_rate(&admin, &from, &to, rate, &1000000);
+        }
+
+        MedianSources {
+            primary: OracleFeed {
+                address: oracles[0].0.clone(),
+                rate_decimals: None,
+            },
+            secondary: OracleFeed {
+                address: oracles[1].0.clone(),
+                rate_decimals: None,
+            },
+            max_deviation_bps: 200,
+        }
+    }
+
+    #[test]
+    fn test_median_pricing_oracles_agree() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let sources = median_setup(&env, 920000, 920000);
+        let from = String::from_str(&env, "USDC");
+        let to = String::from_str(&env, "EUR");
+
+        let result =
+            get_conversion_rate_median(&env, &sources, &from, &to, 1000, 3600, None).unwrap();
+        assert_eq!(result.converted_amount, 920);
+        assert_eq!(result.denominator, RATE_PRECISION);
+    }
+
+    #[test]
+    fn test_median_pricing_uses_mean_within_deviation() {
+        let env = Env::default();
+        env.mock_all_auths();
+        // 1% apart, inside the 2% tolerance
+        let sources = median_setup(&env, 900000, 909000);
+        let from = String::from_str(&env, "USDC");
+        let to = String::from_str(&env, "EUR");
+
+        let result =
+            get_conversion_rate_median(&env, &sources, &from, &to, 10000, 3600, None).unwrap();
+        assert_eq!(result.rate, 904_500_000_000_000_000);
+        assert_eq!(result.converted_amount, 9045);
+    }
+
+    #[test]
+    fn test_median_pricing_rejects_divergent_oracles() {
+        let env = Env::default();
+        env.mock_all_auths();
+        // 10% apart, well beyond the 2% tolerance
+        let sources = median_setup(&env, 900000, 990000);
+        let from = String::from_str(&env, "USDC");
+        let to = String::from_str(&env, "EUR");
+
+        let result = get_conversion_rate_median(&env, &sources, &from, &to, 1000, 3600, None);
+        assert_eq!(result, Err(OracleError::RateDeviation));
+    }
+
+    #[test]
+    fn test_conversion_path_direct_and_inverse() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let oracle_id = env.register_contract(None, MockOracleContract);
+        let oracle_client = MockOracleContractClient::new(&env, &oracle_id);
+        let admin = Address::generate(&env);
+        oracle_client.init_oracle(&admin);
+
+        let usdc = String::from_str(&env, "USDC");
+        let eur = String::from_str(&env, "EUR");
+        oracle_client.set_rate(&admin, &usdc, &eur, &800000, &1000000);
+
+        let direct = get_conversion_rate(&env, &oracle_id, &usdc, &eur, 1000, 3600, None).unwrap();
+        assert_eq!(direct.path, symbol_short!("direct"));
+        assert_eq!(direct.converted_amount, 800);
+
+        // Only USDC->EUR is quoted, so EUR->USDC is derived as 1 / 0.8
+        let inverse = get_conversion_rate(&env, &oracle_id, &eur, &usdc, 800, 3600, None).unwrap();
+        assert_eq!(inverse.path, symbol_short!("inverse"));
+        assert_eq!(inverse.rate, 1_250_000_000_000_000_000);
+        assert_eq!(inverse.denominator, RATE_PRECISION);
+        assert_eq!(inverse.converted_amount, 1000);
+    }
+
+    #[test]
+    fn test_conversion_path_triangulated_through_usd() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let oracle_id = env.register_contract(None, MockOracleContract);
+        let oracle_client = MockOracleContractClient::new(&env, &oracle_id);
+        let admin = Address::generate(&env);
+        oracle_client.init_oracle(&admin);
+
+        let eur = String::from_str(&env, "EUR");
+        let usd = String::from_str(&env, "USD");
+        let kes = String::from_str(&env, "KES");
+        // 1 EUR = 1.1 USD, 1 USD = 130 KES
+        oracle_client.set_rate(&admin, &eur, &usd, &1100000, &1000000);
+        oracle_client.set_rate(&admin, &usd, &kes, &130000000, &1000000);
+
+        let result = get_conversion_rate(&env, &oracle_id, &eur, &kes, 100, 3600, None).unwrap();
+        assert_eq!(result.path, symbol_short!("triang"));
+        assert_eq!(result.rate, 143 * RATE_PRECISION);
+        assert_eq!(result.converted_amount, 14300);
+
+        // No leg to GBP at all: falls through to FallbackFailed
+        let gbp = String::from_str(&env, "GBP");
+        let result = get_conversion_rate(&env, &oracle_id, &eur, &gbp, 100, 3600, None);
+        assert_eq!(result, Err(OracleError::FallbackFailed));
+    }
 }
@@ -1,11 +1,15 @@
+use crate::aml::{self, AmlConfig, AmlStatus};
+use crate::errors::{self, ErrorDomain};
 use crate::events::{self, AssetRef, EventData};
-use crate::kyc::{self, KycConfig, KycDataKey, KycRecord, KycStatus};
+use crate::kyc::{self, KycConfig, KycDataKey, KycError, KycRecord, KycStatus};
+use crate::oracle;
 use crate::rate_limit::{self, FunctionType};
+use crate::remittance_hub::{Invoice as HubInvoice, InvoiceStatus};
 use crate::upgradeable;
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, BytesN, Env,
-    Map, String, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, token, Address, Bytes,
+    BytesN, Env, IntoVal, InvokeError, Map, String, Symbol, Val, Vec,
 };
 
 #[contracterror]
@@ -50,8 +54,10 @@ pub enum Error {
     UnauthorizedCaller = 18,
     /// Not enough funds are available in escrow.
     InsufficientFunds = 19,
-    /// Currency conversion failed.
-    ConversionFailed = 20,
+    /// Token does not match the one registered for the escrow's asset.
+    /// Takes over code 20, which was reserved for currency conversion but
+    /// never returned by this contract.
+    TokenMismatch = 20,
     /// Fee percentage must be within bounds (0–10000 bps).
     InvalidFeePercentage = 21,
     /// Partial release is disabled for this escrow.
@@ -66,14 +72,19 @@ pub enum Error {
     NoFundsAvailable = 26,
     /// Refund amount is invalid (<= 0 or exceeds available).
     InvalidRefundAmount = 27,
-    /// Provided signature does not match expected signer.
-    SignatureMismatch = 28,
-    /// Oracle call or validation failed.
-    OracleFailure = 29,
+    /// Preimage does not hash to the escrow's hash lock. Takes over code
+    /// 28, which was reserved for signature checks but never returned.
+    HashMismatch = 28,
+    /// Condition index is past the end of the escrow's condition list.
+    /// Takes over code 29, which was reserved for oracle failures but never
+    /// returned.
+    ConditionIndexOutOfRange = 29,
     /// Current timestamp has not reached the required time.
     TimestampNotReached = 30,
-    /// Approval is required before continuing.
-    ApprovalRequired = 31,
+    /// No condition template or category rule set is registered under the
+    /// given name. Takes over code 31, which was reserved for approvals but
+    /// never returned.
+    TemplateNotFound = 31,
     /// Total fees exceed or equal the escrow amount.
     FeeExceedsAmount = 32,
     /// Approval already exists for this escrow/approver.
@@ -92,8 +103,10 @@ pub enum Error {
     KycFailed = 39,
     /// KYC has not been configured.
     KycNotConfigured = 40,
-    /// KYC proof/signature is required but missing.
-    KycProofRequired = 41,
+    /// Too many KYC proof checks for this account in the current window.
+    /// Takes over code 41, which was reserved for missing proofs but never
+    /// returned.
+    KycRateLimited = 41,
     /// Dispute already exists for this escrow.
     AlreadyDisputed = 42,
     /// Caller is not an arbitrator for this dispute.
@@ -108,6 +121,10 @@ pub enum Error {
     RateLimitExceeded = 47,
     /// Escrow is non-compliant with registered rules.
     NonCompliant = 48,
+    /// No admin transfer has been proposed.
+    NoPendingAdmin = 49,
+    /// Amount exceeds the limit of the sender's KYC tier.
+    KycLimitExceeded = 50,
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -141,6 +158,10 @@ pub enum ConditionType {
     OraclePrice,
     MultiSignature,
     KYCVerified,
+    RecipientAck,
+    GeoAllowed,
+    MinDeposited,
+    AmlClear,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -194,6 +215,8 @@ pub enum FeeType {
 pub struct FeeBreakdown {
     pub platform_fee: i128,
     pub forex_fee: i128,
+    /// Spread charged only when the payout is converted to another asset.
+    pub conversion_fee: i128,
     pub compliance_fee: i128,
     pub network_fee: i128,
     pub total_fee: i128,
@@ -210,6 +233,16 @@ pub struct FeeStructure {
     pub max_fee: i128,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct EscrowCreateRequest {
+    pub recipient: Address,
+    pub amount: i128,
+    pub asset: Asset,
+    pub expiration_timestamp: u64,
+    pub memo: String,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[contracttype]
 pub struct Milestone {
@@ -270,6 +303,20 @@ pub struct EscrowAnalytics {
     pub last_updated: u64,
 }
 
+/// Number of escrows currently in each status.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct EscrowStats {
+    pub pending: u64,
+    pub funded: u64,
+    pub approved: u64,
+    pub released: u64,
+    pub refunded: u64,
+    pub expired: u64,
+    pub disputed: u64,
+    pub cancelled: u64,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[contracttype]
 pub struct Condition {
@@ -277,6 +324,18 @@ pub struct Condition {
     pub required: bool,
     pub verified: bool,
     pub threshold_value: i128,
+    /// Ledger timestamp of the last successful verification.
+    pub verified_at: u64,
+    /// Seconds a verification stays valid without fresh proof; 0 requires
+    /// every verification to pass on its own.
+    pub validity_period: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct ConditionTemplate {
+    pub conditions: Vec<Condition>,
+    pub operator: ConditionOperator,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -373,6 +432,13 @@ pub struct Escrow {
     pub kyc_compliant: bool,
     pub compliant: bool,
     pub milestones: Vec<Milestone>,
+    /// When non-empty, only these addresses may fund the escrow.
+    pub allowed_funding_sources: Vec<Address>,
+    /// Incremented on every state change so clients can detect missed updates.
+    pub sequence: u64,
+    /// Requires both the recipient's acknowledgment and the sender's release
+    /// authorization before funds move.
+    pub two_sided_release: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -383,6 +449,9 @@ pub struct MultiPartyConfig {
     pub whitelisted_approvers: Vec<Address>,
     pub approvals: Map<Address, bool>,
     pub finalized: bool,
+    /// When non-zero, quorum is `ceil(whitelist_len * bps / 10000)` instead of
+    /// `required_approvals`.
+    pub required_fraction_bps: u32,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -448,6 +517,38 @@ pub struct RecurringEscrow {
     pub created_at: u64,
 }
 
+/// Converts collected fees into a single treasury asset. The converted amount
+/// is paid from `reserve` (which must approve this contract) and the original
+/// fee is handed to `reserve` in exchange.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct FeeConversionConfig {
+    pub oracle: Address,
+    pub treasury_token: Address,
+    pub treasury_asset: String,
+    pub reserve: Address,
+    pub max_staleness: u64,
+}
+
+/// Oracle and liquidity reserve used to pay recipients in their preferred
+/// asset. The reserve must approve this contract for the payout tokens.
+/// `tokens` maps each asset code the reserve pays out to its token contract;
+/// only those assets can be chosen as a preference.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct PayoutConfig {
+    pub oracle: Address,
+    pub reserve: Address,
+    pub max_staleness: u64,
+    pub tokens: Map<String, Address>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct PayoutPreference {
+    pub asset: Asset,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[contracttype]
 pub struct RecurringPayment {
@@ -456,8 +557,104 @@ pub struct RecurringPayment {
     pub sequence: u32,
 }
 
+/// One tranche paid out by `release_partial`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct ReleaseRecord {
+    pub amount: i128,
+    pub fee: i128,
+    pub timestamp: u64,
+    pub caller: Address,
+}
+
+/// A change to one of an escrow's terms, recorded for dispute review.
+/// Recipient changes log zero for both values; the addresses are carried
+/// by the `recip_upd` event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct AmendmentRecord {
+    pub field: Symbol,
+    pub old_value: i128,
+    pub new_value: i128,
+    pub actor: Address,
+    pub timestamp: u64,
+}
+
+/// Defaults applied to escrows created in a business category.
+/// `expiration_offset` of 0 falls back to the contract-wide default.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct CategoryRules {
+    pub platform_fee_bps: i128,
+    pub kyc_required: bool,
+    pub expiration_offset: u64,
+}
+
+/// A recipient-initiated release request; `approved_at` is set once the
+/// sender approves it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct ReleaseRequest {
+    pub requested_at: u64,
+    pub approved_at: Option<u64>,
+}
+
+/// Bounty paid to keepers out of the processing fee of each escrow they
+/// sweep, capped at `max_per_sweep` per call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct KeeperBounty {
+    pub per_escrow: i128,
+    pub max_per_sweep: i128,
+}
+
+/// Share of every release, in basis points, paid into `reserve` to back
+/// guaranteed-settlement claims.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct InsuranceFund {
+    pub insurance_bps: i128,
+    pub reserve: Address,
+}
+
+/// Contract notified via `on_release(escrow_id, amount)` once an escrow's
+/// funds have moved. The call is only made when `enabled` is set and the
+/// contract is on the admin's callback whitelist; the reentrancy guard stays
+/// held for its duration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct ReleaseCallback {
+    pub contract: Address,
+    pub enabled: bool,
+}
+
+/// A beneficiary's weight in the platform fee split.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct FeeShare {
+    pub beneficiary: Address,
+    pub weight: u32,
+}
+
+/// Divides platform fees across `shares` in proportion to their weights.
+/// The rounding remainder goes to `shares[primary]`, so the shares always sum
+/// to the full fee.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct FeeSplit {
+    pub shares: Vec<FeeShare>,
+    pub primary: u32,
+}
+
 const MAX_HOOKS: u32 = 10;
+const MAX_FEE_BENEFICIARIES: u32 = 10;
+const MAX_BATCH_ESCROWS: u32 = 10;
+const MAX_WHITELIST_BATCH: u32 = 50;
+const MAX_RELEASE_HISTORY: u32 = 50;
+const MAX_AMENDMENT_HISTORY: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 50;
 const DEFAULT_MAX_RETRIES: u32 = 2;
+const DEFAULT_UNDERFUNDED_GRACE: u64 = 7 * 86400;
 
 #[derive(Clone)]
 #[contracttype]
@@ -493,13 +690,169 @@ pub enum DataKey {
     EscrowComplianceOverride(u64),
     UserJurisdiction(Address),
     EscrowCancellationConfig(u64),
-Recurring(u64),
-    RecurringHistory(u64),
     NotificationHooks(u64),
     NotificationHistory(u64),
     RecurringCounter,
     Recurring(u64),
     RecurringHistory(u64),
+    MinReleaseIncrement,
+    RecipientAck(u64),
+    ConditionTemplate(Symbol),
+    Tvl(String),
+    AccountRegion(Address),
+    AllowedRegions(u64),
+    KycAutoRefund(u64),
+    AmlHub,
+    FeeConversion,
+    ComplianceOfficer,
+    ConversionSpread,
+    SenderReleaseAuth(u64),
+    DefaultExpirationOffset,
+    SenderEscrows(Address),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum AssetConfigKey {
+    DustThreshold(String),
+    EscrowToken(u64),
+    AssetToken(Asset),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum PayoutKey {
+    Config,
+    Preference(u64),
+    RefundPreference(u64),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum FeeConfigKey {
+    PercentageCap,
+    FeeSplit,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum CategoryKey {
+    CategoryRules(Symbol),
+    EscrowCategory(u64),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum ApprovalKey {
+    ApprovalCooldown,
+    LastApprovalAction(u64, Address),
+    RecipientSelfApproval(u64),
+    MinApprovalInterval,
+    LastEscrowApproval(u64),
+    ApproverKycRequired(u64),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum ClaimKey {
+    HashLock(u64),
+    Preimage(u64),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum RequestKey {
+    RecipientRelease(u64),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum HistoryKey {
+    Release(u64),
+    Amendment(u64),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum KeeperKey {
+    KeeperBounty,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum ScreeningKey {
+    AmlScreening,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum InsuranceKey {
+    InsuranceFund,
+    ReserveBalance(String),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum AdminKey {
+    PendingAdmin,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum VestingKey {
+    Vesting(u64),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum ExposureKey {
+    MaxOpenEscrows,
+    OpenEscrowCount(Address),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum CallbackKey {
+    ReleaseCallback(u64),
+    AccountingHook,
+    Whitelisted(Address),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum FundingKey {
+    UnderfundedGrace,
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum InvoiceLinkKey {
+    InvoiceCancelled(u64),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum StatsKey {
+    StatusCount(EscrowStatus),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum KycTierKey {
+    TierLimit(u32),
+    /// Verification tier of a whitelisted account; tier 0 when unset.
+    ///
+    /// Kept out of `KycRecord` on purpose. A contracttype struct decodes
+    /// only if every field is present, so adding a `tier` field would make
+    /// every record written before the upgrade fail to load. That would
+    /// lock verified accounts out of `create_escrow`.
+    Tier(Address),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum KycReleaseKey {
+    RequireKycOnRelease(u64),
 }
 
 #[contract]
@@ -534,6 +887,68 @@ impl PaymentEscrowContract {
         upgradeable::init_version(&env);
     }
 
+    /// First step of an admin handover. Replaces any earlier proposal; the
+    /// current admin stays in charge until `new_admin` accepts.
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage()
+            .instance()
+            .set(&AdminKey::PendingAdmin, &new_admin);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            Symbol::new(&env, "admin_proposed"),
+            0,
+            &admin,
+            0,
+            symbol_short!("pending"),
+            EventData::AddressAction(symbol_short!("proposed"), new_admin),
+        );
+
+        Ok(())
+    }
+
+    /// Completes the handover started by `propose_admin`.
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        new_admin.require_auth();
+
+        let pending: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::PendingAdmin)
+            .ok_or(Error::NoPendingAdmin)?;
+        if new_admin != pending {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.storage().instance().remove(&AdminKey::PendingAdmin);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            Symbol::new(&env, "admin_changed"),
+            0,
+            &new_admin,
+            0,
+            symbol_short!("na"),
+            EventData::AddressAction(symbol_short!("admin"), new_admin.clone()),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&AdminKey::PendingAdmin)
+    }
+
     pub fn add_supported_asset(env: Env, admin: Address, asset: Asset) {
         admin.require_auth();
 
@@ -553,6 +968,48 @@ impl PaymentEscrowContract {
             .set(&DataKey::SupportedAssets, &assets);
     }
 
+    /// Registers the token contract that carries a supported asset. Escrows
+    /// in that asset can only be funded, and paid out, in this token.
+    pub fn set_asset_token(
+        env: Env,
+        admin: Address,
+        asset: Asset,
+        token_address: Address,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        if !Self::is_supported_asset(&env, &asset) {
+            return Err(Error::InvalidAsset);
+        }
+
+        env.storage()
+            .instance()
+            .set(&AssetConfigKey::AssetToken(asset), &token_address);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("asset_tok"),
+            0,
+            &admin,
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("asset_tok")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_asset_token(env: Env, asset: Asset) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get(&AssetConfigKey::AssetToken(asset))
+    }
+
     pub fn set_platform_fee(env: Env, admin: Address, fee_percentage: i128) -> Result<(), Error> {
         admin.require_auth();
 
@@ -564,6 +1021,12 @@ impl PaymentEscrowContract {
         if fee_percentage < 0 || fee_percentage > 10000 {
             return Err(Error::InvalidFeePercentage);
         }
+        Self::check_fee_percentage_cap(
+            &env,
+            fee_percentage,
+            Self::stored_fee_bps(&env, &DataKey::ForexFeePercentage),
+            Self::stored_fee_bps(&env, &DataKey::ConversionSpread),
+        )?;
 
         env.storage()
             .instance()
@@ -657,7 +1120,11 @@ impl PaymentEscrowContract {
         env.storage().instance().get(&DataKey::FeeWallet)
     }
 
-    pub fn set_forex_fee(env: Env, admin: Address, fee_percentage: i128) -> Result<(), Error> {
+    pub fn set_fee_conversion(
+        env: Env,
+        admin: Address,
+        config: Option<FeeConversionConfig>,
+    ) -> Result<(), Error> {
         admin.require_auth();
 
         let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
@@ -665,29 +1132,33 @@ impl PaymentEscrowContract {
             return Err(Error::Unauthorized);
         }
 
-        if fee_percentage < 0 || fee_percentage > 10000 {
-            return Err(Error::InvalidAmount);
+        match config {
+            Some(config) => env
+                .storage()
+                .instance()
+                .set(&DataKey::FeeConversion, &config),
+            None => env.storage().instance().remove(&DataKey::FeeConversion),
         }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::ForexFeePercentage, &fee_percentage);
-
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("forex_f"),
+            symbol_short!("fee_cvcfg"),
             0,
             &admin,
-            fee_percentage,
+            0,
             symbol_short!("na"),
-            EventData::AdminAction(symbol_short!("forex_f")),
+            EventData::AdminAction(symbol_short!("fee_cvcfg")),
         );
 
         Ok(())
     }
 
-    pub fn set_compliance_fee(env: Env, admin: Address, flat_fee: i128) -> Result<(), Error> {
+    pub fn get_fee_conversion(env: Env) -> Option<FeeConversionConfig> {
+        env.storage().instance().get(&DataKey::FeeConversion)
+    }
+
+    pub fn set_forex_fee(env: Env, admin: Address, fee_percentage: i128) -> Result<(), Error> {
         admin.require_auth();
 
         let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
@@ -695,34 +1166,35 @@ impl PaymentEscrowContract {
             return Err(Error::Unauthorized);
         }
 
-        if flat_fee < 0 {
+        if fee_percentage < 0 || fee_percentage > 10000 {
             return Err(Error::InvalidAmount);
         }
-
-        env.storage()
+        Self::check_fee_percentage_cap(
+            &env,
+            Self::stored_fee_bps(&env, &DataKey::PlatformFeePercentage),
+            fee_percentage,
+            Self::stored_fee_bps(&env, &DataKey::ConversionSpread),
+        )?;
+
+        env.storage()
             .instance()
-            .set(&DataKey::ComplianceFlatFee, &flat_fee);
+            .set(&DataKey::ForexFeePercentage, &fee_percentage);
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("comp_fee"),
+            symbol_short!("forex_f"),
             0,
             &admin,
-            flat_fee,
+            fee_percentage,
             symbol_short!("na"),
-            EventData::AdminAction(symbol_short!("comp_fee")),
+            EventData::AdminAction(symbol_short!("forex_f")),
         );
 
         Ok(())
     }
 
-    pub fn set_fee_limits(
-        env: Env,
-        admin: Address,
-        min_fee: i128,
-        max_fee: i128,
-    ) -> Result<(), Error> {
+    pub fn set_conversion_spread(env: Env, admin: Address, spread_bps: i128) -> Result<(), Error> {
         admin.require_auth();
 
         let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
@@ -730,224 +1202,98 @@ impl PaymentEscrowContract {
             return Err(Error::Unauthorized);
         }
 
-        if min_fee < 0 || max_fee < min_fee {
+        if !(0..=10000).contains(&spread_bps) {
             return Err(Error::InvalidAmount);
         }
+        Self::check_fee_percentage_cap(
+            &env,
+            Self::stored_fee_bps(&env, &DataKey::PlatformFeePercentage),
+            Self::stored_fee_bps(&env, &DataKey::ForexFeePercentage),
+            spread_bps,
+        )?;
 
-        env.storage().instance().set(&DataKey::MinFee, &min_fee);
-        env.storage().instance().set(&DataKey::MaxFee, &max_fee);
+        env.storage()
+            .instance()
+            .set(&DataKey::ConversionSpread, &spread_bps);
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("fee_lim"),
+            symbol_short!("conv_spr"),
             0,
             &admin,
-            0,
+            spread_bps,
             symbol_short!("na"),
-            EventData::AdminAction(symbol_short!("fee_lim")),
+            EventData::AdminAction(symbol_short!("conv_spr")),
         );
 
         Ok(())
     }
 
-    fn enforce_rate_limit(
-        env: &Env,
-        caller: &Address,
-        function_type: FunctionType,
-    ) -> Result<(), Error> {
-        let admin_opt: Option<Address> = env.storage().instance().get(&DataKey::Admin);
-        let admin = match admin_opt {
-            Some(a) => a,
-            None => return Ok(()),
-        };
-        let allowed = rate_limit::check_rate_limit(env, caller, function_type, &admin);
-        if allowed {
-            Ok(())
-        } else {
-            Err(Error::RateLimitExceeded)
-        }
-    }
-
-    fn notify_external(env: &Env, payload: NotificationPayload) {
-        let status = match payload.event_type {
-            EventType::Created => symbol_short!("created"),
-            EventType::Deposit => symbol_short!("deposit"),
-            EventType::Approved => symbol_short!("approved"),
-            EventType::Released => symbol_short!("released"),
-            EventType::PartialRelease => symbol_short!("part_rel"),
-            EventType::Refunded => symbol_short!("refunded"),
-            EventType::PartialRefund => symbol_short!("part_ref"),
-        };
-        let actor = env.current_contract_address();
-        events::emit(
-            env,
-            symbol_short!("escrow"),
-            symbol_short!("notify"),
-            payload.escrow_id,
-            &actor,
-            payload.amount,
-            status.clone(),
-            EventData::AdminAction(symbol_short!("notify")),
-        );
-
-        let hooks: Vec<NotificationConfig> = env
-            .storage()
-            .instance()
-            .get(&DataKey::NotificationHooks(payload.escrow_id))
-            .unwrap_or(Vec::new(env));
-        let mut history: Vec<NotificationDelivery> = env
-            .storage()
-            .instance()
-            .get(&DataKey::NotificationHistory(payload.escrow_id))
-            .unwrap_or(Vec::new(env));
-        for config in hooks.iter() {
-            let retries = if config.max_retries == 0 {
-                DEFAULT_MAX_RETRIES
-            } else {
-                config.max_retries
-            };
-            for url in config.webhook_urls.iter() {
-                history.push_back(NotificationDelivery {
-                    escrow_id: payload.escrow_id,
-                    event_type: payload.event_type,
-                    webhook_url: url,
-                    delivered_at: payload.timestamp,
-                    attempts: retries,
-                });
-                events::emit(
-                    env,
-                    symbol_short!("escrow"),
-                    symbol_short!("hook"),
-                    payload.escrow_id,
-                    &actor,
-                    payload.amount,
-                    status.clone(),
-                    EventData::AdminAction(symbol_short!("hook")),
-                );
-            }
-        }
+    pub fn get_conversion_spread(env: Env) -> i128 {
         env.storage()
             .instance()
-            .set(&DataKey::NotificationHistory(payload.escrow_id), &history);
+            .get(&DataKey::ConversionSpread)
+            .unwrap_or(0i128)
     }
 
-    fn is_supported_asset(env: &Env, asset: &Asset) -> bool {
-        let assets: Vec<Asset> = env
-            .storage()
-            .instance()
-            .get(&DataKey::SupportedAssets)
-            .unwrap_or(Vec::new(env));
-        for supported_asset in assets.iter() {
-            if supported_asset.code == asset.code && supported_asset.issuer == asset.issuer {
-                return true;
-            }
-        }
-        false
-    }
+    /// Caps the combined platform, forex and conversion spread percentages.
+    /// `None` removes the cap; the per-fee 100% limit still applies.
+    pub fn set_fee_percentage_cap(
+        env: Env,
+        admin: Address,
+        cap_bps: Option<i128>,
+    ) -> Result<(), Error> {
+        admin.require_auth();
 
-    fn validate_notification_config(config: &NotificationConfig) -> Result<(), Error> {
-        if config.webhook_urls.len() == 0 || config.webhook_urls.len() > MAX_HOOKS {
-            return Err(Error::InvalidAsset);
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
         }
 
-        for url in config.webhook_urls.iter() {
-            if url.len() < 8 || url.len() > 256 {
-                return Err(Error::InvalidAsset);
+        match cap_bps {
+            Some(cap) => {
+                if !(0..=10000).contains(&cap) {
+                    return Err(Error::InvalidFeePercentage);
+                }
+                // The current configuration must already fit under the new cap.
+                let total = Self::stored_fee_bps(&env, &DataKey::PlatformFeePercentage)
+                    + Self::stored_fee_bps(&env, &DataKey::ForexFeePercentage)
+                    + Self::stored_fee_bps(&env, &DataKey::ConversionSpread);
+                if total > cap {
+                    return Err(Error::InvalidFeePercentage);
+                }
+                env.storage()
+                    .instance()
+                    .set(&FeeConfigKey::PercentageCap, &cap);
             }
+            None => env
+                .storage()
+                .instance()
+                .remove(&FeeConfigKey::PercentageCap),
         }
 
-        Ok(())
-    }
-
-    fn empty_asset_amount_map(env: &Env) -> Map<Asset, i128> {
-        Map::new(env)
-    }
-
-    fn calculate_fees(env: &Env, amount: i128) -> Result<FeeBreakdown, Error> {
-        let platform_percentage = env
-            .storage()
-            .instance()
-            .get(&DataKey::PlatformFeePercentage)
-            .unwrap_or(0i128);
-        let forex_percentage = env
-            .storage()
-            .instance()
-            .get(&DataKey::ForexFeePercentage)
-            .unwrap_or(0i128);
-        let compliance_flat = env
-            .storage()
-            .instance()
-            .get(&DataKey::ComplianceFlatFee)
-            .unwrap_or(0i128);
-        let network_flat = env
-            .storage()
-            .instance()
-            .get(&DataKey::NetworkFlatFee)
-            .unwrap_or(0i128);
-
-        let platform_fee = amount
-            .checked_mul(platform_percentage)
-            .ok_or(Error::ArithmeticOverflow)?
-            .checked_div(10000)
-            .ok_or(Error::ArithmeticOverflow)?;
-
-        let forex_fee = amount
-            .checked_mul(forex_percentage)
-            .ok_or(Error::ArithmeticOverflow)?
-            .checked_div(10000)
-            .ok_or(Error::ArithmeticOverflow)?;
-
-        let mut total_fee = platform_fee
-            .checked_add(forex_fee)
-            .ok_or(Error::ArithmeticOverflow)?
-            .checked_add(compliance_flat)
-            .ok_or(Error::ArithmeticOverflow)?
-            .checked_add(network_flat)
-            .ok_or(Error::ArithmeticOverflow)?;
-
-        let min_fee = env
-            .storage()
-            .instance()
-            .get(&DataKey::MinFee)
-            .unwrap_or(0i128);
-        let max_fee = env
-            .storage()
-            .instance()
-            .get(&DataKey::MaxFee)
-            .unwrap_or(i128::MAX);
-
-        if total_fee < min_fee {
-            total_fee = min_fee;
-        }
-        if total_fee > max_fee {
-            total_fee = max_fee;
-        }
-
-        if total_fee > amount {
-            return Err(Error::FeeExceedsAmount);
-        }
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("fee_cap"),
+            0,
+            &admin,
+            cap_bps.unwrap_or(0),
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("fee_cap")),
+        );
 
-        Ok(FeeBreakdown {
-            platform_fee,
-            forex_fee,
-            compliance_fee: compliance_flat,
-            network_fee: network_flat,
-            total_fee,
-        })
+        Ok(())
     }
 
-    pub fn get_fee_breakdown(env: Env, amount: i128) -> Result<FeeBreakdown, Error> {
-        Self::calculate_fees(&env, amount)
+    pub fn get_fee_percentage_cap(env: Env) -> Option<i128> {
+        env.storage().instance().get(&FeeConfigKey::PercentageCap)
     }
 
-    pub fn configure_kyc(
-        env: Env,
-        admin: Address,
-        oracle_address: Address,
-        use_oracle: bool,
-        proof_validity_period: u64,
-    ) -> Result<(), Error> {
+    /// Splits platform fees across several beneficiaries instead of paying
+    /// them all to the admin. `None` restores the single-recipient default.
+    pub fn set_fee_split(env: Env, admin: Address, split: Option<FeeSplit>) -> Result<(), Error> {
         admin.require_auth();
 
         let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
@@ -955,115 +1301,231 @@ impl PaymentEscrowContract {
             return Err(Error::Unauthorized);
         }
 
-        let config = KycConfig {
-            admin: admin.clone(),
-            oracle_address,
-            use_oracle,
-            proof_validity_period,
-            last_check_ledger: 0,
+        let beneficiaries = match &split {
+            Some(split) => {
+                if split.shares.len() > MAX_FEE_BENEFICIARIES {
+                    return Err(Error::RateLimitExceeded);
+                }
+                if split.primary >= split.shares.len()
+                    || split.shares.iter().any(|share| share.weight == 0)
+                {
+                    return Err(Error::InvalidFeePercentage);
+                }
+                env.storage().instance().set(&FeeConfigKey::FeeSplit, split);
+                split.shares.len()
+            }
+            None => {
+                env.storage().instance().remove(&FeeConfigKey::FeeSplit);
+                0
+            }
         };
 
-        env.storage().instance().set(&DataKey::KycConfig, &config);
-        env.storage().instance().set(&DataKey::KycEnabled, &true);
-
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("kyc_cfg"),
+            symbol_short!("fee_split"),
             0,
             &admin,
-            0,
+            beneficiaries as i128,
             symbol_short!("na"),
-            EventData::AdminAction(symbol_short!("kyc_cfg")),
+            EventData::AdminAction(symbol_short!("fee_split")),
         );
 
         Ok(())
     }
 
-    pub fn register_compliance_rule(env: Env, admin: Address, rule: ComplianceRule) -> Result<(), Error> {
+    /// Caps how many unsettled escrows a sender may have open at once.
+    /// `None` removes the cap.
+    pub fn set_max_open_escrows(env: Env, admin: Address, cap: Option<u32>) -> Result<(), Error> {
         admin.require_auth();
+
         let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         if admin != stored_admin {
             return Err(Error::Unauthorized);
         }
 
-        let mut rules: Vec<ComplianceRule> = env
-            .storage()
-            .instance()
-            .get(&DataKey::ComplianceRules)
-            .unwrap_or_else(|| Vec::new(&env));
-        rules.push_back(rule);
-        env.storage().instance().set(&DataKey::ComplianceRules, &rules);
-        Ok(())
+        match cap {
+            Some(0) => return Err(Error::InvalidAmount),
+            Some(cap) => env
+                .storage()
+                .instance()
+                .set(&ExposureKey::MaxOpenEscrows, &cap),
+            None => env
+                .storage()
+                .instance()
+                .remove(&ExposureKey::MaxOpenEscrows),
+        }
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("open_cap"),
+            0,
+            &admin,
+            cap.unwrap_or(0) as i128,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("open_cap")),
+        );
+
+        Ok(())
     }
 
-    pub fn set_user_jurisdiction(env: Env, admin: Address, user: Address, country: i128) -> Result<(), Error> {
+    pub fn get_max_open_escrows(env: Env) -> Option<u32> {
+        env.storage().instance().get(&ExposureKey::MaxOpenEscrows)
+    }
+
+    pub fn get_open_escrow_count(env: Env, sender: Address) -> u32 {
+        env.storage()
+            .instance()
+            .get(&ExposureKey::OpenEscrowCount(sender))
+            .unwrap_or(0)
+    }
+
+    pub fn get_fee_split(env: Env) -> Option<FeeSplit> {
+        env.storage().instance().get(&FeeConfigKey::FeeSplit)
+    }
+
+    /// Amounts each beneficiary of `split` receives from `total_fee`, in
+    /// share order. Integer division rounds every share down; the remainder
+    /// is added to the primary beneficiary's share.
+    pub fn compute_fee_split(
+        env: Env,
+        total_fee: i128,
+        split: FeeSplit,
+    ) -> Result<Vec<i128>, Error> {
+        let total_weight: i128 = split.shares.iter().map(|share| share.weight as i128).sum();
+        if total_weight == 0 || split.primary >= split.shares.len() {
+            return Err(Error::InvalidFeePercentage);
+        }
+
+        let mut amounts = Vec::new(&env);
+        let mut distributed: i128 = 0;
+        for share in split.shares.iter() {
+            let amount = total_fee
+                .checked_mul(share.weight as i128)
+                .ok_or(Error::ArithmeticOverflow)?
+                / total_weight;
+            distributed += amount;
+            amounts.push_back(amount);
+        }
+
+        let primary_amount = amounts.get(split.primary).unwrap() + (total_fee - distributed);
+        amounts.set(split.primary, primary_amount);
+        Ok(amounts)
+    }
+
+    /// Registers (or with `None`, removes) the defaults for a business
+    /// category. Escrows already created keep their category's current fee.
+    pub fn set_category_rules(
+        env: Env,
+        admin: Address,
+        category: Symbol,
+        rules: Option<CategoryRules>,
+    ) -> Result<(), Error> {
         admin.require_auth();
+
         let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         if admin != stored_admin {
             return Err(Error::Unauthorized);
         }
 
-        env.storage().instance().set(&DataKey::UserJurisdiction(user), &country);
+        let key = CategoryKey::CategoryRules(category.clone());
+        match rules {
+            Some(rules) => {
+                if !(0..=10000).contains(&rules.platform_fee_bps) {
+                    return Err(Error::InvalidFeePercentage);
+                }
+                Self::check_fee_percentage_cap(
+                    &env,
+                    rules.platform_fee_bps,
+                    Self::stored_fee_bps(&env, &DataKey::ForexFeePercentage),
+                    Self::stored_fee_bps(&env, &DataKey::ConversionSpread),
+                )?;
+                env.storage().instance().set(&key, &rules);
+            }
+            None => env.storage().instance().remove(&key),
+        }
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("cat_set"),
+            0,
+            &admin,
+            0,
+            category,
+            EventData::AdminAction(symbol_short!("cat_set")),
+        );
+
         Ok(())
     }
 
-    pub fn check_compliance(env: Env, escrow: Escrow) -> bool {
-        let override_exists: bool = env.storage().instance().get(&DataKey::EscrowComplianceOverride(escrow.escrow_id)).unwrap_or(false);
-        if override_exists {
-            return true;
-        }
+    pub fn get_category_rules(env: Env, category: Symbol) -> Option<CategoryRules> {
+        env.storage()
+            .instance()
+            .get(&CategoryKey::CategoryRules(category))
+    }
 
-        let rules_opt: Option<Vec<ComplianceRule>> = env.storage().instance().get(&DataKey::ComplianceRules);
-        let rules = match rules_opt {
-            Some(r) => r,
-            None => return true,
-        };
+    pub fn get_escrow_category(env: Env, escrow_id: u64) -> Option<Symbol> {
+        env.storage()
+            .instance()
+            .get(&CategoryKey::EscrowCategory(escrow_id))
+    }
 
-        for rule in rules.iter() {
-            let rule_passed = match rule.rule_type {
-                ComplianceRuleType::AmountThreshold => {
-                    escrow.amount < rule.threshold
-                }
-                ComplianceRuleType::Jurisdiction => {
-                    let sender_country: i128 = env.storage().instance().get(&DataKey::UserJurisdiction(escrow.sender.clone())).unwrap_or(0);
-                    let recipient_country: i128 = env.storage().instance().get(&DataKey::UserJurisdiction(escrow.recipient.clone())).unwrap_or(0);
-                    sender_country != rule.threshold && recipient_country != rule.threshold
-                }
-                ComplianceRuleType::RegulatoryRequirement => {
-                    if escrow.amount >= rule.threshold {
-                        escrow.kyc_compliant
-                    } else {
-                        true
-                    }
-                }
-            };
-            if !rule_passed {
-                return false;
+    fn stored_fee_bps(env: &Env, key: &DataKey) -> i128 {
+        env.storage().instance().get(key).unwrap_or(0i128)
+    }
+
+    fn check_fee_percentage_cap(
+        env: &Env,
+        platform_bps: i128,
+        forex_bps: i128,
+        spread_bps: i128,
+    ) -> Result<(), Error> {
+        let cap: Option<i128> = env.storage().instance().get(&FeeConfigKey::PercentageCap);
+        match cap {
+            Some(cap) if platform_bps + forex_bps + spread_bps > cap => {
+                Err(Error::InvalidFeePercentage)
             }
+            _ => Ok(()),
         }
-        true
     }
 
-    pub fn admin_override_compliance(env: Env, admin: Address, escrow_id: u64) -> Result<(), Error> {
+    pub fn set_compliance_fee(env: Env, admin: Address, flat_fee: i128) -> Result<(), Error> {
         admin.require_auth();
+
         let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         if admin != stored_admin {
             return Err(Error::Unauthorized);
         }
 
-        let mut escrow: Escrow = env.storage().instance().get(&DataKey::Escrow(escrow_id)).ok_or(Error::EscrowNotFound)?;
-        escrow.compliant = true;
-        env.storage().instance().set(&DataKey::Escrow(escrow_id), &escrow);
-        env.storage().instance().set(&DataKey::EscrowComplianceOverride(escrow_id), &true);
+        if flat_fee < 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ComplianceFlatFee, &flat_fee);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("comp_fee"),
+            0,
+            &admin,
+            flat_fee,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("comp_fee")),
+        );
+
         Ok(())
     }
 
-    pub fn add_to_whitelist(
+    pub fn set_fee_limits(
         env: Env,
         admin: Address,
-        account: Address,
-        expiry: u64,
+        min_fee: i128,
+        max_fee: i128,
     ) -> Result<(), Error> {
         admin.require_auth();
 
@@ -1072,33 +1534,32 @@ impl PaymentEscrowContract {
             return Err(Error::Unauthorized);
         }
 
-        let record = KycRecord {
-            account: account.clone(),
-            status: KycStatus::Verified,
-            verified_at: env.ledger().timestamp(),
-            issuer: admin.clone(),
-            expiry,
-        };
+        if min_fee < 0 || max_fee < min_fee {
+            return Err(Error::InvalidAmount);
+        }
 
-        env.storage()
-            .persistent()
-            .set(&KycDataKey::Whitelist(account.clone()), &record);
+        env.storage().instance().set(&DataKey::MinFee, &min_fee);
+        env.storage().instance().set(&DataKey::MaxFee, &max_fee);
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("kyc_add"),
+            symbol_short!("fee_lim"),
             0,
             &admin,
             0,
             symbol_short!("na"),
-            EventData::AddressAction(symbol_short!("kyc_add"), account.clone()),
+            EventData::AdminAction(symbol_short!("fee_lim")),
         );
 
         Ok(())
     }
 
-    pub fn remove_from_whitelist(env: Env, admin: Address, account: Address) -> Result<(), Error> {
+    pub fn set_min_release_increment(
+        env: Env,
+        admin: Address,
+        min_increment: i128,
+    ) -> Result<(), Error> {
         admin.require_auth();
 
         let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
@@ -1106,33 +1567,35 @@ impl PaymentEscrowContract {
             return Err(Error::Unauthorized);
         }
 
-        let record = KycRecord {
-            account: account.clone(),
-            status: KycStatus::Rejected,
-            verified_at: env.ledger().timestamp(),
-            issuer: admin.clone(),
-            expiry: 0,
-        };
+        if min_increment < 0 {
+            return Err(Error::InvalidAmount);
+        }
 
         env.storage()
-            .persistent()
-            .set(&KycDataKey::Whitelist(account.clone()), &record);
+            .instance()
+            .set(&DataKey::MinReleaseIncrement, &min_increment);
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("kyc_rem"),
+            symbol_short!("min_rel"),
             0,
             &admin,
-            0,
+            min_increment,
             symbol_short!("na"),
-            EventData::AddressAction(symbol_short!("kyc_rem"), account.clone()),
+            EventData::AdminAction(symbol_short!("min_rel")),
         );
 
         Ok(())
     }
 
-    pub fn add_trusted_issuer(env: Env, admin: Address, issuer: Address) -> Result<(), Error> {
+    /// Sets how many seconds after creation an escrow expires when
+    /// `create_escrow` is given an expiration of 0. Must be non-zero.
+    pub fn set_default_expiration_offset(
+        env: Env,
+        admin: Address,
+        offset: u64,
+    ) -> Result<(), Error> {
         admin.require_auth();
 
         let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
@@ -1140,34 +1603,41 @@ impl PaymentEscrowContract {
             return Err(Error::Unauthorized);
         }
 
+        if offset == 0 {
+            return Err(Error::InvalidAmount);
+        }
+
         env.storage()
-            .persistent()
-            .set(&KycDataKey::TrustedIssuer(issuer.clone()), &true);
+            .instance()
+            .set(&DataKey::DefaultExpirationOffset, &offset);
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("kyc_iss"),
+            symbol_short!("def_exp"),
             0,
             &admin,
-            0,
+            offset as i128,
             symbol_short!("na"),
-            EventData::AddressAction(symbol_short!("kyc_iss"), issuer),
+            EventData::AdminAction(symbol_short!("def_exp")),
         );
 
         Ok(())
     }
 
-    pub fn get_kyc_status(env: Env, account: Address) -> KycStatus {
-        let key = KycDataKey::Whitelist(account);
-        let record: Option<KycRecord> = env.storage().persistent().get(&key);
-        match record {
-            Some(r) => r.status,
-            None => KycStatus::Unknown,
-        }
+    pub fn get_default_expiration_offset(env: Env) -> Option<u64> {
+        env.storage()
+            .instance()
+            .get(&DataKey::DefaultExpirationOffset)
     }
 
-    pub fn admin_override_kyc(env: Env, admin: Address, escrow_id: u64) -> Result<(), Error> {
+    /// Sets how long a partly funded escrow may sit without a new deposit
+    /// before `cancel_underfunded` can return the funds to the sender.
+    pub fn set_underfunded_grace_period(
+        env: Env,
+        admin: Address,
+        grace_period: u64,
+    ) -> Result<(), Error> {
         admin.require_auth();
 
         let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
@@ -1175,451 +1645,4971 @@ impl PaymentEscrowContract {
             return Err(Error::Unauthorized);
         }
 
-        let mut escrow: Escrow = env
-            .storage()
-            .instance()
-            .get(&DataKey::Escrow(escrow_id))
-            .ok_or(Error::EscrowNotFound)?;
+        if grace_period == 0 {
+            return Err(Error::InvalidAmount);
+        }
 
-        escrow.kyc_compliant = true;
         env.storage()
             .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+            .set(&FundingKey::UnderfundedGrace, &grace_period);
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("kyc_ovr"),
-            escrow_id,
-            &admin,
+            symbol_short!("uf_grace"),
             0,
+            &admin,
+            grace_period as i128,
             symbol_short!("na"),
-            EventData::AdminAction(symbol_short!("kyc_ovr")),
+            EventData::AdminAction(symbol_short!("uf_grace")),
         );
 
         Ok(())
     }
 
-    pub fn verify_kyc_proof(
-        env: Env,
-        account: Address,
-        proof_signature: BytesN<64>,
-        trusted_issuer: Address,
-    ) -> Result<bool, Error> {
-        let kyc_enabled: bool = env
-            .storage()
-            .instance()
-            .get(&DataKey::KycEnabled)
-            .unwrap_or(false);
-        if !kyc_enabled {
-            return Err(Error::KycNotConfigured);
-        }
-
-        let config: KycConfig = env
-            .storage()
+    pub fn get_underfunded_grace_period(env: Env) -> u64 {
+        env.storage()
             .instance()
-            .get(&DataKey::KycConfig)
-            .ok_or(Error::KycNotConfigured)?;
-
-        match kyc::verify_proof(
-            &env,
-            &account,
-            &proof_signature,
-            &trusted_issuer,
-            config.proof_validity_period,
-        ) {
-            Ok(valid) => {
-                if valid {
-                    let record = KycRecord {
-                        account: account.clone(),
-                        status: KycStatus::Verified,
-                        verified_at: env.ledger().timestamp(),
-                        issuer: trusted_issuer,
-                        expiry: if config.proof_validity_period > 0 {
-                            env.ledger().timestamp() + config.proof_validity_period
-                        } else {
-                            0
-                        },
-                    };
-                    env.storage()
-                        .persistent()
-                        .set(&KycDataKey::Whitelist(account.clone()), &record);
-
-                    events::emit(
-                        &env,
-                        symbol_short!("escrow"),
-                        symbol_short!("kyc_ok"),
-                        0,
-                        &account,
-                        0,
-                        symbol_short!("na"),
-                        EventData::AddressAction(symbol_short!("kyc_ok"), account.clone()),
-                    );
-                }
-                Ok(valid)
-            }
-            Err(_) => Err(Error::KycFailed),
-        }
+            .get(&FundingKey::UnderfundedGrace)
+            .unwrap_or(DEFAULT_UNDERFUNDED_GRACE)
     }
 
-    pub fn create_escrow(
+    /// Sets the balance below which a leftover amount of `asset_code` is
+    /// swept instead of paid out. Zero disables sweeping.
+    pub fn set_dust_threshold(
         env: Env,
-        sender: Address,
-        recipient: Address,
-        amount: i128,
-        asset: Asset,
-        expiration_timestamp: u64,
-        memo: String,
-    ) -> Result<u64, Error> {
-        if upgradeable::is_paused(&env) {
-            return Err(Error::ContractPaused);
+        admin: Address,
+        asset_code: String,
+        threshold: i128,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
         }
-        sender.require_auth();
-        Self::enforce_rate_limit(&env, &sender, FunctionType::Deposit)?;
 
-        if amount <= 0 {
+        if threshold < 0 {
             return Err(Error::InvalidAmount);
         }
 
-        if sender == recipient {
-            return Err(Error::SameSenderRecipient);
-        }
+        env.storage()
+            .instance()
+            .set(&AssetConfigKey::DustThreshold(asset_code), &threshold);
 
-        if !Self::is_supported_asset(&env, &asset) {
-            return Err(Error::InvalidAsset);
-        }
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("dust_thr"),
+            0,
+            &admin,
+            threshold,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("dust_thr")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_dust_threshold(env: Env, asset_code: String) -> i128 {
+        env.storage()
+            .instance()
+            .get(&AssetConfigKey::DustThreshold(asset_code))
+            .unwrap_or(0i128)
+    }
+
+    /// Moves a sub-dust remainder to the fee wallet (or back to the sender
+    /// when none is set) and marks the escrow fully released.
+    fn sweep_dust(
+        env: &Env,
+        escrow_id: u64,
+        escrow: &mut Escrow,
+        token_client: &token::Client,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let fee_wallet: Option<Address> = env.storage().instance().get(&DataKey::FeeWallet);
+        let destination = fee_wallet.unwrap_or(escrow.sender.clone());
+        token_client.transfer(&env.current_contract_address(), &destination, &amount);
+        Self::adjust_tvl(env, &escrow.asset.code, -amount);
+        Self::emit_fee_collected(env, escrow_id, amount, &destination, symbol_short!("dust"));
+
+        escrow.released_amount = escrow
+            .released_amount
+            .checked_add(amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        let asset_released = escrow
+            .released_amounts
+            .get(escrow.asset.clone())
+            .unwrap_or(0i128);
+        escrow.released_amounts.set(
+            escrow.asset.clone(),
+            asset_released
+                .checked_add(amount)
+                .ok_or(Error::ArithmeticOverflow)?,
+        );
+        escrow.status = EscrowStatus::Released;
+        Ok(())
+    }
+
+    pub fn get_min_release_increment(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinReleaseIncrement)
+            .unwrap_or(0i128)
+    }
 
+    /// Re-checks the recipient's KYC at release time. Returns `Ok(true)` when
+    /// KYC is disabled.
+    fn recipient_kyc_current(env: &Env, recipient: &Address) -> Result<bool, Error> {
         let kyc_enabled: bool = env
             .storage()
             .instance()
             .get(&DataKey::KycEnabled)
             .unwrap_or(false);
-        let mut kyc_compliant = false;
-
-        if kyc_enabled {
-            let config: KycConfig = env
-                .storage()
-                .instance()
-                .get(&DataKey::KycConfig)
-                .ok_or(Error::KycNotConfigured)?;
-
-            let kyc_result = kyc::check_kyc(&env, &config, &sender, &recipient);
+        if !kyc_enabled {
+            return Ok(true);
+        }
 
-            match kyc_result {
-                Ok(result) => {
-                    if !result.sender_verified || !result.recipient_verified {
-                        events::emit(
-                            &env,
-                            symbol_short!("escrow"),
-                            symbol_short!("kyc_fail"),
-                            0,
-                            &sender,
-                            0,
-                            symbol_short!("na"),
-                            EventData::PairAction(
-                                symbol_short!("kyc_fail"),
-                                sender.clone(),
-                                recipient.clone(),
-                            ),
-                        );
-                        return Err(Error::KycFailed);
-                    }
-                    kyc_compliant = true;
+        let config: KycConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::KycConfig)
+            .ok_or(Error::KycNotConfigured)?;
+        kyc::check_kyc(env, &config, recipient, recipient)
+            .map(|result| result.sender_verified)
+            .map_err(|_| Error::KycFailed)
+    }
 
-                    events::emit(
-                        &env,
-                        symbol_short!("escrow"),
-                        symbol_short!("kyc_pass"),
-                        0,
-                        &sender,
-                        0,
-                        symbol_short!("na"),
-                        EventData::PairAction(
-                            symbol_short!("kyc_pass"),
-                            sender.clone(),
-                            recipient.clone(),
-                        ),
-                    );
-                }
-                Err(_) => {
-                    return Err(Error::KycFailed);
-                }
-            }
+    /// Re-runs KYC for both parties of `escrow`, failing with `KycFailed` if
+    /// either is no longer verified or KYC is not configured.
+    fn parties_kyc_current(env: &Env, escrow: &Escrow) -> Result<(), Error> {
+        let config: KycConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::KycConfig)
+            .ok_or(Error::KycFailed)?;
+        match kyc::check_kyc(env, &config, &escrow.sender, &escrow.recipient) {
+            Ok(result) if result.sender_verified && result.recipient_verified => Ok(()),
+            _ => Err(Error::KycFailed),
         }
+    }
 
-        let mut counter: u64 = env
+    /// Whether `approver` holds a current KYC verification at `min_tier` or
+    /// above, via the configured provider or, when none is configured, the
+    /// contract's whitelist.
+    fn approver_kyc_verified(env: &Env, approver: &Address, min_tier: u32) -> bool {
+        if Self::get_kyc_tier(env.clone(), approver.clone()) < min_tier {
+            return false;
+        }
+        if let Some(config) = env
             .storage()
             .instance()
-            .get(&DataKey::EscrowCounter)
-            .unwrap_or(0u64);
-        counter = counter.checked_add(1).ok_or(Error::CounterOverflow)?;
-
-        let mut escrow_assets = Vec::new(&env);
-        escrow_assets.push_back(asset.clone());
-        let mut amounts = Self::empty_asset_amount_map(&env);
-        amounts.set(asset.clone(), amount);
-        let mut deposited_amounts = Self::empty_asset_amount_map(&env);
-        deposited_amounts.set(asset.clone(), 0);
-        let mut released_amounts = Self::empty_asset_amount_map(&env);
-        released_amounts.set(asset.clone(), 0);
-        let mut refunded_amounts = Self::empty_asset_amount_map(&env);
-        refunded_amounts.set(asset.clone(), 0);
+            .get::<_, KycConfig>(&DataKey::KycConfig)
+        {
+            return kyc::check_kyc(env, &config, approver, approver)
+                .map(|result| result.sender_verified)
+                .unwrap_or(false);
+        }
 
-        let mut escrow = Escrow {
-            sender: sender.clone(),
-            recipient,
-            amount,
-            deposited_amount: 0,
-            released_amount: 0,
-            refunded_amount: 0,
-            asset,
-            assets: escrow_assets,
-            amounts,
-            deposited_amounts,
-            released_amounts,
-            refunded_amounts,
-            release_conditions: ReleaseCondition {
-                expiration_timestamp,
-                recipient_approval: false,
-                oracle_confirmation: false,
-                conditions: Vec::new(&env),
-                operator: ConditionOperator::And,
-                min_approvals: 1,
-                current_approvals: 0,
-            },
-            status: EscrowStatus::Pending,
-            created_at: env.ledger().timestamp(),
-            last_deposit_at: 0,
-            release_timestamp: 0,
-            refund_timestamp: 0,
-            escrow_id: counter,
-            memo,
-            allow_partial_release: false,
-            multi_party_enabled: false,
-            kyc_compliant,
-            compliant: true,
-            milestones: Vec::new(&env),
-        };
+        let record: Option<KycRecord> = env
+            .storage()
+            .persistent()
+            .get(&KycDataKey::Whitelist(approver.clone()));
+        match record {
+            Some(record) => {
+                record.status == KycStatus::Verified
+                    && (record.expiry == 0 || record.expiry > env.ledger().timestamp())
+            }
+            None => false,
+        }
+    }
 
-        // Auto-check compliance on creation
-        let rules_opt: Option<Vec<ComplianceRule>> = env.storage().instance().get(&DataKey::ComplianceRules);
-        if let Some(rules) = rules_opt {
-            for rule in rules.iter() {
-                let rule_passed = match rule.rule_type {
-                    ComplianceRuleType::AmountThreshold => {
-                        escrow.amount < rule.threshold
-                    }
-                    ComplianceRuleType::Jurisdiction => {
-                        let sender_country: i128 = env.storage().instance().get(&DataKey::UserJurisdiction(escrow.sender.clone())).unwrap_or(0);
-                        let recipient_country: i128 = env.storage().instance().get(&DataKey::UserJurisdiction(escrow.recipient.clone())).unwrap_or(0);
-                        sender_country != rule.threshold && recipient_country != rule.threshold
-                    }
-                    ComplianceRuleType::RegulatoryRequirement => {
-                        if escrow.amount >= rule.threshold {
-                            escrow.kyc_compliant
-                        } else {
-                            true
+    /// Pays a fee to `destination`, converting it to the treasury asset when
+    /// fee conversion is configured. Falls back to paying in the escrow asset
+    /// if the rate is unavailable or the reserve cannot cover the swap.
+    fn collect_fee(
+        env: &Env,
+        escrow_id: u64,
+        token_client: &token::Client,
+        asset_code: &String,
+        fee_amount: i128,
+        destination: &Address,
+        fee_type: Symbol,
+    ) {
+        let contract_address = env.current_contract_address();
+        if fee_type == symbol_short!("platform") {
+            if let Some(split) = env
+                .storage()
+                .instance()
+                .get::<_, FeeSplit>(&FeeConfigKey::FeeSplit)
+            {
+                if let Ok(amounts) = Self::compute_fee_split(env.clone(), fee_amount, split.clone())
+                {
+                    for (share, amount) in split.shares.iter().zip(amounts.iter()) {
+                        if amount > 0 {
+                            token_client.transfer(&contract_address, &share.beneficiary, &amount);
+                            Self::emit_fee_collected(
+                                env,
+                                escrow_id,
+                                amount,
+                                &share.beneficiary,
+                                fee_type.clone(),
+                            );
                         }
                     }
-                };
+                    return;
+                }
+            }
+        }
 
-                if !rule_passed {
-                    match rule.action {
-                        ComplianceAction::Block => {
-                            return Err(Error::NonCompliant);
-                        }
-                        ComplianceAction::Flag => {
-                            escrow.compliant = false;
-                        }
-                    }
+        let config_opt: Option<FeeConversionConfig> =
+            env.storage().instance().get(&DataKey::FeeConversion);
+        if let Some(config) = config_opt {
+            if *asset_code != config.treasury_asset {
+                let converted = oracle::get_conversion_rate(
+                    env,
+                    &config.oracle,
+                    asset_code,
+                    &config.treasury_asset,
+                    fee_amount,
+                    config.max_staleness,
+                    None,
+                )
+                .map(|result| result.converted_amount)
+                .unwrap_or(0);
+                let treasury_client = token::Client::new(env, &config.treasury_token);
+                if converted > 0
+                    && treasury_client
+                        .try_transfer_from(
+                            &contract_address,
+                            &config.reserve,
+                            destination,
+                            &converted,
+                        )
+                        .is_ok()
+                {
+                    token_client.transfer(&contract_address, &config.reserve, &fee_amount);
+                    events::emit(
+                        env,
+                        symbol_short!("escrow"),
+                        symbol_short!("fee_conv"),
+                        escrow_id,
+                        destination,
+                        converted,
+                        fee_type.clone(),
+                        EventData::ValueChanged(symbol_short!("fee_conv"), fee_amount, converted),
+                    );
+                    Self::emit_fee_collected(env, escrow_id, converted, destination, fee_type);
+                    return;
                 }
             }
         }
 
+        token_client.transfer(&contract_address, destination, &fee_amount);
+        Self::emit_fee_collected(env, escrow_id, fee_amount, destination, fee_type);
+    }
+
+    /// Where platform and processing fees are paid: the configured fee
+    /// wallet, or the admin when none is set.
+    fn fee_destination(env: &Env, admin: &Address) -> Address {
         env.storage()
             .instance()
-            .set(&DataKey::Escrow(counter), &escrow);
-        env.storage()
-            .instance()
-            .set(&DataKey::EscrowCounter, &counter);
+            .get(&DataKey::FeeWallet)
+            .unwrap_or(admin.clone())
+    }
 
+    fn emit_fee_collected(
+        env: &Env,
+        escrow_id: u64,
+        fee_amount: i128,
+        destination: &Address,
+        fee_type: Symbol,
+    ) {
         events::emit(
-            &env,
+            env,
             symbol_short!("escrow"),
-            symbol_short!("created"),
-            counter,
-            &escrow.sender,
-            escrow.amount,
-            symbol_short!("pending"),
-            EventData::EscrowCreated(
-                counter,
-                escrow.sender.clone(),
-                escrow.recipient.clone(),
-                AssetRef {
-                    code: escrow.asset.code.clone(),
-                    issuer: escrow.asset.issuer.clone(),
-                },
-                escrow.amount,
-            ),
-        );
-
-        Self::notify_external(
-            &env,
-            NotificationPayload {
-                escrow_id: counter,
-                event_type: EventType::Created,
-                amount,
-                timestamp: env.ledger().timestamp(),
-            },
+            symbol_short!("fee_coll"),
+            escrow_id,
+            destination,
+            fee_amount,
+            fee_type.clone(),
+            EventData::FeeCollected(escrow_id, fee_amount, destination.clone(), fee_type),
         );
+    }
 
-        Ok(counter)
+    /// Rejects `token_address` unless it is the token registered for `asset`
+    /// with `set_asset_token`.
+    fn check_asset_token(env: &Env, asset: &Asset, token_address: &Address) -> Result<(), Error> {
+        let registered =
+            Self::get_asset_token(env.clone(), asset.clone()).ok_or(Error::InvalidAsset)?;
+        if registered != *token_address {
+            return Err(Error::TokenMismatch);
+        }
+        Ok(())
     }
 
-    pub fn create_multi_asset_escrow(
-        env: Env,
-        sender: Address,
-        recipient: Address,
-        assets: Vec<Asset>,
-        amounts: Map<Asset, i128>,
-        expiration_timestamp: u64,
-        memo: String,
-    ) -> Result<u64, Error> {
-        if upgradeable::is_paused(&env) {
-            return Err(Error::ContractPaused);
+    /// Ties an escrow to the token registered for its asset on first deposit;
+    /// deposits in any other token are rejected.
+    fn bind_escrow_token(
+        env: &Env,
+        escrow_id: u64,
+        asset: &Asset,
+        token_address: &Address,
+    ) -> Result<(), Error> {
+        Self::check_asset_token(env, asset, token_address)?;
+        let key = AssetConfigKey::EscrowToken(escrow_id);
+        match env.storage().instance().get::<_, Address>(&key) {
+            Some(bound) if bound != *token_address => Err(Error::TokenMismatch),
+            Some(_) => Ok(()),
+            None => {
+                env.storage().instance().set(&key, token_address);
+                Ok(())
+            }
         }
-        sender.require_auth();
-        Self::enforce_rate_limit(&env, &sender, FunctionType::Deposit)?;
+    }
 
-        if sender == recipient {
-            return Err(Error::SameSenderRecipient);
-        }
-        if assets.len() == 0 {
-            return Err(Error::InvalidAsset);
+    /// Rejects payouts and refunds in a token other than the one the escrow
+    /// was funded with.
+    fn check_escrow_token(env: &Env, escrow_id: u64, token_address: &Address) -> Result<(), Error> {
+        match Self::get_escrow_token(env.clone(), escrow_id) {
+            Some(bound) if bound != *token_address => Err(Error::TokenMismatch),
+            _ => Ok(()),
         }
+    }
 
-        let mut total_amount = 0i128;
-        let mut deposited_amounts = Self::empty_asset_amount_map(&env);
-        let mut released_amounts = Self::empty_asset_amount_map(&env);
-        let mut refunded_amounts = Self::empty_asset_amount_map(&env);
+    fn check_funding_source(escrow: &Escrow, caller: &Address) -> Result<(), Error> {
+        if escrow.allowed_funding_sources.is_empty() {
+            if *caller != escrow.sender {
+                return Err(Error::WrongSender);
+            }
+        } else if !escrow.allowed_funding_sources.contains(caller) {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
 
-        for asset in assets.iter() {
-            if !Self::is_supported_asset(&env, &asset) {
-                return Err(Error::InvalidAsset);
+    fn store_escrow(env: &Env, escrow_id: u64, escrow: &mut Escrow) {
+        let previous_status = env
+            .storage()
+            .instance()
+            .get::<_, Escrow>(&DataKey::Escrow(escrow_id))
+            .map(|previous| previous.status);
+        if previous_status.as_ref() != Some(&escrow.status) {
+            if let Some(previous_status) = &previous_status {
+                Self::adjust_status_count(env, previous_status, false);
             }
-            let amount = amounts.get(asset.clone()).ok_or(Error::InvalidAmount)?;
-            if amount <= 0 {
-                return Err(Error::InvalidAmount);
+            Self::adjust_status_count(env, &escrow.status, true);
+        }
+
+        let was_open = previous_status.is_some_and(|status| Self::is_open(&status));
+        let is_open = Self::is_open(&escrow.status);
+        if was_open != is_open {
+            let key = ExposureKey::OpenEscrowCount(escrow.sender.clone());
+            let count: u32 = env.storage().instance().get(&key).unwrap_or(0);
+            let count = if is_open {
+                count.saturating_add(1)
+            } else {
+                count.saturating_sub(1)
+            };
+            env.storage().instance().set(&key, &count);
+        }
+
+        escrow.sequence = escrow.sequence.saturating_add(1);
+        env.storage()
+            .instance()
+            .set(&DataKey::Escrow(escrow_id), escrow);
+    }
+
+    fn adjust_status_count(env: &Env, status: &EscrowStatus, increment: bool) {
+        let key = StatsKey::StatusCount(status.clone());
+        let count: u64 = env.storage().instance().get(&key).unwrap_or(0);
+        let count = if increment {
+            count.saturating_add(1)
+        } else {
+            count.saturating_sub(1)
+        };
+        env.storage().instance().set(&key, &count);
+    }
+
+    /// Escrows that have not been paid out, refunded or cancelled count
+    /// against their sender's open-escrow cap; expired ones still hold funds.
+    fn is_open(status: &EscrowStatus) -> bool {
+        !matches!(
+            status,
+            EscrowStatus::Released | EscrowStatus::Refunded | EscrowStatus::Cancelled
+        )
+    }
+
+    fn enforce_open_escrow_cap(env: &Env, sender: &Address) -> Result<(), Error> {
+        let cap: Option<u32> = env.storage().instance().get(&ExposureKey::MaxOpenEscrows);
+        match cap {
+            Some(cap) if Self::get_open_escrow_count(env.clone(), sender.clone()) >= cap => {
+                Err(Error::RateLimitExceeded)
             }
-            total_amount = total_amount
-                .checked_add(amount)
-                .ok_or(Error::ArithmeticOverflow)?;
-            deposited_amounts.set(asset.clone(), 0);
-            released_amounts.set(asset.clone(), 0);
-            refunded_amounts.set(asset.clone(), 0);
+            _ => Ok(()),
         }
+    }
 
-        let mut counter: u64 = env
+    fn adjust_tvl(env: &Env, asset_code: &String, delta: i128) {
+        let key = DataKey::Tvl(asset_code.clone());
+        let tvl: i128 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&key, &tvl.saturating_add(delta));
+    }
+
+    fn enforce_rate_limit(
+        env: &Env,
+        caller: &Address,
+        function_type: FunctionType,
+    ) -> Result<(), Error> {
+        let admin_opt: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        let admin = match admin_opt {
+            Some(a) => a,
+            None => return Ok(()),
+        };
+        let allowed = rate_limit::check_rate_limit(env, caller, function_type, &admin);
+        if allowed {
+            Ok(())
+        } else {
+            Err(Error::RateLimitExceeded)
+        }
+    }
+
+    /// Calls the escrow's whitelisted release callback, if it opted in. The
+    /// reentrancy guard is still held, so a callback that tries to release or
+    /// refund again is rejected; its failure never unwinds the release.
+    fn invoke_release_callback(env: &Env, escrow_id: u64, amount: i128) {
+        let callback: ReleaseCallback = match env
             .storage()
             .instance()
-            .get(&DataKey::EscrowCounter)
-            .unwrap_or(0u64);
-        counter = counter.checked_add(1).ok_or(Error::CounterOverflow)?;
+            .get(&CallbackKey::ReleaseCallback(escrow_id))
+        {
+            Some(cb) => cb,
+            None => return,
+        };
+        // A contract dropped from the whitelist after registration is no
+        // longer called.
+        if !callback.enabled
+            || !Self::is_callback_whitelisted(env.clone(), callback.contract.clone())
+        {
+            return;
+        }
 
-        let primary_asset = assets.get(0).unwrap();
-        let primary_amount = amounts.get(primary_asset.clone()).unwrap();
-        let escrow = Escrow {
-            sender: sender.clone(),
-            recipient,
-            amount: total_amount,
-            deposited_amount: 0,
-            released_amount: 0,
-            refunded_amount: 0,
-            asset: primary_asset,
-            assets,
-            amounts,
-            deposited_amounts,
-            released_amounts,
-            refunded_amounts,
-            release_conditions: ReleaseCondition {
-                expiration_timestamp,
-                recipient_approval: false,
-                oracle_confirmation: false,
-                conditions: Vec::new(&env),
-                operator: ConditionOperator::And,
-                min_approvals: 1,
-                current_approvals: 0,
-            },
-            status: EscrowStatus::Pending,
-            created_at: env.ledger().timestamp(),
-            last_deposit_at: 0,
-            release_timestamp: 0,
-            refund_timestamp: 0,
-            escrow_id: counter,
-            memo,
-            allow_partial_release: false,
-            multi_party_enabled: false,
-            kyc_compliant: false,
-            compliant: true,
-            milestones: Vec::new(&env),
+        let func = Symbol::new(env, "on_release");
+        let args: Vec<Val> = Vec::from_array(env, [escrow_id.into_val(env), amount.into_val(env)]);
+        let status =
+            match env.try_invoke_contract::<(), InvokeError>(&callback.contract, &func, args) {
+                Ok(Ok(())) => symbol_short!("ok"),
+                _ => symbol_short!("failed"),
+            };
+
+        events::emit(
+            env,
+            symbol_short!("escrow"),
+            symbol_short!("cb_call"),
+            escrow_id,
+            &callback.contract,
+            amount,
+            status,
+            EventData::AdminAction(symbol_short!("cb_call")),
+        );
+    }
+
+    /// Reports a release to the configured accounting contract. The hook is
+    /// best-effort: a failing or missing contract never blocks settlement.
+    fn notify_accounting_hook(
+        env: &Env,
+        escrow_id: u64,
+        recipient: &Address,
+        amount: i128,
+        fee: i128,
+    ) {
+        let hook: Address = match env.storage().instance().get(&CallbackKey::AccountingHook) {
+            Some(hook) => hook,
+            None => return,
+        };
+        let func = Symbol::new(env, "on_release");
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                escrow_id.into_val(env),
+                recipient.into_val(env),
+                amount.into_val(env),
+                fee.into_val(env),
+            ],
+        );
+        let _ = env.try_invoke_contract::<(), InvokeError>(&hook, &func, args);
+    }
+
+    fn condition_symbol(condition_type: ConditionType) -> Symbol {
+        match condition_type {
+            ConditionType::Timestamp => symbol_short!("timestamp"),
+            ConditionType::Approval => symbol_short!("approval"),
+            ConditionType::OraclePrice => symbol_short!("oracle"),
+            ConditionType::MultiSignature => symbol_short!("multisig"),
+            ConditionType::KYCVerified => symbol_short!("kyc"),
+            ConditionType::RecipientAck => symbol_short!("recip_ack"),
+            ConditionType::GeoAllowed => symbol_short!("geo"),
+            ConditionType::MinDeposited => symbol_short!("min_dep"),
+            ConditionType::AmlClear => symbol_short!("aml_clear"),
+        }
+    }
+
+    fn index_sender_escrow(env: &Env, sender: &Address, escrow_id: u64) {
+        let key = DataKey::SenderEscrows(sender.clone());
+        let mut ids: Vec<u64> = env.storage().instance().get(&key).unwrap_or(Vec::new(env));
+        ids.push_back(escrow_id);
+        env.storage().instance().set(&key, &ids);
+    }
+
+    fn notify_external(env: &Env, payload: NotificationPayload) {
+        let status = match payload.event_type {
+            EventType::Created => symbol_short!("created"),
+            EventType::Deposit => symbol_short!("deposit"),
+            EventType::Approved => symbol_short!("approved"),
+            EventType::Released => symbol_short!("released"),
+            EventType::PartialRelease => symbol_short!("part_rel"),
+            EventType::Refunded => symbol_short!("refunded"),
+            EventType::PartialRefund => symbol_short!("part_ref"),
         };
+        let actor = env.current_contract_address();
+        events::emit(
+            env,
+            symbol_short!("escrow"),
+            symbol_short!("notify"),
+            payload.escrow_id,
+            &actor,
+            payload.amount,
+            status.clone(),
+            EventData::AdminAction(symbol_short!("notify")),
+        );
+
+        let hooks: Vec<NotificationConfig> = env
+            .storage()
+            .instance()
+            .get(&DataKey::NotificationHooks(payload.escrow_id))
+            .unwrap_or(Vec::new(env));
+        let mut history: Vec<NotificationDelivery> = env
+            .storage()
+            .instance()
+            .get(&DataKey::NotificationHistory(payload.escrow_id))
+            .unwrap_or(Vec::new(env));
+        for config in hooks.iter() {
+            let retries = if config.max_retries == 0 {
+                DEFAULT_MAX_RETRIES
+            } else {
+                config.max_retries
+            };
+            for url in config.webhook_urls.iter() {
+                history.push_back(NotificationDelivery {
+                    escrow_id: payload.escrow_id,
+                    event_type: payload.event_type,
+                    webhook_url: url,
+                    delivered_at: payload.timestamp,
+                    attempts: retries,
+                });
+                events::emit(
+                    env,
+                    symbol_short!("escrow"),
+                    symbol_short!("hook"),
+                    payload.escrow_id,
+                    &actor,
+                    payload.amount,
+                    status.clone(),
+                    EventData::AdminAction(symbol_short!("hook")),
+                );
+            }
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::NotificationHistory(payload.escrow_id), &history);
+    }
+
+    fn is_supported_asset(env: &Env, asset: &Asset) -> bool {
+        let assets: Vec<Asset> = env
+            .storage()
+            .instance()
+            .get(&DataKey::SupportedAssets)
+            .unwrap_or(Vec::new(env));
+        for supported_asset in assets.iter() {
+            if supported_asset.code == asset.code && supported_asset.issuer == asset.issuer {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn validate_notification_config(config: &NotificationConfig) -> Result<(), Error> {
+        if config.webhook_urls.len() == 0 || config.webhook_urls.len() > MAX_HOOKS {
+            return Err(Error::InvalidAsset);
+        }
+
+        for url in config.webhook_urls.iter() {
+            if url.len() < 8 || url.len() > 256 {
+                return Err(Error::InvalidAsset);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn empty_asset_amount_map(env: &Env) -> Map<Asset, i128> {
+        Map::new(env)
+    }
+
+    fn calculate_fees(
+        env: &Env,
+        amount: i128,
+        platform_percentage: i128,
+        converting: bool,
+    ) -> Result<FeeBreakdown, Error> {
+        let forex_percentage = env
+            .storage()
+            .instance()
+            .get(&DataKey::ForexFeePercentage)
+            .unwrap_or(0i128);
+        let compliance_flat = env
+            .storage()
+            .instance()
+            .get(&DataKey::ComplianceFlatFee)
+            .unwrap_or(0i128);
+        let network_flat = env
+            .storage()
+            .instance()
+            .get(&DataKey::NetworkFlatFee)
+            .unwrap_or(0i128);
+
+        let platform_fee = amount
+            .checked_mul(platform_percentage)
+            .ok_or(Error::ArithmeticOverflow)?
+            .checked_div(10000)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        let forex_fee = amount
+            .checked_mul(forex_percentage)
+            .ok_or(Error::ArithmeticOverflow)?
+            .checked_div(10000)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        let conversion_fee = if converting {
+            let spread_bps: i128 = env
+                .storage()
+                .instance()
+                .get(&DataKey::ConversionSpread)
+                .unwrap_or(0i128);
+            amount
+                .checked_mul(spread_bps)
+                .ok_or(Error::ArithmeticOverflow)?
+                .checked_div(10000)
+                .ok_or(Error::ArithmeticOverflow)?
+        } else {
+            0
+        };
+
+        let mut total_fee = platform_fee
+            .checked_add(forex_fee)
+            .ok_or(Error::ArithmeticOverflow)?
+            .checked_add(conversion_fee)
+            .ok_or(Error::ArithmeticOverflow)?
+            .checked_add(compliance_flat)
+            .ok_or(Error::ArithmeticOverflow)?
+            .checked_add(network_flat)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        let min_fee = env
+            .storage()
+            .instance()
+            .get(&DataKey::MinFee)
+            .unwrap_or(0i128);
+        let max_fee = env
+            .storage()
+            .instance()
+            .get(&DataKey::MaxFee)
+            .unwrap_or(i128::MAX);
+
+        if total_fee < min_fee {
+            total_fee = min_fee;
+        }
+        if total_fee > max_fee {
+            total_fee = max_fee;
+        }
+
+        if total_fee > amount {
+            return Err(Error::FeeExceedsAmount);
+        }
+
+        Ok(FeeBreakdown {
+            platform_fee,
+            forex_fee,
+            conversion_fee,
+            compliance_fee: compliance_flat,
+            network_fee: network_flat,
+            total_fee,
+        })
+    }
+
+    pub fn get_fee_breakdown(env: Env, amount: i128) -> Result<FeeBreakdown, Error> {
+        Self::calculate_fees(&env, amount, Self::get_platform_fee(env.clone()), false)
+    }
+
+    /// Fee breakdown for an escrow's full amount, including the conversion
+    /// spread when the recipient is paid in a different asset.
+    pub fn get_escrow_fee_breakdown(env: Env, escrow_id: u64) -> Result<FeeBreakdown, Error> {
+        let escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+        Self::calculate_fees(
+            &env,
+            escrow.amount,
+            Self::platform_fee_for(&env, escrow_id),
+            Self::payout_converts(&env, escrow_id),
+        )
+    }
+
+    /// Platform fee for an escrow: its category's fee when it was created in
+    /// one, otherwise the contract-wide fee.
+    fn platform_fee_for(env: &Env, escrow_id: u64) -> i128 {
+        let category: Option<Symbol> = env
+            .storage()
+            .instance()
+            .get(&CategoryKey::EscrowCategory(escrow_id));
+        category
+            .and_then(|category| {
+                env.storage()
+                    .instance()
+                    .get::<_, CategoryRules>(&CategoryKey::CategoryRules(category))
+            })
+            .map(|rules| rules.platform_fee_bps)
+            .unwrap_or_else(|| Self::get_platform_fee(env.clone()))
+    }
+
+    fn payout_converts(env: &Env, escrow_id: u64) -> bool {
+        env.storage().instance().has(&PayoutKey::Config)
+            && env
+                .storage()
+                .instance()
+                .has(&PayoutKey::Preference(escrow_id))
+    }
+
+    pub fn configure_kyc(
+        env: Env,
+        admin: Address,
+        oracle_address: Address,
+        use_oracle: bool,
+        proof_validity_period: u64,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        let config = KycConfig {
+            admin: admin.clone(),
+            oracle_address,
+            use_oracle,
+            proof_validity_period,
+            last_check_ledger: 0,
+        };
+
+        env.storage().instance().set(&DataKey::KycConfig, &config);
+        env.storage().instance().set(&DataKey::KycEnabled, &true);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("kyc_cfg"),
+            0,
+            &admin,
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("kyc_cfg")),
+        );
+
+        Ok(())
+    }
+
+    pub fn register_compliance_rule(env: Env, admin: Address, rule: ComplianceRule) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut rules: Vec<ComplianceRule> = env
+            .storage()
+            .instance()
+            .get(&DataKey::ComplianceRules)
+            .unwrap_or_else(|| Vec::new(&env));
+        rules.push_back(rule);
+        env.storage().instance().set(&DataKey::ComplianceRules, &rules);
+        Ok(())
+    }
+
+    pub fn set_user_jurisdiction(env: Env, admin: Address, user: Address, country: i128) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage().instance().set(&DataKey::UserJurisdiction(user), &country);
+        Ok(())
+    }
+
+    pub fn set_account_region(
+        env: Env,
+        admin: Address,
+        account: Address,
+        region: Symbol,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::AccountRegion(account), &region);
+        Ok(())
+    }
+
+    pub fn get_account_region(env: Env, account: Address) -> Option<Symbol> {
+        env.storage()
+            .instance()
+            .get(&DataKey::AccountRegion(account))
+    }
+
+    pub fn set_aml_hub(env: Env, admin: Address, hub: Address) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage().instance().set(&DataKey::AmlHub, &hub);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("aml_hub"),
+            0,
+            &admin,
+            0,
+            symbol_short!("na"),
+            EventData::AddressAction(symbol_short!("aml_hub"), hub),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_aml_hub(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::AmlHub)
+    }
+
+    /// Records that the hub invoice linked to `escrow_id` was cancelled, so
+    /// the escrow can no longer be released and is left for the sender or
+    /// admin to cancel or refund. Anyone may call this; the cancellation is
+    /// confirmed with the hub set through `set_aml_hub`, and the invoice must
+    /// name the escrow's own sender and recipient so a third party cannot
+    /// freeze an escrow by invoicing against its id.
+    pub fn notify_invoice_cancelled(env: Env, escrow_id: u64) -> Result<(), Error> {
+        let escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+        if !matches!(
+            escrow.status,
+            EscrowStatus::Pending | EscrowStatus::Funded | EscrowStatus::Approved
+        ) {
+            return Err(Error::InvalidStatus);
+        }
+
+        let hub: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::AmlHub)
+            .ok_or(Error::ConditionsNotMet)?;
+        let func = Symbol::new(&env, "get_escrow_invoice");
+        let args: Vec<Val> = Vec::from_array(&env, [escrow_id.into_val(&env)]);
+        let invoice =
+            match env.try_invoke_contract::<Option<HubInvoice>, InvokeError>(&hub, &func, args) {
+                Ok(Ok(Some(invoice))) => invoice,
+                _ => return Err(Error::ConditionsNotMet),
+            };
+        if invoice.sender != escrow.sender || invoice.recipient != escrow.recipient {
+            return Err(Error::Unauthorized);
+        }
+        if invoice.status != InvoiceStatus::Cancelled {
+            return Err(Error::ConditionsNotMet);
+        }
+
+        env.storage()
+            .instance()
+            .set(&InvoiceLinkKey::InvoiceCancelled(escrow_id), &true);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("inv_canc"),
+            escrow_id,
+            &hub,
+            escrow.deposited_amount,
+            symbol_short!("refund"),
+            EventData::AddressAction(symbol_short!("inv_canc"), hub.clone()),
+        );
+
+        Ok(())
+    }
+
+    pub fn is_invoice_cancelled(env: Env, escrow_id: u64) -> bool {
+        env.storage()
+            .instance()
+            .get(&InvoiceLinkKey::InvoiceCancelled(escrow_id))
+            .unwrap_or(false)
+    }
+
+    /// Sets (or clears, with `None`) the AML oracle used to re-screen escrow
+    /// parties for `ConditionType::AmlClear`.
+    pub fn set_aml_screening(
+        env: Env,
+        admin: Address,
+        config: Option<AmlConfig>,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        match config {
+            Some(config) => env
+                .storage()
+                .instance()
+                .set(&ScreeningKey::AmlScreening, &config),
+            None => env.storage().instance().remove(&ScreeningKey::AmlScreening),
+        }
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("aml_scrn"),
+            0,
+            &admin,
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("aml_scrn")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_aml_screening(env: Env) -> Option<AmlConfig> {
+        env.storage().instance().get(&ScreeningKey::AmlScreening)
+    }
+
+    /// Screens sender and recipient now; fails closed when screening is not
+    /// configured or the oracle is unreachable.
+    fn parties_pass_aml(env: &Env, escrow: &Escrow) -> bool {
+        let config: Option<AmlConfig> = env.storage().instance().get(&ScreeningKey::AmlScreening);
+        match config {
+            Some(config) => aml::screen_transaction(
+                env,
+                &config,
+                &escrow.sender,
+                &escrow.recipient,
+                escrow.amount,
+                0,
+            )
+            .map(|result| result.status == AmlStatus::Clear)
+            .unwrap_or(false),
+            None => false,
+        }
+    }
+
+    pub fn check_compliance(env: Env, escrow: Escrow) -> bool {
+        let override_exists: bool = env.storage().instance().get(&DataKey::EscrowComplianceOverride(escrow.escrow_id)).unwrap_or(false);
+        if override_exists {
+            return true;
+        }
+
+        let rules_opt: Option<Vec<ComplianceRule>> = env.storage().instance().get(&DataKey::ComplianceRules);
+        let rules = match rules_opt {
+            Some(r) => r,
+            None => return true,
+        };
+
+        for rule in rules.iter() {
+            let rule_passed = match rule.rule_type {
+                ComplianceRuleType::AmountThreshold => {
+                    escrow.amount < rule.threshold
+                }
+                ComplianceRuleType::Jurisdiction => {
+                    let sender_country: i128 = env.storage().instance().get(&DataKey::UserJurisdiction(escrow.sender.clone())).unwrap_or(0);
+                    let recipient_country: i128 = env.storage().instance().get(&DataKey::UserJurisdiction(escrow.recipient.clone())).unwrap_or(0);
+                    sender_country != rule.threshold && recipient_country != rule.threshold
+                }
+                ComplianceRuleType::RegulatoryRequirement => {
+                    if escrow.amount >= rule.threshold {
+                        escrow.kyc_compliant
+                    } else {
+                        true
+                    }
+                }
+            };
+            if !rule_passed {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Sets (or clears) the compliance officer, who may override KYC and
+    /// compliance holds alongside the admin.
+    pub fn set_compliance_officer(
+        env: Env,
+        admin: Address,
+        officer: Option<Address>,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        match &officer {
+            Some(officer) => env
+                .storage()
+                .instance()
+                .set(&DataKey::ComplianceOfficer, officer),
+            None => env.storage().instance().remove(&DataKey::ComplianceOfficer),
+        }
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("comp_off"),
+            0,
+            &admin,
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("comp_off")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_compliance_officer(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::ComplianceOfficer)
+    }
+
+    fn require_compliance_authority(env: &Env, caller: &Address) -> Result<(), Error> {
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if *caller == stored_admin {
+            return Ok(());
+        }
+        let officer: Option<Address> = env.storage().instance().get(&DataKey::ComplianceOfficer);
+        if officer.as_ref() == Some(caller) {
+            return Ok(());
+        }
+        Err(Error::Unauthorized)
+    }
+
+    pub fn admin_override_compliance(env: Env, admin: Address, escrow_id: u64) -> Result<(), Error> {
+        admin.require_auth();
+        Self::require_compliance_authority(&env, &admin)?;
+
+        let mut escrow: Escrow = env.storage().instance().get(&DataKey::Escrow(escrow_id)).ok_or(Error::EscrowNotFound)?;
+        escrow.compliant = true;
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+        env.storage().instance().set(&DataKey::EscrowComplianceOverride(escrow_id), &true);
+        Ok(())
+    }
+
+    pub fn add_to_whitelist(
+        env: Env,
+        admin: Address,
+        account: Address,
+        expiry: u64,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        Self::write_whitelist_record(&env, &admin, &account, KycStatus::Verified, expiry);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("kyc_add"),
+            0,
+            &admin,
+            0,
+            symbol_short!("na"),
+            EventData::AddressAction(symbol_short!("kyc_add"), account.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Whitelists up to `MAX_WHITELIST_BATCH` accounts in one call. The batch is
+    /// all-or-nothing and emits a single summary event.
+    pub fn batch_add_to_whitelist(
+        env: Env,
+        admin: Address,
+        accounts: Vec<Address>,
+        expiry: u64,
+    ) -> Result<u32, Error> {
+        Self::batch_update_whitelist(&env, &admin, &accounts, KycStatus::Verified, expiry)?;
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("kyc_badd"),
+            0,
+            &admin,
+            accounts.len() as i128,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("kyc_badd")),
+        );
+
+        Ok(accounts.len())
+    }
+
+    /// Removes up to `MAX_WHITELIST_BATCH` accounts from the whitelist in one
+    /// call, marking each as rejected.
+    pub fn batch_remove_from_whitelist(
+        env: Env,
+        admin: Address,
+        accounts: Vec<Address>,
+    ) -> Result<u32, Error> {
+        Self::batch_update_whitelist(&env, &admin, &accounts, KycStatus::Rejected, 0)?;
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("kyc_brem"),
+            0,
+            &admin,
+            accounts.len() as i128,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("kyc_brem")),
+        );
+
+        Ok(accounts.len())
+    }
+
+    fn batch_update_whitelist(
+        env: &Env,
+        admin: &Address,
+        accounts: &Vec<Address>,
+        status: KycStatus,
+        expiry: u64,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if *admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        if accounts.is_empty() {
+            return Err(Error::InvalidAmount);
+        }
+        if accounts.len() > MAX_WHITELIST_BATCH {
+            return Err(Error::RateLimitExceeded);
+        }
+
+        for account in accounts.iter() {
+            Self::write_whitelist_record(env, admin, &account, status, expiry);
+        }
+        Ok(())
+    }
+
+    /// Stores an admin-issued KYC record, keeping the account's current tier
+    /// while it stays verified.
+    fn write_whitelist_record(
+        env: &Env,
+        admin: &Address,
+        account: &Address,
+        status: KycStatus,
+        expiry: u64,
+    ) {
+        if status != KycStatus::Verified {
+            env.storage()
+                .persistent()
+                .remove(&KycTierKey::Tier(account.clone()));
+        }
+        let record = KycRecord {
+            account: account.clone(),
+            status,
+            verified_at: env.ledger().timestamp(),
+            issuer: admin.clone(),
+            expiry,
+        };
+        env.storage()
+            .persistent()
+            .set(&KycDataKey::Whitelist(account.clone()), &record);
+    }
+
+    /// Moves a whitelisted account to `tier`, which caps the escrows it can
+    /// create when a limit is configured for that tier.
+    pub fn set_kyc_tier(
+        env: Env,
+        admin: Address,
+        account: Address,
+        tier: u32,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&KycDataKey::Whitelist(account.clone()))
+        {
+            return Err(Error::KycFailed);
+        }
+        let old_tier = Self::get_kyc_tier(env.clone(), account.clone());
+        env.storage()
+            .persistent()
+            .set(&KycTierKey::Tier(account), &tier);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("kyc_tier"),
+            0,
+            &admin,
+            0,
+            symbol_short!("na"),
+            EventData::ValueChanged(symbol_short!("kyc_tier"), old_tier as i128, tier as i128),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_kyc_tier(env: Env, account: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&KycTierKey::Tier(account))
+            .unwrap_or(0)
+    }
+
+    /// Caps the escrow amount senders in `tier` may create; `None` removes the
+    /// cap. Tiers without a limit are unrestricted.
+    pub fn set_tier_limit(
+        env: Env,
+        admin: Address,
+        tier: u32,
+        max_amount: Option<i128>,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        let key = KycTierKey::TierLimit(tier);
+        match max_amount {
+            Some(limit) if limit <= 0 => return Err(Error::InvalidAmount),
+            Some(limit) => env.storage().instance().set(&key, &limit),
+            None => env.storage().instance().remove(&key),
+        }
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("tier_lim"),
+            tier as u64,
+            &admin,
+            max_amount.unwrap_or(0),
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("tier_lim")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_tier_limit(env: Env, tier: u32) -> Option<i128> {
+        env.storage().instance().get(&KycTierKey::TierLimit(tier))
+    }
+
+    pub fn remove_from_whitelist(env: Env, admin: Address, account: Address) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        Self::write_whitelist_record(&env, &admin, &account, KycStatus::Rejected, 0);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("kyc_rem"),
+            0,
+            &admin,
+            0,
+            symbol_short!("na"),
+            EventData::AddressAction(symbol_short!("kyc_rem"), account.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Allows at most `max_checks` proof verifications per account within
+    /// `window` seconds; `max_checks` of 0 removes the limit. Proofs with a
+    /// bad signature count too, so the limit throttles brute-force attempts.
+    pub fn set_kyc_check_limit(
+        env: Env,
+        admin: Address,
+        max_checks: u32,
+        window: u64,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        if max_checks == 0 {
+            env.storage().persistent().remove(&KycDataKey::CheckLimit);
+        } else {
+            if window == 0 {
+                return Err(Error::InvalidAmount);
+            }
+            env.storage()
+                .persistent()
+                .set(&KycDataKey::CheckLimit, &(max_checks, window));
+        }
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("kyc_lim"),
+            0,
+            &admin,
+            max_checks as i128,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("kyc_lim")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_kyc_check_limit(env: Env) -> Option<(u32, u64)> {
+        env.storage().persistent().get(&KycDataKey::CheckLimit)
+    }
+
+    /// Trusts `issuer` to sign KYC proofs with the ed25519 `public_key`.
+    pub fn add_trusted_issuer(
+        env: Env,
+        admin: Address,
+        issuer: Address,
+        public_key: BytesN<32>,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&KycDataKey::TrustedIssuer(issuer.clone()), &public_key);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("kyc_iss"),
+            0,
+            &admin,
+            0,
+            symbol_short!("na"),
+            EventData::AddressAction(symbol_short!("kyc_iss"), issuer),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_kyc_status(env: Env, account: Address) -> KycStatus {
+        let key = KycDataKey::Whitelist(account);
+        let record: Option<KycRecord> = env.storage().persistent().get(&key);
+        match record {
+            Some(r) => r.status,
+            None => KycStatus::Unknown,
+        }
+    }
+
+    /// Read-only pre-flight: whether `account`, as sender, would pass the KYC
+    /// check and the blocking amount-based compliance rules for `amount`.
+    pub fn is_kyc_eligible(env: Env, account: Address, amount: i128) -> bool {
+        let kyc_enabled: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::KycEnabled)
+            .unwrap_or(false);
+
+        let mut kyc_verified = false;
+        if kyc_enabled {
+            let config: KycConfig = match env.storage().instance().get(&DataKey::KycConfig) {
+                Some(c) => c,
+                None => return false,
+            };
+            kyc_verified = match kyc::check_kyc(&env, &config, &account, &account) {
+                Ok(result) => result.sender_verified,
+                Err(_) => false,
+            };
+            if !kyc_verified {
+                return false;
+            }
+            let tier = Self::get_kyc_tier(env.clone(), account.clone());
+            if Self::get_tier_limit(env.clone(), tier).is_some_and(|limit| amount > limit) {
+                return false;
+            }
+        }
+
+        let rules_opt: Option<Vec<ComplianceRule>> =
+            env.storage().instance().get(&DataKey::ComplianceRules);
+        if let Some(rules) = rules_opt {
+            for rule in rules.iter() {
+                if rule.action != ComplianceAction::Block {
+                    continue;
+                }
+                let rule_passed = match rule.rule_type {
+                    ComplianceRuleType::AmountThreshold => amount < rule.threshold,
+                    ComplianceRuleType::RegulatoryRequirement => {
+                        amount < rule.threshold || kyc_verified
+                    }
+                    // Depends on the counterparty, so it cannot be pre-checked here.
+                    ComplianceRuleType::Jurisdiction => true,
+                };
+                if !rule_passed {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    pub fn admin_override_kyc(env: Env, admin: Address, escrow_id: u64) -> Result<(), Error> {
+        admin.require_auth();
+        Self::require_compliance_authority(&env, &admin)?;
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        escrow.kyc_compliant = true;
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("kyc_ovr"),
+            escrow_id,
+            &admin,
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("kyc_ovr")),
+        );
+
+        Ok(())
+    }
+
+    /// Verifies a proof signed by `trusted_issuer` over
+    /// `kyc::proof_message(account, trusted_issuer, expiry)` and whitelists the
+    /// account until `expiry` (or the configured validity period when 0).
+    /// A signature that does not match returns `false` rather than an error
+    /// so the attempt stays counted against the check limit.
+    pub fn verify_kyc_proof(
+        env: Env,
+        account: Address,
+        proof_signature: BytesN<64>,
+        trusted_issuer: Address,
+        expiry: u64,
+    ) -> Result<bool, Error> {
+        let kyc_enabled: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::KycEnabled)
+            .unwrap_or(false);
+        if !kyc_enabled {
+            return Err(Error::KycNotConfigured);
+        }
+
+        let config: KycConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::KycConfig)
+            .ok_or(Error::KycNotConfigured)?;
+
+        kyc::record_proof_check(&env, &account).map_err(|_| Error::KycRateLimited)?;
+
+        match kyc::verify_proof(
+            &env,
+            &account,
+            &proof_signature,
+            &trusted_issuer,
+            expiry,
+        ) {
+            Ok(()) => {
+                let record = KycRecord {
+                    account: account.clone(),
+                    status: KycStatus::Verified,
+                    verified_at: env.ledger().timestamp(),
+                    issuer: trusted_issuer,
+                    expiry: if expiry > 0 {
+                        expiry
+                    } else if config.proof_validity_period > 0 {
+                        env.ledger().timestamp() + config.proof_validity_period
+                    } else {
+                        0
+                    },
+                };
+                env.storage()
+                    .persistent()
+                    .set(&KycDataKey::Whitelist(account.clone()), &record);
+
+                events::emit(
+                    &env,
+                    symbol_short!("escrow"),
+                    symbol_short!("kyc_ok"),
+                    0,
+                    &account,
+                    0,
+                    symbol_short!("na"),
+                    EventData::AddressAction(symbol_short!("kyc_ok"), account.clone()),
+                );
+                Ok(true)
+            }
+            Err(KycError::InvalidProof) => Ok(false),
+            Err(_) => Err(Error::KycFailed),
+        }
+    }
+
+    pub fn create_escrow(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        amount: i128,
+        asset: Asset,
+        expiration_timestamp: u64,
+        memo: String,
+    ) -> Result<u64, Error> {
+        if upgradeable::is_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        sender.require_auth();
+        Self::enforce_rate_limit(&env, &sender, FunctionType::Deposit)?;
+
+        Self::create_escrow_internal(
+            env,
+            sender,
+            EscrowCreateRequest {
+                recipient,
+                amount,
+                asset,
+                expiration_timestamp,
+                memo,
+            },
+            None,
+        )
+    }
+
+    /// Creates an escrow with no fixed recipient. Whoever presents a preimage
+    /// of `hash_lock` via `claim` before `expiration` receives the funds;
+    /// after that the sender can refund it as usual.
+    pub fn create_claimable(
+        env: Env,
+        sender: Address,
+        amount: i128,
+        asset: Asset,
+        hash_lock: BytesN<32>,
+        expiration: u64,
+    ) -> Result<u64, Error> {
+        if upgradeable::is_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        sender.require_auth();
+        Self::enforce_rate_limit(&env, &sender, FunctionType::Deposit)?;
+
+        // The contract stands in as recipient until the escrow is claimed.
+        let escrow_id = Self::create_escrow_internal(
+            env.clone(),
+            sender,
+            EscrowCreateRequest {
+                recipient: env.current_contract_address(),
+                amount,
+                asset,
+                expiration_timestamp: expiration,
+                memo: String::from_str(&env, ""),
+            },
+            None,
+        )?;
+        env.storage()
+            .instance()
+            .set(&ClaimKey::HashLock(escrow_id), &hash_lock);
+
+        Ok(escrow_id)
+    }
+
+    /// Releases a claimable escrow to `claimer` if `sha256(preimage)` matches
+    /// its hash lock. The usual release checks and fees apply.
+    pub fn claim(
+        env: Env,
+        escrow_id: u64,
+        claimer: Address,
+        preimage: Bytes,
+        token_address: Address,
+    ) -> Result<(), Error> {
+        claimer.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+        let hash_lock: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&ClaimKey::HashLock(escrow_id))
+            .ok_or(Error::InvalidStatus)?;
+
+        if !Self::hash_lock_active(&env, escrow_id) {
+            return Err(Error::InvalidStatus);
+        }
+        // Claimable escrows go to whoever claims; HTLCs only to their recipient.
+        let open_claim = escrow.recipient == env.current_contract_address();
+        if !open_claim && claimer != escrow.recipient {
+            return Err(Error::UnauthorizedCaller);
+        }
+        if claimer == escrow.sender {
+            return Err(Error::SameSenderRecipient);
+        }
+        if env.ledger().timestamp() > escrow.release_conditions.expiration_timestamp {
+            return Err(Error::Expired);
+        }
+        if BytesN::from(env.crypto().sha256(&preimage)) != hash_lock {
+            return Err(Error::HashMismatch);
+        }
+
+        env.storage()
+            .instance()
+            .set(&ClaimKey::Preimage(escrow_id), &preimage);
+        if open_claim {
+            escrow.recipient = claimer.clone();
+            Self::store_escrow(&env, escrow_id, &mut escrow);
+        }
+
+        // The preimage is published so the counterparty chain can claim too.
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("claimed"),
+            escrow_id,
+            &claimer,
+            escrow.deposited_amount,
+            symbol_short!("na"),
+            EventData::PreimageRevealed(escrow_id, preimage),
+        );
+
+        Self::enforce_rate_limit(&env, &claimer, FunctionType::Release)?;
+        Self::release_escrow_internal(env, escrow_id, claimer, token_address, 0)
+    }
+
+    pub fn get_hash_lock(env: Env, escrow_id: u64) -> Option<BytesN<32>> {
+        env.storage().instance().get(&ClaimKey::HashLock(escrow_id))
+    }
+
+    pub fn get_revealed_preimage(env: Env, escrow_id: u64) -> Option<Bytes> {
+        env.storage().instance().get(&ClaimKey::Preimage(escrow_id))
+    }
+
+    /// True while a hash-locked escrow is still waiting for its preimage.
+    fn hash_lock_active(env: &Env, escrow_id: u64) -> bool {
+        env.storage().instance().has(&ClaimKey::HashLock(escrow_id))
+            && !env.storage().instance().has(&ClaimKey::Preimage(escrow_id))
+    }
+
+    /// Keeps the sender's funds in a hash-locked escrow until its timelock
+    /// passes, so they cannot be pulled back while the recipient can still
+    /// claim them.
+    fn check_timelock(env: &Env, escrow: &Escrow) -> Result<(), Error> {
+        if Self::hash_lock_active(env, escrow.escrow_id)
+            && env.ledger().timestamp() <= escrow.release_conditions.expiration_timestamp
+        {
+            return Err(Error::TimestampNotReached);
+        }
+        Ok(())
+    }
+
+    /// Creates a hash-timelocked escrow for cross-chain swaps: `recipient`
+    /// can claim with the preimage of `hash_lock` until `timelock`, after
+    /// which only the sender can refund.
+    pub fn create_htlc(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        amount: i128,
+        asset: Asset,
+        hash_lock: BytesN<32>,
+        timelock: u64,
+    ) -> Result<u64, Error> {
+        if upgradeable::is_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        sender.require_auth();
+        Self::enforce_rate_limit(&env, &sender, FunctionType::Deposit)?;
+
+        if timelock <= env.ledger().timestamp() {
+            return Err(Error::Expired);
+        }
+
+        let escrow_id = Self::create_escrow_internal(
+            env.clone(),
+            sender,
+            EscrowCreateRequest {
+                recipient,
+                amount,
+                asset,
+                expiration_timestamp: timelock,
+                memo: String::from_str(&env, ""),
+            },
+            None,
+        )?;
+        env.storage()
+            .instance()
+            .set(&ClaimKey::HashLock(escrow_id), &hash_lock);
+
+        Ok(escrow_id)
+    }
+
+    /// Creates an escrow that inherits the fee, KYC and expiration defaults
+    /// registered for `category`.
+    pub fn create_escrow_in_category(
+        env: Env,
+        sender: Address,
+        request: EscrowCreateRequest,
+        category: Symbol,
+    ) -> Result<u64, Error> {
+        if upgradeable::is_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        sender.require_auth();
+        Self::enforce_rate_limit(&env, &sender, FunctionType::Deposit)?;
+
+        Self::create_escrow_internal(env, sender, request, Some(category))
+    }
+
+    /// Creates several escrows from one sender in a single transaction.
+    ///
+    /// Each request goes through the same checks as `create_escrow` (asset
+    /// support, KYC, compliance rules, AML hold). Any failure reverts the
+    /// whole batch. Batches larger than `MAX_BATCH_ESCROWS` are rejected
+    /// with `RateLimitExceeded`.
+    pub fn batch_create_escrows(
+        env: Env,
+        sender: Address,
+        requests: Vec<EscrowCreateRequest>,
+    ) -> Result<Vec<u64>, Error> {
+        if upgradeable::is_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        sender.require_auth();
+
+        if requests.is_empty() {
+            return Err(Error::InvalidAmount);
+        }
+        if requests.len() > MAX_BATCH_ESCROWS {
+            return Err(Error::RateLimitExceeded);
+        }
+        Self::enforce_rate_limit(&env, &sender, FunctionType::Deposit)?;
+
+        let mut ids = Vec::new(&env);
+        for request in requests.iter() {
+            let id = Self::create_escrow_internal(env.clone(), sender.clone(), request, None)?;
+            ids.push_back(id);
+        }
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("batch_cre"),
+            0,
+            &sender,
+            ids.len() as i128,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("batch_cre")),
+        );
+
+        Ok(ids)
+    }
+
+    fn create_escrow_internal(
+        env: Env,
+        sender: Address,
+        request: EscrowCreateRequest,
+        category: Option<Symbol>,
+    ) -> Result<u64, Error> {
+        let EscrowCreateRequest {
+            recipient,
+            amount,
+            asset,
+            expiration_timestamp,
+            memo,
+        } = request;
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let category_rules: Option<CategoryRules> = match &category {
+            Some(category) => Some(
+                env.storage()
+                    .instance()
+                    .get(&CategoryKey::CategoryRules(category.clone()))
+                    .ok_or(Error::TemplateNotFound)?,
+            ),
+            None => None,
+        };
+
+        // With a default offset configured, an expiration of 0 means "the
+        // default window from now"; otherwise 0 is kept as given. A category
+        // offset takes precedence over the contract-wide one.
+        let default_offset: Option<u64> = match &category_rules {
+            Some(rules) if rules.expiration_offset > 0 => Some(rules.expiration_offset),
+            _ => env
+                .storage()
+                .instance()
+                .get(&DataKey::DefaultExpirationOffset),
+        };
+        let expiration_timestamp = if let (0, Some(offset)) = (expiration_timestamp, default_offset)
+        {
+            let expiration = env
+                .ledger()
+                .timestamp()
+                .checked_add(offset)
+                .ok_or(Error::ArithmeticOverflow)?;
+            if expiration <= env.ledger().timestamp() {
+                return Err(Error::Expired);
+            }
+            expiration
+        } else {
+            expiration_timestamp
+        };
+
+        if sender == recipient {
+            return Err(Error::SameSenderRecipient);
+        }
+
+        if !Self::is_supported_asset(&env, &asset) {
+            return Err(Error::InvalidAsset);
+        }
+        Self::enforce_open_escrow_cap(&env, &sender)?;
+
+        let kyc_enabled: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::KycEnabled)
+            .unwrap_or(false)
+            || category_rules
+                .as_ref()
+                .is_some_and(|rules| rules.kyc_required);
+        let mut kyc_compliant = false;
+
+        if kyc_enabled {
+            let config: KycConfig = env
+                .storage()
+                .instance()
+                .get(&DataKey::KycConfig)
+                .ok_or(Error::KycNotConfigured)?;
+
+            let kyc_result = kyc::check_kyc(&env, &config, &sender, &recipient);
+
+            match kyc_result {
+                Ok(result) => {
+                    if !result.sender_verified || !result.recipient_verified {
+                        events::emit(
+                            &env,
+                            symbol_short!("escrow"),
+                            symbol_short!("kyc_fail"),
+                            0,
+                            &sender,
+                            0,
+                            symbol_short!("na"),
+                            EventData::PairAction(
+                                symbol_short!("kyc_fail"),
+                                sender.clone(),
+                                recipient.clone(),
+                            ),
+                        );
+                        return Err(Error::KycFailed);
+                    }
+
+                    let tier = Self::get_kyc_tier(env.clone(), sender.clone());
+                    if let Some(limit) = Self::get_tier_limit(env.clone(), tier) {
+                        if amount > limit {
+                            return Err(Error::KycLimitExceeded);
+                        }
+                    }
+                    kyc_compliant = true;
+
+                    events::emit(
+                        &env,
+                        symbol_short!("escrow"),
+                        symbol_short!("kyc_pass"),
+                        0,
+                        &sender,
+                        0,
+                        symbol_short!("na"),
+                        EventData::PairAction(
+                            symbol_short!("kyc_pass"),
+                            sender.clone(),
+                            recipient.clone(),
+                        ),
+                    );
+                }
+                Err(_) => {
+                    return Err(Error::KycFailed);
+                }
+            }
+        }
+
+        let mut counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EscrowCounter)
+            .unwrap_or(0u64);
+        counter = counter.checked_add(1).ok_or(Error::CounterOverflow)?;
+
+        let mut escrow_assets = Vec::new(&env);
+        escrow_assets.push_back(asset.clone());
+        let mut amounts = Self::empty_asset_amount_map(&env);
+        amounts.set(asset.clone(), amount);
+        let mut deposited_amounts = Self::empty_asset_amount_map(&env);
+        deposited_amounts.set(asset.clone(), 0);
+        let mut released_amounts = Self::empty_asset_amount_map(&env);
+        released_amounts.set(asset.clone(), 0);
+        let mut refunded_amounts = Self::empty_asset_amount_map(&env);
+        refunded_amounts.set(asset.clone(), 0);
+
+        let mut escrow = Escrow {
+            sender: sender.clone(),
+            recipient,
+            amount,
+            deposited_amount: 0,
+            released_amount: 0,
+            refunded_amount: 0,
+            asset,
+            assets: escrow_assets,
+            amounts,
+            deposited_amounts,
+            released_amounts,
+            refunded_amounts,
+            release_conditions: ReleaseCondition {
+                expiration_timestamp,
+                recipient_approval: false,
+                oracle_confirmation: false,
+                conditions: Vec::new(&env),
+                operator: ConditionOperator::And,
+                min_approvals: 1,
+                current_approvals: 0,
+            },
+            status: EscrowStatus::Pending,
+            created_at: env.ledger().timestamp(),
+            last_deposit_at: 0,
+            release_timestamp: 0,
+            refund_timestamp: 0,
+            escrow_id: counter,
+            memo,
+            allow_partial_release: false,
+            multi_party_enabled: false,
+            kyc_compliant,
+            compliant: true,
+            milestones: Vec::new(&env),
+            allowed_funding_sources: Vec::new(&env),
+            sequence: 0,
+            two_sided_release: false,
+        };
+
+        // Auto-check compliance on creation
+        let rules_opt: Option<Vec<ComplianceRule>> = env.storage().instance().get(&DataKey::ComplianceRules);
+        if let Some(rules) = rules_opt {
+            for rule in rules.iter() {
+                let rule_passed = match rule.rule_type {
+                    ComplianceRuleType::AmountThreshold => {
+                        escrow.amount < rule.threshold
+                    }
+                    ComplianceRuleType::Jurisdiction => {
+                        let sender_country: i128 = env.storage().instance().get(&DataKey::UserJurisdiction(escrow.sender.clone())).unwrap_or(0);
+                        let recipient_country: i128 = env.storage().instance().get(&DataKey::UserJurisdiction(escrow.recipient.clone())).unwrap_or(0);
+                        sender_country != rule.threshold && recipient_country != rule.threshold
+                    }
+                    ComplianceRuleType::RegulatoryRequirement => {
+                        if escrow.amount >= rule.threshold {
+                            escrow.kyc_compliant
+                        } else {
+                            true
+                        }
+                    }
+                };
+
+                if !rule_passed {
+                    match rule.action {
+                        ComplianceAction::Block => {
+                            return Err(Error::NonCompliant);
+                        }
+                        ComplianceAction::Flag => {
+                            escrow.compliant = false;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Hold escrows from senders with an open AML flag on the linked hub.
+        // An unreachable hub is treated as flagged, matching AML review behavior.
+        let aml_hub: Option<Address> = env.storage().instance().get(&DataKey::AmlHub);
+        if let Some(hub) = aml_hub {
+            let flagged = aml::is_sender_flagged(&env, &hub, &sender).unwrap_or(true);
+            if flagged {
+                escrow.compliant = false;
+                events::emit(
+                    &env,
+                    symbol_short!("escrow"),
+                    symbol_short!("aml_hold"),
+                    counter,
+                    &sender,
+                    amount,
+                    symbol_short!("held"),
+                    EventData::AddressAction(symbol_short!("aml_hold"), sender.clone()),
+                );
+            }
+        }
+
+        Self::store_escrow(&env, counter, &mut escrow);
+        env.storage()
+            .instance()
+            .set(&DataKey::EscrowCounter, &counter);
+        Self::index_sender_escrow(&env, &escrow.sender, counter);
+        if let Some(category) = category {
+            env.storage()
+                .instance()
+                .set(&CategoryKey::EscrowCategory(counter), &category);
+        }
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("created"),
+            counter,
+            &escrow.sender,
+            escrow.amount,
+            symbol_short!("pending"),
+            EventData::EscrowCreated(
+                counter,
+                escrow.sender.clone(),
+                escrow.recipient.clone(),
+                AssetRef {
+                    code: escrow.asset.code.clone(),
+                    issuer: escrow.asset.issuer.clone(),
+                },
+                escrow.amount,
+            ),
+        );
+
+        Self::notify_external(
+            &env,
+            NotificationPayload {
+                escrow_id: counter,
+                event_type: EventType::Created,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(counter)
+    }
+
+    pub fn create_multi_asset_escrow(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        assets: Vec<Asset>,
+        amounts: Map<Asset, i128>,
+        expiration_timestamp: u64,
+        memo: String,
+    ) -> Result<u64, Error> {
+        if upgradeable::is_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        sender.require_auth();
+        Self::enforce_rate_limit(&env, &sender, FunctionType::Deposit)?;
+
+        if sender == recipient {
+            return Err(Error::SameSenderRecipient);
+        }
+        if assets.len() == 0 {
+            return Err(Error::InvalidAsset);
+        }
+        Self::enforce_open_escrow_cap(&env, &sender)?;
+
+        let mut total_amount = 0i128;
+        let mut deposited_amounts = Self::empty_asset_amount_map(&env);
+        let mut released_amounts = Self::empty_asset_amount_map(&env);
+        let mut refunded_amounts = Self::empty_asset_amount_map(&env);
+
+        for asset in assets.iter() {
+            if !Self::is_supported_asset(&env, &asset) {
+                return Err(Error::InvalidAsset);
+            }
+            let amount = amounts.get(asset.clone()).ok_or(Error::InvalidAmount)?;
+            if amount <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+            total_amount = total_amount
+                .checked_add(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            deposited_amounts.set(asset.clone(), 0);
+            released_amounts.set(asset.clone(), 0);
+            refunded_amounts.set(asset.clone(), 0);
+        }
+
+        let mut counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EscrowCounter)
+            .unwrap_or(0u64);
+        counter = counter.checked_add(1).ok_or(Error::CounterOverflow)?;
+
+        let primary_asset = assets.get(0).unwrap();
+        let primary_amount = amounts.get(primary_asset.clone()).unwrap();
+        let mut escrow = Escrow {
+            sender: sender.clone(),
+            recipient,
+            amount: total_amount,
+            deposited_amount: 0,
+            released_amount: 0,
+            refunded_amount: 0,
+            asset: primary_asset,
+            assets,
+            amounts,
+            deposited_amounts,
+            released_amounts,
+            refunded_amounts,
+            release_conditions: ReleaseCondition {
+                expiration_timestamp,
+                recipient_approval: false,
+                oracle_confirmation: false,
+                conditions: Vec::new(&env),
+                operator: ConditionOperator::And,
+                min_approvals: 1,
+                current_approvals: 0,
+            },
+            status: EscrowStatus::Pending,
+            created_at: env.ledger().timestamp(),
+            last_deposit_at: 0,
+            release_timestamp: 0,
+            refund_timestamp: 0,
+            escrow_id: counter,
+            memo,
+            allow_partial_release: false,
+            multi_party_enabled: false,
+            kyc_compliant: false,
+            compliant: true,
+            milestones: Vec::new(&env),
+            allowed_funding_sources: Vec::new(&env),
+            sequence: 0,
+            two_sided_release: false,
+        };
+
+        Self::store_escrow(&env, counter, &mut escrow);
+        env.storage()
+            .instance()
+            .set(&DataKey::EscrowCounter, &counter);
+        Self::index_sender_escrow(&env, &escrow.sender, counter);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("created"),
+            counter,
+            &escrow.sender,
+            primary_amount,
+            symbol_short!("pending"),
+            EventData::EscrowCreated(
+                counter,
+                escrow.sender.clone(),
+                escrow.recipient.clone(),
+                AssetRef {
+                    code: escrow.asset.code.clone(),
+                    issuer: escrow.asset.issuer.clone(),
+                },
+                primary_amount,
+            ),
+        );
+
+        Self::notify_external(
+            &env,
+            NotificationPayload {
+                escrow_id: counter,
+                event_type: EventType::Created,
+                amount: total_amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(counter)
+    }
+
+    pub fn set_funding_sources(
+        env: Env,
+        escrow_id: u64,
+        sender: Address,
+        sources: Vec<Address>,
+    ) -> Result<(), Error> {
+        sender.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        if sender != escrow.sender {
+            return Err(Error::Unauthorized);
+        }
+
+        if escrow.status != EscrowStatus::Pending {
+            return Err(Error::InvalidStatus);
+        }
+
+        escrow.allowed_funding_sources = sources;
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("fund_src"),
+            escrow_id,
+            &sender,
+            escrow.allowed_funding_sources.len() as i128,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("fund_src")),
+        );
+
+        Ok(())
+    }
+
+    pub fn deposit(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        amount: i128,
+        token_address: Address,
+    ) -> Result<(), Error> {
+        if upgradeable::is_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        caller.require_auth();
+        Self::enforce_rate_limit(&env, &caller, FunctionType::Deposit)?;
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        if !escrow.compliant {
+            return Err(Error::NonCompliant);
+        }
+
+        Self::check_funding_source(&escrow, &caller)?;
+
+        if escrow.status != EscrowStatus::Pending && escrow.status != EscrowStatus::Funded {
+            return Err(Error::EscrowNotPending);
+        }
+
+        let new_deposited = escrow
+            .deposited_amount
+            .checked_add(amount)
+            .ok_or(Error::DepositOverflow)?;
+
+        if new_deposited > escrow.amount {
+            return Err(Error::InsufficientAmount);
+        }
+
+        Self::bind_escrow_token(&env, escrow_id, &escrow.asset, &token_address)?;
+
+        let token_client = token::Client::new(&env, &token_address);
+        let contract_address = env.current_contract_address();
+
+        token_client.transfer(&caller, &contract_address, &amount);
+        Self::adjust_tvl(&env, &escrow.asset.code, amount);
+
+        escrow.deposited_amount = new_deposited;
+        escrow
+            .deposited_amounts
+            .set(escrow.asset.clone(), new_deposited);
+        escrow.last_deposit_at = env.ledger().timestamp();
+
+        if escrow.deposited_amount == escrow.amount {
+            escrow.status = EscrowStatus::Funded;
+        }
+
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        let deposit_status = if escrow.deposited_amount == escrow.amount {
+            symbol_short!("funded")
+        } else {
+            symbol_short!("pending")
+        };
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("deposit"),
+            escrow_id,
+            &caller,
+            amount,
+            deposit_status,
+            EventData::EscrowDeposited(escrow_id, amount, escrow.deposited_amount),
+        );
+
+        Self::notify_external(
+            &env,
+            NotificationPayload {
+                escrow_id,
+                event_type: EventType::Deposit,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Returns part of a pending escrow's deposit to the sender. Only the
+    /// portion above the highest `MinDeposited` condition can be withdrawn.
+    pub fn withdraw_excess_deposit(
+        env: Env,
+        escrow_id: u64,
+        sender: Address,
+        token_address: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        if upgradeable::is_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        sender.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        if sender != escrow.sender {
+            return Err(Error::WrongSender);
+        }
+        if escrow.status != EscrowStatus::Pending {
+            return Err(Error::EscrowNotPending);
+        }
+        Self::check_timelock(&env, &escrow)?;
+        Self::check_escrow_token(&env, escrow_id, &token_address)?;
+
+        let excess = escrow
+            .deposited_amount
+            .checked_sub(Self::required_deposit(&escrow))
+            .ok_or(Error::ArithmeticOverflow)?;
+        if amount > excess {
+            return Err(Error::InsufficientFunds);
+        }
+
+        let token_client = token::Client::new(&env, &token_address);
+        token_client.transfer(&env.current_contract_address(), &sender, &amount);
+        Self::adjust_tvl(&env, &escrow.asset.code, -amount);
+
+        let previous = escrow.deposited_amount;
+        escrow.deposited_amount = previous
+            .checked_sub(amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        escrow
+            .deposited_amounts
+            .set(escrow.asset.clone(), escrow.deposited_amount);
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("dep_wdraw"),
+            escrow_id,
+            &sender,
+            amount,
+            symbol_short!("pending"),
+            EventData::ValueChanged(
+                symbol_short!("dep_wdraw"),
+                previous,
+                escrow.deposited_amount,
+            ),
+        );
+
+        Ok(())
+    }
+
+    /// The deposit a pending escrow must keep: the highest `MinDeposited`
+    /// threshold among its conditions, capped at the escrow amount.
+    fn required_deposit(escrow: &Escrow) -> i128 {
+        let mut required = 0i128;
+        for condition in escrow.release_conditions.conditions.iter() {
+            if condition.condition_type == ConditionType::MinDeposited {
+                required = required.max(condition.threshold_value);
+            }
+        }
+        required.min(escrow.amount).max(0)
+    }
+
+    pub fn deposit_asset(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        asset: Asset,
+        amount: i128,
+        token_address: Address,
+    ) -> Result<(), Error> {
+        if upgradeable::is_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        caller.require_auth();
+        Self::enforce_rate_limit(&env, &caller, FunctionType::Deposit)?;
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        if !escrow.compliant {
+            return Err(Error::NonCompliant);
+        }
+
+        Self::check_funding_source(&escrow, &caller)?;
+        if escrow.status != EscrowStatus::Pending && escrow.status != EscrowStatus::Funded {
+            return Err(Error::EscrowNotPending);
+        }
+
+        let required_amount = escrow
+            .amounts
+            .get(asset.clone())
+            .ok_or(Error::InvalidAsset)?;
+        Self::check_asset_token(&env, &asset, &token_address)?;
+        let current_deposited = escrow.deposited_amounts.get(asset.clone()).unwrap_or(0i128);
+        let new_asset_deposit = current_deposited
+            .checked_add(amount)
+            .ok_or(Error::DepositOverflow)?;
+        if new_asset_deposit > required_amount {
+            return Err(Error::InsufficientAmount);
+        }
+
+        let token_client = token::Client::new(&env, &token_address);
+        let contract_address = env.current_contract_address();
+        token_client.transfer(&caller, &contract_address, &amount);
+        Self::adjust_tvl(&env, &asset.code, amount);
+
+        escrow
+            .deposited_amounts
+            .set(asset.clone(), new_asset_deposit);
+        escrow.deposited_amount = escrow
+            .deposited_amount
+            .checked_add(amount)
+            .ok_or(Error::DepositOverflow)?;
+        escrow.last_deposit_at = env.ledger().timestamp();
+
+        let mut fully_funded = true;
+        for required_asset in escrow.assets.iter() {
+            let required_asset: Asset = required_asset;
+            let required: i128 = escrow.amounts.get(required_asset.clone()).unwrap_or(0i128);
+            let deposited: i128 = escrow
+                .deposited_amounts
+                .get(required_asset.clone())
+                .unwrap_or(0i128);
+            if deposited < required {
+                fully_funded = false;
+                break;
+            }
+        }
+        if fully_funded {
+            escrow.status = EscrowStatus::Funded;
+        }
+
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("deposit"),
+            escrow_id,
+            &caller,
+            amount,
+            if fully_funded {
+                symbol_short!("funded")
+            } else {
+                symbol_short!("pending")
+            },
+            EventData::EscrowDeposited(escrow_id, amount, escrow.deposited_amount),
+        );
+
+        Self::notify_external(
+            &env,
+            NotificationPayload {
+                escrow_id,
+                event_type: EventType::Deposit,
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn get_escrow(env: Env, escrow_id: u64) -> Option<Escrow> {
+        env.storage().instance().get(&DataKey::Escrow(escrow_id))
+    }
+
+    /// Pages through `sender`'s escrows in creation order, returning up to
+    /// `limit` (capped at 50) escrows with ids greater than `start_after`.
+    pub fn get_escrows_by_sender(
+        env: Env,
+        sender: Address,
+        start_after: u64,
+        limit: u32,
+    ) -> Result<Vec<Escrow>, Error> {
+        if limit == 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let limit = limit.min(MAX_PAGE_SIZE);
+
+        let ids: Vec<u64> = env
+            .storage()
+            .instance()
+            .get(&DataKey::SenderEscrows(sender))
+            .unwrap_or(Vec::new(&env));
+        let mut results = Vec::new(&env);
+        for id in ids.iter().filter(|id| *id > start_after) {
+            if results.len() >= limit {
+                break;
+            }
+            if let Some(escrow) = env
+                .storage()
+                .instance()
+                .get::<_, Escrow>(&DataKey::Escrow(id))
+            {
+                results.push_back(escrow);
+            }
+        }
+        Ok(results)
+    }
+
+    pub fn query_escrows_by_sender(
+        env: Env,
+        sender: Address,
+        limit: u32,
+        offset: u32,
+    ) -> Vec<Escrow> {
+        let mut results = Vec::new(&env);
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EscrowCounter)
+            .unwrap_or(0u64);
+        let mut skipped = 0u32;
+        for id in 1..=counter {
+            if let Some(escrow) = env
+                .storage()
+                .instance()
+                .get::<_, Escrow>(&DataKey::Escrow(id))
+            {
+                if escrow.sender == sender {
+                    if skipped < offset {
+                        skipped += 1;
+                    } else if results.len() < limit {
+                        results.push_back(escrow);
+                    }
+                }
+            }
+        }
+        results
+    }
+
+    pub fn query_escrows_by_recipient(
+        env: Env,
+        recipient: Address,
+        limit: u32,
+        offset: u32,
+    ) -> Vec<Escrow> {
+        let mut results = Vec::new(&env);
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EscrowCounter)
+            .unwrap_or(0u64);
+        let mut skipped = 0u32;
+        for id in 1..=counter {
+            if let Some(escrow) = env
+                .storage()
+                .instance()
+                .get::<_, Escrow>(&DataKey::Escrow(id))
+            {
+                if escrow.recipient == recipient {
+                    if skipped < offset {
+                        skipped += 1;
+                    } else if results.len() < limit {
+                        results.push_back(escrow);
+                    }
+                }
+            }
+        }
+        results
+    }
+
+    /// Lists the recipient's escrows that `release_escrow` would currently
+    /// accept. Scans at most 50 ids from `start` and returns at most `limit`
+    /// (capped at 50) ids, plus the id to pass as `start` for the next page,
+    /// or 0 once every escrow has been scanned.
+    pub fn get_actionable_escrows(
+        env: Env,
+        recipient: Address,
+        start: u64,
+        limit: u32,
+    ) -> (Vec<u64>, u64) {
+        let limit = limit.min(MAX_PAGE_SIZE);
+        let mut results = Vec::new(&env);
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EscrowCounter)
+            .unwrap_or(0u64);
+        let first = start.max(1);
+        let last = counter.min(first.saturating_add(MAX_PAGE_SIZE as u64 - 1));
+        for id in first..=last {
+            if results.len() >= limit {
+                return (results, id);
+            }
+            if let Some(escrow) = env
+                .storage()
+                .instance()
+                .get::<_, Escrow>(&DataKey::Escrow(id))
+            {
+                if escrow.recipient == recipient && Self::can_release(&env, &escrow) {
+                    results.push_back(id);
+                }
+            }
+        }
+        let next = if last < counter { last + 1 } else { 0 };
+        (results, next)
+    }
+
+    fn can_release(env: &Env, escrow: &Escrow) -> bool {
+        if !escrow.compliant {
+            return false;
+        }
+        if escrow.status != EscrowStatus::Approved && escrow.status != EscrowStatus::Funded {
+            return false;
+        }
+        if env.ledger().timestamp() > escrow.release_conditions.expiration_timestamp {
+            return false;
+        }
+        if !Self::handshake_complete(env, escrow) {
+            return false;
+        }
+        if escrow.deposited_amount <= escrow.released_amount {
+            return false;
+        }
+        if escrow.multi_party_enabled {
+            let config_opt: Option<MultiPartyConfig> = env
+                .storage()
+                .instance()
+                .get(&DataKey::EscrowApprovals(escrow.escrow_id));
+            match config_opt {
+                Some(config) => {
+                    if config.approval_timeout > 0
+                        && env.ledger().timestamp() > config.approval_timeout
+                    {
+                        return false;
+                    }
+                    if config.approvals.len() < Self::effective_quorum(&config) {
+                        return false;
+                    }
+                }
+                None => return false,
+            }
+        }
+
+        let mut passed_count = 0u32;
+        let mut required_count = 0u32;
+        let mut required_failed = false;
+        let current_time = env.ledger().timestamp();
+        for condition in escrow.release_conditions.conditions.iter() {
+            if condition.required {
+                required_count += 1;
+            }
+            if Self::condition_is_current(&condition, current_time) {
+                passed_count += 1;
+            } else if condition.required {
+                required_failed = true;
+            }
+        }
+        if escrow.release_conditions.conditions.is_empty() {
+            return true;
+        }
+        match escrow.release_conditions.operator {
+            ConditionOperator::And => {
+                !required_failed && (required_count == 0 || passed_count >= required_count)
+            }
+            ConditionOperator::Or => passed_count > 0,
+        }
+    }
+
+    pub fn query_escrows_by_status(
+        env: Env,
+        status: EscrowStatus,
+        limit: u32,
+        offset: u32,
+    ) -> Vec<Escrow> {
+        let mut results = Vec::new(&env);
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EscrowCounter)
+            .unwrap_or(0u64);
+        let mut skipped = 0u32;
+        for id in 1..=counter {
+            if let Some(escrow) = env
+                .storage()
+                .instance()
+                .get::<_, Escrow>(&DataKey::Escrow(id))
+            {
+                if escrow.status == status {
+                    if skipped < offset {
+                        skipped += 1;
+                    } else if results.len() < limit {
+                        results.push_back(escrow);
+                    }
+                }
+            }
+        }
+        results
+    }
+
+    pub fn query_escrows_by_date_range(
+        env: Env,
+        start: u64,
+        end: u64,
+        limit: u32,
+        offset: u32,
+    ) -> Vec<Escrow> {
+        let mut results = Vec::new(&env);
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EscrowCounter)
+            .unwrap_or(0u64);
+        let mut skipped = 0u32;
+        for id in 1..=counter {
+            if let Some(escrow) = env
+                .storage()
+                .instance()
+                .get::<_, Escrow>(&DataKey::Escrow(id))
+            {
+                if escrow.created_at >= start && escrow.created_at <= end {
+                    if skipped < offset {
+                        skipped += 1;
+                    } else if results.len() < limit {
+                        results.push_back(escrow);
+                    }
+                }
+            }
+        }
+        results
+    }
+
+    pub fn query_escrows_by_amount_range(
+        env: Env,
+        min_amount: i128,
+        max_amount: i128,
+        limit: u32,
+        offset: u32,
+    ) -> Vec<Escrow> {
+        let mut results = Vec::new(&env);
+        let counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EscrowCounter)
+            .unwrap_or(0u64);
+        let mut skipped = 0u32;
+        for id in 1..=counter {
+            if let Some(escrow) = env
+                .storage()
+                .instance()
+                .get::<_, Escrow>(&DataKey::Escrow(id))
+            {
+                if escrow.amount >= min_amount && escrow.amount <= max_amount {
+                    if skipped < offset {
+                        skipped += 1;
+                    } else if results.len() < limit {
+                        results.push_back(escrow);
+                    }
+                }
+            }
+        }
+        results
+    }
+
+    pub fn register_notification_hook(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        config: NotificationConfig,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::validate_notification_config(&config)?;
+
+        let escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != escrow.sender && caller != escrow.recipient && caller != admin {
+            return Err(Error::UnauthorizedCaller);
+        }
+
+        let mut hooks: Vec<NotificationConfig> = env
+            .storage()
+            .instance()
+            .get(&DataKey::NotificationHooks(escrow_id))
+            .unwrap_or(Vec::new(&env));
+        if hooks.len() >= MAX_HOOKS {
+            return Err(Error::InvalidStatus);
+        }
+        hooks.push_back(config);
+        env.storage()
+            .instance()
+            .set(&DataKey::NotificationHooks(escrow_id), &hooks);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("hook_reg"),
+            escrow_id,
+            &caller,
+            hooks.len() as i128,
+            symbol_short!("active"),
+            EventData::AdminAction(symbol_short!("hook_reg")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_notification_hooks(env: Env, escrow_id: u64) -> Vec<NotificationConfig> {
+        env.storage()
+            .instance()
+            .get(&DataKey::NotificationHooks(escrow_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_notification_history(env: Env, escrow_id: u64) -> Vec<NotificationDelivery> {
+        env.storage()
+            .instance()
+            .get(&DataKey::NotificationHistory(escrow_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn create_recurring_escrow(
+        env: Env,
+        sender: Address,
+        config: RecurringConfig,
+    ) -> Result<u64, Error> {
+        sender.require_auth();
+        if config.interval == 0 || config.count == 0 || config.amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if sender == config.recipient {
+            return Err(Error::SameSenderRecipient);
+        }
+        if !Self::is_supported_asset(&env, &config.asset) {
+            return Err(Error::InvalidAsset);
+        }
+
+        let mut counter: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::RecurringCounter)
+            .unwrap_or(0u64);
+        counter = counter.checked_add(1).ok_or(Error::CounterOverflow)?;
+
+        let recurring = RecurringEscrow {
+            recurring_id: counter,
+            sender: sender.clone(),
+            config,
+            next_run_at: env.ledger().timestamp(),
+            processed_count: 0,
+            cancelled: false,
+            created_at: env.ledger().timestamp(),
+        };
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Recurring(counter), &recurring);
+        env.storage()
+            .instance()
+            .set(&DataKey::RecurringCounter, &counter);
+        env.storage().instance().set(
+            &DataKey::RecurringHistory(counter),
+            &Vec::<RecurringPayment>::new(&env),
+        );
+
+        Ok(counter)
+    }
+
+    pub fn process_recurring_payment(env: Env, recurring_id: u64) -> Result<u64, Error> {
+        let mut recurring: RecurringEscrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Recurring(recurring_id))
+            .ok_or(Error::EscrowNotFound)?;
+        if recurring.cancelled {
+            return Err(Error::InvalidStatus);
+        }
+        if recurring.processed_count >= recurring.config.count {
+            return Err(Error::InvalidStatus);
+        }
+        let now = env.ledger().timestamp();
+        if now < recurring.next_run_at {
+            return Err(Error::NotExpired);
+        }
+
+        let escrow_id = Self::create_escrow(
+            env.clone(),
+            recurring.sender.clone(),
+            recurring.config.recipient.clone(),
+            recurring.config.amount,
+            recurring.config.asset.clone(),
+            now.checked_add(recurring.config.expiration_window)
+                .ok_or(Error::ArithmeticOverflow)?,
+            recurring.config.memo.clone(),
+        )?;
+
+        if recurring.config.auto_release {
+            let mut escrow: Escrow = env
+                .storage()
+                .instance()
+                .get(&DataKey::Escrow(escrow_id))
+                .ok_or(Error::EscrowNotFound)?;
+            escrow.allow_partial_release = false;
+            Self::store_escrow(&env, escrow_id, &mut escrow);
+        }
+
+        recurring.processed_count = recurring
+            .processed_count
+            .checked_add(1)
+            .ok_or(Error::ArithmeticOverflow)?;
+        recurring.next_run_at = now
+            .checked_add(recurring.config.interval)
+            .ok_or(Error::ArithmeticOverflow)?;
+        if recurring.processed_count >= recurring.config.count {
+            recurring.cancelled = true;
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Recurring(recurring_id), &recurring);
+
+        let mut history: Vec<RecurringPayment> = env
+            .storage()
+            .instance()
+            .get(&DataKey::RecurringHistory(recurring_id))
+            .unwrap_or(Vec::new(&env));
+        history.push_back(RecurringPayment {
+            escrow_id,
+            processed_at: now,
+            sequence: recurring.processed_count,
+        });
+        env.storage()
+            .instance()
+            .set(&DataKey::RecurringHistory(recurring_id), &history);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("rec_pay"),
+            escrow_id,
+            &recurring.sender,
+            recurring.config.amount,
+            symbol_short!("created"),
+            EventData::AdminAction(symbol_short!("rec_pay")),
+        );
+
+        Ok(escrow_id)
+    }
+
+    pub fn cancel_recurring_escrow(
+        env: Env,
+        recurring_id: u64,
+        caller: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        let mut recurring: RecurringEscrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Recurring(recurring_id))
+            .ok_or(Error::EscrowNotFound)?;
+        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != recurring.sender && caller != admin {
+            return Err(Error::UnauthorizedCaller);
+        }
+        recurring.cancelled = true;
+        env.storage()
+            .instance()
+            .set(&DataKey::Recurring(recurring_id), &recurring);
+        Ok(())
+    }
+
+    pub fn get_recurring_escrow(env: Env, recurring_id: u64) -> Option<RecurringEscrow> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Recurring(recurring_id))
+    }
+
+    pub fn get_recurring_history(env: Env, recurring_id: u64) -> Vec<RecurringPayment> {
+        env.storage()
+            .instance()
+            .get(&DataKey::RecurringHistory(recurring_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn describe_error(env: Env, domain: ErrorDomain, code: u32) -> String {
+        errors::describe_error(&env, domain, code)
+    }
+
+    pub fn get_release_history(env: Env, escrow_id: u64) -> Vec<ReleaseRecord> {
+        env.storage()
+            .instance()
+            .get(&HistoryKey::Release(escrow_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_amendment_history(env: Env, escrow_id: u64) -> Vec<AmendmentRecord> {
+        env.storage()
+            .instance()
+            .get(&HistoryKey::Amendment(escrow_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Appends a term change to the escrow's amendment log, dropping the
+    /// oldest entry once `MAX_AMENDMENT_HISTORY` is reached.
+    fn record_amendment(
+        env: &Env,
+        escrow_id: u64,
+        field: Symbol,
+        old_value: i128,
+        new_value: i128,
+        actor: &Address,
+    ) {
+        let key = HistoryKey::Amendment(escrow_id);
+        let mut history: Vec<AmendmentRecord> =
+            env.storage().instance().get(&key).unwrap_or(Vec::new(env));
+        if history.len() >= MAX_AMENDMENT_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(AmendmentRecord {
+            field,
+            old_value,
+            new_value,
+            actor: actor.clone(),
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage().instance().set(&key, &history);
+    }
+
+    pub fn approve_escrow(env: Env, escrow_id: u64, approver: Address) -> Result<(), Error> {
+        approver.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        if escrow.status != EscrowStatus::Funded {
+            return Err(Error::InvalidStatus);
+        }
+
+        escrow.status = EscrowStatus::Approved;
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("approved"),
+            escrow_id,
+            &approver,
+            escrow.amount,
+            symbol_short!("approved"),
+            EventData::EscrowApproved(escrow_id),
+        );
+
+        Self::notify_external(
+            &env,
+            NotificationPayload {
+                escrow_id,
+                event_type: EventType::Approved,
+                amount: escrow.amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Points an unfunded escrow at a different recipient. Any
+    /// acknowledgement from the previous recipient is discarded.
+    pub fn update_recipient(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        new_recipient: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != escrow.sender && caller != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        if escrow.deposited_amount > 0 {
+            return Err(Error::AlreadyFunded);
+        }
+        if escrow.status != EscrowStatus::Pending {
+            return Err(Error::EscrowNotPending);
+        }
+        if new_recipient == escrow.sender {
+            return Err(Error::SameSenderRecipient);
+        }
+
+        Self::record_amendment(&env, escrow_id, symbol_short!("recipient"), 0, 0, &caller);
+        let old_recipient = escrow.recipient.clone();
+        escrow.recipient = new_recipient.clone();
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+        env.storage()
+            .instance()
+            .remove(&DataKey::RecipientAck(escrow_id));
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("recip_upd"),
+            escrow_id,
+            &caller,
+            0,
+            symbol_short!("pending"),
+            EventData::PairAction(symbol_short!("recip_upd"), old_recipient, new_recipient),
+        );
+
+        Ok(())
+    }
+
+    pub fn extend_escrow(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        new_expiration: u64,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        if caller != escrow.sender && caller != escrow.recipient {
+            return Err(Error::Unauthorized);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if current_time > escrow.release_conditions.expiration_timestamp {
+            return Err(Error::Expired);
+        }
+
+        if new_expiration <= current_time {
+            return Err(Error::InvalidAmount);
+        }
+
+        Self::record_amendment(
+            &env,
+            escrow_id,
+            symbol_short!("expiry"),
+            escrow.release_conditions.expiration_timestamp as i128,
+            new_expiration as i128,
+            &caller,
+        );
+        escrow.release_conditions.expiration_timestamp = new_expiration;
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("extended"),
+            escrow_id,
+            &caller,
+            new_expiration as i128,
+            symbol_short!("active"),
+            EventData::EscrowExtended(escrow_id, new_expiration),
+        );
+
+        Ok(())
+    }
+
+    pub fn set_kyc_auto_refund(
+        env: Env,
+        escrow_id: u64,
+        sender: Address,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        sender.require_auth();
+
+        let escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        if sender != escrow.sender {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::KycAutoRefund(escrow_id), &enabled);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("kyc_auto"),
+            escrow_id,
+            &sender,
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("kyc_auto")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_kyc_auto_refund(env: Env, escrow_id: u64) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::KycAutoRefund(escrow_id))
+            .unwrap_or(false)
+    }
+
+    /// Makes `release_escrow` re-verify KYC for both parties of a long-lived
+    /// escrow instead of relying on the check made at creation.
+    pub fn set_require_kyc_on_release(
+        env: Env,
+        admin: Address,
+        escrow_id: u64,
+        required: bool,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        if !env.storage().instance().has(&DataKey::Escrow(escrow_id)) {
+            return Err(Error::EscrowNotFound);
+        }
+
+        let key = KycReleaseKey::RequireKycOnRelease(escrow_id);
+        if required {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("kyc_rel"),
+            escrow_id,
+            &admin,
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("kyc_rel")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_require_kyc_on_release(env: Env, escrow_id: u64) -> bool {
+        env.storage()
+            .instance()
+            .get(&KycReleaseKey::RequireKycOnRelease(escrow_id))
+            .unwrap_or(false)
+    }
+
+    /// Re-checks both parties' KYC before any payout to the recipient of an
+    /// escrow flagged with `set_require_kyc_on_release`.
+    fn check_release_kyc(env: &Env, escrow_id: u64, escrow: &Escrow) -> Result<(), Error> {
+        if Self::get_require_kyc_on_release(env.clone(), escrow_id) {
+            Self::parties_kyc_current(env, escrow)?;
+        }
+        Ok(())
+    }
+
+    pub fn set_payout_config(
+        env: Env,
+        admin: Address,
+        config: Option<PayoutConfig>,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        match config {
+            Some(config) => env.storage().instance().set(&PayoutKey::Config, &config),
+            None => env.storage().instance().remove(&PayoutKey::Config),
+        }
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("pay_cfg"),
+            0,
+            &admin,
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("pay_cfg")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_payout_config(env: Env) -> Option<PayoutConfig> {
+        env.storage().instance().get(&PayoutKey::Config)
+    }
+
+    /// Lets the recipient choose a supported asset to be paid in at release.
+    /// The asset must have a payout token registered in the payout config.
+    /// Choosing the escrowed asset clears the preference.
+    pub fn set_payout_preference(
+        env: Env,
+        escrow_id: u64,
+        recipient: Address,
+        asset: Asset,
+    ) -> Result<(), Error> {
+        recipient.require_auth();
+
+        let escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        if recipient != escrow.recipient {
+            return Err(Error::Unauthorized);
+        }
+        if escrow.status != EscrowStatus::Pending
+            && escrow.status != EscrowStatus::Funded
+            && escrow.status != EscrowStatus::Approved
+        {
+            return Err(Error::InvalidStatus);
+        }
+        let key = PayoutKey::Preference(escrow_id);
+        if asset == escrow.asset {
+            env.storage().instance().remove(&key);
+        } else {
+            if !Self::is_supported_asset(&env, &asset)
+                || Self::payout_token_for(&env, &asset.code).is_none()
+            {
+                return Err(Error::InvalidAsset);
+            }
+            env.storage()
+                .instance()
+                .set(&key, &PayoutPreference { asset });
+        }
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("pay_pref"),
+            escrow_id,
+            &recipient,
+            0,
+            symbol_short!("na"),
+            EventData::AddressAction(symbol_short!("pay_pref"), recipient.clone()),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_payout_preference(env: Env, escrow_id: u64) -> Option<PayoutPreference> {
+        env.storage()
+            .instance()
+            .get(&PayoutKey::Preference(escrow_id))
+    }
+
+    /// Lets the sender choose a supported asset to be refunded in. As with
+    /// payouts, the asset needs a token registered in the payout config.
+    /// Choosing the escrowed asset clears the preference.
+    pub fn set_refund_preference(
+        env: Env,
+        escrow_id: u64,
+        sender: Address,
+        asset: Asset,
+    ) -> Result<(), Error> {
+        sender.require_auth();
+
+        let escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        if sender != escrow.sender {
+            return Err(Error::WrongSender);
+        }
+        if escrow.status != EscrowStatus::Pending
+            && escrow.status != EscrowStatus::Funded
+            && escrow.status != EscrowStatus::Approved
+        {
+            return Err(Error::InvalidStatus);
+        }
+        let key = PayoutKey::RefundPreference(escrow_id);
+        if asset == escrow.asset {
+            env.storage().instance().remove(&key);
+        } else {
+            if !Self::is_supported_asset(&env, &asset)
+                || Self::payout_token_for(&env, &asset.code).is_none()
+            {
+                return Err(Error::InvalidAsset);
+            }
+            env.storage()
+                .instance()
+                .set(&key, &PayoutPreference { asset });
+        }
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("rfnd_pref"),
+            escrow_id,
+            &sender,
+            0,
+            symbol_short!("na"),
+            EventData::AddressAction(symbol_short!("rfnd_pref"), sender.clone()),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_refund_preference(env: Env, escrow_id: u64) -> Option<PayoutPreference> {
+        env.storage()
+            .instance()
+            .get(&PayoutKey::RefundPreference(escrow_id))
+    }
+
+    /// Pays the recipient, converting into their preferred asset through the
+    /// payout reserve when one is set. The conversion spread is taken from the
+    /// converted amount only. Falls back to the escrowed asset if the rate is
+    /// unavailable or the reserve cannot cover the payout.
+    fn pay_recipient(
+        env: &Env,
+        escrow_id: u64,
+        escrow: &Escrow,
+        token_client: &token::Client,
+        asset_code: &String,
+        amount: i128,
+    ) {
+        Self::pay_in_preferred_asset(
+            env,
+            escrow_id,
+            &escrow.recipient,
+            PayoutKey::Preference(escrow_id),
+            token_client,
+            asset_code,
+            amount,
+        );
+    }
+
+    /// Refunds the sender, converting into their refund preference the same
+    /// way `pay_recipient` does.
+    fn refund_sender(
+        env: &Env,
+        escrow_id: u64,
+        escrow: &Escrow,
+        token_client: &token::Client,
+        asset_code: &String,
+        amount: i128,
+    ) {
+        Self::pay_in_preferred_asset(
+            env,
+            escrow_id,
+            &escrow.sender,
+            PayoutKey::RefundPreference(escrow_id),
+            token_client,
+            asset_code,
+            amount,
+        );
+    }
+
+    /// Token the payout reserve pays out for `asset_code`, if registered.
+    fn payout_token_for(env: &Env, asset_code: &String) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get::<_, PayoutConfig>(&PayoutKey::Config)
+            .and_then(|config| config.tokens.get(asset_code.clone()))
+    }
+
+    fn pay_in_preferred_asset(
+        env: &Env,
+        escrow_id: u64,
+        to: &Address,
+        preference_key: PayoutKey,
+        token_client: &token::Client,
+        asset_code: &String,
+        amount: i128,
+    ) {
+        let contract_address = env.current_contract_address();
+        let preference: Option<PayoutPreference> = env.storage().instance().get(&preference_key);
+        let config: Option<PayoutConfig> = env.storage().instance().get(&PayoutKey::Config);
+        if let (Some(preference), Some(config)) = (preference, config) {
+            let payout_token = config.tokens.get(preference.asset.code.clone());
+            let spread = amount
+                .checked_mul(Self::get_conversion_spread(env.clone()))
+                .map(|value| value / 10000)
+                .unwrap_or(0);
+            let net_amount = amount - spread;
+            let converted = oracle::get_conversion_rate(
+                env,
+                &config.oracle,
+                asset_code,
+                &preference.asset.code,
+                net_amount,
+                config.max_staleness,
+                None,
+            )
+            .map(|result| result.converted_amount)
+            .unwrap_or(0);
+            let paid_out = match payout_token {
+                Some(payout_token) if converted > 0 => token::Client::new(env, &payout_token)
+                    .try_transfer_from(&contract_address, &config.reserve, to, &converted)
+                    .is_ok(),
+                _ => false,
+            };
+            if paid_out {
+                token_client.transfer(&contract_address, &config.reserve, &net_amount);
+                if spread > 0 {
+                    let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+                    Self::collect_fee(
+                        env,
+                        escrow_id,
+                        token_client,
+                        asset_code,
+                        spread,
+                        &Self::fee_destination(env, &admin),
+                        symbol_short!("spread"),
+                    );
+                }
+                let action = match preference_key {
+                    PayoutKey::RefundPreference(_) => symbol_short!("refund_cv"),
+                    _ => symbol_short!("payout"),
+                };
+                events::emit(
+                    env,
+                    symbol_short!("escrow"),
+                    action.clone(),
+                    escrow_id,
+                    to,
+                    converted,
+                    symbol_short!("convert"),
+                    EventData::ValueChanged(action, amount, converted),
+                );
+                return;
+            }
+        }
+
+        token_client.transfer(&contract_address, to, &amount);
+    }
+
+    pub fn release_escrow(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        token_address: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::enforce_rate_limit(&env, &caller, FunctionType::Release)?;
+
+        Self::release_escrow_internal(env, escrow_id, caller, token_address, 0)
+    }
+
+    /// `release_escrow` for escrows gated on an `OraclePrice` condition;
+    /// `proof_data` is the observed value checked against its threshold.
+    pub fn release_escrow_with_proof(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        token_address: Address,
+        proof_data: i128,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::enforce_rate_limit(&env, &caller, FunctionType::Release)?;
+
+        Self::release_escrow_internal(env, escrow_id, caller, token_address, proof_data)
+    }
+
+    /// Runs `verify_conditions` for escrows that have release conditions and
+    /// rejects the release unless they pass.
+    fn enforce_release_conditions(
+        env: &Env,
+        escrow_id: u64,
+        proof_data: i128,
+    ) -> Result<(), Error> {
+        let escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+        if escrow.release_conditions.conditions.is_empty() {
+            return Ok(());
+        }
+
+        let result = Self::verify_conditions(env.clone(), escrow_id, proof_data)?;
+        if !result.all_passed {
+            return Err(Error::ConditionsNotMet);
+        }
+        Ok(())
+    }
+
+    /// Handshake and multi-party approval checks every release path applies
+    /// before paying the recipient.
+    fn check_release_approvals(env: &Env, escrow_id: u64, escrow: &Escrow) -> Result<(), Error> {
+        if !Self::handshake_complete(env, escrow) {
+            return Err(Error::NotApproved);
+        }
+        if !escrow.multi_party_enabled {
+            return Ok(());
+        }
+
+        let config: MultiPartyConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::EscrowApprovals(escrow_id))
+            .ok_or(Error::QuorumNotMet)?;
+        if config.approval_timeout > 0 && env.ledger().timestamp() > config.approval_timeout {
+            return Err(Error::ApprovalExpired);
+        }
+        if config.approvals.len() < Self::effective_quorum(&config) {
+            return Err(Error::QuorumNotMet);
+        }
+        Ok(())
+    }
+
+    /// Release body shared with `claim`; the caller must already be
+    /// authorized.
+    fn release_escrow_internal(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        token_address: Address,
+        proof_data: i128,
+    ) -> Result<(), Error> {
+        Self::check_escrow_token(&env, escrow_id, &token_address)?;
+        if Self::is_invoice_cancelled(env.clone(), escrow_id) {
+            return Err(Error::InvalidStatus);
+        }
+        Self::enforce_release_conditions(&env, escrow_id, proof_data)?;
+
+        let guard: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::ReentrancyGuard)
+            .unwrap_or(false);
+        if guard {
+            return Err(Error::UnauthorizedCaller);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ReentrancyGuard, &true);
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        if !escrow.compliant {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::NonCompliant);
+        }
+
+        if escrow.status != EscrowStatus::Approved && escrow.status != EscrowStatus::Funded {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::NotApproved);
+        }
+
+        // Vesting escrows pay out only through `release_vested`.
+        if Self::has_vesting_schedule(&env, escrow_id) {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::ConditionsNotMet);
+        }
+
+        if escrow.status == EscrowStatus::Released && !escrow.allow_partial_release {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::AlreadyReleased);
+        }
+
+        if let Err(e) = Self::check_release_approvals(&env, escrow_id, &escrow) {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(e);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if current_time > escrow.release_conditions.expiration_timestamp {
+            escrow.status = EscrowStatus::Expired;
+            Self::store_escrow(&env, escrow_id, &mut escrow);
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::Expired);
+        }
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != escrow.recipient && caller != stored_admin {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::Unauthorized);
+        }
+
+        if escrow.deposited_amount == 0 {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::InsufficientFunds);
+        }
+
+        let available_amount = escrow
+            .deposited_amount
+            .checked_sub(escrow.released_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        if available_amount <= 0 {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::InsufficientFunds);
+        }
+
+        // A sub-dust residual left behind by an earlier release costs more to
+        // pay out than it is worth; sweep it and finalize instead of leaving
+        // the escrow stranded. An escrow nothing was released from yet is
+        // paid out in full, however small.
+        if escrow.released_amount > 0
+            && available_amount < Self::get_dust_threshold(env.clone(), escrow.asset.code.clone())
+        {
+            let token_client = token::Client::new(&env, &token_address);
+            Self::sweep_dust(
+                &env,
+                escrow_id,
+                &mut escrow,
+                &token_client,
+                available_amount,
+            )?;
+            escrow.release_timestamp = current_time;
+            Self::store_escrow(&env, escrow_id, &mut escrow);
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Ok(());
+        }
+
+        if Self::get_kyc_auto_refund(env.clone(), escrow_id) {
+            match Self::recipient_kyc_current(&env, &escrow.recipient) {
+                Ok(true) => {}
+                Ok(false) => {
+                    let token_client = token::Client::new(&env, &token_address);
+                    token_client.transfer(
+                        &env.current_contract_address(),
+                        &escrow.sender,
+                        &available_amount,
+                    );
+                    Self::adjust_tvl(&env, &escrow.asset.code, -available_amount);
+
+                    escrow.refunded_amount = escrow
+                        .refunded_amount
+                        .checked_add(available_amount)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                    escrow.status = EscrowStatus::Refunded;
+                    escrow.refund_timestamp = current_time;
+                    Self::store_escrow(&env, escrow_id, &mut escrow);
+                    env.storage()
+                        .instance()
+                        .set(&DataKey::ReentrancyGuard, &false);
+
+                    events::emit(
+                        &env,
+                        symbol_short!("escrow"),
+                        symbol_short!("kyc_rfnd"),
+                        escrow_id,
+                        &escrow.sender,
+                        available_amount,
+                        symbol_short!("refunded"),
+                        EventData::EscrowRefunded(
+                            escrow_id,
+                            available_amount,
+                            Self::fee_destination(&env, &stored_admin),
+                        ),
+                    );
+                    return Ok(());
+                }
+                Err(e) => {
+                    env.storage()
+                        .instance()
+                        .set(&DataKey::ReentrancyGuard, &false);
+                    return Err(e);
+                }
+            }
+        }
+
+        if let Err(e) = Self::check_release_kyc(&env, escrow_id, &escrow) {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(e);
+        }
+
+        let fee_percentage = Self::platform_fee_for(&env, escrow_id);
+        let fee_amount = available_amount
+            .checked_mul(fee_percentage)
+            .ok_or(Error::ArithmeticOverflow)?
+            .checked_div(10000)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        let insurance_premium = Self::insurance_premium(&env, available_amount)?;
+        let recipient_amount = available_amount
+            .checked_sub(fee_amount)
+            .ok_or(Error::ArithmeticOverflow)?
+            .checked_sub(insurance_premium)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        if recipient_amount <= 0 {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::InsufficientAmount);
+        }
+
+        let token_client = token::Client::new(&env, &token_address);
+
+        Self::pay_recipient(
+            &env,
+            escrow_id,
+            &escrow,
+            &token_client,
+            &escrow.asset.code,
+            recipient_amount,
+        );
+
+        if fee_amount > 0 {
+            Self::collect_fee(
+                &env,
+                escrow_id,
+                &token_client,
+                &escrow.asset.code,
+                fee_amount,
+                &Self::fee_destination(&env, &stored_admin),
+                symbol_short!("platform"),
+            );
+        }
+        Self::contribute_to_reserve(&env, &token_client, &escrow.asset.code, insurance_premium);
+        Self::adjust_tvl(&env, &escrow.asset.code, -available_amount);
+
+        escrow.released_amount = escrow
+            .released_amount
+            .checked_add(available_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        let current_asset_released = escrow
+            .released_amounts
+            .get(escrow.asset.clone())
+            .unwrap_or(0i128);
+        escrow.released_amounts.set(
+            escrow.asset.clone(),
+            current_asset_released
+                .checked_add(available_amount)
+                .ok_or(Error::ArithmeticOverflow)?,
+        );
+        escrow.status = EscrowStatus::Released;
+        escrow.release_timestamp = current_time;
+
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        if escrow.multi_party_enabled {
+            if let Some(mut config) = env
+                .storage()
+                .instance()
+                .get::<_, MultiPartyConfig>(&DataKey::EscrowApprovals(escrow_id))
+            {
+                config.finalized = true;
+                env.storage()
+                    .instance()
+                    .set(&DataKey::EscrowApprovals(escrow_id), &config);
+            }
+        }
+
+        // Balances and status are final at this point, so the whitelisted
+        // callback can only observe the release, never replay it.
+        Self::invoke_release_callback(&env, escrow_id, recipient_amount);
+        Self::notify_accounting_hook(
+            &env,
+            escrow_id,
+            &escrow.recipient,
+            recipient_amount,
+            fee_amount,
+        );
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("released"),
+            escrow_id,
+            &caller,
+            recipient_amount,
+            symbol_short!("released"),
+            EventData::EscrowReleased(
+                escrow_id,
+                recipient_amount,
+                Self::fee_destination(&env, &stored_admin),
+            ),
+        );
+
+        Self::notify_external(
+            &env,
+            NotificationPayload {
+                escrow_id,
+                event_type: EventType::Released,
+                amount: recipient_amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ReentrancyGuard, &false);
+
+        Ok(())
+    }
+
+    pub fn release_asset(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        asset: Asset,
+        token_address: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::enforce_rate_limit(&env, &caller, FunctionType::Release)?;
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        if escrow.status != EscrowStatus::Approved && escrow.status != EscrowStatus::Funded {
+            return Err(Error::NotApproved);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if current_time > escrow.release_conditions.expiration_timestamp {
+            escrow.status = EscrowStatus::Expired;
+            Self::store_escrow(&env, escrow_id, &mut escrow);
+            return Err(Error::Expired);
+        }
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != escrow.recipient && caller != stored_admin && caller != escrow.sender {
+            return Err(Error::UnauthorizedCaller);
+        }
+
+        if !Self::handshake_complete(&env, &escrow) {
+            return Err(Error::NotApproved);
+        }
+
+        // Approvals gathered before the timeout stop counting once it passes.
+        if escrow.multi_party_enabled {
+            let config: MultiPartyConfig = env
+                .storage()
+                .instance()
+                .get(&DataKey::EscrowApprovals(escrow_id))
+                .ok_or(Error::QuorumNotMet)?;
+            if config.approval_timeout > 0 && current_time > config.approval_timeout {
+                return Err(Error::ApprovalExpired);
+            }
+            if config.approvals.len() < Self::effective_quorum(&config) {
+                return Err(Error::QuorumNotMet);
+            }
+        }
+
+        Self::check_asset_token(&env, &asset, &token_address)?;
+        let deposited = escrow.deposited_amounts.get(asset.clone()).unwrap_or(0i128);
+        let released = escrow.released_amounts.get(asset.clone()).unwrap_or(0i128);
+        let available_amount = deposited
+            .checked_sub(released)
+            .ok_or(Error::ArithmeticOverflow)?;
+        if available_amount <= 0 {
+            return Err(Error::InsufficientFunds);
+        }
+
+        // The conversion spread is charged by `pay_recipient` when it converts.
+        let fee_breakdown = Self::calculate_fees(
+            &env,
+            available_amount,
+            Self::platform_fee_for(&env, escrow_id),
+            false,
+        )?;
+        let insurance_premium = Self::insurance_premium(&env, available_amount)?;
+        let recipient_amount = available_amount
+            .checked_sub(fee_breakdown.total_fee)
+            .ok_or(Error::ArithmeticOverflow)?
+            .checked_sub(insurance_premium)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        let token_client = token::Client::new(&env, &token_address);
+        Self::pay_recipient(
+            &env,
+            escrow_id,
+            &escrow,
+            &token_client,
+            &asset.code,
+            recipient_amount,
+        );
+        if fee_breakdown.total_fee > 0 {
+            Self::collect_fee(
+                &env,
+                escrow_id,
+                &token_client,
+                &asset.code,
+                fee_breakdown.total_fee,
+                &Self::fee_destination(&env, &stored_admin),
+                symbol_short!("platform"),
+            );
+        }
+        Self::contribute_to_reserve(&env, &token_client, &asset.code, insurance_premium);
+        Self::adjust_tvl(&env, &asset.code, -available_amount);
+
+        escrow.released_amounts.set(asset.clone(), deposited);
+        escrow.released_amount = escrow
+            .released_amount
+            .checked_add(available_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        let mut all_released = true;
+        for escrow_asset in escrow.assets.iter() {
+            let escrow_asset: Asset = escrow_asset;
+            let asset_deposited: i128 = escrow
+                .deposited_amounts
+                .get(escrow_asset.clone())
+                .unwrap_or(0i128);
+            let asset_released: i128 = escrow
+                .released_amounts
+                .get(escrow_asset.clone())
+                .unwrap_or(0i128);
+            if asset_deposited > asset_released {
+                all_released = false;
+                break;
+            }
+        }
+        if all_released {
+            escrow.status = EscrowStatus::Released;
+            escrow.release_timestamp = current_time;
+        }
+
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+        Self::notify_accounting_hook(
+            &env,
+            escrow_id,
+            &escrow.recipient,
+            recipient_amount,
+            fee_breakdown.total_fee,
+        );
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("rel_ast"),
+            escrow_id,
+            &caller,
+            recipient_amount,
+            if all_released {
+                symbol_short!("released")
+            } else {
+                symbol_short!("funded")
+            },
+            EventData::EscrowReleased(
+                escrow_id,
+                recipient_amount,
+                Self::fee_destination(&env, &stored_admin),
+            ),
+        );
+
+        Self::notify_external(
+            &env,
+            NotificationPayload {
+                escrow_id,
+                event_type: EventType::Released,
+                amount: recipient_amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Unlocks the escrow in tranches. Each `(timestamp, cumulative_amount)`
+    /// point makes `cumulative_amount` releasable from `timestamp` on; points
+    /// must be in time order with non-decreasing amounts.
+    pub fn set_vesting_schedule(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        unlocks: Vec<(u64, i128)>,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+        if caller != escrow.sender {
+            return Err(Error::WrongSender);
+        }
+        if escrow.released_amount > 0
+            || (escrow.status != EscrowStatus::Pending
+                && escrow.status != EscrowStatus::Funded
+                && escrow.status != EscrowStatus::Approved)
+        {
+            return Err(Error::InvalidStatus);
+        }
+        if unlocks.is_empty() {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut previous: Option<(u64, i128)> = None;
+        for (timestamp, cumulative) in unlocks.iter() {
+            if cumulative <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+            if let Some((prev_timestamp, prev_cumulative)) = previous {
+                if timestamp <= prev_timestamp || cumulative < prev_cumulative {
+                    return Err(Error::InvalidAmount);
+                }
+            }
+            previous = Some((timestamp, cumulative));
+        }
+        if previous.is_some_and(|(_, total)| total > escrow.amount) {
+            return Err(Error::InvalidAmount);
+        }
+
+        env.storage()
+            .instance()
+            .set(&VestingKey::Vesting(escrow_id), &unlocks);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("vesting"),
+            escrow_id,
+            &caller,
+            unlocks.len() as i128,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("vesting")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_vesting_schedule(env: Env, escrow_id: u64) -> Option<Vec<(u64, i128)>> {
+        env.storage()
+            .instance()
+            .get(&VestingKey::Vesting(escrow_id))
+    }
+
+    fn has_vesting_schedule(env: &Env, escrow_id: u64) -> bool {
+        env.storage()
+            .instance()
+            .has(&VestingKey::Vesting(escrow_id))
+    }
+
+    /// Pays the recipient whatever the vesting schedule has unlocked so far,
+    /// less what was already released and the platform fee.
+    pub fn release_vested(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        token_address: Address,
+    ) -> Result<i128, Error> {
+        if upgradeable::is_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        caller.require_auth();
+        Self::enforce_rate_limit(&env, &caller, FunctionType::Release)?;
+
+        Self::check_escrow_token(&env, escrow_id, &token_address)?;
+        if Self::is_invoice_cancelled(env.clone(), escrow_id) {
+            return Err(Error::InvalidStatus);
+        }
+        Self::enforce_release_conditions(&env, escrow_id, 0)?;
+
+        let guard: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::ReentrancyGuard)
+            .unwrap_or(false);
+        if guard {
+            return Err(Error::UnauthorizedCaller);
+        }
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != escrow.recipient && caller != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        if escrow.status != EscrowStatus::Funded && escrow.status != EscrowStatus::Approved {
+            return Err(Error::InvalidStatus);
+        }
+        if !escrow.compliant {
+            return Err(Error::NonCompliant);
+        }
+        Self::check_release_approvals(&env, escrow_id, &escrow)?;
+        Self::check_release_kyc(&env, escrow_id, &escrow)?;
+
+        let schedule: Vec<(u64, i128)> = env
+            .storage()
+            .instance()
+            .get(&VestingKey::Vesting(escrow_id))
+            .ok_or(Error::ConditionsNotMet)?;
+        let current_time = env.ledger().timestamp();
+        let mut unlocked = 0i128;
+        for (timestamp, cumulative) in schedule.iter() {
+            if timestamp > current_time {
+                break;
+            }
+            unlocked = cumulative;
+        }
+
+        let releasable = unlocked
+            .min(
+                escrow
+                    .deposited_amount
+                    .saturating_sub(escrow.refunded_amount),
+            )
+            .checked_sub(escrow.released_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        if releasable <= 0 {
+            return Err(Error::NoFundsAvailable);
+        }
+
+        let fee_amount = releasable
+            .checked_mul(Self::platform_fee_for(&env, escrow_id))
+            .ok_or(Error::ArithmeticOverflow)?
+            .checked_div(10000)
+            .ok_or(Error::ArithmeticOverflow)?;
+        let recipient_amount = releasable
+            .checked_sub(fee_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ReentrancyGuard, &true);
+
+        let token_client = token::Client::new(&env, &token_address);
+        Self::pay_recipient(
+            &env,
+            escrow_id,
+            &escrow,
+            &token_client,
+            &escrow.asset.code,
+            recipient_amount,
+        );
+        if fee_amount > 0 {
+            Self::collect_fee(
+                &env,
+                escrow_id,
+                &token_client,
+                &escrow.asset.code,
+                fee_amount,
+                &Self::fee_destination(&env, &stored_admin),
+                symbol_short!("platform"),
+            );
+        }
+        Self::adjust_tvl(&env, &escrow.asset.code, -releasable);
+
+        escrow.released_amount = escrow
+            .released_amount
+            .checked_add(releasable)
+            .ok_or(Error::ArithmeticOverflow)?;
+        let asset_released = escrow
+            .released_amounts
+            .get(escrow.asset.clone())
+            .unwrap_or(0i128);
+        escrow.released_amounts.set(
+            escrow.asset.clone(),
+            asset_released
+                .checked_add(releasable)
+                .ok_or(Error::ArithmeticOverflow)?,
+        );
+        if escrow.released_amount >= escrow.amount {
+            escrow.status = EscrowStatus::Released;
+            escrow.release_timestamp = current_time;
+        }
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("vested"),
+            escrow_id,
+            &caller,
+            recipient_amount,
+            if escrow.status == EscrowStatus::Released {
+                symbol_short!("released")
+            } else {
+                symbol_short!("funded")
+            },
+            EventData::EscrowReleased(
+                escrow_id,
+                recipient_amount,
+                Self::fee_destination(&env, &stored_admin),
+            ),
+        );
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ReentrancyGuard, &false);
+
+        Ok(recipient_amount)
+    }
+
+    pub fn release_partial(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        token_address: Address,
+        release_amount: i128,
+    ) -> Result<(), Error> {
+        if upgradeable::is_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        caller.require_auth();
+        Self::enforce_rate_limit(&env, &caller, FunctionType::Release)?;
+
+        if release_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        Self::check_escrow_token(&env, escrow_id, &token_address)?;
+        if Self::is_invoice_cancelled(env.clone(), escrow_id) {
+            return Err(Error::InvalidStatus);
+        }
+        Self::enforce_release_conditions(&env, escrow_id, 0)?;
+
+        let guard: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::ReentrancyGuard)
+            .unwrap_or(false);
+        if guard {
+            return Err(Error::UnauthorizedCaller);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::ReentrancyGuard, &true);
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        if !escrow.allow_partial_release {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::PartialReleaseNotAllowed);
+        }
+
+        if Self::has_vesting_schedule(&env, escrow_id) {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::ConditionsNotMet);
+        }
+
+        if escrow.status != EscrowStatus::Approved
+            && escrow.status != EscrowStatus::Funded
+            && escrow.status != EscrowStatus::Released
+        {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::InvalidStatus);
+        }
+
+        if let Err(e) = Self::check_release_approvals(&env, escrow_id, &escrow) {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(e);
+        }
+
+        let current_time = env.ledger().timestamp();
+        if current_time > escrow.release_conditions.expiration_timestamp {
+            escrow.status = EscrowStatus::Expired;
+            Self::store_escrow(&env, escrow_id, &mut escrow);
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::Expired);
+        }
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != escrow.recipient && caller != stored_admin {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::UnauthorizedCaller);
+        }
+
+        if let Err(e) = Self::check_release_kyc(&env, escrow_id, &escrow) {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(e);
+        }
+
+        let available_amount = escrow
+            .deposited_amount
+            .checked_sub(escrow.released_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        if release_amount > available_amount {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::InsufficientFunds);
+        }
+
+        // Sub-minimum increments are rejected unless they clear the balance.
+        let min_increment = Self::get_min_release_increment(env.clone());
+        if release_amount < min_increment && release_amount != available_amount {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::InvalidAmount);
+        }
+
+        let fee_percentage = Self::platform_fee_for(&env, escrow_id);
+        let fee_amount = release_amount
+            .checked_mul(fee_percentage)
+            .ok_or(Error::ArithmeticOverflow)?
+            .checked_div(10000)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        let insurance_premium = Self::insurance_premium(&env, release_amount)?;
+        let recipient_amount = release_amount
+            .checked_sub(fee_amount)
+            .ok_or(Error::ArithmeticOverflow)?
+            .checked_sub(insurance_premium)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        let token_client = token::Client::new(&env, &token_address);
+
+        Self::pay_recipient(
+            &env,
+            escrow_id,
+            &escrow,
+            &token_client,
+            &escrow.asset.code,
+            recipient_amount,
+        );
+
+        if fee_amount > 0 {
+            Self::collect_fee(
+                &env,
+                escrow_id,
+                &token_client,
+                &escrow.asset.code,
+                fee_amount,
+                &Self::fee_destination(&env, &stored_admin),
+                symbol_short!("platform"),
+            );
+        }
+        Self::contribute_to_reserve(&env, &token_client, &escrow.asset.code, insurance_premium);
+        Self::adjust_tvl(&env, &escrow.asset.code, -release_amount);
+
+        escrow.released_amount = escrow
+            .released_amount
+            .checked_add(release_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        let remainder = escrow
+            .deposited_amount
+            .checked_sub(escrow.released_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        if remainder > 0
+            && remainder < Self::get_dust_threshold(env.clone(), escrow.asset.code.clone())
+        {
+            Self::sweep_dust(&env, escrow_id, &mut escrow, &token_client, remainder)?;
+        }
+
+        if escrow.released_amount >= escrow.deposited_amount {
+            escrow.status = EscrowStatus::Released;
+        }
+
+        escrow.release_timestamp = current_time;
+
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        if escrow.multi_party_enabled {
+            if let Some(mut config) = env
+                .storage()
+                .instance()
+                .get::<_, MultiPartyConfig>(&DataKey::EscrowApprovals(escrow_id))
+            {
+                if escrow.released_amount >= escrow.deposited_amount {
+                    config.finalized = true;
+                    env.storage()
+                        .instance()
+                        .set(&DataKey::EscrowApprovals(escrow_id), &config);
+                }
+            }
+        }
+
+        // Keep the most recent tranches; the oldest entry drops off at the cap.
+        let mut history: Vec<ReleaseRecord> = env
+            .storage()
+            .instance()
+            .get(&HistoryKey::Release(escrow_id))
+            .unwrap_or(Vec::new(&env));
+        if history.len() >= MAX_RELEASE_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(ReleaseRecord {
+            amount: release_amount,
+            fee: fee_amount,
+            timestamp: current_time,
+            caller: caller.clone(),
+        });
+        env.storage()
+            .instance()
+            .set(&HistoryKey::Release(escrow_id), &history);
+
+        Self::notify_accounting_hook(
+            &env,
+            escrow_id,
+            &escrow.recipient,
+            recipient_amount,
+            fee_amount,
+        );
+
+        let partial_status = if escrow.released_amount >= escrow.deposited_amount {
+            symbol_short!("released")
+        } else {
+            symbol_short!("funded")
+        };
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("partial"),
+            escrow_id,
+            &caller,
+            recipient_amount,
+            partial_status,
+            EventData::EscrowReleased(
+                escrow_id,
+                recipient_amount,
+                Self::fee_destination(&env, &stored_admin),
+            ),
+        );
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ReentrancyGuard, &false);
+
+        Ok(())
+    }
+
+    pub fn enable_partial_release(env: Env, escrow_id: u64, caller: Address) -> Result<(), Error> {
+        caller.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        if caller != escrow.sender {
+            return Err(Error::Unauthorized);
+        }
+
+        escrow.allow_partial_release = true;
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("part_enab"),
+            escrow_id,
+            &caller,
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("na")),
+        );
+
+        Ok(())
+    }
+
+    pub fn add_condition(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        condition_type: ConditionType,
+        required: bool,
+        threshold_value: i128,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != escrow.sender && caller != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        if escrow.status != EscrowStatus::Pending && escrow.status != EscrowStatus::Funded {
+            return Err(Error::InvalidStatus);
+        }
+
+        let condition = Condition {
+            condition_type,
+            required,
+            verified: false,
+            threshold_value,
+            verified_at: 0,
+            validity_period: 0,
+        };
+
+        escrow.release_conditions.conditions.push_back(condition);
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("cond_add"),
+            escrow_id,
+            &caller,
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("na")),
+        );
+
+        Ok(())
+    }
+
+    /// Removes the condition at `index`, keeping the order of the rest.
+    pub fn remove_condition(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        index: u32,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != escrow.sender && caller != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        if escrow.status != EscrowStatus::Pending && escrow.status != EscrowStatus::Funded {
+            return Err(Error::InvalidStatus);
+        }
+
+        let removed = escrow
+            .release_conditions
+            .conditions
+            .get(index)
+            .ok_or(Error::ConditionIndexOutOfRange)?;
+        escrow.release_conditions.conditions.remove(index);
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("cond_rem"),
+            escrow_id,
+            &caller,
+            index as i128,
+            symbol_short!("removed"),
+            EventData::AdminAction(Self::condition_symbol(removed.condition_type)),
+        );
+
+        Ok(())
+    }
+
+    pub fn clear_conditions(env: Env, escrow_id: u64, caller: Address) -> Result<(), Error> {
+        caller.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != escrow.sender && caller != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        if escrow.status != EscrowStatus::Pending && escrow.status != EscrowStatus::Funded {
+            return Err(Error::InvalidStatus);
+        }
+
+        let cleared = escrow.release_conditions.conditions.len();
+        escrow.release_conditions.conditions = Vec::new(&env);
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("cond_clr"),
+            escrow_id,
+            &caller,
+            cleared as i128,
+            symbol_short!("removed"),
+            EventData::AdminAction(symbol_short!("cond_clr")),
+        );
+
+        Ok(())
+    }
+
+    /// Sets how long a verification of the condition at `index` is honored
+    /// before it must be proven again.
+    pub fn set_condition_validity(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        index: u32,
+        validity_period: u64,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != escrow.sender && caller != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        if escrow.status != EscrowStatus::Pending && escrow.status != EscrowStatus::Funded {
+            return Err(Error::InvalidStatus);
+        }
+
+        let mut condition = escrow
+            .release_conditions
+            .conditions
+            .get(index)
+            .ok_or(Error::ConditionsNotMet)?;
+        condition.validity_period = validity_period;
+        escrow.release_conditions.conditions.set(index, condition);
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("cond_val"),
+            escrow_id,
+            &caller,
+            validity_period as i128,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("cond_val")),
+        );
+
+        Ok(())
+    }
+
+    fn condition_is_current(condition: &Condition, current_time: u64) -> bool {
+        condition.verified
+            && (condition.validity_period == 0
+                || current_time.saturating_sub(condition.verified_at) <= condition.validity_period)
+    }
+
+    pub fn set_condition_operator(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        operator: ConditionOperator,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let mut escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != escrow.sender && caller != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        escrow.release_conditions.operator = operator;
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("cond_op"),
+            escrow_id,
+            &caller,
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("na")),
+        );
 
+        Ok(())
+    }
+
+    pub fn save_condition_template(
+        env: Env,
+        admin: Address,
+        name: Symbol,
+        conditions: Vec<Condition>,
+        operator: ConditionOperator,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        let template = ConditionTemplate {
+            conditions,
+            operator,
+        };
         env.storage()
             .instance()
-            .set(&DataKey::Escrow(counter), &escrow);
-        env.storage()
-            .instance()
-            .set(&DataKey::EscrowCounter, &counter);
+            .set(&DataKey::ConditionTemplate(name.clone()), &template);
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("created"),
-            counter,
-            &escrow.sender,
-            primary_amount,
-            symbol_short!("pending"),
-            EventData::EscrowCreated(
-                counter,
-                escrow.sender.clone(),
-                escrow.recipient.clone(),
-                AssetRef {
-                    code: escrow.asset.code.clone(),
-                    issuer: escrow.asset.issuer.clone(),
-                },
-                primary_amount,
-            ),
+            symbol_short!("tmpl_save"),
+            0,
+            &admin,
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(name),
         );
 
-        Self::notify_external(
-            &env,
-            NotificationPayload {
-                escrow_id: counter,
-                event_type: EventType::Created,
-                amount: total_amount,
-                timestamp: env.ledger().timestamp(),
-            },
-        );
+        Ok(())
+    }
 
-        Ok(counter)
+    pub fn get_condition_template(env: Env, name: Symbol) -> Option<ConditionTemplate> {
+        env.storage()
+            .instance()
+            .get(&DataKey::ConditionTemplate(name))
     }
 
-    pub fn deposit(
+    pub fn apply_condition_template(
         env: Env,
         escrow_id: u64,
         caller: Address,
-        amount: i128,
-        token_address: Address,
+        name: Symbol,
     ) -> Result<(), Error> {
-        if upgradeable::is_paused(&env) {
-            return Err(Error::ContractPaused);
-        }
         caller.require_auth();
-        Self::enforce_rate_limit(&env, &caller, FunctionType::Deposit)?;
-
-        if amount <= 0 {
-            return Err(Error::InvalidAmount);
-        }
 
         let mut escrow: Escrow = env
             .storage()
@@ -1627,570 +6617,479 @@ impl PaymentEscrowContract {
             .get(&DataKey::Escrow(escrow_id))
             .ok_or(Error::EscrowNotFound)?;
 
-        if !escrow.compliant {
-            return Err(Error::NonCompliant);
-        }
-
-        if caller != escrow.sender {
-            return Err(Error::WrongSender);
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != escrow.sender && caller != stored_admin {
+            return Err(Error::Unauthorized);
         }
 
         if escrow.status != EscrowStatus::Pending && escrow.status != EscrowStatus::Funded {
-            return Err(Error::EscrowNotPending);
+            return Err(Error::InvalidStatus);
         }
 
-        let new_deposited = escrow
-            .deposited_amount
-            .checked_add(amount)
-            .ok_or(Error::DepositOverflow)?;
+        let template: ConditionTemplate = env
+            .storage()
+            .instance()
+            .get(&DataKey::ConditionTemplate(name.clone()))
+            .ok_or(Error::TemplateNotFound)?;
 
-        if new_deposited > escrow.amount {
-            return Err(Error::InsufficientAmount);
+        for condition in template.conditions.iter() {
+            escrow.release_conditions.conditions.push_back(Condition {
+                condition_type: condition.condition_type,
+                required: condition.required,
+                verified: false,
+                threshold_value: condition.threshold_value,
+                verified_at: 0,
+                validity_period: condition.validity_period,
+            });
         }
+        escrow.release_conditions.operator = template.operator;
+        Self::store_escrow(&env, escrow_id, &mut escrow);
 
-        let token_client = token::Client::new(&env, &token_address);
-        let contract_address = env.current_contract_address();
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("tmpl_app"),
+            escrow_id,
+            &caller,
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(name),
+        );
 
-        token_client.transfer(&caller, &contract_address, &amount);
+        Ok(())
+    }
 
-        escrow.deposited_amount = new_deposited;
-        escrow
-            .deposited_amounts
-            .set(escrow.asset.clone(), new_deposited);
-        escrow.last_deposit_at = env.ledger().timestamp();
+    pub fn set_allowed_regions(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        regions: Vec<Symbol>,
+    ) -> Result<(), Error> {
+        caller.require_auth();
 
-        if escrow.deposited_amount == escrow.amount {
-            escrow.status = EscrowStatus::Funded;
+        let escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != escrow.sender && caller != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        if escrow.status != EscrowStatus::Pending && escrow.status != EscrowStatus::Funded {
+            return Err(Error::InvalidStatus);
         }
 
         env.storage()
             .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+            .set(&DataKey::AllowedRegions(escrow_id), &regions);
 
-        let deposit_status = if escrow.deposited_amount == escrow.amount {
-            symbol_short!("funded")
-        } else {
-            symbol_short!("pending")
-        };
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("deposit"),
+            symbol_short!("geo_set"),
             escrow_id,
             &caller,
-            amount,
-            deposit_status,
-            EventData::EscrowDeposited(escrow_id, amount, escrow.deposited_amount),
-        );
-
-        Self::notify_external(
-            &env,
-            NotificationPayload {
-                escrow_id,
-                event_type: EventType::Deposit,
-                amount,
-                timestamp: env.ledger().timestamp(),
-            },
+            regions.len() as i128,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("geo_set")),
         );
 
         Ok(())
     }
 
-    pub fn deposit_asset(
-        env: Env,
-        escrow_id: u64,
-        caller: Address,
-        asset: Asset,
-        amount: i128,
-        token_address: Address,
-    ) -> Result<(), Error> {
-        if upgradeable::is_paused(&env) {
-            return Err(Error::ContractPaused);
-        }
-        caller.require_auth();
-        Self::enforce_rate_limit(&env, &caller, FunctionType::Deposit)?;
+    pub fn get_allowed_regions(env: Env, escrow_id: u64) -> Vec<Symbol> {
+        env.storage()
+            .instance()
+            .get(&DataKey::AllowedRegions(escrow_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
 
-        if amount <= 0 {
-            return Err(Error::InvalidAmount);
+    fn parties_in_allowed_regions(env: &Env, escrow: &Escrow) -> bool {
+        let allowed: Vec<Symbol> = match env
+            .storage()
+            .instance()
+            .get(&DataKey::AllowedRegions(escrow.escrow_id))
+        {
+            Some(regions) => regions,
+            None => return false,
+        };
+
+        let sender_region: Option<Symbol> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AccountRegion(escrow.sender.clone()));
+        let recipient_region: Option<Symbol> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AccountRegion(escrow.recipient.clone()));
+
+        match (sender_region, recipient_region) {
+            (Some(s), Some(r)) => allowed.contains(&s) && allowed.contains(&r),
+            _ => false,
         }
+    }
 
+    pub fn verify_conditions(
+        env: Env,
+        escrow_id: u64,
+        proof_data: i128,
+    ) -> Result<VerificationResult, Error> {
         let mut escrow: Escrow = env
             .storage()
             .instance()
             .get(&DataKey::Escrow(escrow_id))
             .ok_or(Error::EscrowNotFound)?;
 
-        if !escrow.compliant {
-            return Err(Error::NonCompliant);
-        }
+        let current_time = env.ledger().timestamp();
+        let mut failed_conditions = Vec::new(&env);
+        let mut passed_count = 0;
+        let mut required_count = 0;
 
-        if caller != escrow.sender {
-            return Err(Error::WrongSender);
-        }
-        if escrow.status != EscrowStatus::Pending && escrow.status != EscrowStatus::Funded {
-            return Err(Error::EscrowNotPending);
-        }
+        for i in 0..escrow.release_conditions.conditions.len() {
+            let mut condition = escrow.release_conditions.conditions.get(i).unwrap();
+            let condition_type_copy = condition.condition_type;
+            let is_required = condition.required;
 
-        let required_amount = escrow
-            .amounts
-            .get(asset.clone())
-            .ok_or(Error::InvalidAsset)?;
-        let current_deposited = escrow.deposited_amounts.get(asset.clone()).unwrap_or(0i128);
-        let new_asset_deposit = current_deposited
-            .checked_add(amount)
-            .ok_or(Error::DepositOverflow)?;
-        if new_asset_deposit > required_amount {
-            return Err(Error::InsufficientAmount);
-        }
+            if is_required {
+                required_count += 1;
+            }
 
-        let token_client = token::Client::new(&env, &token_address);
-        let contract_address = env.current_contract_address();
-        token_client.transfer(&caller, &contract_address, &amount);
+            let evaluated = match condition.condition_type {
+                ConditionType::Timestamp => {
+                    current_time >= escrow.release_conditions.expiration_timestamp
+                }
+                ConditionType::Approval => {
+                    escrow.release_conditions.current_approvals
+                        >= escrow.release_conditions.min_approvals
+                }
+                ConditionType::OraclePrice => {
+                    if proof_data > 0 {
+                        proof_data >= condition.threshold_value
+                    } else {
+                        false
+                    }
+                }
+                ConditionType::MultiSignature => {
+                    escrow.release_conditions.current_approvals
+                        >= escrow.release_conditions.min_approvals
+                }
+                ConditionType::KYCVerified => escrow.kyc_compliant,
+                ConditionType::RecipientAck => env
+                    .storage()
+                    .instance()
+                    .has(&DataKey::RecipientAck(escrow_id)),
+                ConditionType::GeoAllowed => Self::parties_in_allowed_regions(&env, &escrow),
+                ConditionType::MinDeposited => escrow.deposited_amount >= condition.threshold_value,
+                ConditionType::AmlClear => Self::parties_pass_aml(&env, &escrow),
+            };
 
-        escrow
-            .deposited_amounts
-            .set(asset.clone(), new_asset_deposit);
-        escrow.deposited_amount = escrow
-            .deposited_amount
-            .checked_add(amount)
-            .ok_or(Error::DepositOverflow)?;
-        escrow.last_deposit_at = env.ledger().timestamp();
+            // AML clearance must come from a fresh screen, never a cached pass.
+            let verified = evaluated
+                || (condition.validity_period > 0
+                    && condition.condition_type != ConditionType::AmlClear
+                    && Self::condition_is_current(&condition, current_time));
+            if evaluated {
+                condition.verified_at = current_time;
+            }
+            condition.verified = verified;
+            escrow.release_conditions.conditions.set(i, condition);
 
-        let mut fully_funded = true;
-        for required_asset in escrow.assets.iter() {
-            let required_asset: Asset = required_asset;
-            let required: i128 = escrow.amounts.get(required_asset.clone()).unwrap_or(0i128);
-            let deposited: i128 = escrow
-                .deposited_amounts
-                .get(required_asset.clone())
-                .unwrap_or(0i128);
-            if deposited < required {
-                fully_funded = false;
-                break;
+            if verified {
+                passed_count += 1;
+            } else if is_required {
+                failed_conditions.push_back(condition_type_copy);
             }
         }
-        if fully_funded {
-            escrow.status = EscrowStatus::Funded;
-        }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        let all_passed = match escrow.release_conditions.operator {
+            ConditionOperator::And => {
+                failed_conditions.is_empty()
+                    && (required_count == 0 || passed_count >= required_count)
+            }
+            ConditionOperator::Or => passed_count > 0,
+        };
+
+        let result = VerificationResult {
+            all_passed,
+            failed_conditions,
+        };
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("deposit"),
+            symbol_short!("verified"),
             escrow_id,
-            &caller,
-            amount,
-            if fully_funded {
-                symbol_short!("funded")
+            &env.current_contract_address(),
+            0,
+            if all_passed {
+                symbol_short!("pass")
             } else {
-                symbol_short!("pending")
-            },
-            EventData::EscrowDeposited(escrow_id, amount, escrow.deposited_amount),
-        );
-
-        Self::notify_external(
-            &env,
-            NotificationPayload {
-                escrow_id,
-                event_type: EventType::Deposit,
-                amount,
-                timestamp: env.ledger().timestamp(),
+                symbol_short!("fail")
             },
+            EventData::AdminAction(symbol_short!("na")),
         );
 
-        Ok(())
+        Ok(result)
     }
 
-    pub fn get_escrow(env: Env, escrow_id: u64) -> Option<Escrow> {
-        env.storage().instance().get(&DataKey::Escrow(escrow_id))
-    }
+    pub fn acknowledge(env: Env, escrow_id: u64, recipient: Address) -> Result<(), Error> {
+        recipient.require_auth();
 
-    pub fn query_escrows_by_sender(
-        env: Env,
-        sender: Address,
-        limit: u32,
-        offset: u32,
-    ) -> Vec<Escrow> {
-        let mut results = Vec::new(&env);
-        let counter: u64 = env
+        let escrow: Escrow = env
             .storage()
             .instance()
-            .get(&DataKey::EscrowCounter)
-            .unwrap_or(0u64);
-        let mut skipped = 0u32;
-        for id in 1..=counter {
-            if let Some(escrow) = env
-                .storage()
-                .instance()
-                .get::<_, Escrow>(&DataKey::Escrow(id))
-            {
-                if escrow.sender == sender {
-                    if skipped < offset {
-                        skipped += 1;
-                    } else if results.len() < limit {
-                        results.push_back(escrow);
-                    }
-                }
-            }
-        }
-        results
-    }
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
 
-    pub fn query_escrows_by_recipient(
-        env: Env,
-        recipient: Address,
-        limit: u32,
-        offset: u32,
-    ) -> Vec<Escrow> {
-        let mut results = Vec::new(&env);
-        let counter: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::EscrowCounter)
-            .unwrap_or(0u64);
-        let mut skipped = 0u32;
-        for id in 1..=counter {
-            if let Some(escrow) = env
-                .storage()
-                .instance()
-                .get::<_, Escrow>(&DataKey::Escrow(id))
-            {
-                if escrow.recipient == recipient {
-                    if skipped < offset {
-                        skipped += 1;
-                    } else if results.len() < limit {
-                        results.push_back(escrow);
-                    }
-                }
-            }
+        if recipient != escrow.recipient {
+            return Err(Error::Unauthorized);
         }
-        results
-    }
 
-    pub fn query_escrows_by_status(
-        env: Env,
-        status: EscrowStatus,
-        limit: u32,
-        offset: u32,
-    ) -> Vec<Escrow> {
-        let mut results = Vec::new(&env);
-        let counter: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::EscrowCounter)
-            .unwrap_or(0u64);
-        let mut skipped = 0u32;
-        for id in 1..=counter {
-            if let Some(escrow) = env
-                .storage()
-                .instance()
-                .get::<_, Escrow>(&DataKey::Escrow(id))
-            {
-                if escrow.status == status {
-                    if skipped < offset {
-                        skipped += 1;
-                    } else if results.len() < limit {
-                        results.push_back(escrow);
-                    }
-                }
-            }
+        if escrow.status != EscrowStatus::Pending
+            && escrow.status != EscrowStatus::Funded
+            && escrow.status != EscrowStatus::Approved
+        {
+            return Err(Error::InvalidStatus);
         }
-        results
-    }
 
-    pub fn query_escrows_by_date_range(
-        env: Env,
-        start: u64,
-        end: u64,
-        limit: u32,
-        offset: u32,
-    ) -> Vec<Escrow> {
-        let mut results = Vec::new(&env);
-        let counter: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::EscrowCounter)
-            .unwrap_or(0u64);
-        let mut skipped = 0u32;
-        for id in 1..=counter {
-            if let Some(escrow) = env
-                .storage()
-                .instance()
-                .get::<_, Escrow>(&DataKey::Escrow(id))
-            {
-                if escrow.created_at >= start && escrow.created_at <= end {
-                    if skipped < offset {
-                        skipped += 1;
-                    } else if results.len() < limit {
-                        results.push_back(escrow);
-                    }
-                }
-            }
+        let ack_key = DataKey::RecipientAck(escrow_id);
+        if env.storage().instance().has(&ack_key) {
+            return Err(Error::AlreadyApproved);
         }
-        results
-    }
 
-    pub fn query_escrows_by_amount_range(
-        env: Env,
-        min_amount: i128,
-        max_amount: i128,
-        limit: u32,
-        offset: u32,
-    ) -> Vec<Escrow> {
-        let mut results = Vec::new(&env);
-        let counter: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::EscrowCounter)
-            .unwrap_or(0u64);
-        let mut skipped = 0u32;
-        for id in 1..=counter {
-            if let Some(escrow) = env
-                .storage()
-                .instance()
-                .get::<_, Escrow>(&DataKey::Escrow(id))
-            {
-                if escrow.amount >= min_amount && escrow.amount <= max_amount {
-                    if skipped < offset {
-                        skipped += 1;
-                    } else if results.len() < limit {
-                        results.push_back(escrow);
-                    }
-                }
-            }
-        }
-        results
+        let acknowledged_at = env.ledger().timestamp();
+        env.storage().instance().set(&ack_key, &acknowledged_at);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("ack"),
+            escrow_id,
+            &recipient,
+            0,
+            symbol_short!("na"),
+            EventData::AddressAction(symbol_short!("ack"), recipient.clone()),
+        );
+
+        Ok(())
     }
 
-    pub fn register_notification_hook(
+    /// Turns the two-sided release handshake on or off. Only the sender may
+    /// change it, and only before the escrow is released.
+    pub fn set_two_sided_release(
         env: Env,
         escrow_id: u64,
-        caller: Address,
-        config: NotificationConfig,
+        sender: Address,
+        enabled: bool,
     ) -> Result<(), Error> {
-        caller.require_auth();
-        Self::validate_notification_config(&config)?;
+        sender.require_auth();
 
-        let escrow: Escrow = env
+        let mut escrow: Escrow = env
             .storage()
             .instance()
             .get(&DataKey::Escrow(escrow_id))
             .ok_or(Error::EscrowNotFound)?;
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if caller != escrow.sender && caller != escrow.recipient && caller != admin {
-            return Err(Error::UnauthorizedCaller);
+
+        if sender != escrow.sender {
+            return Err(Error::WrongSender);
+        }
+        if escrow.status != EscrowStatus::Pending && escrow.status != EscrowStatus::Funded {
+            return Err(Error::InvalidStatus);
         }
 
-        let mut hooks: Vec<NotificationConfig> = env
+        escrow.two_sided_release = enabled;
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("two_side"),
+            escrow_id,
+            &sender,
+            enabled as i128,
+            symbol_short!("na"),
+            EventData::AddressAction(symbol_short!("two_side"), sender.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Records the sender's side of the two-sided release handshake.
+    pub fn authorize_release(env: Env, escrow_id: u64, sender: Address) -> Result<(), Error> {
+        sender.require_auth();
+
+        let escrow: Escrow = env
             .storage()
             .instance()
-            .get(&DataKey::NotificationHooks(escrow_id))
-            .unwrap_or(Vec::new(&env));
-        if hooks.len() >= MAX_HOOKS {
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        if sender != escrow.sender {
+            return Err(Error::WrongSender);
+        }
+        if escrow.status != EscrowStatus::Pending
+            && escrow.status != EscrowStatus::Funded
+            && escrow.status != EscrowStatus::Approved
+        {
             return Err(Error::InvalidStatus);
         }
-        hooks.push_back(config);
+
+        let key = DataKey::SenderReleaseAuth(escrow_id);
+        if env.storage().instance().has(&key) {
+            return Err(Error::AlreadyApproved);
+        }
         env.storage()
             .instance()
-            .set(&DataKey::NotificationHooks(escrow_id), &hooks);
+            .set(&key, &env.ledger().timestamp());
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("hook_reg"),
+            symbol_short!("rel_auth"),
             escrow_id,
-            &caller,
-            hooks.len() as i128,
-            symbol_short!("active"),
-            EventData::AdminAction(symbol_short!("hook_reg")),
+            &sender,
+            0,
+            symbol_short!("na"),
+            EventData::AddressAction(symbol_short!("rel_auth"), sender.clone()),
         );
 
         Ok(())
     }
 
-    pub fn get_notification_hooks(env: Env, escrow_id: u64) -> Vec<NotificationConfig> {
-        env.storage()
-            .instance()
-            .get(&DataKey::NotificationHooks(escrow_id))
-            .unwrap_or(Vec::new(&env))
-    }
-
-    pub fn get_notification_history(env: Env, escrow_id: u64) -> Vec<NotificationDelivery> {
+    pub fn get_release_authorization(env: Env, escrow_id: u64) -> Option<u64> {
         env.storage()
             .instance()
-            .get(&DataKey::NotificationHistory(escrow_id))
-            .unwrap_or(Vec::new(&env))
+            .get(&DataKey::SenderReleaseAuth(escrow_id))
     }
 
-    pub fn create_recurring_escrow(
-        env: Env,
-        sender: Address,
-        config: RecurringConfig,
-    ) -> Result<u64, Error> {
-        sender.require_auth();
-        if config.interval == 0 || config.count == 0 || config.amount <= 0 {
-            return Err(Error::InvalidAmount);
-        }
-        if sender == config.recipient {
-            return Err(Error::SameSenderRecipient);
-        }
-        if !Self::is_supported_asset(&env, &config.asset) {
-            return Err(Error::InvalidAsset);
-        }
+    /// Records the recipient's request to release. Release stays blocked
+    /// until the sender approves it.
+    pub fn request_release(env: Env, escrow_id: u64, recipient: Address) -> Result<(), Error> {
+        recipient.require_auth();
 
-        let mut counter: u64 = env
+        let escrow: Escrow = env
             .storage()
             .instance()
-            .get(&DataKey::RecurringCounter)
-            .unwrap_or(0u64);
-        counter = counter.checked_add(1).ok_or(Error::CounterOverflow)?;
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
 
-        let recurring = RecurringEscrow {
-            recurring_id: counter,
-            sender: sender.clone(),
-            config,
-            next_run_at: env.ledger().timestamp(),
-            processed_count: 0,
-            cancelled: false,
-            created_at: env.ledger().timestamp(),
-        };
+        if recipient != escrow.recipient {
+            return Err(Error::Unauthorized);
+        }
+        if escrow.status != EscrowStatus::Funded && escrow.status != EscrowStatus::Approved {
+            return Err(Error::InvalidStatus);
+        }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Recurring(counter), &recurring);
-        env.storage()
-            .instance()
-            .set(&DataKey::RecurringCounter, &counter);
+        let key = RequestKey::RecipientRelease(escrow_id);
+        if env.storage().instance().has(&key) {
+            return Err(Error::AlreadyApproved);
+        }
         env.storage().instance().set(
-            &DataKey::RecurringHistory(counter),
-            &Vec::<RecurringPayment>::new(&env),
+            &key,
+            &ReleaseRequest {
+                requested_at: env.ledger().timestamp(),
+                approved_at: None,
+            },
         );
 
-        Ok(counter)
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("rel_req"),
+            escrow_id,
+            &recipient,
+            0,
+            symbol_short!("na"),
+            EventData::AddressAction(symbol_short!("rel_req"), recipient.clone()),
+        );
+
+        Ok(())
     }
 
-    pub fn process_recurring_payment(env: Env, recurring_id: u64) -> Result<u64, Error> {
-        let mut recurring: RecurringEscrow = env
+    pub fn approve_release_request(env: Env, escrow_id: u64, sender: Address) -> Result<(), Error> {
+        sender.require_auth();
+
+        let escrow: Escrow = env
             .storage()
             .instance()
-            .get(&DataKey::Recurring(recurring_id))
+            .get(&DataKey::Escrow(escrow_id))
             .ok_or(Error::EscrowNotFound)?;
-        if recurring.cancelled {
-            return Err(Error::InvalidStatus);
-        }
-        if recurring.processed_count >= recurring.config.count {
-            return Err(Error::InvalidStatus);
-        }
-        let now = env.ledger().timestamp();
-        if now < recurring.next_run_at {
-            return Err(Error::NotExpired);
-        }
-
-        let escrow_id = Self::create_escrow(
-            env.clone(),
-            recurring.sender.clone(),
-            recurring.config.recipient.clone(),
-            recurring.config.amount,
-            recurring.config.asset.clone(),
-            now.checked_add(recurring.config.expiration_window)
-                .ok_or(Error::ArithmeticOverflow)?,
-            recurring.config.memo.clone(),
-        )?;
 
-        if recurring.config.auto_release {
-            let mut escrow: Escrow = env
-                .storage()
-                .instance()
-                .get(&DataKey::Escrow(escrow_id))
-                .ok_or(Error::EscrowNotFound)?;
-            escrow.allow_partial_release = false;
-            env.storage()
-                .instance()
-                .set(&DataKey::Escrow(escrow_id), &escrow);
-        }
-
-        recurring.processed_count = recurring
-            .processed_count
-            .checked_add(1)
-            .ok_or(Error::ArithmeticOverflow)?;
-        recurring.next_run_at = now
-            .checked_add(recurring.config.interval)
-            .ok_or(Error::ArithmeticOverflow)?;
-        if recurring.processed_count >= recurring.config.count {
-            recurring.cancelled = true;
+        if sender != escrow.sender {
+            return Err(Error::WrongSender);
         }
-        env.storage()
-            .instance()
-            .set(&DataKey::Recurring(recurring_id), &recurring);
 
-        let mut history: Vec<RecurringPayment> = env
+        let key = RequestKey::RecipientRelease(escrow_id);
+        let mut request: ReleaseRequest = env
             .storage()
             .instance()
-            .get(&DataKey::RecurringHistory(recurring_id))
-            .unwrap_or(Vec::new(&env));
-        history.push_back(RecurringPayment {
-            escrow_id,
-            processed_at: now,
-            sequence: recurring.processed_count,
-        });
-        env.storage()
-            .instance()
-            .set(&DataKey::RecurringHistory(recurring_id), &history);
+            .get(&key)
+            .ok_or(Error::ConditionsNotMet)?;
+        if request.approved_at.is_some() {
+            return Err(Error::AlreadyApproved);
+        }
+        request.approved_at = Some(env.ledger().timestamp());
+        env.storage().instance().set(&key, &request);
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("rec_pay"),
+            symbol_short!("rel_appr"),
             escrow_id,
-            &recurring.sender,
-            recurring.config.amount,
-            symbol_short!("created"),
-            EventData::AdminAction(symbol_short!("rec_pay")),
+            &sender,
+            0,
+            symbol_short!("na"),
+            EventData::AddressAction(symbol_short!("rel_appr"), sender.clone()),
         );
 
-        Ok(escrow_id)
+        Ok(())
     }
 
-    pub fn cancel_recurring_escrow(
-        env: Env,
-        recurring_id: u64,
-        caller: Address,
-    ) -> Result<(), Error> {
-        caller.require_auth();
-        let mut recurring: RecurringEscrow = env
-            .storage()
-            .instance()
-            .get(&DataKey::Recurring(recurring_id))
-            .ok_or(Error::EscrowNotFound)?;
-        let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if caller != recurring.sender && caller != admin {
-            return Err(Error::UnauthorizedCaller);
-        }
-        recurring.cancelled = true;
+    pub fn get_release_request(env: Env, escrow_id: u64) -> Option<ReleaseRequest> {
         env.storage()
             .instance()
-            .set(&DataKey::Recurring(recurring_id), &recurring);
-        Ok(())
+            .get(&RequestKey::RecipientRelease(escrow_id))
     }
 
-    pub fn get_recurring_escrow(env: Env, recurring_id: u64) -> Option<RecurringEscrow> {
+    fn handshake_complete(env: &Env, escrow: &Escrow) -> bool {
+        // Hash-locked escrows release only through `claim`.
+        if Self::hash_lock_active(env, escrow.escrow_id) {
+            return false;
+        }
+        // A pending recipient request holds release until the sender approves.
+        if let Some(request) = env
+            .storage()
+            .instance()
+            .get::<_, ReleaseRequest>(&RequestKey::RecipientRelease(escrow.escrow_id))
+        {
+            if request.approved_at.is_none() {
+                return false;
+            }
+        }
+        if !escrow.two_sided_release {
+            return true;
+        }
         env.storage()
             .instance()
-            .get(&DataKey::Recurring(recurring_id))
+            .has(&DataKey::RecipientAck(escrow.escrow_id))
+            && env
+                .storage()
+                .instance()
+                .has(&DataKey::SenderReleaseAuth(escrow.escrow_id))
     }
 
-    pub fn get_recurring_history(env: Env, recurring_id: u64) -> Vec<RecurringPayment> {
+    pub fn get_acknowledgment(env: Env, escrow_id: u64) -> Option<u64> {
         env.storage()
             .instance()
-            .get(&DataKey::RecurringHistory(recurring_id))
-            .unwrap_or(Vec::new(&env))
+            .get(&DataKey::RecipientAck(escrow_id))
     }
 
-    pub fn approve_escrow(env: Env, escrow_id: u64, approver: Address) -> Result<(), Error> {
+    pub fn add_approval(env: Env, escrow_id: u64, approver: Address) -> Result<(), Error> {
         approver.require_auth();
 
         let mut escrow: Escrow = env
@@ -2199,299 +7098,277 @@ impl PaymentEscrowContract {
             .get(&DataKey::Escrow(escrow_id))
             .ok_or(Error::EscrowNotFound)?;
 
-        if escrow.status != EscrowStatus::Funded {
-            return Err(Error::InvalidStatus);
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if approver != stored_admin && approver != escrow.recipient && approver != escrow.sender {
+            return Err(Error::Unauthorized);
+        }
+        if approver == escrow.recipient && !Self::recipient_can_self_approve(env.clone(), escrow_id)
+        {
+            return Err(Error::Unauthorized);
         }
 
-        escrow.status = EscrowStatus::Approved;
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+        escrow.release_conditions.current_approvals = escrow
+            .release_conditions
+            .current_approvals
+            .checked_add(1)
+            .unwrap_or(escrow.release_conditions.current_approvals);
+
+        Self::store_escrow(&env, escrow_id, &mut escrow);
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("approved"),
+            symbol_short!("approval"),
             escrow_id,
             &approver,
-            escrow.amount,
-            symbol_short!("approved"),
-            EventData::EscrowApproved(escrow_id),
-        );
-
-        Self::notify_external(
-            &env,
-            NotificationPayload {
-                escrow_id,
-                event_type: EventType::Approved,
-                amount: escrow.amount,
-                timestamp: env.ledger().timestamp(),
-            },
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("na")),
         );
 
         Ok(())
     }
-    pub fn extend_escrow(
+
+    /// Lets the sender stop the recipient from counting towards the approvals
+    /// gating their own release. Recipients may self-approve by default.
+    pub fn set_recipient_can_self_approve(
         env: Env,
         escrow_id: u64,
-        caller: Address,
-        new_expiration: u64,
+        sender: Address,
+        allowed: bool,
     ) -> Result<(), Error> {
-        caller.require_auth();
-
-        let mut escrow: Escrow = env
-            .storage()
-            .instance()
-            .get(&DataKey::Escrow(escrow_id))
-            .ok_or(Error::EscrowNotFound)?;
-
-        if caller != escrow.sender && caller != escrow.recipient {
-            return Err(Error::Unauthorized);
-        }
-
-        let current_time = env.ledger().timestamp();
-        if current_time > escrow.release_conditions.expiration_timestamp {
-            return Err(Error::Expired);
-        }
-
-        if new_expiration <= current_time {
-            return Err(Error::InvalidAmount);
+        sender.require_auth();
+
+        let escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+        if sender != escrow.sender {
+            return Err(Error::WrongSender);
         }
 
-        escrow.release_conditions.expiration_timestamp = new_expiration;
         env.storage()
             .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+            .set(&ApprovalKey::RecipientSelfApproval(escrow_id), &allowed);
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("extended"),
+            symbol_short!("self_appr"),
             escrow_id,
-            &caller,
-            new_expiration as i128,
-            symbol_short!("active"),
-            EventData::EscrowExtended(escrow_id, new_expiration),
+            &sender,
+            allowed as i128,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("self_appr")),
         );
 
         Ok(())
     }
 
-    pub fn release_escrow(
+    pub fn recipient_can_self_approve(env: Env, escrow_id: u64) -> bool {
+        env.storage()
+            .instance()
+            .get(&ApprovalKey::RecipientSelfApproval(escrow_id))
+            .unwrap_or(true)
+    }
+
+    /// Requires every multi-party approver on this escrow to hold a current
+    /// KYC verification at `min_tier` or above; `None` lifts the requirement.
+    /// Off by default.
+    pub fn set_approver_kyc_required(
         env: Env,
         escrow_id: u64,
         caller: Address,
-        token_address: Address,
+        min_tier: Option<u32>,
     ) -> Result<(), Error> {
         caller.require_auth();
-        Self::enforce_rate_limit(&env, &caller, FunctionType::Release)?;
-
-        let guard: bool = env
-            .storage()
-            .instance()
-            .get(&DataKey::ReentrancyGuard)
-            .unwrap_or(false);
-        if guard {
-            return Err(Error::UnauthorizedCaller);
-        }
-        env.storage()
-            .instance()
-            .set(&DataKey::ReentrancyGuard, &true);
 
-        let mut escrow: Escrow = env
+        let escrow: Escrow = env
             .storage()
             .instance()
             .get(&DataKey::Escrow(escrow_id))
             .ok_or(Error::EscrowNotFound)?;
-
-        if !escrow.compliant {
-            env.storage()
-                .instance()
-                .set(&DataKey::ReentrancyGuard, &false);
-            return Err(Error::NonCompliant);
-        }
-
-        if escrow.status != EscrowStatus::Approved && escrow.status != EscrowStatus::Funded {
-            env.storage()
-                .instance()
-                .set(&DataKey::ReentrancyGuard, &false);
-            return Err(Error::NotApproved);
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != escrow.sender && caller != stored_admin {
+            return Err(Error::Unauthorized);
         }
 
-        if escrow.status == EscrowStatus::Released && !escrow.allow_partial_release {
-            env.storage()
+        match min_tier {
+            Some(tier) => env
+                .storage()
                 .instance()
-                .set(&DataKey::ReentrancyGuard, &false);
-            return Err(Error::AlreadyReleased);
-        }
-
-        if escrow.multi_party_enabled {
-            let config_opt: Option<MultiPartyConfig> = env
+                .set(&ApprovalKey::ApproverKycRequired(escrow_id), &tier),
+            None => env
                 .storage()
                 .instance()
-                .get(&DataKey::EscrowApprovals(escrow_id));
-            match config_opt {
-                Some(config) => {
-                    let current_time = env.ledger().timestamp();
-                    if config.approval_timeout > 0 && current_time > config.approval_timeout {
-                        env.storage()
-                            .instance()
-                            .set(&DataKey::ReentrancyGuard, &false);
-                        return Err(Error::ApprovalExpired);
-                    }
-                    if config.approvals.len() < config.required_approvals {
-                        env.storage()
-                            .instance()
-                            .set(&DataKey::ReentrancyGuard, &false);
-                        return Err(Error::QuorumNotMet);
-                    }
-                }
-                None => {
-                    env.storage()
-                        .instance()
-                        .set(&DataKey::ReentrancyGuard, &false);
-                    return Err(Error::QuorumNotMet);
-                }
-            }
+                .remove(&ApprovalKey::ApproverKycRequired(escrow_id)),
         }
 
-        let current_time = env.ledger().timestamp();
-        if current_time > escrow.release_conditions.expiration_timestamp {
-            escrow.status = EscrowStatus::Expired;
-            env.storage()
-                .instance()
-                .set(&DataKey::Escrow(escrow_id), &escrow);
-            env.storage()
-                .instance()
-                .set(&DataKey::ReentrancyGuard, &false);
-            return Err(Error::Expired);
-        }
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("appr_kyc"),
+            escrow_id,
+            &caller,
+            min_tier.map(|tier| tier as i128).unwrap_or(-1),
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("appr_kyc")),
+        );
+
+        Ok(())
+    }
+
+    /// Minimum KYC tier required of this escrow's approvers, if any.
+    pub fn approver_kyc_required(env: Env, escrow_id: u64) -> Option<u32> {
+        env.storage()
+            .instance()
+            .get(&ApprovalKey::ApproverKycRequired(escrow_id))
+    }
+
+    /// Adds `contract` to, or removes it from, the set of contracts senders
+    /// may register as release callbacks.
+    pub fn set_callback_whitelisted(
+        env: Env,
+        admin: Address,
+        contract: Address,
+        allowed: bool,
+    ) -> Result<(), Error> {
+        admin.require_auth();
 
         let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if caller != escrow.recipient && caller != stored_admin {
-            env.storage()
-                .instance()
-                .set(&DataKey::ReentrancyGuard, &false);
+        if admin != stored_admin {
             return Err(Error::Unauthorized);
         }
 
-        if escrow.deposited_amount == 0 {
-            env.storage()
-                .instance()
-                .set(&DataKey::ReentrancyGuard, &false);
-            return Err(Error::InsufficientFunds);
+        let key = CallbackKey::Whitelisted(contract.clone());
+        if allowed {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
         }
 
-        let available_amount = escrow
-            .deposited_amount
-            .checked_sub(escrow.released_amount)
-            .ok_or(Error::ArithmeticOverflow)?;
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("cb_list"),
+            0,
+            &admin,
+            allowed as i128,
+            symbol_short!("na"),
+            EventData::AddressAction(symbol_short!("cb_list"), contract),
+        );
 
-        if available_amount <= 0 {
-            env.storage()
-                .instance()
-                .set(&DataKey::ReentrancyGuard, &false);
-            return Err(Error::InsufficientFunds);
-        }
+        Ok(())
+    }
 
-        let fee_percentage = Self::get_platform_fee(env.clone());
-        let fee_amount = available_amount
-            .checked_mul(fee_percentage)
-            .ok_or(Error::ArithmeticOverflow)?
-            .checked_div(10000)
-            .ok_or(Error::ArithmeticOverflow)?;
+    pub fn is_callback_whitelisted(env: Env, contract: Address) -> bool {
+        env.storage()
+            .instance()
+            .has(&CallbackKey::Whitelisted(contract))
+    }
 
-        let recipient_amount = available_amount
-            .checked_sub(fee_amount)
-            .ok_or(Error::ArithmeticOverflow)?;
+    /// Registers the contract notified when this escrow is released. Only
+    /// whitelisted contracts are accepted, and the callback is skipped unless
+    /// `enabled` is set.
+    pub fn set_release_callback(
+        env: Env,
+        escrow_id: u64,
+        sender: Address,
+        callback: Option<ReleaseCallback>,
+    ) -> Result<(), Error> {
+        sender.require_auth();
 
-        if recipient_amount <= 0 {
-            env.storage()
-                .instance()
-                .set(&DataKey::ReentrancyGuard, &false);
-            return Err(Error::InsufficientAmount);
+        let escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+        if sender != escrow.sender {
+            return Err(Error::WrongSender);
+        }
+        if escrow.status != EscrowStatus::Pending && escrow.status != EscrowStatus::Funded {
+            return Err(Error::InvalidStatus);
         }
 
-        let token_client = token::Client::new(&env, &token_address);
-        let contract_address = env.current_contract_address();
+        let key = CallbackKey::ReleaseCallback(escrow_id);
+        let enabled = match &callback {
+            Some(cb) => {
+                if !Self::is_callback_whitelisted(env.clone(), cb.contract.clone()) {
+                    return Err(Error::Unauthorized);
+                }
+                env.storage().instance().set(&key, cb);
+                cb.enabled
+            }
+            None => {
+                env.storage().instance().remove(&key);
+                false
+            }
+        };
 
-        token_client.transfer(&contract_address, &escrow.recipient, &recipient_amount);
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("callback"),
+            escrow_id,
+            &sender,
+            enabled as i128,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("callback")),
+        );
 
-        if fee_amount > 0 {
-            token_client.transfer(&contract_address, &stored_admin, &fee_amount);
-        }
+        Ok(())
+    }
 
-        escrow.released_amount = escrow
-            .released_amount
-            .checked_add(available_amount)
-            .ok_or(Error::ArithmeticOverflow)?;
-        let current_asset_released = escrow
-            .released_amounts
-            .get(escrow.asset.clone())
-            .unwrap_or(0i128);
-        escrow.released_amounts.set(
-            escrow.asset.clone(),
-            current_asset_released
-                .checked_add(available_amount)
-                .ok_or(Error::ArithmeticOverflow)?,
-        );
-        escrow.status = EscrowStatus::Released;
-        escrow.release_timestamp = current_time;
+    /// Sets the accounting contract notified with
+    /// `on_release(escrow_id, recipient, amount, fee)` on every release.
+    pub fn set_accounting_hook(
+        env: Env,
+        admin: Address,
+        hook_address: Address,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
 
         env.storage()
             .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
-
-        if escrow.multi_party_enabled {
-            if let Some(mut config) = env
-                .storage()
-                .instance()
-                .get::<_, MultiPartyConfig>(&DataKey::EscrowApprovals(escrow_id))
-            {
-                config.finalized = true;
-                env.storage()
-                    .instance()
-                    .set(&DataKey::EscrowApprovals(escrow_id), &config);
-            }
-        }
+            .set(&CallbackKey::AccountingHook, &hook_address);
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("released"),
-            escrow_id,
-            &caller,
-            recipient_amount,
-            symbol_short!("released"),
-            EventData::EscrowReleased(escrow_id, recipient_amount),
+            symbol_short!("acct_hook"),
+            0,
+            &admin,
+            0,
+            symbol_short!("na"),
+            EventData::AddressAction(symbol_short!("acct_hook"), hook_address),
         );
 
-        Self::notify_external(
-            &env,
-            NotificationPayload {
-                escrow_id,
-                event_type: EventType::Released,
-                amount: recipient_amount,
-                timestamp: env.ledger().timestamp(),
-            },
-        );
+        Ok(())
+    }
+
+    pub fn get_accounting_hook(env: Env) -> Option<Address> {
+        env.storage().instance().get(&CallbackKey::AccountingHook)
+    }
 
+    pub fn get_release_callback(env: Env, escrow_id: u64) -> Option<ReleaseCallback> {
         env.storage()
             .instance()
-            .set(&DataKey::ReentrancyGuard, &false);
-
-        Ok(())
+            .get(&CallbackKey::ReleaseCallback(escrow_id))
     }
 
-    pub fn release_asset(
+    pub fn set_min_approvals(
         env: Env,
         escrow_id: u64,
         caller: Address,
-        asset: Asset,
-        token_address: Address,
+        min_approvals: u32,
     ) -> Result<(), Error> {
         caller.require_auth();
-        Self::enforce_rate_limit(&env, &caller, FunctionType::Release)?;
 
         let mut escrow: Escrow = env
             .storage()
@@ -2499,120 +7376,42 @@ impl PaymentEscrowContract {
             .get(&DataKey::Escrow(escrow_id))
             .ok_or(Error::EscrowNotFound)?;
 
-        if escrow.status != EscrowStatus::Approved && escrow.status != EscrowStatus::Funded {
-            return Err(Error::NotApproved);
-        }
-
-        let current_time = env.ledger().timestamp();
-        if current_time > escrow.release_conditions.expiration_timestamp {
-            escrow.status = EscrowStatus::Expired;
-            env.storage()
-                .instance()
-                .set(&DataKey::Escrow(escrow_id), &escrow);
-            return Err(Error::Expired);
-        }
-
         let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if caller != escrow.recipient && caller != stored_admin && caller != escrow.sender {
-            return Err(Error::UnauthorizedCaller);
-        }
-
-        let deposited = escrow.deposited_amounts.get(asset.clone()).unwrap_or(0i128);
-        let released = escrow.released_amounts.get(asset.clone()).unwrap_or(0i128);
-        let available_amount = deposited
-            .checked_sub(released)
-            .ok_or(Error::ArithmeticOverflow)?;
-        if available_amount <= 0 {
-            return Err(Error::InsufficientFunds);
-        }
-
-        let fee_breakdown = Self::calculate_fees(&env, available_amount)?;
-        let recipient_amount = available_amount
-            .checked_sub(fee_breakdown.total_fee)
-            .ok_or(Error::ArithmeticOverflow)?;
-
-        let token_client = token::Client::new(&env, &token_address);
-        let contract_address = env.current_contract_address();
-        token_client.transfer(&contract_address, &escrow.recipient, &recipient_amount);
-        if fee_breakdown.total_fee > 0 {
-            token_client.transfer(&contract_address, &stored_admin, &fee_breakdown.total_fee);
-        }
-
-        escrow.released_amounts.set(asset.clone(), deposited);
-        escrow.released_amount = escrow
-            .released_amount
-            .checked_add(available_amount)
-            .ok_or(Error::ArithmeticOverflow)?;
-
-        let mut all_released = true;
-        for escrow_asset in escrow.assets.iter() {
-            let escrow_asset: Asset = escrow_asset;
-            let asset_deposited: i128 = escrow
-                .deposited_amounts
-                .get(escrow_asset.clone())
-                .unwrap_or(0i128);
-            let asset_released: i128 = escrow
-                .released_amounts
-                .get(escrow_asset.clone())
-                .unwrap_or(0i128);
-            if asset_deposited > asset_released {
-                all_released = false;
-                break;
-            }
-        }
-        if all_released {
-            escrow.status = EscrowStatus::Released;
-            escrow.release_timestamp = current_time;
+        if caller != escrow.sender && caller != stored_admin {
+            return Err(Error::Unauthorized);
         }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+        escrow.release_conditions.min_approvals = min_approvals;
+        Self::store_escrow(&env, escrow_id, &mut escrow);
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("rel_ast"),
+            symbol_short!("min_appr"),
             escrow_id,
             &caller,
-            recipient_amount,
-            if all_released {
-                symbol_short!("released")
-            } else {
-                symbol_short!("funded")
-            },
-            EventData::EscrowReleased(escrow_id, recipient_amount),
-        );
-
-        Self::notify_external(
-            &env,
-            NotificationPayload {
-                escrow_id,
-                event_type: EventType::Released,
-                amount: recipient_amount,
-                timestamp: env.ledger().timestamp(),
-            },
+            min_approvals as i128,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("na")),
         );
 
         Ok(())
     }
 
-    pub fn release_partial(
+    pub fn refund_escrow(
         env: Env,
         escrow_id: u64,
         caller: Address,
         token_address: Address,
-        release_amount: i128,
+        reason: RefundReason,
     ) -> Result<(), Error> {
         if upgradeable::is_paused(&env) {
             return Err(Error::ContractPaused);
         }
         caller.require_auth();
-        Self::enforce_rate_limit(&env, &caller, FunctionType::Release)?;
+        Self::enforce_rate_limit(&env, &caller, FunctionType::Refund)?;
 
-        if release_amount <= 0 {
-            return Err(Error::InvalidAmount);
-        }
+        Self::check_escrow_token(&env, escrow_id, &token_address)?;
 
         let guard: bool = env
             .storage()
@@ -2632,16 +7431,31 @@ impl PaymentEscrowContract {
             .get(&DataKey::Escrow(escrow_id))
             .ok_or(Error::EscrowNotFound)?;
 
-        if !escrow.allow_partial_release {
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != escrow.sender && caller != stored_admin {
             env.storage()
                 .instance()
                 .set(&DataKey::ReentrancyGuard, &false);
-            return Err(Error::PartialReleaseNotAllowed);
+            return Err(Error::UnauthorizedRefund);
         }
 
-        if escrow.status != EscrowStatus::Approved
+        if escrow.status == EscrowStatus::Released {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::AlreadyReleased);
+        }
+
+        if escrow.status == EscrowStatus::Refunded {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(Error::AlreadyRefunded);
+        }
+
+        if escrow.status != EscrowStatus::Pending
             && escrow.status != EscrowStatus::Funded
-            && escrow.status != EscrowStatus::Released
+            && escrow.status != EscrowStatus::Approved
         {
             env.storage()
                 .instance()
@@ -2656,14 +7470,14 @@ impl PaymentEscrowContract {
                 .get(&DataKey::EscrowApprovals(escrow_id));
             match config_opt {
                 Some(config) => {
-                    let current_time = env.ledger().timestamp();
-                    if config.approval_timeout > 0 && current_time > config.approval_timeout {
+                    let now = env.ledger().timestamp();
+                    if config.approval_timeout > 0 && now > config.approval_timeout {
                         env.storage()
                             .instance()
                             .set(&DataKey::ReentrancyGuard, &false);
                         return Err(Error::ApprovalExpired);
                     }
-                    if config.approvals.len() < config.required_approvals {
+                    if config.approvals.len() < Self::effective_quorum(&config) {
                         env.storage()
                             .instance()
                             .set(&DataKey::ReentrancyGuard, &false);
@@ -2680,411 +7494,408 @@ impl PaymentEscrowContract {
         }
 
         let current_time = env.ledger().timestamp();
-        if current_time > escrow.release_conditions.expiration_timestamp {
-            escrow.status = EscrowStatus::Expired;
-            env.storage()
-                .instance()
-                .set(&DataKey::Escrow(escrow_id), &escrow);
+
+        if let Err(err) = Self::check_timelock(&env, &escrow) {
             env.storage()
                 .instance()
                 .set(&DataKey::ReentrancyGuard, &false);
-            return Err(Error::Expired);
+            return Err(err);
         }
-
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if caller != escrow.recipient && caller != stored_admin {
+        if reason == RefundReason::Expiration
+            && current_time <= escrow.release_conditions.expiration_timestamp
+        {
             env.storage()
                 .instance()
                 .set(&DataKey::ReentrancyGuard, &false);
-            return Err(Error::UnauthorizedCaller);
+            return Err(Error::NotExpired);
         }
 
-        let available_amount = escrow
+        let available_for_refund = escrow
             .deposited_amount
             .checked_sub(escrow.released_amount)
+            .ok_or(Error::ArithmeticOverflow)?
+            .checked_sub(escrow.refunded_amount)
             .ok_or(Error::ArithmeticOverflow)?;
 
-        if release_amount > available_amount {
+        if available_for_refund <= 0 {
             env.storage()
                 .instance()
                 .set(&DataKey::ReentrancyGuard, &false);
-            return Err(Error::InsufficientFunds);
+            return Err(Error::NoFundsAvailable);
         }
 
-        let fee_percentage = Self::get_platform_fee(env.clone());
-        let fee_amount = release_amount
-            .checked_mul(fee_percentage)
+        let processing_fee_percentage = Self::get_processing_fee(env.clone());
+        let processing_fee = available_for_refund
+            .checked_mul(processing_fee_percentage)
             .ok_or(Error::ArithmeticOverflow)?
             .checked_div(10000)
             .ok_or(Error::ArithmeticOverflow)?;
 
-        let recipient_amount = release_amount
-            .checked_sub(fee_amount)
-            .ok_or(Error::ArithmeticOverflow)?;
-
-        let token_client = token::Client::new(&env, &token_address);
-        let contract_address = env.current_contract_address();
-
-        token_client.transfer(&contract_address, &escrow.recipient, &recipient_amount);
-
-        if fee_amount > 0 {
-            token_client.transfer(&contract_address, &stored_admin, &fee_amount);
-        }
-
-        escrow.released_amount = escrow
-            .released_amount
-            .checked_add(release_amount)
+        let refund_amount = available_for_refund
+            .checked_sub(processing_fee)
             .ok_or(Error::ArithmeticOverflow)?;
 
-        if escrow.released_amount >= escrow.deposited_amount {
-            escrow.status = EscrowStatus::Released;
-        }
-
-        escrow.release_timestamp = current_time;
-
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
-
-        if escrow.multi_party_enabled {
-            if let Some(mut config) = env
-                .storage()
-                .instance()
-                .get::<_, MultiPartyConfig>(&DataKey::EscrowApprovals(escrow_id))
-            {
-                if escrow.released_amount >= escrow.deposited_amount {
-                    config.finalized = true;
-                    env.storage()
-                        .instance()
-                        .set(&DataKey::EscrowApprovals(escrow_id), &config);
-                }
-            }
-        }
-
-        let partial_status = if escrow.released_amount >= escrow.deposited_amount {
-            symbol_short!("released")
-        } else {
-            symbol_short!("funded")
-        };
-        events::emit(
-            &env,
-            symbol_short!("escrow"),
-            symbol_short!("partial"),
-            escrow_id,
-            &caller,
-            recipient_amount,
-            partial_status,
-            EventData::EscrowReleased(escrow_id, recipient_amount),
-        );
-
-        env.storage()
-            .instance()
-            .set(&DataKey::ReentrancyGuard, &false);
-
-        Ok(())
-    }
-
-    pub fn enable_partial_release(env: Env, escrow_id: u64, caller: Address) -> Result<(), Error> {
-        caller.require_auth();
-
-        let mut escrow: Escrow = env
-            .storage()
-            .instance()
-            .get(&DataKey::Escrow(escrow_id))
-            .ok_or(Error::EscrowNotFound)?;
-
-        if caller != escrow.sender {
-            return Err(Error::Unauthorized);
-        }
-
-        escrow.allow_partial_release = true;
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
-
-        events::emit(
-            &env,
-            symbol_short!("escrow"),
-            symbol_short!("part_enab"),
-            escrow_id,
-            &caller,
-            0,
-            symbol_short!("na"),
-            EventData::AdminAction(symbol_short!("na")),
-        );
-
-        Ok(())
-    }
-
-    pub fn add_condition(
-        env: Env,
-        escrow_id: u64,
-        caller: Address,
-        condition_type: ConditionType,
-        required: bool,
-        threshold_value: i128,
-    ) -> Result<(), Error> {
-        caller.require_auth();
-
-        let mut escrow: Escrow = env
-            .storage()
-            .instance()
-            .get(&DataKey::Escrow(escrow_id))
-            .ok_or(Error::EscrowNotFound)?;
+        if refund_amount > 0 {
+            let token_client = token::Client::new(&env, &token_address);
 
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if caller != escrow.sender && caller != stored_admin {
-            return Err(Error::Unauthorized);
-        }
+            Self::refund_sender(
+                &env,
+                escrow_id,
+                &escrow,
+                &token_client,
+                &escrow.asset.code,
+                refund_amount,
+            );
 
-        if escrow.status != EscrowStatus::Pending && escrow.status != EscrowStatus::Funded {
-            return Err(Error::InvalidStatus);
+            if processing_fee > 0 {
+                Self::collect_fee(
+                    &env,
+                    escrow_id,
+                    &token_client,
+                    &escrow.asset.code,
+                    processing_fee,
+                    &Self::fee_destination(&env, &stored_admin),
+                    symbol_short!("process"),
+                );
+            }
+            Self::adjust_tvl(&env, &escrow.asset.code, -available_for_refund);
         }
 
-        let condition = Condition {
-            condition_type,
-            required,
-            verified: false,
-            threshold_value,
-        };
+        escrow.refunded_amount = escrow
+            .refunded_amount
+            .checked_add(available_for_refund)
+            .ok_or(Error::ArithmeticOverflow)?;
+        let current_asset_refunded = escrow
+            .refunded_amounts
+            .get(escrow.asset.clone())
+            .unwrap_or(0i128);
+        escrow.refunded_amounts.set(
+            escrow.asset.clone(),
+            current_asset_refunded
+                .checked_add(available_for_refund)
+                .ok_or(Error::ArithmeticOverflow)?,
+        );
+        escrow.status = EscrowStatus::Refunded;
+        escrow.refund_timestamp = current_time;
 
-        escrow.release_conditions.conditions.push_back(condition);
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+        Self::store_escrow(&env, escrow_id, &mut escrow);
+
+        if escrow.multi_party_enabled {
+            if let Some(mut config) = env
+                .storage()
+                .instance()
+                .get::<_, MultiPartyConfig>(&DataKey::EscrowApprovals(escrow_id))
+            {
+                config.finalized = true;
+                env.storage()
+                    .instance()
+                    .set(&DataKey::EscrowApprovals(escrow_id), &config);
+            }
+        }
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("cond_add"),
+            symbol_short!("refunded"),
             escrow_id,
             &caller,
-            0,
-            symbol_short!("na"),
-            EventData::AdminAction(symbol_short!("na")),
+            refund_amount,
+            symbol_short!("refunded"),
+            EventData::EscrowRefunded(
+                escrow_id,
+                refund_amount,
+                Self::fee_destination(&env, &stored_admin),
+            ),
+        );
+
+        Self::notify_external(
+            &env,
+            NotificationPayload {
+                escrow_id,
+                event_type: EventType::Refunded,
+                amount: refund_amount,
+                timestamp: env.ledger().timestamp(),
+            },
         );
 
+        env.storage()
+            .instance()
+            .set(&DataKey::ReentrancyGuard, &false);
+
         Ok(())
     }
 
-    pub fn set_condition_operator(
+    /// Lets the recipient decline a funded payment. The undisbursed balance
+    /// is returned to the sender in full, without a processing fee.
+    pub fn reject_escrow(
         env: Env,
         escrow_id: u64,
-        caller: Address,
-        operator: ConditionOperator,
+        recipient: Address,
+        token_address: Address,
     ) -> Result<(), Error> {
-        caller.require_auth();
+        if upgradeable::is_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        recipient.require_auth();
+        Self::check_escrow_token(&env, escrow_id, &token_address)?;
 
-        let mut escrow: Escrow = env
+        let guard: bool = env
             .storage()
             .instance()
-            .get(&DataKey::Escrow(escrow_id))
-            .ok_or(Error::EscrowNotFound)?;
-
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if caller != escrow.sender && caller != stored_admin {
-            return Err(Error::Unauthorized);
+            .get(&DataKey::ReentrancyGuard)
+            .unwrap_or(false);
+        if guard {
+            return Err(Error::UnauthorizedCaller);
         }
 
-        escrow.release_conditions.operator = operator;
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
-
-        events::emit(
-            &env,
-            symbol_short!("escrow"),
-            symbol_short!("cond_op"),
-            escrow_id,
-            &caller,
-            0,
-            symbol_short!("na"),
-            EventData::AdminAction(symbol_short!("na")),
-        );
-
-        Ok(())
-    }
-
-    pub fn verify_conditions(
-        env: Env,
-        escrow_id: u64,
-        proof_data: i128,
-    ) -> Result<VerificationResult, Error> {
         let mut escrow: Escrow = env
             .storage()
             .instance()
             .get(&DataKey::Escrow(escrow_id))
             .ok_or(Error::EscrowNotFound)?;
+        if recipient != escrow.recipient {
+            return Err(Error::Unauthorized);
+        }
+        if escrow.status != EscrowStatus::Funded && escrow.status != EscrowStatus::Approved {
+            return Err(Error::InvalidStatus);
+        }
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
 
-        let current_time = env.ledger().timestamp();
-        let mut failed_conditions = Vec::new(&env);
-        let mut passed_count = 0;
-        let mut required_count = 0;
-
-        for i in 0..escrow.release_conditions.conditions.len() {
-            let mut condition = escrow.release_conditions.conditions.get(i).unwrap();
-            let condition_type_copy = condition.condition_type;
-            let is_required = condition.required;
-
-            if is_required {
-                required_count += 1;
-            }
-
-            let verified = match condition.condition_type {
-                ConditionType::Timestamp => {
-                    current_time >= escrow.release_conditions.expiration_timestamp
-                }
-                ConditionType::Approval => {
-                    escrow.release_conditions.current_approvals
-                        >= escrow.release_conditions.min_approvals
-                }
-                ConditionType::OraclePrice => {
-                    if proof_data > 0 {
-                        proof_data >= condition.threshold_value
-                    } else {
-                        false
-                    }
-                }
-                ConditionType::MultiSignature => {
-                    escrow.release_conditions.current_approvals
-                        >= escrow.release_conditions.min_approvals
-                }
-                ConditionType::KYCVerified => escrow.kyc_compliant,
-            };
-
-            condition.verified = verified;
-            escrow.release_conditions.conditions.set(i, condition);
-
-            if verified {
-                passed_count += 1;
-            } else if is_required {
-                failed_conditions.push_back(condition_type_copy);
-            }
+        let refund_amount = escrow
+            .deposited_amount
+            .checked_sub(escrow.released_amount)
+            .ok_or(Error::ArithmeticOverflow)?
+            .checked_sub(escrow.refunded_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        if refund_amount <= 0 {
+            return Err(Error::NoFundsAvailable);
         }
 
         env.storage()
             .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
-
-        let all_passed = match escrow.release_conditions.operator {
-            ConditionOperator::And => {
-                failed_conditions.is_empty()
-                    && (required_count == 0 || passed_count >= required_count)
-            }
-            ConditionOperator::Or => passed_count > 0,
-        };
-
-        let result = VerificationResult {
-            all_passed,
-            failed_conditions,
-        };
+            .set(&DataKey::ReentrancyGuard, &true);
 
-        events::emit(
+        let token_client = token::Client::new(&env, &token_address);
+        Self::refund_sender(
             &env,
-            symbol_short!("escrow"),
-            symbol_short!("verified"),
             escrow_id,
-            &env.current_contract_address(),
-            0,
-            if all_passed {
-                symbol_short!("pass")
-            } else {
-                symbol_short!("fail")
-            },
-            EventData::AdminAction(symbol_short!("na")),
+            &escrow,
+            &token_client,
+            &escrow.asset.code,
+            refund_amount,
         );
+        Self::adjust_tvl(&env, &escrow.asset.code, -refund_amount);
 
-        Ok(result)
-    }
-
-    pub fn add_approval(env: Env, escrow_id: u64, approver: Address) -> Result<(), Error> {
-        approver.require_auth();
-
-        let mut escrow: Escrow = env
-            .storage()
-            .instance()
-            .get(&DataKey::Escrow(escrow_id))
-            .ok_or(Error::EscrowNotFound)?;
-
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if approver != stored_admin && approver != escrow.recipient && approver != escrow.sender {
-            return Err(Error::Unauthorized);
-        }
-
-        escrow.release_conditions.current_approvals = escrow
-            .release_conditions
-            .current_approvals
-            .checked_add(1)
-            .unwrap_or(escrow.release_conditions.current_approvals);
-
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+        escrow.refunded_amount = escrow
+            .refunded_amount
+            .checked_add(refund_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        let current_asset_refunded = escrow
+            .refunded_amounts
+            .get(escrow.asset.clone())
+            .unwrap_or(0i128);
+        escrow.refunded_amounts.set(
+            escrow.asset.clone(),
+            current_asset_refunded
+                .checked_add(refund_amount)
+                .ok_or(Error::ArithmeticOverflow)?,
+        );
+        escrow.status = EscrowStatus::Refunded;
+        escrow.refund_timestamp = env.ledger().timestamp();
+        Self::store_escrow(&env, escrow_id, &mut escrow);
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("approval"),
+            symbol_short!("rejected"),
             escrow_id,
-            &approver,
-            0,
-            symbol_short!("na"),
-            EventData::AdminAction(symbol_short!("na")),
+            &recipient,
+            refund_amount,
+            symbol_short!("refunded"),
+            EventData::EscrowRefunded(
+                escrow_id,
+                refund_amount,
+                Self::fee_destination(&env, &stored_admin),
+            ),
+        );
+
+        Self::notify_external(
+            &env,
+            NotificationPayload {
+                escrow_id,
+                event_type: EventType::Refunded,
+                amount: refund_amount,
+                timestamp: env.ledger().timestamp(),
+            },
         );
 
+        env.storage()
+            .instance()
+            .set(&DataKey::ReentrancyGuard, &false);
+
         Ok(())
     }
 
-    pub fn set_min_approvals(
+    /// Returns a partial deposit to the sender once the escrow has gone the
+    /// grace period without another deposit while still `Pending`. The
+    /// refund counts as a `RefundReason::SenderRequest` and carries no
+    /// processing fee.
+    pub fn cancel_underfunded(
         env: Env,
         escrow_id: u64,
         caller: Address,
-        min_approvals: u32,
+        token_address: Address,
     ) -> Result<(), Error> {
+        if upgradeable::is_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
         caller.require_auth();
+        Self::check_escrow_token(&env, escrow_id, &token_address)?;
+
+        let guard: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::ReentrancyGuard)
+            .unwrap_or(false);
+        if guard {
+            return Err(Error::UnauthorizedCaller);
+        }
 
         let mut escrow: Escrow = env
             .storage()
             .instance()
             .get(&DataKey::Escrow(escrow_id))
             .ok_or(Error::EscrowNotFound)?;
-
         let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         if caller != escrow.sender && caller != stored_admin {
-            return Err(Error::Unauthorized);
+            return Err(Error::UnauthorizedRefund);
+        }
+        if escrow.status != EscrowStatus::Pending {
+            return Err(Error::EscrowNotPending);
+        }
+        Self::check_timelock(&env, &escrow)?;
+
+        let refund_amount = escrow
+            .deposited_amount
+            .checked_sub(escrow.released_amount)
+            .ok_or(Error::ArithmeticOverflow)?
+            .checked_sub(escrow.refunded_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        if refund_amount <= 0 {
+            return Err(Error::NoFundsAvailable);
+        }
+
+        let now = env.ledger().timestamp();
+        let grace_period = Self::get_underfunded_grace_period(env.clone());
+        if now <= escrow.last_deposit_at.saturating_add(grace_period) {
+            return Err(Error::NotExpired);
         }
 
-        escrow.release_conditions.min_approvals = min_approvals;
         env.storage()
             .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+            .set(&DataKey::ReentrancyGuard, &true);
+
+        let token_client = token::Client::new(&env, &token_address);
+        Self::refund_sender(
+            &env,
+            escrow_id,
+            &escrow,
+            &token_client,
+            &escrow.asset.code,
+            refund_amount,
+        );
+        Self::adjust_tvl(&env, &escrow.asset.code, -refund_amount);
+
+        escrow.refunded_amount = escrow
+            .refunded_amount
+            .checked_add(refund_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        let current_asset_refunded = escrow
+            .refunded_amounts
+            .get(escrow.asset.clone())
+            .unwrap_or(0i128);
+        escrow.refunded_amounts.set(
+            escrow.asset.clone(),
+            current_asset_refunded
+                .checked_add(refund_amount)
+                .ok_or(Error::ArithmeticOverflow)?,
+        );
+        escrow.status = EscrowStatus::Refunded;
+        escrow.refund_timestamp = now;
+        Self::store_escrow(&env, escrow_id, &mut escrow);
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("min_appr"),
+            symbol_short!("underfund"),
             escrow_id,
             &caller,
-            min_approvals as i128,
-            symbol_short!("na"),
-            EventData::AdminAction(symbol_short!("na")),
+            refund_amount,
+            symbol_short!("refunded"),
+            EventData::EscrowRefunded(
+                escrow_id,
+                refund_amount,
+                Self::fee_destination(&env, &stored_admin),
+            ),
+        );
+
+        Self::notify_external(
+            &env,
+            NotificationPayload {
+                escrow_id,
+                event_type: EventType::Refunded,
+                amount: refund_amount,
+                timestamp: now,
+            },
         );
 
+        env.storage()
+            .instance()
+            .set(&DataKey::ReentrancyGuard, &false);
+
         Ok(())
     }
 
-    pub fn refund_escrow(
+    /// Settles a negotiated split in one step: `recipient_amount` is released
+    /// and `refund_amount` returned to the sender. The two must add up to the
+    /// available balance. The platform fee is taken once, from the released
+    /// share; the refunded share carries no processing fee.
+    pub fn split_settle(
         env: Env,
         escrow_id: u64,
-        caller: Address,
+        admin: Address,
         token_address: Address,
-        reason: RefundReason,
+        recipient_amount: i128,
+        refund_amount: i128,
     ) -> Result<(), Error> {
-        if upgradeable::is_paused(&env) {
-            return Err(Error::ContractPaused);
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
         }
-        caller.require_auth();
-        Self::enforce_rate_limit(&env, &caller, FunctionType::Refund)?;
+
+        Self::split_settle_internal(
+            &env,
+            escrow_id,
+            &admin,
+            &stored_admin,
+            &token_address,
+            recipient_amount,
+            refund_amount,
+        )
+    }
+
+    fn split_settle_internal(
+        env: &Env,
+        escrow_id: u64,
+        admin: &Address,
+        stored_admin: &Address,
+        token_address: &Address,
+        recipient_amount: i128,
+        refund_amount: i128,
+    ) -> Result<(), Error> {
+        Self::check_escrow_token(env, escrow_id, token_address)?;
 
         let guard: bool = env
             .storage()
@@ -3094,185 +7905,401 @@ impl PaymentEscrowContract {
         if guard {
             return Err(Error::UnauthorizedCaller);
         }
-        env.storage()
-            .instance()
-            .set(&DataKey::ReentrancyGuard, &true);
 
         let mut escrow: Escrow = env
             .storage()
             .instance()
             .get(&DataKey::Escrow(escrow_id))
             .ok_or(Error::EscrowNotFound)?;
-
-        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if caller != escrow.sender && caller != stored_admin {
-            env.storage()
-                .instance()
-                .set(&DataKey::ReentrancyGuard, &false);
-            return Err(Error::UnauthorizedRefund);
+        if escrow.status != EscrowStatus::Funded
+            && escrow.status != EscrowStatus::Approved
+            && escrow.status != EscrowStatus::Disputed
+        {
+            return Err(Error::InvalidStatus);
+        }
+        if recipient_amount < 0 || refund_amount < 0 {
+            return Err(Error::InvalidAmount);
         }
 
-        if escrow.status == EscrowStatus::Released {
-            env.storage()
-                .instance()
-                .set(&DataKey::ReentrancyGuard, &false);
-            return Err(Error::AlreadyReleased);
+        let available = escrow
+            .deposited_amount
+            .checked_sub(escrow.released_amount)
+            .ok_or(Error::ArithmeticOverflow)?
+            .checked_sub(escrow.refunded_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        let total = recipient_amount
+            .checked_add(refund_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        if available <= 0 || total != available {
+            return Err(Error::InvalidAmount);
+        }
+        if recipient_amount > 0 {
+            Self::check_release_kyc(env, escrow_id, &escrow)?;
         }
 
-        if escrow.status == EscrowStatus::Refunded {
-            env.storage()
-                .instance()
-                .set(&DataKey::ReentrancyGuard, &false);
-            return Err(Error::AlreadyRefunded);
+        let fee_amount = recipient_amount
+            .checked_mul(Self::platform_fee_for(env, escrow_id))
+            .ok_or(Error::ArithmeticOverflow)?
+            .checked_div(10000)
+            .ok_or(Error::ArithmeticOverflow)?;
+        let payout = recipient_amount
+            .checked_sub(fee_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ReentrancyGuard, &true);
+
+        let token_client = token::Client::new(env, token_address);
+        if payout > 0 {
+            Self::pay_recipient(
+                env,
+                escrow_id,
+                &escrow,
+                &token_client,
+                &escrow.asset.code,
+                payout,
+            );
         }
+        if fee_amount > 0 {
+            Self::collect_fee(
+                env,
+                escrow_id,
+                &token_client,
+                &escrow.asset.code,
+                fee_amount,
+                &Self::fee_destination(env, stored_admin),
+                symbol_short!("platform"),
+            );
+        }
+        if refund_amount > 0 {
+            Self::refund_sender(
+                env,
+                escrow_id,
+                &escrow,
+                &token_client,
+                &escrow.asset.code,
+                refund_amount,
+            );
+        }
+        Self::adjust_tvl(env, &escrow.asset.code, -available);
 
-        if escrow.status != EscrowStatus::Pending
-            && escrow.status != EscrowStatus::Funded
-            && escrow.status != EscrowStatus::Approved
-        {
-            env.storage()
-                .instance()
-                .set(&DataKey::ReentrancyGuard, &false);
-            return Err(Error::InvalidStatus);
+        escrow.released_amount = escrow
+            .released_amount
+            .checked_add(recipient_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        escrow.refunded_amount = escrow
+            .refunded_amount
+            .checked_add(refund_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        let asset_released = escrow
+            .released_amounts
+            .get(escrow.asset.clone())
+            .unwrap_or(0i128);
+        escrow.released_amounts.set(
+            escrow.asset.clone(),
+            asset_released
+                .checked_add(recipient_amount)
+                .ok_or(Error::ArithmeticOverflow)?,
+        );
+        let asset_refunded = escrow
+            .refunded_amounts
+            .get(escrow.asset.clone())
+            .unwrap_or(0i128);
+        escrow.refunded_amounts.set(
+            escrow.asset.clone(),
+            asset_refunded
+                .checked_add(refund_amount)
+                .ok_or(Error::ArithmeticOverflow)?,
+        );
+        let current_time = env.ledger().timestamp();
+        let final_status = if recipient_amount > 0 {
+            escrow.status = EscrowStatus::Released;
+            escrow.release_timestamp = current_time;
+            symbol_short!("released")
+        } else {
+            escrow.status = EscrowStatus::Refunded;
+            symbol_short!("refunded")
+        };
+        if refund_amount > 0 {
+            escrow.refund_timestamp = current_time;
         }
+        Self::store_escrow(env, escrow_id, &mut escrow);
 
-        if escrow.multi_party_enabled {
-            let config_opt: Option<MultiPartyConfig> = env
-                .storage()
-                .instance()
-                .get(&DataKey::EscrowApprovals(escrow_id));
-            match config_opt {
-                Some(config) => {
-                    let now = env.ledger().timestamp();
-                    if config.approval_timeout > 0 && now > config.approval_timeout {
-                        env.storage()
-                            .instance()
-                            .set(&DataKey::ReentrancyGuard, &false);
-                        return Err(Error::ApprovalExpired);
-                    }
-                    if config.approvals.len() < config.required_approvals {
-                        env.storage()
-                            .instance()
-                            .set(&DataKey::ReentrancyGuard, &false);
-                        return Err(Error::QuorumNotMet);
-                    }
-                }
-                None => {
-                    env.storage()
-                        .instance()
-                        .set(&DataKey::ReentrancyGuard, &false);
-                    return Err(Error::QuorumNotMet);
-                }
-            }
+        events::emit(
+            env,
+            symbol_short!("escrow"),
+            symbol_short!("split"),
+            escrow_id,
+            admin,
+            payout,
+            final_status,
+            EventData::ValueChanged(symbol_short!("split"), recipient_amount, refund_amount),
+        );
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ReentrancyGuard, &false);
+
+        Ok(())
+    }
+
+    pub fn set_keeper_bounty(
+        env: Env,
+        admin: Address,
+        per_escrow: i128,
+        max_per_sweep: i128,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        if per_escrow < 0 || max_per_sweep < 0 {
+            return Err(Error::InvalidAmount);
         }
 
-        let current_time = env.ledger().timestamp();
+        env.storage().instance().set(
+            &KeeperKey::KeeperBounty,
+            &KeeperBounty {
+                per_escrow,
+                max_per_sweep,
+            },
+        );
 
-        if reason == RefundReason::Expiration {
-            if current_time <= escrow.release_conditions.expiration_timestamp {
-                env.storage()
-                    .instance()
-                    .set(&DataKey::ReentrancyGuard, &false);
-                return Err(Error::NotExpired);
-            }
-        }
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("kp_bounty"),
+            0,
+            &admin,
+            per_escrow,
+            symbol_short!("na"),
+            EventData::ValueChanged(symbol_short!("kp_bounty"), per_escrow, max_per_sweep),
+        );
 
-        let available_for_refund = escrow
-            .deposited_amount
-            .checked_sub(escrow.released_amount)
-            .ok_or(Error::ArithmeticOverflow)?
-            .checked_sub(escrow.refunded_amount)
-            .ok_or(Error::ArithmeticOverflow)?;
+        Ok(())
+    }
 
-        if available_for_refund <= 0 {
-            env.storage()
-                .instance()
-                .set(&DataKey::ReentrancyGuard, &false);
-            return Err(Error::NoFundsAvailable);
+    pub fn get_keeper_bounty(env: Env) -> Option<KeeperBounty> {
+        env.storage().instance().get(&KeeperKey::KeeperBounty)
+    }
+
+    /// Refunds and marks as expired every listed escrow past its expiration,
+    /// paying `keeper` a bounty out of each one's processing fee. Escrows that
+    /// are missing, not yet expired, already settled, funded in another token
+    /// or under multi-party approval are skipped rather than failing the
+    /// batch. A summary event carries the swept and skipped counts. Returns
+    /// the number of escrows swept.
+    pub fn sweep_expired(
+        env: Env,
+        keeper: Address,
+        escrow_ids: Vec<u64>,
+        token_address: Address,
+    ) -> Result<u32, Error> {
+        if upgradeable::is_paused(&env) {
+            return Err(Error::ContractPaused);
         }
+        keeper.require_auth();
 
-        let processing_fee_percentage = Self::get_processing_fee(env.clone());
-        let processing_fee = available_for_refund
-            .checked_mul(processing_fee_percentage)
-            .ok_or(Error::ArithmeticOverflow)?
-            .checked_div(10000)
-            .ok_or(Error::ArithmeticOverflow)?;
+        Self::guarded_sweep(&env, &keeper, &escrow_ids, &token_address, true)
+    }
 
-        let refund_amount = available_for_refund
-            .checked_sub(processing_fee)
-            .ok_or(Error::ArithmeticOverflow)?;
+    /// Admin counterpart of `sweep_expired`: refunds and expires the same
+    /// escrows, skipping those that don't qualify, but pays no keeper bounty,
+    /// so the whole processing fee goes to the fee destination.
+    pub fn admin_sweep_expired(
+        env: Env,
+        admin: Address,
+        escrow_ids: Vec<u64>,
+        token_address: Address,
+    ) -> Result<u32, Error> {
+        if upgradeable::is_paused(&env) {
+            return Err(Error::ContractPaused);
+        }
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
 
-        if refund_amount > 0 {
-            let token_client = token::Client::new(&env, &token_address);
-            let contract_address = env.current_contract_address();
+        Self::guarded_sweep(&env, &admin, &escrow_ids, &token_address, false)
+    }
 
-            token_client.transfer(&contract_address, &escrow.sender, &refund_amount);
+    fn guarded_sweep(
+        env: &Env,
+        caller: &Address,
+        escrow_ids: &Vec<u64>,
+        token_address: &Address,
+        pay_bounty: bool,
+    ) -> Result<u32, Error> {
+        if escrow_ids.len() > MAX_BATCH_ESCROWS {
+            return Err(Error::RateLimitExceeded);
+        }
 
-            if processing_fee > 0 {
-                token_client.transfer(&contract_address, &stored_admin, &processing_fee);
-            }
+        let guard: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::ReentrancyGuard)
+            .unwrap_or(false);
+        if guard {
+            return Err(Error::UnauthorizedCaller);
         }
+        env.storage()
+            .instance()
+            .set(&DataKey::ReentrancyGuard, &true);
 
-        escrow.refunded_amount = escrow
-            .refunded_amount
-            .checked_add(available_for_refund)
-            .ok_or(Error::ArithmeticOverflow)?;
-        let current_asset_refunded = escrow
-            .refunded_amounts
-            .get(escrow.asset.clone())
-            .unwrap_or(0i128);
-        escrow.refunded_amounts.set(
-            escrow.asset.clone(),
-            current_asset_refunded
-                .checked_add(available_for_refund)
-                .ok_or(Error::ArithmeticOverflow)?,
-        );
-        escrow.status = EscrowStatus::Refunded;
-        escrow.refund_timestamp = current_time;
+        let result =
+            Self::sweep_expired_internal(env, caller, escrow_ids, token_address, pay_bounty);
 
         env.storage()
             .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+            .set(&DataKey::ReentrancyGuard, &false);
 
-        if escrow.multi_party_enabled {
-            if let Some(mut config) = env
-                .storage()
-                .instance()
-                .get::<_, MultiPartyConfig>(&DataKey::EscrowApprovals(escrow_id))
+        result
+    }
+
+    fn sweep_expired_internal(
+        env: &Env,
+        keeper: &Address,
+        escrow_ids: &Vec<u64>,
+        token_address: &Address,
+        pay_bounty: bool,
+    ) -> Result<u32, Error> {
+        let no_bounty = KeeperBounty {
+            per_escrow: 0,
+            max_per_sweep: 0,
+        };
+        let bounty = if pay_bounty {
+            Self::get_keeper_bounty(env.clone()).unwrap_or(no_bounty)
+        } else {
+            no_bounty
+        };
+        let processing_fee_percentage = Self::get_processing_fee(env.clone());
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        let token_client = token::Client::new(env, token_address);
+        let contract_address = env.current_contract_address();
+        let now = env.ledger().timestamp();
+
+        let mut swept: u32 = 0;
+        let mut skipped: u32 = 0;
+        let mut total_bounty: i128 = 0;
+
+        for escrow_id in escrow_ids.iter() {
+            let mut escrow: Escrow = match env.storage().instance().get(&DataKey::Escrow(escrow_id))
             {
-                config.finalized = true;
-                env.storage()
-                    .instance()
-                    .set(&DataKey::EscrowApprovals(escrow_id), &config);
+                Some(escrow) => escrow,
+                None => {
+                    skipped += 1;
+                    continue;
+                }
+            };
+
+            let open = matches!(
+                escrow.status,
+                EscrowStatus::Pending | EscrowStatus::Funded | EscrowStatus::Approved
+            );
+            if !open
+                || escrow.multi_party_enabled
+                || now <= escrow.release_conditions.expiration_timestamp
+                || Self::check_escrow_token(env, escrow_id, token_address).is_err()
+            {
+                skipped += 1;
+                continue;
+            }
+
+            let available = escrow
+                .deposited_amount
+                .checked_sub(escrow.released_amount)
+                .ok_or(Error::ArithmeticOverflow)?
+                .checked_sub(escrow.refunded_amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+
+            let processing_fee = available
+                .checked_mul(processing_fee_percentage)
+                .ok_or(Error::ArithmeticOverflow)?
+                .checked_div(10000)
+                .ok_or(Error::ArithmeticOverflow)?;
+            let keeper_share = bounty
+                .per_escrow
+                .min(processing_fee)
+                .min(bounty.max_per_sweep - total_bounty)
+                .max(0);
+            let refund_amount = available
+                .checked_sub(processing_fee)
+                .ok_or(Error::ArithmeticOverflow)?;
+
+            if refund_amount > 0 {
+                token_client.transfer(&contract_address, &escrow.sender, &refund_amount);
+            }
+            if keeper_share > 0 {
+                token_client.transfer(&contract_address, keeper, &keeper_share);
+            }
+            if processing_fee > keeper_share {
+                Self::collect_fee(
+                    env,
+                    escrow_id,
+                    &token_client,
+                    &escrow.asset.code,
+                    processing_fee - keeper_share,
+                    &Self::fee_destination(env, &stored_admin),
+                    symbol_short!("process"),
+                );
+            }
+            if available > 0 {
+                Self::adjust_tvl(env, &escrow.asset.code, -available);
             }
+
+            escrow.refunded_amount = escrow
+                .refunded_amount
+                .checked_add(available)
+                .ok_or(Error::ArithmeticOverflow)?;
+            let current_asset_refunded = escrow
+                .refunded_amounts
+                .get(escrow.asset.clone())
+                .unwrap_or(0i128);
+            escrow.refunded_amounts.set(
+                escrow.asset.clone(),
+                current_asset_refunded
+                    .checked_add(available)
+                    .ok_or(Error::ArithmeticOverflow)?,
+            );
+            escrow.status = EscrowStatus::Expired;
+            escrow.refund_timestamp = now;
+            Self::store_escrow(env, escrow_id, &mut escrow);
+
+            events::emit(
+                env,
+                symbol_short!("escrow"),
+                symbol_short!("expired"),
+                escrow_id,
+                keeper,
+                refund_amount,
+                symbol_short!("expired"),
+                EventData::EscrowRefunded(
+                    escrow_id,
+                    refund_amount,
+                    Self::fee_destination(env, &stored_admin),
+                ),
+            );
+
+            swept += 1;
+            total_bounty += keeper_share;
         }
 
         events::emit(
-            &env,
+            env,
             symbol_short!("escrow"),
-            symbol_short!("refunded"),
-            escrow_id,
-            &caller,
-            refund_amount,
-            symbol_short!("refunded"),
-            EventData::EscrowRefunded(escrow_id, refund_amount),
-        );
-
-        Self::notify_external(
-            &env,
-            NotificationPayload {
-                escrow_id,
-                event_type: EventType::Refunded,
-                amount: refund_amount,
-                timestamp: env.ledger().timestamp(),
-            },
+            symbol_short!("swept"),
+            0,
+            keeper,
+            total_bounty,
+            symbol_short!("na"),
+            EventData::ValueChanged(symbol_short!("swept"), swept as i128, skipped as i128),
         );
 
-        env.storage()
-            .instance()
-            .set(&DataKey::ReentrancyGuard, &false);
-
-        Ok(())
+        Ok(swept)
     }
 
     pub fn refund_asset(
@@ -3309,6 +8336,7 @@ impl PaymentEscrowContract {
             return Err(Error::InvalidStatus);
         }
 
+        Self::check_timelock(&env, &escrow)?;
         let current_time = env.ledger().timestamp();
         if reason == RefundReason::Expiration
             && current_time <= escrow.release_conditions.expiration_timestamp
@@ -3316,6 +8344,7 @@ impl PaymentEscrowContract {
             return Err(Error::NotExpired);
         }
 
+        Self::check_asset_token(&env, &asset, &token_address)?;
         let deposited = escrow.deposited_amounts.get(asset.clone()).unwrap_or(0i128);
         let released = escrow.released_amounts.get(asset.clone()).unwrap_or(0i128);
         let refunded = escrow.refunded_amounts.get(asset.clone()).unwrap_or(0i128);
@@ -3342,8 +8371,17 @@ impl PaymentEscrowContract {
         let contract_address = env.current_contract_address();
         token_client.transfer(&contract_address, &escrow.sender, &refund_amount);
         if processing_fee > 0 {
-            token_client.transfer(&contract_address, &stored_admin, &processing_fee);
+            Self::collect_fee(
+                &env,
+                escrow_id,
+                &token_client,
+                &asset.code,
+                processing_fee,
+                &Self::fee_destination(&env, &stored_admin),
+                symbol_short!("process"),
+            );
         }
+        Self::adjust_tvl(&env, &asset.code, -available_for_refund);
 
         escrow.refunded_amounts.set(
             asset.clone(),
@@ -3381,9 +8419,7 @@ impl PaymentEscrowContract {
             escrow.refund_timestamp = current_time;
         }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+        Self::store_escrow(&env, escrow_id, &mut escrow);
 
         events::emit(
             &env,
@@ -3397,7 +8433,11 @@ impl PaymentEscrowContract {
             } else {
                 symbol_short!("funded")
             },
-            EventData::EscrowRefunded(escrow_id, refund_amount),
+            EventData::EscrowRefunded(
+                escrow_id,
+                refund_amount,
+                Self::fee_destination(&env, &stored_admin),
+            ),
         );
 
         Self::notify_external(
@@ -3431,6 +8471,8 @@ impl PaymentEscrowContract {
             return Err(Error::InvalidRefundAmount);
         }
 
+        Self::check_escrow_token(&env, escrow_id, &token_address)?;
+
         let guard: bool = env
             .storage()
             .instance()
@@ -3475,6 +8517,13 @@ impl PaymentEscrowContract {
             return Err(Error::InvalidStatus);
         }
 
+        if let Err(err) = Self::check_timelock(&env, &escrow) {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(err);
+        }
+
         if escrow.multi_party_enabled {
             let config_opt: Option<MultiPartyConfig> = env
                 .storage()
@@ -3489,7 +8538,7 @@ impl PaymentEscrowContract {
                             .set(&DataKey::ReentrancyGuard, &false);
                         return Err(Error::ApprovalExpired);
                     }
-                    if config.approvals.len() < config.required_approvals {
+                    if config.approvals.len() < Self::effective_quorum(&config) {
                         env.storage()
                             .instance()
                             .set(&DataKey::ReentrancyGuard, &false);
@@ -3536,8 +8585,17 @@ impl PaymentEscrowContract {
         token_client.transfer(&contract_address, &escrow.sender, &net_refund);
 
         if processing_fee > 0 {
-            token_client.transfer(&contract_address, &stored_admin, &processing_fee);
+            Self::collect_fee(
+                &env,
+                escrow_id,
+                &token_client,
+                &escrow.asset.code,
+                processing_fee,
+                &Self::fee_destination(&env, &stored_admin),
+                symbol_short!("process"),
+            );
         }
+        Self::adjust_tvl(&env, &escrow.asset.code, -refund_amount);
 
         escrow.refunded_amount = escrow
             .refunded_amount
@@ -3556,9 +8614,7 @@ impl PaymentEscrowContract {
             escrow.status = EscrowStatus::Refunded;
         }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+        Self::store_escrow(&env, escrow_id, &mut escrow);
 
         if escrow.multi_party_enabled {
             if let Some(mut config) = env
@@ -3588,7 +8644,11 @@ impl PaymentEscrowContract {
             &caller,
             net_refund,
             refund_status,
-            EventData::EscrowRefunded(escrow_id, net_refund),
+            EventData::EscrowRefunded(
+                escrow_id,
+                net_refund,
+                Self::fee_destination(&env, &stored_admin),
+            ),
         );
 
         env.storage()
@@ -3640,6 +8700,8 @@ impl PaymentEscrowContract {
     ) -> Result<(), Error> {
         caller.require_auth();
 
+        Self::check_escrow_token(&env, escrow_id, &token_address)?;
+
         let guard: bool = env
             .storage()
             .instance()
@@ -3675,7 +8737,8 @@ impl PaymentEscrowContract {
             EscrowStatus::Released
             | EscrowStatus::Refunded
             | EscrowStatus::Expired
-            | EscrowStatus::Cancelled => {
+            | EscrowStatus::Cancelled
+            | EscrowStatus::Disputed => {
                 env.storage()
                     .instance()
                     .set(&DataKey::ReentrancyGuard, &false);
@@ -3683,6 +8746,12 @@ impl PaymentEscrowContract {
             }
             _ => {}
         }
+        if let Err(err) = Self::check_timelock(&env, &escrow) {
+            env.storage()
+                .instance()
+                .set(&DataKey::ReentrancyGuard, &false);
+            return Err(err);
+        }
 
         let deposited = escrow.deposited_amount;
 
@@ -3722,7 +8791,15 @@ impl PaymentEscrowContract {
                     let fee_wallet_opt: Option<Address> =
                         env.storage().instance().get(&DataKey::FeeWallet);
                     let penalty_dest = fee_wallet_opt.unwrap_or(escrow.sender.clone());
-                    token_client.transfer(&contract_address, &penalty_dest, &penalty);
+                    Self::collect_fee(
+                        &env,
+                        escrow_id,
+                        &token_client,
+                        &escrow.asset.code,
+                        penalty,
+                        &penalty_dest,
+                        symbol_short!("penalty"),
+                    );
                 }
 
                 if sender_refund > 0 {
@@ -3731,15 +8808,14 @@ impl PaymentEscrowContract {
             } else {
                 token_client.transfer(&contract_address, &escrow.sender, &deposited);
             }
+            Self::adjust_tvl(&env, &escrow.asset.code, -deposited);
         }
 
         // Silence unused variable warning for reason
         let _ = reason;
 
         escrow.status = EscrowStatus::Cancelled;
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+        Self::store_escrow(&env, escrow_id, &mut escrow);
 
         events::emit(
             &env,
@@ -3798,18 +8874,28 @@ impl PaymentEscrowContract {
             return Err(Error::InvalidStatus);
         }
 
+        if Self::sender_meets_quorum_alone(&escrow, &approvers, required_approvals) {
+            return Err(Error::InvalidStatus);
+        }
+        if let Some(min_tier) = Self::approver_kyc_required(env.clone(), escrow_id) {
+            for approver in approvers.iter() {
+                if !Self::approver_kyc_verified(&env, &approver, min_tier) {
+                    return Err(Error::KycFailed);
+                }
+            }
+        }
+
         let config = MultiPartyConfig {
             required_approvals,
             approval_timeout,
             whitelisted_approvers: approvers,
             approvals: Map::new(&env),
             finalized: false,
+            required_fraction_bps: 0,
         };
 
         escrow.multi_party_enabled = true;
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+        Self::store_escrow(&env, escrow_id, &mut escrow);
         env.storage()
             .instance()
             .set(&DataKey::EscrowApprovals(escrow_id), &config);
@@ -3862,8 +8948,20 @@ impl PaymentEscrowContract {
                 return Err(Error::AlreadyApproved);
             }
         }
+        if let Some(min_tier) = Self::approver_kyc_required(env.clone(), escrow_id) {
+            if !Self::approver_kyc_verified(&env, &new_approver, min_tier) {
+                return Err(Error::KycFailed);
+            }
+        }
 
         config.whitelisted_approvers.push_back(new_approver.clone());
+        if Self::sender_meets_quorum_alone(
+            &escrow,
+            &config.whitelisted_approvers,
+            Self::effective_quorum(&config),
+        ) {
+            return Err(Error::InvalidStatus);
+        }
         env.storage()
             .instance()
             .set(&DataKey::EscrowApprovals(escrow_id), &config);
@@ -3918,22 +9016,111 @@ impl PaymentEscrowContract {
             return Err(Error::ApproverNotWhitelisted);
         }
 
-        if new_approvers.len() < config.required_approvals {
+        let new_quorum = Self::quorum_for(&config, new_approvers.len());
+        if new_approvers.is_empty()
+            || new_approvers.len() < new_quorum
+            || Self::sender_meets_quorum_alone(&escrow, &new_approvers, new_quorum)
+        {
+            return Err(Error::InvalidStatus);
+        }
+
+        config.approvals.remove(approver.clone());
+        config.whitelisted_approvers = new_approvers;
+        env.storage()
+            .instance()
+            .set(&DataKey::EscrowApprovals(escrow_id), &config);
+
+        env.events()
+            .publish((symbol_short!("appr_rem"), escrow_id), approver);
+
+        Ok(())
+    }
+
+    pub fn set_quorum_fraction(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        fraction_bps: u32,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if caller != escrow.sender && caller != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        if fraction_bps > 10000 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut config: MultiPartyConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::EscrowApprovals(escrow_id))
+            .ok_or(Error::ConditionsNotMet)?;
+
+        if config.finalized {
+            return Err(Error::EscrowFinalized);
+        }
+
+        config.required_fraction_bps = fraction_bps;
+        if Self::sender_meets_quorum_alone(
+            &escrow,
+            &config.whitelisted_approvers,
+            Self::effective_quorum(&config),
+        ) {
             return Err(Error::InvalidStatus);
         }
-
-        config.approvals.remove(approver.clone());
-        config.whitelisted_approvers = new_approvers;
         env.storage()
             .instance()
             .set(&DataKey::EscrowApprovals(escrow_id), &config);
 
-        env.events()
-            .publish((symbol_short!("appr_rem"), escrow_id), approver);
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("mp_frac"),
+            escrow_id,
+            &caller,
+            fraction_bps as i128,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("mp_frac")),
+        );
 
         Ok(())
     }
 
+    pub fn get_effective_quorum(env: Env, escrow_id: u64) -> Option<u32> {
+        let config: Option<MultiPartyConfig> = env
+            .storage()
+            .instance()
+            .get(&DataKey::EscrowApprovals(escrow_id));
+        config.map(|c| Self::effective_quorum(&c))
+    }
+
+    /// A sender listed as an approver must not be able to reach quorum on
+    /// their own, otherwise the multi-party control is meaningless.
+    fn sender_meets_quorum_alone(escrow: &Escrow, approvers: &Vec<Address>, quorum: u32) -> bool {
+        quorum < 2 && approvers.contains(&escrow.sender)
+    }
+
+    fn effective_quorum(config: &MultiPartyConfig) -> u32 {
+        Self::quorum_for(config, config.whitelisted_approvers.len())
+    }
+
+    fn quorum_for(config: &MultiPartyConfig, approver_count: u32) -> u32 {
+        if config.required_fraction_bps == 0 {
+            return config.required_approvals;
+        }
+        let scaled = (approver_count as u64) * (config.required_fraction_bps as u64);
+        scaled.div_ceil(10000) as u32
+    }
+
     pub fn multi_party_approve(env: Env, escrow_id: u64, approver: Address) -> Result<bool, Error> {
         approver.require_auth();
 
@@ -3973,14 +9160,25 @@ impl PaymentEscrowContract {
         if !is_whitelisted {
             return Err(Error::ApproverNotWhitelisted);
         }
+        if approver == escrow.recipient && !Self::recipient_can_self_approve(env.clone(), escrow_id)
+        {
+            return Err(Error::Unauthorized);
+        }
+        if let Some(min_tier) = Self::approver_kyc_required(env.clone(), escrow_id) {
+            if !Self::approver_kyc_verified(&env, &approver, min_tier) {
+                return Err(Error::KycFailed);
+            }
+        }
 
         if config.approvals.contains_key(approver.clone()) {
             return Err(Error::AlreadyApproved);
         }
+        Self::enforce_approval_interval(&env, escrow_id)?;
+        Self::enforce_approval_cooldown(&env, escrow_id, &approver)?;
 
         config.approvals.set(approver.clone(), true);
         let approval_count = config.approvals.len();
-        let quorum_met = approval_count >= config.required_approvals;
+        let quorum_met = approval_count >= Self::effective_quorum(&config);
 
         env.storage()
             .instance()
@@ -4039,6 +9237,7 @@ impl PaymentEscrowContract {
         if !config.approvals.contains_key(approver.clone()) {
             return Err(Error::ApprovalNotFound);
         }
+        Self::enforce_approval_cooldown(&env, escrow_id, &approver)?;
 
         config.approvals.remove(approver.clone());
         env.storage()
@@ -4051,6 +9250,114 @@ impl PaymentEscrowContract {
         Ok(())
     }
 
+    /// Minimum seconds between one approver's approve/revoke actions on an
+    /// escrow. 0 disables the cooldown.
+    pub fn set_approval_cooldown(env: Env, admin: Address, cooldown: u64) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage()
+            .instance()
+            .set(&ApprovalKey::ApprovalCooldown, &cooldown);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("appr_cool"),
+            0,
+            &admin,
+            cooldown as i128,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("appr_cool")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_approval_cooldown(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&ApprovalKey::ApprovalCooldown)
+            .unwrap_or(0)
+    }
+
+    /// Rejects an approve/revoke that follows the approver's previous one on
+    /// this escrow too closely, then records the new action time.
+    fn enforce_approval_cooldown(
+        env: &Env,
+        escrow_id: u64,
+        approver: &Address,
+    ) -> Result<(), Error> {
+        let now = env.ledger().timestamp();
+        let key = ApprovalKey::LastApprovalAction(escrow_id, approver.clone());
+        let cooldown = Self::get_approval_cooldown(env.clone());
+        if cooldown > 0 {
+            if let Some(last) = env.storage().instance().get::<_, u64>(&key) {
+                if now < last.saturating_add(cooldown) {
+                    return Err(Error::RateLimitExceeded);
+                }
+            }
+        }
+        env.storage().instance().set(&key, &now);
+        Ok(())
+    }
+
+    /// Minimum seconds between any two multi-party approvals on the same
+    /// escrow, so signatures cannot all be gathered at once. 0 disables it.
+    pub fn set_min_approval_interval(env: Env, admin: Address, interval: u64) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage()
+            .instance()
+            .set(&ApprovalKey::MinApprovalInterval, &interval);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("appr_intv"),
+            0,
+            &admin,
+            interval as i128,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("appr_intv")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_min_approval_interval(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&ApprovalKey::MinApprovalInterval)
+            .unwrap_or(0)
+    }
+
+    /// Rejects an approval that follows the escrow's previous approval, by
+    /// anyone, too closely, then records the new approval time.
+    fn enforce_approval_interval(env: &Env, escrow_id: u64) -> Result<(), Error> {
+        let now = env.ledger().timestamp();
+        let key = ApprovalKey::LastEscrowApproval(escrow_id);
+        let interval = Self::get_min_approval_interval(env.clone());
+        if interval > 0 {
+            if let Some(last) = env.storage().instance().get::<_, u64>(&key) {
+                if now < last.saturating_add(interval) {
+                    return Err(Error::RateLimitExceeded);
+                }
+            }
+        }
+        env.storage().instance().set(&key, &now);
+        Ok(())
+    }
+
     pub fn get_multi_party_status(env: Env, escrow_id: u64) -> Option<MultiPartyConfig> {
         env.storage()
             .instance()
@@ -4107,9 +9414,7 @@ impl PaymentEscrowContract {
 
         escrow.status = EscrowStatus::Disputed;
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+        Self::store_escrow(&env, escrow_id, &mut escrow);
 
         env.events()
             .publish((symbol_short!("disp_rais"), escrow_id), (disputer, reason));
@@ -4201,7 +9506,10 @@ impl PaymentEscrowContract {
         Ok(())
     }
 
-    pub fn resolve_dispute(
+    /// Rules on a dispute without moving funds: the escrow reopens as
+    /// `Approved` for the recipient or `Funded` for the sender, who then
+    /// release or refund it as usual.
+    pub fn finalize_dispute(
         env: Env,
         escrow_id: u64,
         caller: Address,
@@ -4229,6 +9537,71 @@ impl PaymentEscrowContract {
         Self::finalize_dispute_internal(&env, &mut escrow, &mut dispute, outcome)
     }
 
+    /// Closes an open dispute and pays out in the same call: the whole
+    /// available balance goes to the recipient (less the platform fee) when
+    /// `release_to_recipient` is set, otherwise back to the sender.
+    pub fn resolve_dispute(
+        env: Env,
+        escrow_id: u64,
+        admin: Address,
+        release_to_recipient: bool,
+        token_address: Address,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        let escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(Error::InvalidStatus);
+        }
+
+        let mut dispute: Dispute = env
+            .storage()
+            .instance()
+            .get(&DataKey::Dispute(escrow_id))
+            .ok_or(Error::DisputeNotFound)?;
+        dispute.status = DisputeStatus::Resolved;
+        dispute.resolved_at = env.ledger().timestamp();
+        env.storage()
+            .instance()
+            .set(&DataKey::Dispute(escrow_id), &dispute);
+
+        let available = escrow
+            .deposited_amount
+            .checked_sub(escrow.released_amount)
+            .ok_or(Error::ArithmeticOverflow)?
+            .checked_sub(escrow.refunded_amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+        let (outcome, recipient_amount, refund_amount) = if release_to_recipient {
+            (ResolutionOutcome::FavorRecipient, available, 0)
+        } else {
+            (ResolutionOutcome::FavorSender, 0, available)
+        };
+
+        Self::split_settle_internal(
+            &env,
+            escrow_id,
+            &admin,
+            &stored_admin,
+            &token_address,
+            recipient_amount,
+            refund_amount,
+        )?;
+
+        env.events()
+            .publish((symbol_short!("disp_res"), escrow_id), outcome);
+
+        Ok(())
+    }
+
     fn finalize_dispute_internal(
         env: &Env,
         escrow: &mut Escrow,
@@ -4251,9 +9624,7 @@ impl PaymentEscrowContract {
             }
         }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow.escrow_id), &*escrow);
+        Self::store_escrow(env, escrow.escrow_id, escrow);
 
         env.events()
             .publish((symbol_short!("disp_res"), escrow.escrow_id), outcome);
@@ -4330,6 +9701,26 @@ impl PaymentEscrowContract {
         rate_limit::is_exempt(&env, &address)
     }
 
+    pub fn reset_rate_limit(env: Env, admin: Address, account: Address) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::UnauthorizedCaller);
+        }
+        rate_limit::reset_user(&env, &account);
+        Ok(())
+    }
+
+    pub fn clear_all_rate_limits(env: Env, admin: Address) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::UnauthorizedCaller);
+        }
+        rate_limit::clear_all(&env);
+        Ok(())
+    }
+
     // ── Delegation Functions (#132) ────────────────────────────────────
 
     pub fn delegate_escrow(
@@ -4562,67 +9953,210 @@ impl PaymentEscrowContract {
             &caller,
             premium,
             symbol_short!("na"),
-            EventData::AdminAction(symbol_short!("na")),
+            EventData::AdminAction(symbol_short!("na")),
+        );
+
+        Ok(())
+    }
+
+    pub fn claim_insurance(
+        env: Env,
+        escrow_id: u64,
+        caller: Address,
+        reason: String,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let escrow: Escrow = env
+            .storage()
+            .instance()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(Error::EscrowNotFound)?;
+
+        let mut insurance: EscrowInsurance = env
+            .storage()
+            .instance()
+            .get(&DataKey::EscrowInsurance(escrow_id))
+            .ok_or(Error::ConditionsNotMet)?;
+
+        if insurance.claimed {
+            return Err(Error::AlreadyReleased);
+        }
+
+        if caller != escrow.recipient && caller != escrow.sender {
+            return Err(Error::Unauthorized);
+        }
+
+        if reason.len() == 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        insurance.claimed = true;
+        insurance.claim_reason = Some(reason);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::EscrowInsurance(escrow_id), &insurance);
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("ins_clm"),
+            escrow_id,
+            &caller,
+            insurance.coverage,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("na")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_escrow_insurance(env: Env, escrow_id: u64) -> Option<EscrowInsurance> {
+        env.storage().instance().get(&DataKey::EscrowInsurance(escrow_id))
+    }
+
+    /// Sets (or clears, with `None`) the premium skimmed from every release
+    /// into the insurance reserve.
+    pub fn set_insurance_fund(
+        env: Env,
+        admin: Address,
+        fund: Option<InsuranceFund>,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        let insurance_bps = match fund {
+            Some(ref fund) => {
+                if fund.insurance_bps < 0 || fund.insurance_bps > 10000 {
+                    return Err(Error::InvalidFeePercentage);
+                }
+                env.storage()
+                    .instance()
+                    .set(&InsuranceKey::InsuranceFund, fund);
+                fund.insurance_bps
+            }
+            None => {
+                env.storage()
+                    .instance()
+                    .remove(&InsuranceKey::InsuranceFund);
+                0
+            }
+        };
+
+        events::emit(
+            &env,
+            symbol_short!("escrow"),
+            symbol_short!("ins_fund"),
+            0,
+            &admin,
+            insurance_bps,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("ins_fund")),
         );
 
         Ok(())
     }
 
-    pub fn claim_insurance(
+    pub fn get_insurance_fund(env: Env) -> Option<InsuranceFund> {
+        env.storage().instance().get(&InsuranceKey::InsuranceFund)
+    }
+
+    pub fn get_insurance_reserve(env: Env, asset_code: String) -> i128 {
+        env.storage()
+            .instance()
+            .get(&InsuranceKey::ReserveBalance(asset_code))
+            .unwrap_or(0)
+    }
+
+    /// Pays `amount` of the escrow's asset from the insurance reserve to its
+    /// recipient to cover a loss on that escrow.
+    pub fn claim_insurance_reserve(
         env: Env,
+        admin: Address,
         escrow_id: u64,
-        caller: Address,
-        reason: String,
+        amount: i128,
+        token_address: Address,
     ) -> Result<(), Error> {
-        caller.require_auth();
+        admin.require_auth();
+
+        let stored_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
+        if admin != stored_admin {
+            return Err(Error::Unauthorized);
+        }
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
 
         let escrow: Escrow = env
             .storage()
             .instance()
             .get(&DataKey::Escrow(escrow_id))
             .ok_or(Error::EscrowNotFound)?;
+        let fund = Self::get_insurance_fund(env.clone()).ok_or(Error::ConditionsNotMet)?;
+        Self::check_asset_token(&env, &escrow.asset, &token_address)?;
 
-        let mut insurance: EscrowInsurance = env
-            .storage()
-            .instance()
-            .get(&DataKey::EscrowInsurance(escrow_id))
-            .ok_or(Error::ConditionsNotMet)?;
-
-        if insurance.claimed {
-            return Err(Error::AlreadyReleased);
-        }
-
-        if caller != escrow.recipient && caller != escrow.sender {
-            return Err(Error::Unauthorized);
-        }
-
-        if reason.len() == 0 {
-            return Err(Error::InvalidAmount);
+        let balance = Self::get_insurance_reserve(env.clone(), escrow.asset.code.clone());
+        if amount > balance {
+            return Err(Error::InsufficientFunds);
         }
 
-        insurance.claimed = true;
-        insurance.claim_reason = Some(reason);
-
-        env.storage()
-            .instance()
-            .set(&DataKey::EscrowInsurance(escrow_id), &insurance);
+        // The reserve is an external account, so it co-signs every draw.
+        fund.reserve.require_auth();
+        token::Client::new(&env, &token_address).transfer(
+            &fund.reserve,
+            &escrow.recipient,
+            &amount,
+        );
+        env.storage().instance().set(
+            &InsuranceKey::ReserveBalance(escrow.asset.code.clone()),
+            &(balance - amount),
+        );
 
         events::emit(
             &env,
             symbol_short!("escrow"),
-            symbol_short!("ins_clm"),
+            symbol_short!("ins_draw"),
             escrow_id,
-            &caller,
-            insurance.coverage,
+            &admin,
+            amount,
             symbol_short!("na"),
-            EventData::AdminAction(symbol_short!("na")),
+            EventData::ValueChanged(symbol_short!("ins_draw"), balance, balance - amount),
         );
 
         Ok(())
     }
 
-    pub fn get_escrow_insurance(env: Env, escrow_id: u64) -> Option<EscrowInsurance> {
-        env.storage().instance().get(&DataKey::EscrowInsurance(escrow_id))
+    fn insurance_premium(env: &Env, amount: i128) -> Result<i128, Error> {
+        match Self::get_insurance_fund(env.clone()) {
+            Some(fund) => amount
+                .checked_mul(fund.insurance_bps)
+                .ok_or(Error::ArithmeticOverflow)?
+                .checked_div(10000)
+                .ok_or(Error::ArithmeticOverflow),
+            None => Ok(0),
+        }
+    }
+
+    fn contribute_to_reserve(
+        env: &Env,
+        token_client: &token::Client,
+        asset_code: &String,
+        premium: i128,
+    ) {
+        if premium <= 0 {
+            return;
+        }
+        if let Some(fund) = Self::get_insurance_fund(env.clone()) {
+            token_client.transfer(&env.current_contract_address(), &fund.reserve, &premium);
+            let key = InsuranceKey::ReserveBalance(asset_code.clone());
+            let balance: i128 = env.storage().instance().get(&key).unwrap_or(0);
+            env.storage().instance().set(&key, &(balance + premium));
+        }
     }
 
     // ── Milestone Functions (#129) ─────────────────────────────────────
@@ -4659,9 +10193,7 @@ impl PaymentEscrowContract {
         milestone.completed_by = Some(caller.clone());
         escrow.milestones.set(milestone_index, milestone);
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+        Self::store_escrow(&env, escrow_id, &mut escrow);
 
         events::emit(
             &env,
@@ -4723,9 +10255,7 @@ impl PaymentEscrowContract {
             escrow.status = EscrowStatus::Released;
         }
 
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+        Self::store_escrow(&env, escrow_id, &mut escrow);
 
         events::emit(
             &env,
@@ -4787,9 +10317,7 @@ impl PaymentEscrowContract {
         };
 
         escrow.milestones.push_back(milestone);
-        env.storage()
-            .instance()
-            .set(&DataKey::Escrow(escrow_id), &escrow);
+        Self::store_escrow(&env, escrow_id, &mut escrow);
 
         events::emit(
             &env,
@@ -4880,6 +10408,73 @@ impl PaymentEscrowContract {
         (completed as i128) * 10000 / (counter as i128)
     }
 
+    /// Token the escrow was funded with, once its first deposit has landed.
+    pub fn get_escrow_token(env: Env, escrow_id: u64) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get(&AssetConfigKey::EscrowToken(escrow_id))
+    }
+
+    pub fn get_tvl(env: Env, asset_code: String) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Tvl(asset_code))
+            .unwrap_or(0)
+    }
+
+    /// Sums the funds still held (deposited less released and refunded) by
+    /// the listed escrows that have not been paid out, refunded or cancelled.
+    /// Unknown ids are ignored.
+    pub fn get_locked_total(env: Env, escrow_ids: Vec<u64>) -> i128 {
+        let mut total: i128 = 0;
+        for escrow_id in escrow_ids.iter() {
+            if let Some(escrow) = env
+                .storage()
+                .instance()
+                .get::<_, Escrow>(&DataKey::Escrow(escrow_id))
+            {
+                if Self::is_open(&escrow.status) {
+                    let held = escrow
+                        .deposited_amount
+                        .saturating_sub(escrow.released_amount)
+                        .saturating_sub(escrow.refunded_amount);
+                    total = total.saturating_add(held);
+                }
+            }
+        }
+        total
+    }
+
+    /// Returns how many escrows are in each status, read from counters kept
+    /// up to date on every status transition.
+    pub fn get_escrow_stats(env: Env) -> EscrowStats {
+        let count = |status: EscrowStatus| -> u64 {
+            env.storage()
+                .instance()
+                .get(&StatsKey::StatusCount(status))
+                .unwrap_or(0)
+        };
+        EscrowStats {
+            pending: count(EscrowStatus::Pending),
+            funded: count(EscrowStatus::Funded),
+            approved: count(EscrowStatus::Approved),
+            released: count(EscrowStatus::Released),
+            refunded: count(EscrowStatus::Refunded),
+            expired: count(EscrowStatus::Expired),
+            disputed: count(EscrowStatus::Disputed),
+            cancelled: count(EscrowStatus::Cancelled),
+        }
+    }
+
+    /// Compares the contract's token balance with the TVL accumulator for
+    /// `asset_code`. Returns `(actual_balance, expected_balance, drift)`.
+    pub fn reconcile(env: Env, token_address: Address, asset_code: String) -> (i128, i128, i128) {
+        let token_client = token::Client::new(&env, &token_address);
+        let actual = token_client.balance(&env.current_contract_address());
+        let expected = Self::get_tvl(env, asset_code);
+        (actual, expected, actual.saturating_sub(expected))
+    }
+
     pub fn get_user_statistics(env: Env, user: Address) -> EscrowAnalytics {
         let counter: u64 = env
             .storage()
@@ -5039,6 +10634,7 @@ mod test {
         };
 
         client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
 
         let escrow_id = client.create_escrow(
             &sender,
@@ -5083,6 +10679,7 @@ mod test {
         };
 
         client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
 
         let escrow_id = client.create_escrow(
             &sender,
@@ -5128,6 +10725,7 @@ mod test {
         };
 
         client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
 
         let escrow_id = client.create_escrow(
             &sender,
@@ -5165,6 +10763,7 @@ mod test {
         };
 
         client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
 
         let escrow_id = client.create_escrow(
             &sender,
@@ -5205,6 +10804,7 @@ mod test {
         };
 
         client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
 
         let escrow_id = client.create_escrow(
             &sender,
@@ -5257,6 +10857,7 @@ mod test {
         };
 
         client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
 
         let escrow_id = client.create_escrow(
             &sender,
@@ -5332,6 +10933,7 @@ mod test {
         };
 
         client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
         client.set_platform_fee(&admin, &250);
 
         let escrow_id = client.create_escrow(
@@ -5392,6 +10994,7 @@ mod test {
         };
 
         client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
 
         let escrow_id = client.create_escrow(
             &sender,
@@ -5450,6 +11053,7 @@ mod test {
         };
 
         client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
 
         let escrow_id = client.create_escrow(
             &sender,
@@ -5493,6 +11097,7 @@ mod test {
         };
 
         client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
         client.set_processing_fee(&admin, &100);
 
         let escrow_id = client.create_escrow(
@@ -5558,6 +11163,7 @@ mod test {
         };
 
         client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
 
         let escrow_id = client.create_escrow(
             &sender,
@@ -5611,6 +11217,7 @@ mod test {
         };
 
         client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
 
         let escrow_id = client.create_escrow(
             &sender,
@@ -5679,6 +11286,7 @@ mod test {
         };
 
         client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
 
         let escrow_id = client.create_escrow(
             &sender,
@@ -5730,6 +11338,7 @@ mod test {
         };
 
         client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
 
         let escrow_id = client.create_escrow(
             &sender,
@@ -6218,6 +11827,7 @@ mod test {
         };
 
         client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
 
         let escrow_id = client.create_escrow(
             &sender,
@@ -6288,6 +11898,29 @@ mod test {
         assert_eq!(result, Err(Ok(Error::InvalidStatus)));
     }
 
+    #[test]
+    fn test_setup_multi_party_sender_only_rejected() {
+        let env = Env::default();
+        let (client, admin, sender, recipient, escrow_id, _token, _token_addr) =
+            setup_escrow_for_multi_party(&env);
+
+        // Sender as the sole 1-of-1 approver
+        let mut approvers = Vec::new(&env);
+        approvers.push_back(sender.clone());
+        let result =
+            client.try_setup_multi_party_approval(&escrow_id, &admin, &approvers, &1, &5000);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
+
+        // Sender included but quorum still reachable by the sender alone
+        approvers.push_back(recipient.clone());
+        let result =
+            client.try_setup_multi_party_approval(&escrow_id, &admin, &approvers, &1, &5000);
+        assert_eq!(result, Err(Ok(Error::InvalidStatus)));
+
+        // Requiring a second approver is allowed
+        client.setup_multi_party_approval(&escrow_id, &admin, &approvers, &2, &5000);
+    }
+
     #[test]
     fn test_setup_multi_party_duplicate_rejected() {
         let env = Env::default();
@@ -6514,6 +12147,220 @@ mod test {
         assert_eq!(result, Err(Ok(Error::QuorumNotMet)));
     }
 
+    #[test]
+    fn test_release_rejected_after_approval_timeout_despite_quorum() {
+        let env = Env::default();
+        let (client, admin, sender, recipient, escrow_id, _token, token_addr) =
+            setup_escrow_for_multi_party(&env);
+
+        let mut approvers = Vec::new(&env);
+        approvers.push_back(sender.clone());
+        approvers.push_back(recipient.clone());
+        approvers.push_back(admin.clone());
+
+        client.setup_multi_party_approval(&escrow_id, &admin, &approvers, &2, &5000);
+        client.approve_escrow(&escrow_id, &admin);
+        client.multi_party_approve(&escrow_id, &sender);
+        client.multi_party_approve(&escrow_id, &recipient);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = 6000;
+        });
+
+        let result = client.try_release_escrow(&escrow_id, &recipient, &token_addr);
+        assert_eq!(result, Err(Ok(Error::ApprovalExpired)));
+    }
+
+    #[test]
+    fn test_release_asset_enforces_multi_party_timeout() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let admin = Address::generate(&env);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let (token, token_admin) = create_token_contract(&env, &admin);
+        token_admin.mint(&sender, &1000);
+
+        let contract_id = env.register_contract(None, PaymentEscrowContract);
+        let client = PaymentEscrowContractClient::new(&env, &contract_id);
+        client.init_escrow(&admin);
+
+        let asset = Asset {
+            code: String::from_str(&env, "USDC"),
+            issuer: admin.clone(),
+        };
+        client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
+
+        let mut assets = Vec::new(&env);
+        assets.push_back(asset.clone());
+        let mut amounts = Map::new(&env);
+        amounts.set(asset.clone(), 1000);
+        let escrow_id = client.create_multi_asset_escrow(
+            &sender,
+            &recipient,
+            &assets,
+            &amounts,
+            &10000,
+            &String::from_str(&env, "multi"),
+        );
+        client.deposit_asset(&escrow_id, &sender, &asset, &1000, &token.address);
+
+        let mut approvers = Vec::new(&env);
+        approvers.push_back(sender.clone());
+        approvers.push_back(recipient.clone());
+        client.setup_multi_party_approval(&escrow_id, &admin, &approvers, &2, &5000);
+
+        let result = client.try_release_asset(&escrow_id, &recipient, &asset, &token.address);
+        assert_eq!(result, Err(Ok(Error::QuorumNotMet)));
+
+        client.multi_party_approve(&escrow_id, &sender);
+        client.multi_party_approve(&escrow_id, &recipient);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = 6000;
+        });
+
+        let result = client.try_release_asset(&escrow_id, &recipient, &asset, &token.address);
+        assert_eq!(result, Err(Ok(Error::ApprovalExpired)));
+    }
+
+    #[test]
+    fn test_multi_party_recipient_self_approval_disabled() {
+        let env = Env::default();
+        let (client, _admin, sender, recipient, escrow_id, _token, _token_addr) =
+            setup_escrow_for_multi_party(&env);
+
+        let approver = Address::generate(&env);
+        let mut approvers = Vec::new(&env);
+        approvers.push_back(approver.clone());
+        approvers.push_back(recipient.clone());
+        client.setup_multi_party_approval(&escrow_id, &sender, &approvers, &1, &0);
+        client.set_recipient_can_self_approve(&escrow_id, &sender, &false);
+
+        let result = client.try_multi_party_approve(&escrow_id, &recipient);
+        assert_eq!(result, Err(Ok(Error::Unauthorized)));
+        assert!(client.multi_party_approve(&escrow_id, &approver));
+    }
+
+    #[test]
+    fn test_multi_party_approver_kyc_required() {
+        let env = Env::default();
+        let (client, admin, sender, recipient, escrow_id, _token, _token_addr) =
+            setup_escrow_for_multi_party(&env);
+
+        let approver = Address::generate(&env);
+        let mut approvers = Vec::new(&env);
+        approvers.push_back(approver.clone());
+        approvers.push_back(recipient.clone());
+        client.set_approver_kyc_required(&escrow_id, &sender, &Some(0));
+
+        let result = client.try_setup_multi_party_approval(&escrow_id, &sender, &approvers, &1, &0);
+        assert_eq!(result, Err(Ok(Error::KycFailed)));
+
+        client.add_to_whitelist(&admin, &approver, &0);
+        client.add_to_whitelist(&admin, &recipient, &0);
+        client.setup_multi_party_approval(&escrow_id, &sender, &approvers, &1, &0);
+
+        client.remove_from_whitelist(&admin, &approver);
+        let result = client.try_multi_party_approve(&escrow_id, &approver);
+        assert_eq!(result, Err(Ok(Error::KycFailed)));
+        assert!(client.multi_party_approve(&escrow_id, &recipient));
+    }
+
+    #[test]
+    fn test_multi_party_approver_kyc_min_tier() {
+        let env = Env::default();
+        let (client, admin, sender, recipient, escrow_id, _token, _token_addr) =
+            setup_escrow_for_multi_party(&env);
+
+        let approver = Address::generate(&env);
+        let mut approvers = Vec::new(&env);
+        approvers.push_back(approver.clone());
+        approvers.push_back(recipient.clone());
+        client.add_to_whitelist(&admin, &approver, &0);
+        client.add_to_whitelist(&admin, &recipient, &0);
+        client.set_kyc_tier(&admin, &approver, &2);
+        client.set_approver_kyc_required(&escrow_id, &sender, &Some(2));
+        assert_eq!(client.approver_kyc_required(&escrow_id), Some(2));
+
+        // The recipient is verified but only at tier 0.
+        let result = client.try_setup_multi_party_approval(&escrow_id, &sender, &approvers, &1, &0);
+        assert_eq!(result, Err(Ok(Error::KycFailed)));
+
+        client.set_kyc_tier(&admin, &recipient, &3);
+        client.setup_multi_party_approval(&escrow_id, &sender, &approvers, &1, &0);
+
+        client.set_kyc_tier(&admin, &approver, &1);
+        let result = client.try_multi_party_approve(&escrow_id, &approver);
+        assert_eq!(result, Err(Ok(Error::KycFailed)));
+        assert!(client.multi_party_approve(&escrow_id, &recipient));
+
+        client.set_approver_kyc_required(&escrow_id, &sender, &None);
+        assert_eq!(client.approver_kyc_required(&escrow_id), None);
+    }
+
+    #[test]
+    fn test_multi_party_min_approval_interval() {
+        let env = Env::default();
+        let (client, admin, sender, recipient, escrow_id, _token, _token_addr) =
+            setup_escrow_for_multi_party(&env);
+
+        let approver = Address::generate(&env);
+        let mut approvers = Vec::new(&env);
+        approvers.push_back(approver.clone());
+        approvers.push_back(recipient.clone());
+        client.setup_multi_party_approval(&escrow_id, &sender, &approvers, &2, &0);
+        client.set_min_approval_interval(&admin, &300);
+
+        client.multi_party_approve(&escrow_id, &approver);
+        let result = client.try_multi_party_approve(&escrow_id, &recipient);
+        assert_eq!(result, Err(Ok(Error::RateLimitExceeded)));
+
+        env.ledger().with_mut(|li| {
+            li.timestamp += 300;
+        });
+        assert!(client.multi_party_approve(&escrow_id, &recipient));
+    }
+
+    #[test]
+    fn test_multi_party_approval_cooldown() {
+        let env = Env::default();
+        let (client, admin, sender, recipient, escrow_id, _token, _token_addr) =
+            setup_escrow_for_multi_party(&env);
+
+        let approver = Address::generate(&env);
+        let mut approvers = Vec::new(&env);
+        approvers.push_back(approver.clone());
+        approvers.push_back(recipient.clone());
+        client.setup_multi_party_approval(&escrow_id, &sender, &approvers, &2, &0);
+        client.set_approval_cooldown(&admin, &60);
+
+        client.multi_party_approve(&escrow_id, &approver);
+        let result = client.try_revoke_approval(&escrow_id, &approver);
+        assert_eq!(result, Err(Ok(Error::RateLimitExceeded)));
+
+        env.ledger().with_mut(|li| {
+            li.timestamp += 60;
+        });
+        client.revoke_approval(&escrow_id, &approver);
+
+        let result = client.try_multi_party_approve(&escrow_id, &approver);
+        assert_eq!(result, Err(Ok(Error::RateLimitExceeded)));
+
+        // Other approvers are tracked separately.
+        client.multi_party_approve(&escrow_id, &recipient);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp += 60;
+        });
+        client.multi_party_approve(&escrow_id, &approver);
+    }
+
     #[test]
     fn test_release_succeeds_with_quorum() {
         let env = Env::default();
@@ -6921,6 +12768,7 @@ mod test {
         };
 
         client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
 
         let escrow_id = client.create_escrow(
             &sender,
@@ -30,6 +30,9 @@ pub struct RateLimitEntry {
     pub last_call_time: u64,
     pub count: u32,
     pub window_start: u64,
+    /// Reset epoch the entry was recorded under; entries from an older epoch
+    /// are ignored after `clear_all`.
+    pub epoch: u32,
 }
 
 #[derive(Clone)]
@@ -40,8 +43,17 @@ pub enum RateLimitKey {
     Exempt(Address),
     UserLimit(Address, FunctionType),
     GlobalCount(FunctionType),
+    Epoch,
 }
 
+const ALL_FUNCTION_TYPES: [FunctionType; 5] = [
+    FunctionType::Deposit,
+    FunctionType::Release,
+    FunctionType::Refund,
+    FunctionType::Remittance,
+    FunctionType::Invoice,
+];
+
 /// Check and enforce rate limit for a caller + function type.
 ///
 /// Returns `true` if the call is allowed, `false` if rate limited.
@@ -85,10 +97,15 @@ pub fn check_rate_limit(
     }
 
     let now = env.ledger().timestamp();
+    let epoch = current_epoch(env);
 
     // --- Per-user check ---
     let user_key = RateLimitKey::UserLimit(caller.clone(), function_type);
-    let entry: Option<RateLimitEntry> = env.storage().temporary().get(&user_key);
+    let entry: Option<RateLimitEntry> = env
+        .storage()
+        .temporary()
+        .get::<RateLimitKey, RateLimitEntry>(&user_key)
+        .filter(|e| e.epoch == epoch);
 
     match entry {
         Some(mut e) => {
@@ -116,6 +133,7 @@ pub fn check_rate_limit(
                 last_call_time: now,
                 count: 1,
                 window_start: now,
+                epoch,
             };
             env.storage().temporary().set(&user_key, &e);
         }
@@ -154,6 +172,7 @@ pub fn check_rate_limit(
                         last_call_time: now,
                         count: 1,
                         window_start: now,
+                        epoch,
                     };
                     env.storage().temporary().set(&global_key, &ge);
                 }
@@ -212,3 +231,36 @@ pub fn is_exempt(env: &Env, address: &Address) -> bool {
         .get(&RateLimitKey::Exempt(address.clone()))
         .unwrap_or(false)
 }
+
+/// Clear an account's per-user counters for every function type.
+pub fn reset_user(env: &Env, account: &Address) {
+    for function_type in ALL_FUNCTION_TYPES {
+        env.storage()
+            .temporary()
+            .remove(&RateLimitKey::UserLimit(account.clone(), function_type));
+    }
+    env.events()
+        .publish((symbol_short!("rl_reset"),), account.clone());
+}
+
+/// Invalidate every per-user counter and reset the global counters.
+///
+/// Per-user entries live in temporary storage and cannot be enumerated, so
+/// they are invalidated by bumping the reset epoch instead of being removed.
+pub fn clear_all(env: &Env) {
+    let epoch = current_epoch(env).wrapping_add(1);
+    env.storage().instance().set(&RateLimitKey::Epoch, &epoch);
+    for function_type in ALL_FUNCTION_TYPES {
+        env.storage()
+            .temporary()
+            .remove(&RateLimitKey::GlobalCount(function_type));
+    }
+    env.events().publish((symbol_short!("rl_clear"),), epoch);
+}
+
+fn current_epoch(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&RateLimitKey::Epoch)
+        .unwrap_or(0)
+}
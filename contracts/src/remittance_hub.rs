@@ -1,10 +1,11 @@
 use crate::aml::{self, AmlConfig, AmlScreeningResult, AmlStatus};
+use crate::errors::{self, ErrorDomain};
 use crate::events::{self, AssetRef, EventData};
 use crate::oracle::{self as oracle_mod, CachedRate, OracleConfig};
 use crate::rate_limit::{self, FunctionType};
 use crate::upgradeable;
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Map,
     String, Symbol,
 };
 
@@ -56,10 +57,24 @@ pub enum RemittanceError {
     BatchTooLarge = 21,
     /// Duplicate escrow id encountered.
     DuplicateEscrowId = 22,
+    /// Remittance is still within its post-clearance cooling-off period.
+    CooldownActive = 23,
+    /// Remittance amount exceeds the configured per-transaction cap.
+    AmountExceedsCap = 24,
+    /// Asset pair is already in the supported-pair registry.
+    PairAlreadySupported = 25,
+    /// Invoice and remittance do not describe the same payment.
+    PaymentMismatch = 26,
     /// Contract is paused (upgradeable pause flag set).
     ContractPaused = 32,
     /// Metric type is invalid or unsupported.
     InvalidMetric = 33,
+    /// Primary and secondary oracle rates diverge beyond the allowed deviation.
+    RateDeviation = 34,
+    /// Recurring invoice is not yet due for its next issue.
+    NotYetDue = 35,
+    /// Escrow asset or amount does not match the remittance it backs.
+    EscrowMismatch = 36,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -86,6 +101,17 @@ pub struct Asset {
     pub issuer: Address,
 }
 
+/// A change to one of an invoice's terms, recorded for dispute review.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[contracttype]
+pub struct AmendmentRecord {
+    pub field: Symbol,
+    pub old_value: i128,
+    pub new_value: i128,
+    pub actor: Address,
+    pub timestamp: u64,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub struct Invoice {
@@ -106,6 +132,21 @@ pub struct Invoice {
     pub memo: String,
 }
 
+/// Template from which `mint_next_invoice` issues one invoice per interval.
+#[derive(Clone)]
+#[contracttype]
+pub struct RecurringInvoice {
+    pub template_id: u64,
+    pub sender: Address,
+    pub recipient: Address,
+    pub amount: i128,
+    pub asset: Asset,
+    pub interval: u64,
+    pub remaining_occurrences: u32,
+    pub last_issued: u64,
+    pub invoice_ids: soroban_sdk::Vec<u64>,
+}
+
 #[derive(Clone)]
 #[contracttype]
 pub struct RemittanceData {
@@ -123,6 +164,7 @@ pub struct EscrowRequest {
     pub amount: i128,
     pub asset: Asset,
     pub expiration_timestamp: u64,
+    pub client_ref: Option<String>,
 }
 
 #[derive(Clone)]
@@ -134,6 +176,9 @@ pub struct EscrowData {
     pub asset: Asset,
     pub expiration_timestamp: u64,
     pub status: Symbol,
+    /// Oracle pinned for this escrow's settlement conversion; the hub's
+    /// primary/secondary oracles are used when unset.
+    pub oracle_address: Option<Address>,
 }
 
 #[derive(Clone, Copy)]
@@ -148,22 +193,111 @@ pub enum DataKey {
     MetricDaily(MetricType, u64),
     MetricWeekly(MetricType, u64),
     MaxBatchSize,
+    MaxRemittanceAmount,
+    ComplianceOfficer,
+    RemittanceEscrow(u64),
+    InvoiceAmendments(u64),
+    SettlementQueue,
+    SettlementWindow,
+    SettlementOpenedAt,
+    RemittanceFee,
+    RecurringInvoiceCounter,
+    RecurringInvoice(u64),
+    /// Fee collected for an escrow by `batch_deposit`, forwarded on release.
+    EscrowFee(u64),
+    /// Token `batch_deposit` funded an escrow with.
+    EscrowToken(u64),
+    /// USD value of an escrow at release, priced with its settlement oracle.
+    /// Absent when no rate was available.
+    EscrowSettlement(u64),
+    /// Running total of installments recorded by `pay_invoice_partial`.
+    /// Stored beside the invoice, not as a field on `Invoice`. Invoices
+    /// saved before partial payments existed have no such field. They would
+    /// no longer decode, and could never be paid or settled.
+    InvoicePaidAmount(u64),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum CurrencyKey {
+    /// Token contract that carries a remittance currency.
+    Token(Symbol),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum SendLimitKey {
+    /// Minimum number of ledgers between two remittances from one sender.
+    SendCooldown,
+    LastSendLedger(Address),
+}
+
+#[derive(Clone)]
+#[contracttype]
+pub enum BatchKey {
+    ClientRef(Address, String),
 }
 
+/// Oracle settings added after launch live under their own keys, not as
+/// `OracleConfig` fields: the config is stored as a map, and an entry
+/// written without a newer field fails to decode. Each key falls back to
+/// the old behaviour when unset.
 #[derive(Clone)]
 #[contracttype]
 pub enum HubOracleKey {
     OracleConfig,
     CachedRate(String, String),
+    SupportedPairs,
+    RateDeviationThreshold,
+    OutlierBand,
+    /// Per-pair staleness threshold overriding `OracleConfig::max_staleness`.
+    PairStaleness(String, String),
+    /// Oracles taking part in aggregation, keyed to their trust weight.
+    TrustWeights,
+    /// Largest primary/secondary gap, in basis points, tolerated by median
+    /// pricing; set only while median pricing is on.
+    MedianPricing,
+    /// Implied decimal places of each oracle's rates; feeds listed here are
+    /// normalized to a common precision instead of trusting their denominator.
+    RateDecimals,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 #[contracttype]
 pub enum AmlKey {
     Config,
     Flag(u64),
+    ClearCooldown,
+    ClearedAt(u64),
+    OpenFlags(Address),
+    /// Rolling `(window_start, total)` of a sender's remittance volume.
+    Volume(Address),
+    VelocityThreshold,
+    Exempt(Address),
+    /// Amount above which remittances are escalated for review. Stored
+    /// apart from `AmlConfig` so hubs configured before it existed keep
+    /// loading their config; `screen_transaction` takes it as an argument
+    /// for the same reason.
+    AmountThreshold,
 }
 
+/// Length of the rolling window used for AML velocity tracking.
+const AML_VELOCITY_WINDOW: u64 = 86400;
+/// Maximum number of remittance ids scanned by a single AML snapshot export.
+const MAX_AML_SNAPSHOT_RANGE: u64 = 200;
+/// Maximum number of pairs returned by a single `get_supported_pairs` page.
+const MAX_PAIR_PAGE: u32 = 50;
+/// Rate moves above this many basis points are flagged as deviations.
+const DEFAULT_RATE_DEVIATION_BPS: i128 = 1000;
+/// Maximum number of amendments kept per invoice; older entries drop off.
+const MAX_AMENDMENT_HISTORY: u32 = 50;
+/// Invoice and batch fee used until `set_remittance_fee` is called.
+const DEFAULT_REMITTANCE_FEE_BPS: i128 = 250;
+/// Batch size used until `set_batch_limit` is called.
+const DEFAULT_BATCH_LIMIT: u32 = 10;
+/// Upper bound for `set_batch_limit`, keeping batches within the instruction budget.
+const MAX_BATCH_LIMIT: u32 = 100;
+
 #[contract]
 pub struct RemittanceHubContract;
 
@@ -280,13 +414,12 @@ impl RemittanceHubContract {
         Ok(())
     }
 
-    pub fn set_cached_rate(
+    pub fn set_pair_staleness(
         env: Env,
         caller: Address,
         from_asset: String,
         to_asset: String,
-        rate: i128,
-        denominator: i128,
+        max_staleness: u64,
     ) -> Result<(), RemittanceError> {
         caller.require_auth();
         let stored_admin: Address = env
@@ -297,36 +430,57 @@ impl RemittanceHubContract {
         if caller != stored_admin {
             return Err(RemittanceError::Unauthorized);
         }
-        if rate <= 0 || denominator <= 0 {
-            return Err(RemittanceError::InvalidRate);
+
+        if !env.storage().persistent().has(&HubOracleKey::OracleConfig) {
+            return Err(RemittanceError::OracleNotConfigured);
         }
 
-        let cached = CachedRate {
-            rate,
-            denominator,
-            timestamp: env.ledger().timestamp(),
-            from_asset: from_asset.clone(),
-            to_asset: to_asset.clone(),
-        };
-        env.storage()
-            .persistent()
-            .set(&HubOracleKey::CachedRate(from_asset, to_asset), &cached);
+        env.storage().persistent().set(
+            &HubOracleKey::PairStaleness(from_asset, to_asset),
+            &max_staleness,
+        );
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("pair_stl"),
+            0,
+            &caller,
+            max_staleness as i128,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("pair_stl")),
+        );
 
         Ok(())
     }
 
-    pub fn get_oracle_config(env: Env) -> Option<OracleConfig> {
-        env.storage().persistent().get(&HubOracleKey::OracleConfig)
+    /// Returns the staleness threshold for a pair, preferring a per-pair
+    /// override over the global `max_staleness`.
+    fn max_staleness_for(
+        env: &Env,
+        config: &OracleConfig,
+        from_asset: &String,
+        to_asset: &String,
+    ) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&HubOracleKey::PairStaleness(
+                from_asset.clone(),
+                to_asset.clone(),
+            ))
+            .unwrap_or(config.max_staleness)
     }
 
-    pub fn configure_aml(
+    /// Declares how many implied decimal places `oracle`'s rates carry, so they
+    /// are normalized to a common precision before conversion. `None` reverts
+    /// to the feed's reported denominator.
+    pub fn set_oracle_decimals(
         env: Env,
         caller: Address,
-        oracle_address: Address,
-        risk_threshold: u32,
+        oracle: Address,
+        decimals: Option<u32>,
     ) -> Result<(), RemittanceError> {
         caller.require_auth();
-
         let stored_admin: Address = env
             .storage()
             .persistent()
@@ -336,32 +490,54 @@ impl RemittanceHubContract {
             return Err(RemittanceError::Unauthorized);
         }
 
-        let config = AmlConfig {
-            admin: caller.clone(),
-            oracle_address,
-            risk_threshold,
-            enabled: true,
-        };
-        env.storage().persistent().set(&AmlKey::Config, &config);
+        if !env.storage().persistent().has(&HubOracleKey::OracleConfig) {
+            return Err(RemittanceError::OracleNotConfigured);
+        }
+
+        let mut rate_decimals = Self::rate_decimals(&env);
+        match decimals {
+            Some(d) if d > oracle_mod::RATE_DECIMALS => return Err(RemittanceError::InvalidRate),
+            Some(d) => rate_decimals.set(oracle.clone(), d),
+            None => {
+                rate_decimals.remove(oracle.clone());
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&HubOracleKey::RateDecimals, &rate_decimals);
 
         events::emit(
             &env,
             symbol_short!("hub"),
-            symbol_short!("aml_cfg"),
+            symbol_short!("orc_dec"),
             0,
             &caller,
-            0,
+            decimals.map(|d| d as i128).unwrap_or(0),
             symbol_short!("na"),
-            EventData::AdminAction(symbol_short!("aml_cfg")),
+            EventData::AddressAction(symbol_short!("orc_dec"), oracle),
         );
 
         Ok(())
     }
 
-    pub fn set_aml_threshold(
+    pub fn get_oracle_decimals(env: Env, oracle: Address) -> Option<u32> {
+        Self::rate_decimals(&env).get(oracle)
+    }
+
+    fn rate_decimals(env: &Env) -> Map<Address, u32> {
+        env.storage()
+            .persistent()
+            .get(&HubOracleKey::RateDecimals)
+            .unwrap_or(Map::new(env))
+    }
+
+    /// Adds `oracle` to the aggregation set with the given trust weight, or
+    /// with `None` removes it.
+    pub fn set_oracle_weight(
         env: Env,
         caller: Address,
-        risk_threshold: u32,
+        oracle: Address,
+        weight: Option<u32>,
     ) -> Result<(), RemittanceError> {
         caller.require_auth();
         let stored_admin: Address = env
@@ -373,33 +549,55 @@ impl RemittanceHubContract {
             return Err(RemittanceError::Unauthorized);
         }
 
-        let mut config: AmlConfig = env
-            .storage()
-            .persistent()
-            .get(&AmlKey::Config)
-            .ok_or(RemittanceError::AmlNotConfigured)?;
+        if !env.storage().persistent().has(&HubOracleKey::OracleConfig) {
+            return Err(RemittanceError::OracleNotConfigured);
+        }
 
-        config.risk_threshold = risk_threshold;
-        env.storage().persistent().set(&AmlKey::Config, &config);
+        let mut weights = Self::trust_weights(&env);
+        match weight {
+            Some(0) => return Err(RemittanceError::InvalidAmount),
+            Some(w) => weights.set(oracle.clone(), w),
+            None => {
+                weights.remove(oracle.clone());
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&HubOracleKey::TrustWeights, &weights);
 
         events::emit(
             &env,
             symbol_short!("hub"),
-            symbol_short!("aml_thr"),
+            symbol_short!("orc_wgt"),
             0,
             &caller,
-            risk_threshold as i128,
+            weight.map(|w| w as i128).unwrap_or(0),
             symbol_short!("na"),
-            EventData::AdminAction(symbol_short!("aml_thr")),
+            EventData::AddressAction(symbol_short!("orc_wgt"), oracle),
         );
 
         Ok(())
     }
 
-    pub fn set_aml_oracle(
+    pub fn get_oracle_weight(env: Env, oracle: Address) -> Option<u32> {
+        Self::trust_weights(&env).get(oracle)
+    }
+
+    fn trust_weights(env: &Env) -> Map<Address, u32> {
+        env.storage()
+            .persistent()
+            .get(&HubOracleKey::TrustWeights)
+            .unwrap_or(Map::new(env))
+    }
+
+    /// Switches `convert_currency` between primary-with-fallback pricing and the
+    /// mean of both oracles, rejecting conversions whose feeds diverge by more
+    /// than `max_deviation_bps`.
+    pub fn set_median_pricing(
         env: Env,
         caller: Address,
-        oracle_address: Address,
+        enabled: bool,
+        max_deviation_bps: i128,
     ) -> Result<(), RemittanceError> {
         caller.require_auth();
         let stored_admin: Address = env
@@ -410,38 +608,112 @@ impl RemittanceHubContract {
         if caller != stored_admin {
             return Err(RemittanceError::Unauthorized);
         }
+        if max_deviation_bps <= 0 {
+            return Err(RemittanceError::InvalidAmount);
+        }
 
-        let mut config: AmlConfig = env
-            .storage()
-            .persistent()
-            .get(&AmlKey::Config)
-            .ok_or(RemittanceError::AmlNotConfigured)?;
+        if !env.storage().persistent().has(&HubOracleKey::OracleConfig) {
+            return Err(RemittanceError::OracleNotConfigured);
+        }
 
-        config.oracle_address = oracle_address.clone();
-        env.storage().persistent().set(&AmlKey::Config, &config);
+        if enabled {
+            env.storage()
+                .persistent()
+                .set(&HubOracleKey::MedianPricing, &max_deviation_bps);
+        } else {
+            env.storage()
+                .persistent()
+                .remove(&HubOracleKey::MedianPricing);
+        }
 
         events::emit(
             &env,
             symbol_short!("hub"),
-            symbol_short!("aml_orc"),
+            symbol_short!("median"),
             0,
             &caller,
-            0,
-            symbol_short!("na"),
-            EventData::AddressAction(symbol_short!("aml_orc"), oracle_address),
+            max_deviation_bps,
+            if enabled {
+                symbol_short!("on")
+            } else {
+                symbol_short!("off")
+            },
+            EventData::AdminAction(symbol_short!("median")),
         );
 
         Ok(())
     }
 
-    pub fn get_aml_config(env: Env) -> Option<AmlConfig> {
-        env.storage().persistent().get(&AmlKey::Config)
+    /// Sets how far, in basis points of the median, an oracle's rate may sit
+    /// before it is excluded from aggregation.
+    pub fn set_outlier_band(
+        env: Env,
+        caller: Address,
+        band_bps: i128,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if caller != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+        if band_bps <= 0 {
+            return Err(RemittanceError::InvalidAmount);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&HubOracleKey::OutlierBand, &band_bps);
+
+        Ok(())
     }
 
-    pub fn clear_aml_flag(
+    pub fn get_outlier_band(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&HubOracleKey::OutlierBand)
+            .unwrap_or(oracle_mod::DEFAULT_OUTLIER_BAND_BPS)
+    }
+
+    /// Consensus rate for the pair across all weighted oracles, scaled to 18
+    /// decimals. Outliers are dropped and the rest combined by weighted median.
+    pub fn get_aggregated_rate(
+        env: Env,
+        from_asset: String,
+        to_asset: String,
+    ) -> Result<i128, RemittanceError> {
+        let config: OracleConfig = env
+            .storage()
+            .persistent()
+            .get(&HubOracleKey::OracleConfig)
+            .ok_or(RemittanceError::OracleNotConfigured)?;
+        let max_staleness = Self::max_staleness_for(&env, &config, &from_asset, &to_asset);
+
+        oracle_mod::aggregate_rate(
+            &env,
+            &Self::rate_decimals(&env),
+            &Self::trust_weights(&env),
+            &from_asset,
+            &to_asset,
+            max_staleness,
+            Self::get_outlier_band(env.clone()),
+        )
+        .map_err(|e| match e {
+            oracle_mod::OracleError::OracleNotConfigured => RemittanceError::OracleNotConfigured,
+            _ => RemittanceError::ConversionFailed,
+        })
+    }
+
+    pub fn set_cached_rate(
         env: Env,
         caller: Address,
-        remittance_id: u64,
+        from_asset: String,
+        to_asset: String,
+        rate: i128,
+        denominator: i128,
     ) -> Result<(), RemittanceError> {
         caller.require_auth();
         let stored_admin: Address = env
@@ -452,81 +724,1097 @@ impl RemittanceHubContract {
         if caller != stored_admin {
             return Err(RemittanceError::Unauthorized);
         }
+        if rate <= 0 || denominator <= 0 {
+            return Err(RemittanceError::InvalidRate);
+        }
 
-        let mut flag: AmlScreeningResult = env
+        let cached = CachedRate {
+            rate,
+            denominator,
+            timestamp: env.ledger().timestamp(),
+            from_asset,
+            to_asset,
+        };
+        Self::store_cached_rate(&env, &caller, &cached);
+
+        Ok(())
+    }
+
+    /// Rate changes larger than `threshold_bps` relative to the stored rate
+    /// are flagged in the `rate_upd` event.
+    pub fn set_rate_deviation_threshold(
+        env: Env,
+        caller: Address,
+        threshold_bps: i128,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+        let stored_admin: Address = env
             .storage()
             .persistent()
-            .get(&AmlKey::Flag(remittance_id))
-            .ok_or(RemittanceError::AmlFlagNotFound)?;
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if caller != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+        if threshold_bps <= 0 {
+            return Err(RemittanceError::InvalidAmount);
+        }
 
-        flag.status = AmlStatus::Cleared;
         env.storage()
             .persistent()
-            .set(&AmlKey::Flag(remittance_id), &flag);
+            .set(&HubOracleKey::RateDeviationThreshold, &threshold_bps);
 
-        let mut remittance: RemittanceData = env
-            .storage()
+        Ok(())
+    }
+
+    pub fn get_rate_deviation_threshold(env: Env) -> i128 {
+        env.storage()
             .persistent()
-            .get(&remittance_id)
-            .ok_or(RemittanceError::NotFound)?;
+            .get(&HubOracleKey::RateDeviationThreshold)
+            .unwrap_or(DEFAULT_RATE_DEVIATION_BPS)
+    }
 
-        remittance.status = symbol_short!("pending");
-        env.storage().persistent().set(&remittance_id, &remittance);
+    /// Stores `cached` and emits `rate_upd` carrying its deviation in basis
+    /// points from the previously stored rate, flagged `deviated` when it
+    /// exceeds the configured threshold.
+    fn store_cached_rate(env: &Env, actor: &Address, cached: &CachedRate) {
+        let (old_rate, deviation_bps) = Self::write_cached_rate(env, cached);
+        let status = if deviation_bps > Self::get_rate_deviation_threshold(env.clone()) {
+            symbol_short!("deviated")
+        } else {
+            symbol_short!("na")
+        };
 
         events::emit(
-            &env,
+            env,
             symbol_short!("hub"),
-            symbol_short!("aml_clr"),
-            remittance_id,
-            &caller,
+            symbol_short!("rate_upd"),
             0,
-            symbol_short!("na"),
-            EventData::AdminAction(symbol_short!("aml_clr")),
+            actor,
+            deviation_bps,
+            status,
+            EventData::ValueChanged(symbol_short!("rate_upd"), old_rate, cached.rate),
         );
+    }
 
-        Ok(())
+    /// Stores `cached` without emitting, returning the previous rate (0 if
+    /// none) and the deviation from it in basis points.
+    fn write_cached_rate(env: &Env, cached: &CachedRate) -> (i128, i128) {
+        let key = HubOracleKey::CachedRate(cached.from_asset.clone(), cached.to_asset.clone());
+        let previous: Option<CachedRate> = env.storage().persistent().get(&key);
+        env.storage().persistent().set(&key, cached);
+        oracle_mod::record_rate_sample(env, cached);
+
+        match previous {
+            Some(prev) => (prev.rate, Self::rate_deviation_bps(&prev, cached)),
+            None => (0, 0),
+        }
     }
 
-    pub fn get_aml_flag(env: Env, remittance_id: u64) -> Option<AmlScreeningResult> {
-        env.storage().persistent().get(&AmlKey::Flag(remittance_id))
+    /// Average of the cached rates recorded for a pair over the last `window`
+    /// seconds, scaled to 18 decimals.
+    pub fn get_twap(
+        env: Env,
+        from_asset: String,
+        to_asset: String,
+        window: u64,
+    ) -> Result<i128, RemittanceError> {
+        oracle_mod::get_twap(&env, &from_asset, &to_asset, window).map_err(|e| match e {
+            oracle_mod::OracleError::StaleRate => RemittanceError::StaleRate,
+            _ => RemittanceError::InvalidRate,
+        })
     }
 
-    pub fn send_remittance(
+    pub fn get_rate_history(
         env: Env,
-        from: Address,
-        to: Address,
-        amount: i128,
-        currency: Symbol,
-    ) -> Result<u64, RemittanceError> {
-        if upgradeable::is_paused(&env) {
-            return Err(RemittanceError::ContractPaused);
-        }
-        from.require_auth();
-        Self::enforce_rate_limit(&env, &from, FunctionType::Remittance)?;
+        from_asset: String,
+        to_asset: String,
+    ) -> soroban_sdk::Vec<(i128, i128, u64)> {
+        oracle_mod::get_rate_history(&env, &from_asset, &to_asset)
+    }
 
-        if amount <= 0 {
-            return Err(RemittanceError::InvalidAmount);
+    fn rate_deviation_bps(previous: &CachedRate, current: &CachedRate) -> i128 {
+        // Cross-multiply so rates quoted with different denominators compare.
+        let (Some(new_scaled), Some(old_scaled)) = (
+            current.rate.checked_mul(previous.denominator),
+            previous.rate.checked_mul(current.denominator),
+        ) else {
+            return i128::MAX;
+        };
+        if old_scaled <= 0 {
+            return 0;
         }
+        let diff = (new_scaled - old_scaled).abs();
+        match diff.checked_mul(10000) {
+            Some(scaled) => scaled / old_scaled,
+            None => diff / (old_scaled / 10000).max(1),
+        }
+    }
 
-        let remittance_id = env.ledger().sequence() as u64;
-
-        // AML screening — gate remittance status on risk score
-        let status = if let Some(config) = env
+    pub fn batch_set_cached_rates(
+        env: Env,
+        caller: Address,
+        rates: soroban_sdk::Vec<(String, String, i128, i128)>,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+        let stored_admin: Address = env
             .storage()
             .persistent()
-            .get::<AmlKey, AmlConfig>(&AmlKey::Config)
-        {
-            match aml::screen_transaction(&env, &config, &from, &to, amount) {
-                Ok(result) => {
-                    if result.status == AmlStatus::Flagged {
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if caller != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+
+        let max_batch = Self::get_max_batch_size(env.clone());
+        if rates.len() > max_batch {
+            return Err(RemittanceError::BatchTooLarge);
+        }
+
+        // One `rate_bat` event covers the whole batch; it is flagged
+        // `deviated` if any pair moved past the deviation threshold.
+        let threshold = Self::get_rate_deviation_threshold(env.clone());
+        let mut deviated = false;
+        let timestamp = env.ledger().timestamp();
+        for (from_asset, to_asset, rate, denominator) in rates.iter() {
+            if rate <= 0 || denominator <= 0 {
+                return Err(RemittanceError::InvalidRate);
+            }
+
+            let cached = CachedRate {
+                rate,
+                denominator,
+                timestamp,
+                from_asset,
+                to_asset,
+            };
+            let (_, deviation_bps) = Self::write_cached_rate(&env, &cached);
+            deviated |= deviation_bps > threshold;
+        }
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("rate_bat"),
+            0,
+            &caller,
+            rates.len() as i128,
+            if deviated {
+                symbol_short!("deviated")
+            } else {
+                symbol_short!("na")
+            },
+            EventData::AdminAction(symbol_short!("rate_bat")),
+        );
+
+        Ok(())
+    }
+
+    pub fn describe_error(env: Env, domain: ErrorDomain, code: u32) -> String {
+        errors::describe_error(&env, domain, code)
+    }
+
+    pub fn get_oracle_config(env: Env) -> Option<OracleConfig> {
+        env.storage().persistent().get(&HubOracleKey::OracleConfig)
+    }
+
+    pub fn get_cached_rate(env: Env, from_asset: String, to_asset: String) -> Option<CachedRate> {
+        env.storage()
+            .persistent()
+            .get(&HubOracleKey::CachedRate(from_asset, to_asset))
+    }
+
+    pub fn add_supported_pair(
+        env: Env,
+        caller: Address,
+        from_asset: String,
+        to_asset: String,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if caller != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+
+        let mut pairs: soroban_sdk::Vec<(String, String)> = env
+            .storage()
+            .persistent()
+            .get(&HubOracleKey::SupportedPairs)
+            .unwrap_or(soroban_sdk::Vec::new(&env));
+        let pair = (from_asset.clone(), to_asset.clone());
+        if pairs.contains(&pair) {
+            return Err(RemittanceError::PairAlreadySupported);
+        }
+        pairs.push_back(pair);
+        env.storage()
+            .persistent()
+            .set(&HubOracleKey::SupportedPairs, &pairs);
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("pair_add"),
+            0,
+            &caller,
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("pair_add")),
+        );
+
+        Ok(())
+    }
+
+    /// Lists registered pairs in insertion order, returning at most
+    /// `MAX_PAIR_PAGE` entries per call.
+    pub fn get_supported_pairs(
+        env: Env,
+        start: u32,
+        limit: u32,
+    ) -> soroban_sdk::Vec<(String, String)> {
+        let pairs: soroban_sdk::Vec<(String, String)> = env
+            .storage()
+            .persistent()
+            .get(&HubOracleKey::SupportedPairs)
+            .unwrap_or(soroban_sdk::Vec::new(&env));
+        let end = start
+            .saturating_add(limit.min(MAX_PAIR_PAGE))
+            .min(pairs.len());
+        if start >= end {
+            return soroban_sdk::Vec::new(&env);
+        }
+        pairs.slice(start..end)
+    }
+
+    /// Finds a route from `from_asset` to `to_asset` over registered pairs:
+    /// `[from, to]` for a direct pair, `[from, via, to]` through the first
+    /// intermediate that links both, or an empty vec when neither exists.
+    pub fn find_conversion_path(
+        env: Env,
+        from_asset: String,
+        to_asset: String,
+    ) -> soroban_sdk::Vec<String> {
+        let pairs: soroban_sdk::Vec<(String, String)> = env
+            .storage()
+            .persistent()
+            .get(&HubOracleKey::SupportedPairs)
+            .unwrap_or(soroban_sdk::Vec::new(&env));
+
+        if pairs.contains(&(from_asset.clone(), to_asset.clone())) {
+            return soroban_sdk::vec![&env, from_asset, to_asset];
+        }
+
+        for (pair_from, via) in pairs.iter() {
+            if pair_from == from_asset
+                && via != to_asset
+                && pairs.contains(&(via.clone(), to_asset.clone()))
+            {
+                return soroban_sdk::vec![&env, from_asset, via, to_asset];
+            }
+        }
+
+        soroban_sdk::Vec::new(&env)
+    }
+
+    pub fn configure_aml(
+        env: Env,
+        caller: Address,
+        oracle_address: Address,
+        risk_threshold: u32,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if caller != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+
+        let config = AmlConfig {
+            admin: caller.clone(),
+            oracle_address,
+            risk_threshold,
+            enabled: true,
+        };
+        env.storage().persistent().set(&AmlKey::Config, &config);
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("aml_cfg"),
+            0,
+            &caller,
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("aml_cfg")),
+        );
+
+        Ok(())
+    }
+
+    pub fn set_aml_threshold(
+        env: Env,
+        caller: Address,
+        risk_threshold: u32,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if caller != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+
+        let mut config: AmlConfig = env
+            .storage()
+            .persistent()
+            .get(&AmlKey::Config)
+            .ok_or(RemittanceError::AmlNotConfigured)?;
+
+        let old_threshold = config.risk_threshold;
+        config.risk_threshold = risk_threshold;
+        env.storage().persistent().set(&AmlKey::Config, &config);
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("aml_thr"),
+            0,
+            &caller,
+            risk_threshold as i128,
+            symbol_short!("na"),
+            EventData::ValueChanged(
+                symbol_short!("aml_thr"),
+                old_threshold as i128,
+                risk_threshold as i128,
+            ),
+        );
+
+        Ok(())
+    }
+
+    /// Escalates remittances above `amount_threshold` to manual review even
+    /// when their risk score is low. Zero turns amount screening off.
+    pub fn set_aml_amount_threshold(
+        env: Env,
+        caller: Address,
+        amount_threshold: i128,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if caller != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+        if amount_threshold < 0 {
+            return Err(RemittanceError::InvalidAmount);
+        }
+
+        if !env.storage().persistent().has(&AmlKey::Config) {
+            return Err(RemittanceError::AmlNotConfigured);
+        }
+
+        let old_threshold = Self::get_aml_amount_threshold(env.clone());
+        env.storage()
+            .persistent()
+            .set(&AmlKey::AmountThreshold, &amount_threshold);
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("aml_amt"),
+            0,
+            &caller,
+            amount_threshold,
+            symbol_short!("na"),
+            EventData::ValueChanged(symbol_short!("aml_amt"), old_threshold, amount_threshold),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_aml_amount_threshold(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&AmlKey::AmountThreshold)
+            .unwrap_or(0)
+    }
+
+    pub fn set_aml_oracle(
+        env: Env,
+        caller: Address,
+        oracle_address: Address,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if caller != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+
+        let mut config: AmlConfig = env
+            .storage()
+            .persistent()
+            .get(&AmlKey::Config)
+            .ok_or(RemittanceError::AmlNotConfigured)?;
+
+        let old_oracle = config.oracle_address.clone();
+        config.oracle_address = oracle_address.clone();
+        env.storage().persistent().set(&AmlKey::Config, &config);
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("aml_orc"),
+            0,
+            &caller,
+            0,
+            symbol_short!("na"),
+            EventData::PairAction(symbol_short!("aml_orc"), old_oracle, oracle_address),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_aml_config(env: Env) -> Option<AmlConfig> {
+        env.storage().persistent().get(&AmlKey::Config)
+    }
+
+    pub fn set_aml_clear_cooldown(
+        env: Env,
+        caller: Address,
+        cooldown_seconds: u64,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if caller != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&AmlKey::ClearCooldown, &cooldown_seconds);
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("aml_cool"),
+            0,
+            &caller,
+            cooldown_seconds as i128,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("aml_cool")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_aml_clear_cooldown(env: Env) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&AmlKey::ClearCooldown)
+            .unwrap_or(0)
+    }
+
+    /// Sets (or clears) the compliance officer. The officer may take
+    /// compliance actions such as clearing AML flags; the admin keeps the
+    /// same authority.
+    pub fn set_compliance_officer(
+        env: Env,
+        caller: Address,
+        officer: Option<Address>,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if caller != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+
+        match &officer {
+            Some(officer) => env
+                .storage()
+                .persistent()
+                .set(&DataKey::ComplianceOfficer, officer),
+            None => env
+                .storage()
+                .persistent()
+                .remove(&DataKey::ComplianceOfficer),
+        }
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("comp_off"),
+            0,
+            &caller,
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("comp_off")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_compliance_officer(env: Env) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::ComplianceOfficer)
+    }
+
+    fn require_compliance_authority(env: &Env, caller: &Address) -> Result<(), RemittanceError> {
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if *caller == stored_admin {
+            return Ok(());
+        }
+        let officer: Option<Address> = env.storage().persistent().get(&DataKey::ComplianceOfficer);
+        if officer.as_ref() == Some(caller) {
+            return Ok(());
+        }
+        Err(RemittanceError::Unauthorized)
+    }
+
+    pub fn clear_aml_flag(
+        env: Env,
+        caller: Address,
+        remittance_id: u64,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+        Self::require_compliance_authority(&env, &caller)?;
+
+        let mut flag: AmlScreeningResult = env
+            .storage()
+            .persistent()
+            .get(&AmlKey::Flag(remittance_id))
+            .ok_or(RemittanceError::AmlFlagNotFound)?;
+
+        if flag.status == AmlStatus::Rejected {
+            return Err(RemittanceError::InvalidStatus);
+        }
+
+        let mut remittance: RemittanceData = env
+            .storage()
+            .persistent()
+            .get(&remittance_id)
+            .ok_or(RemittanceError::NotFound)?;
+        // A cancelled remittance stays cancelled; its flag was closed then.
+        if remittance.status == symbol_short!("cancelled") {
+            return Err(RemittanceError::InvalidStatus);
+        }
+
+        if flag.status != AmlStatus::Cleared {
+            Self::adjust_open_flags(&env, &flag.sender, -1);
+        }
+        flag.status = AmlStatus::Cleared;
+        env.storage()
+            .persistent()
+            .set(&AmlKey::Flag(remittance_id), &flag);
+
+        remittance.status = symbol_short!("pending");
+        env.storage().persistent().set(&remittance_id, &remittance);
+        env.storage()
+            .persistent()
+            .set(&AmlKey::ClearedAt(remittance_id), &env.ledger().timestamp());
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("aml_clr"),
+            remittance_id,
+            &caller,
+            0,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("aml_clr")),
+        );
+
+        Ok(())
+    }
+
+    /// Links a remittance to a hub escrow holding its funds, so an AML
+    /// rejection can unwind the custody. The escrow must be for the
+    /// remittance's amount and currency.
+    pub fn link_remittance_escrow(
+        env: Env,
+        caller: Address,
+        remittance_id: u64,
+        escrow_id: u64,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+
+        let remittance: RemittanceData = env
+            .storage()
+            .persistent()
+            .get(&remittance_id)
+            .ok_or(RemittanceError::NotFound)?;
+        let escrow: EscrowData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(RemittanceError::MissingEscrow)?;
+        if caller != remittance.from || escrow.sender != remittance.from {
+            return Err(RemittanceError::Unauthorized);
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::RemittanceEscrow(remittance_id))
+        {
+            return Err(RemittanceError::InvalidStatus);
+        }
+        if remittance.status == symbol_short!("complete")
+            || remittance.status == symbol_short!("cancelled")
+            || remittance.status == symbol_short!("rejected")
+        {
+            return Err(RemittanceError::InvalidStatus);
+        }
+        if escrow.status != symbol_short!("pending") && escrow.status != symbol_short!("funded") {
+            return Err(RemittanceError::InvalidStatus);
+        }
+        if escrow.amount != remittance.amount
+            || !Self::currency_matches(&env, &escrow.asset.code, &remittance.currency)
+        {
+            return Err(RemittanceError::EscrowMismatch);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::RemittanceEscrow(remittance_id), &escrow_id);
+
+        Ok(())
+    }
+
+    pub fn get_remittance_escrow(env: Env, remittance_id: u64) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RemittanceEscrow(remittance_id))
+    }
+
+    /// Permanently rejects a flagged remittance. A funded linked escrow is
+    /// refunded to its sender, fee included, in the token that funded it; an
+    /// unfunded one is cancelled.
+    pub fn reject_remittance(
+        env: Env,
+        compliance: Address,
+        remittance_id: u64,
+    ) -> Result<(), RemittanceError> {
+        compliance.require_auth();
+        Self::require_compliance_authority(&env, &compliance)?;
+
+        let mut flag: AmlScreeningResult = env
+            .storage()
+            .persistent()
+            .get(&AmlKey::Flag(remittance_id))
+            .ok_or(RemittanceError::AmlFlagNotFound)?;
+        if flag.status == AmlStatus::Rejected {
+            return Err(RemittanceError::InvalidStatus);
+        }
+
+        let mut remittance: RemittanceData = env
+            .storage()
+            .persistent()
+            .get(&remittance_id)
+            .ok_or(RemittanceError::NotFound)?;
+        if remittance.status == symbol_short!("complete")
+            || remittance.status == symbol_short!("cancelled")
+        {
+            return Err(RemittanceError::InvalidStatus);
+        }
+
+        if flag.status != AmlStatus::Cleared {
+            Self::adjust_open_flags(&env, &flag.sender, -1);
+        }
+        flag.status = AmlStatus::Rejected;
+        env.storage()
+            .persistent()
+            .set(&AmlKey::Flag(remittance_id), &flag);
+
+        remittance.status = symbol_short!("rejected");
+        env.storage().persistent().set(&remittance_id, &remittance);
+
+        let linked: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RemittanceEscrow(remittance_id));
+        if let Some(escrow_id) = linked {
+            let mut escrow: EscrowData = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Escrow(escrow_id))
+                .ok_or(RemittanceError::MissingEscrow)?;
+            let mut refunded = 0;
+            if escrow.status == symbol_short!("funded") {
+                // Escrows funded before the token was recorded fall back to
+                // the currency's registered token.
+                let token_address = match env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::EscrowToken(escrow_id))
+                {
+                    Some(token) => token,
+                    None => Self::get_currency_token(env.clone(), remittance.currency)
+                        .ok_or(RemittanceError::AssetNotSupported)?,
+                };
+                let fee: i128 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::EscrowFee(escrow_id))
+                    .unwrap_or(0);
+                refunded = escrow
+                    .amount
+                    .checked_add(fee)
+                    .ok_or(RemittanceError::InvalidAmount)?;
+                let token_client = soroban_sdk::token::Client::new(&env, &token_address);
+                token_client.transfer(&env.current_contract_address(), &escrow.sender, &refunded);
+                env.storage()
+                    .persistent()
+                    .remove(&DataKey::EscrowFee(escrow_id));
+                env.storage()
+                    .persistent()
+                    .remove(&DataKey::EscrowToken(escrow_id));
+                escrow.status = symbol_short!("refunded");
+            } else if escrow.status == symbol_short!("pending") {
+                escrow.status = symbol_short!("cancelled");
+            }
+            env.storage()
+                .persistent()
+                .set(&DataKey::Escrow(escrow_id), &escrow);
+
+            events::emit(
+                &env,
+                symbol_short!("hub"),
+                symbol_short!("rej_rfnd"),
+                escrow_id,
+                &escrow.sender,
+                refunded,
+                escrow.status.clone(),
+                EventData::AdminAction(symbol_short!("rej_rfnd")),
+            );
+        }
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("aml_rej"),
+            remittance_id,
+            &compliance,
+            remittance.amount,
+            symbol_short!("rejected"),
+            EventData::AdminAction(symbol_short!("aml_rej")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_aml_flag(env: Env, remittance_id: u64) -> Option<AmlScreeningResult> {
+        env.storage().persistent().get(&AmlKey::Flag(remittance_id))
+    }
+
+    /// Whether `sender` has any flagged or under-review remittance that has not
+    /// been cleared yet.
+    pub fn is_sender_flagged(env: Env, sender: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get::<AmlKey, u32>(&AmlKey::OpenFlags(sender))
+            .unwrap_or(0)
+            > 0
+    }
+
+    /// Exempts a trusted account (e.g. a licensed partner) from AML screening
+    /// and velocity tracking on the remittances it sends.
+    pub fn add_aml_exempt(
+        env: Env,
+        admin: Address,
+        account: Address,
+    ) -> Result<(), RemittanceError> {
+        Self::set_aml_exempt(&env, &admin, &account, true)
+    }
+
+    pub fn remove_aml_exempt(
+        env: Env,
+        admin: Address,
+        account: Address,
+    ) -> Result<(), RemittanceError> {
+        Self::set_aml_exempt(&env, &admin, &account, false)
+    }
+
+    pub fn is_aml_exempt(env: Env, account: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&AmlKey::Exempt(account))
+            .unwrap_or(false)
+    }
+
+    fn set_aml_exempt(
+        env: &Env,
+        admin: &Address,
+        account: &Address,
+        exempt: bool,
+    ) -> Result<(), RemittanceError> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if *admin != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+
+        let key = AmlKey::Exempt(account.clone());
+        let action = if exempt {
+            env.storage().persistent().set(&key, &true);
+            Symbol::new(env, "aml_exempt_add")
+        } else {
+            env.storage().persistent().remove(&key);
+            Symbol::new(env, "aml_exempt_rem")
+        };
+
+        events::emit(
+            env,
+            symbol_short!("hub"),
+            action.clone(),
+            0,
+            admin,
+            0,
+            symbol_short!("na"),
+            EventData::AddressAction(action, account.clone()),
+        );
+
+        Ok(())
+    }
+
+    /// Adds `amount` to the sender's rolling 24h volume and reports whether the
+    /// total now exceeds the velocity threshold (never, when none is set).
+    fn track_sender_volume(
+        env: &Env,
+        sender: &Address,
+        amount: i128,
+    ) -> Result<bool, RemittanceError> {
+        let key = AmlKey::Volume(sender.clone());
+        let now = env.ledger().timestamp();
+        let (window_start, total): (u64, i128) = match env.storage().persistent().get(&key) {
+            Some((start, total)) if now.saturating_sub(start) <= AML_VELOCITY_WINDOW => {
+                (start, total)
+            }
+            _ => (now, 0),
+        };
+        let total = total
+            .checked_add(amount)
+            .ok_or(RemittanceError::InvalidAmount)?;
+        env.storage().persistent().set(&key, &(window_start, total));
+
+        let threshold = Self::get_velocity_threshold(env.clone());
+        Ok(threshold > 0 && total > threshold)
+    }
+
+    /// Sets the rolling 24h volume above which a sender's remittances are held
+    /// for review. Zero disables velocity screening.
+    pub fn set_velocity_threshold(
+        env: Env,
+        caller: Address,
+        threshold: i128,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if caller != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+        if threshold < 0 {
+            return Err(RemittanceError::InvalidAmount);
+        }
+
+        let old_threshold = Self::get_velocity_threshold(env.clone());
+        env.storage()
+            .persistent()
+            .set(&AmlKey::VelocityThreshold, &threshold);
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("aml_vel"),
+            0,
+            &caller,
+            threshold,
+            symbol_short!("na"),
+            EventData::ValueChanged(symbol_short!("aml_vel"), old_threshold, threshold),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_velocity_threshold(env: Env) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&AmlKey::VelocityThreshold)
+            .unwrap_or(0)
+    }
+
+    /// Returns the sender's current `(window_start, total)` remittance volume.
+    pub fn get_sender_volume(env: Env, sender: Address) -> Option<(u64, i128)> {
+        env.storage().persistent().get(&AmlKey::Volume(sender))
+    }
+
+    fn adjust_open_flags(env: &Env, sender: &Address, delta: i32) {
+        let key = AmlKey::OpenFlags(sender.clone());
+        let open: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        let updated = open.saturating_add_signed(delta);
+        env.storage().persistent().set(&key, &updated);
+    }
+
+    /// Returns the AML screening records for remittance ids in
+    /// `start_id..=end_id`. The scan is capped at `MAX_AML_SNAPSHOT_RANGE` ids.
+    pub fn export_aml_snapshot(
+        env: Env,
+        start_id: u64,
+        end_id: u64,
+    ) -> soroban_sdk::Vec<AmlScreeningResult> {
+        let mut results = soroban_sdk::Vec::new(&env);
+        if end_id < start_id {
+            return results;
+        }
+
+        let last_id = end_id.min(start_id.saturating_add(MAX_AML_SNAPSHOT_RANGE - 1));
+        for id in start_id..=last_id {
+            if let Some(flag) = env
+                .storage()
+                .persistent()
+                .get::<AmlKey, AmlScreeningResult>(&AmlKey::Flag(id))
+            {
+                results.push_back(flag);
+            }
+        }
+        results
+    }
+
+    pub fn set_max_remittance_amount(
+        env: Env,
+        caller: Address,
+        max_amount: Option<i128>,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if caller != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+
+        match max_amount {
+            Some(cap) => {
+                if cap <= 0 {
+                    return Err(RemittanceError::InvalidAmount);
+                }
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::MaxRemittanceAmount, &cap);
+            }
+            None => env
+                .storage()
+                .persistent()
+                .remove(&DataKey::MaxRemittanceAmount),
+        }
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("max_amt"),
+            0,
+            &caller,
+            max_amount.unwrap_or(0),
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("max_amt")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_max_remittance_amount(env: Env) -> Option<i128> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MaxRemittanceAmount)
+    }
+
+    pub fn send_remittance(
+        env: Env,
+        from: Address,
+        to: Address,
+        amount: i128,
+        currency: Symbol,
+    ) -> Result<u64, RemittanceError> {
+        if upgradeable::is_paused(&env) {
+            return Err(RemittanceError::ContractPaused);
+        }
+        from.require_auth();
+        Self::enforce_rate_limit(&env, &from, FunctionType::Remittance)?;
+        Self::enforce_send_cooldown(&env, &from)?;
+
+        if amount <= 0 {
+            return Err(RemittanceError::InvalidAmount);
+        }
+
+        if let Some(cap) = Self::get_max_remittance_amount(env.clone()) {
+            if amount > cap {
+                return Err(RemittanceError::AmountExceedsCap);
+            }
+        }
+
+        let remittance_id = env.ledger().sequence() as u64;
+
+        // AML screening — gate remittance status on risk score
+        let aml_exempt = Self::is_aml_exempt(env.clone(), from.clone());
+        let status = if aml_exempt {
+            symbol_short!("pending")
+        } else if let Some(config) = env
+            .storage()
+            .persistent()
+            .get::<AmlKey, AmlConfig>(&AmlKey::Config)
+        {
+            let amount_threshold = Self::get_aml_amount_threshold(env.clone());
+            match aml::screen_transaction(&env, &config, &from, &to, amount, amount_threshold) {
+                Ok(result) => match result.status {
+                    AmlStatus::Flagged | AmlStatus::Reviewing => {
+                        let status = if result.status == AmlStatus::Flagged {
+                            symbol_short!("flagged")
+                        } else {
+                            symbol_short!("review")
+                        };
                         env.storage()
                             .persistent()
                             .set(&AmlKey::Flag(remittance_id), &result);
-                        symbol_short!("flagged")
-                    } else {
-                        symbol_short!("pending")
+                        Self::adjust_open_flags(&env, &from, 1);
+                        status
                     }
-                }
+                    _ => symbol_short!("pending"),
+                },
                 Err(_) => {
                     let review_result = AmlScreeningResult {
                         sender: from.clone(),
@@ -539,11 +1827,32 @@ impl RemittanceHubContract {
                     env.storage()
                         .persistent()
                         .set(&AmlKey::Flag(remittance_id), &review_result);
+                    Self::adjust_open_flags(&env, &from, 1);
                     symbol_short!("review")
                 }
             }
         } else {
-            symbol_short!("pending")
+            symbol_short!("pending")
+        };
+
+        // Structuring check: many small transfers adding up within a day
+        let over_velocity = !aml_exempt && Self::track_sender_volume(&env, &from, amount)?;
+        let status = if over_velocity && status == symbol_short!("pending") {
+            let review_result = AmlScreeningResult {
+                sender: from.clone(),
+                recipient: to.clone(),
+                amount,
+                risk_score: 0,
+                status: AmlStatus::Reviewing,
+                timestamp: env.ledger().timestamp(),
+            };
+            env.storage()
+                .persistent()
+                .set(&AmlKey::Flag(remittance_id), &review_result);
+            Self::adjust_open_flags(&env, &from, 1);
+            symbol_short!("review")
+        } else {
+            status
         };
 
         let remittance = RemittanceData {
@@ -564,6 +1873,31 @@ impl RemittanceHubContract {
         amount: i128,
         from_asset: String,
         to_asset: String,
+    ) -> Result<oracle_mod::ConversionResult, RemittanceError> {
+        Self::convert_within(&env, amount, from_asset, to_asset, None)
+    }
+
+    /// Like `convert_currency`, but only accepts a rate no older than
+    /// `max_age` seconds (or the configured staleness, whichever is stricter).
+    pub fn convert_currency_fresh(
+        env: Env,
+        amount: i128,
+        from_asset: String,
+        to_asset: String,
+        max_age: u64,
+    ) -> Result<oracle_mod::ConversionResult, RemittanceError> {
+        if max_age == 0 {
+            return Err(RemittanceError::InvalidAmount);
+        }
+        Self::convert_within(&env, amount, from_asset, to_asset, Some(max_age))
+    }
+
+    fn convert_within(
+        env: &Env,
+        amount: i128,
+        from_asset: String,
+        to_asset: String,
+        max_age: Option<u64>,
     ) -> Result<oracle_mod::ConversionResult, RemittanceError> {
         if amount <= 0 {
             return Err(RemittanceError::InvalidAmount);
@@ -580,13 +1914,58 @@ impl RemittanceHubContract {
             to_asset.clone(),
         ));
 
-        let result = oracle_mod::get_conversion_rate(
-            &env,
-            &config.primary_oracle,
+        let configured = Self::max_staleness_for(env, &config, &from_asset, &to_asset);
+        let max_staleness = match max_age {
+            Some(age) if configured == 0 => age,
+            Some(age) => age.min(configured),
+            None => configured,
+        };
+
+        let median_deviation: Option<i128> =
+            env.storage().persistent().get(&HubOracleKey::MedianPricing);
+        if let Some(max_deviation_bps) = median_deviation {
+            let sources = oracle_mod::MedianSources {
+                primary: oracle_mod::feed_for(&Self::rate_decimals(env), &config.primary_oracle),
+                secondary: oracle_mod::feed_for(
+                    &Self::rate_decimals(env),
+                    &config.secondary_oracle,
+                ),
+                max_deviation_bps,
+            };
+            let conversion = oracle_mod::get_conversion_rate_median(
+                env,
+                &sources,
+                &from_asset,
+                &to_asset,
+                amount,
+                max_staleness,
+                cached,
+            )
+            .map_err(|e| match e {
+                oracle_mod::OracleError::RateDeviation => RemittanceError::RateDeviation,
+                oracle_mod::OracleError::StaleRate if max_age.is_some() => {
+                    RemittanceError::StaleRate
+                }
+                _ => RemittanceError::ConversionFailed,
+            })?;
+            let new_cache = CachedRate {
+                rate: conversion.rate,
+                denominator: conversion.denominator,
+                timestamp: conversion.timestamp,
+                from_asset,
+                to_asset,
+            };
+            Self::store_cached_rate(env, &env.current_contract_address(), &new_cache);
+            return Ok(conversion);
+        }
+
+        let result = oracle_mod::convert_with_feed(
+            env,
+            &oracle_mod::feed_for(&Self::rate_decimals(env), &config.primary_oracle),
             &from_asset,
             &to_asset,
             amount,
-            config.max_staleness,
+            max_staleness,
             cached.clone(),
         );
 
@@ -596,22 +1975,20 @@ impl RemittanceHubContract {
                     rate: conversion.rate,
                     denominator: conversion.denominator,
                     timestamp: conversion.timestamp,
-                    from_asset: from_asset.clone(),
-                    to_asset: to_asset.clone(),
+                    from_asset,
+                    to_asset,
                 };
-                env.storage()
-                    .persistent()
-                    .set(&HubOracleKey::CachedRate(from_asset, to_asset), &new_cache);
+                Self::store_cached_rate(env, &env.current_contract_address(), &new_cache);
                 Ok(conversion)
             }
             Err(_) => {
-                let secondary_result = oracle_mod::get_conversion_rate(
-                    &env,
-                    &config.secondary_oracle,
+                let secondary_result = oracle_mod::convert_with_feed(
+                    env,
+                    &oracle_mod::feed_for(&Self::rate_decimals(env), &config.secondary_oracle),
                     &from_asset,
                     &to_asset,
                     amount,
-                    config.max_staleness,
+                    max_staleness,
                     cached,
                 );
                 match secondary_result {
@@ -620,14 +1997,15 @@ impl RemittanceHubContract {
                             rate: conversion.rate,
                             denominator: conversion.denominator,
                             timestamp: conversion.timestamp,
-                            from_asset: from_asset.clone(),
-                            to_asset: to_asset.clone(),
+                            from_asset,
+                            to_asset,
                         };
-                        env.storage()
-                            .persistent()
-                            .set(&HubOracleKey::CachedRate(from_asset, to_asset), &new_cache);
+                        Self::store_cached_rate(env, &env.current_contract_address(), &new_cache);
                         Ok(conversion)
                     }
+                    Err(oracle_mod::OracleError::StaleRate) if max_age.is_some() => {
+                        Err(RemittanceError::StaleRate)
+                    }
                     Err(_) => Err(RemittanceError::ConversionFailed),
                 }
             }
@@ -641,12 +2019,79 @@ impl RemittanceHubContract {
     ) -> Result<(), RemittanceError> {
         caller.require_auth();
 
+        let remittance: RemittanceData = env
+            .storage()
+            .persistent()
+            .get(&remittance_id)
+            .ok_or(RemittanceError::NotFound)?;
+
+        Self::finish_remittance(&env, remittance_id, remittance)
+    }
+
+    /// Lets the sender withdraw a remittance that has not completed yet,
+    /// including one held for AML review. Any AML flag raised against it is
+    /// kept for the compliance record.
+    pub fn cancel_remittance(
+        env: Env,
+        remittance_id: u64,
+        caller: Address,
+    ) -> Result<(), RemittanceError> {
+        if upgradeable::is_paused(&env) {
+            return Err(RemittanceError::ContractPaused);
+        }
+        caller.require_auth();
+
         let mut remittance: RemittanceData = env
             .storage()
             .persistent()
             .get(&remittance_id)
             .ok_or(RemittanceError::NotFound)?;
 
+        if caller != remittance.from {
+            return Err(RemittanceError::Unauthorized);
+        }
+
+        if remittance.status != symbol_short!("pending")
+            && remittance.status != symbol_short!("flagged")
+            && remittance.status != symbol_short!("review")
+        {
+            return Err(RemittanceError::InvalidStatus);
+        }
+
+        // Cancelling withdraws the remittance from review, so its open AML
+        // flag no longer counts against the sender.
+        if let Some(flag) = env
+            .storage()
+            .persistent()
+            .get::<_, AmlScreeningResult>(&AmlKey::Flag(remittance_id))
+        {
+            if flag.status == AmlStatus::Flagged || flag.status == AmlStatus::Reviewing {
+                Self::adjust_open_flags(&env, &flag.sender, -1);
+            }
+        }
+
+        remittance.status = symbol_short!("cancelled");
+        env.storage().persistent().set(&remittance_id, &remittance);
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("rem_canc"),
+            remittance_id,
+            &caller,
+            remittance.amount,
+            symbol_short!("cancelled"),
+            EventData::AddressAction(symbol_short!("rem_canc"), remittance.to.clone()),
+        );
+
+        Ok(())
+    }
+
+    fn finish_remittance(
+        env: &Env,
+        remittance_id: u64,
+        mut remittance: RemittanceData,
+    ) -> Result<(), RemittanceError> {
         if remittance.status == symbol_short!("flagged")
             || remittance.status == symbol_short!("review")
         {
@@ -657,10 +2102,21 @@ impl RemittanceHubContract {
             return Err(RemittanceError::InvalidStatus);
         }
 
+        let cleared_at: Option<u64> = env
+            .storage()
+            .persistent()
+            .get(&AmlKey::ClearedAt(remittance_id));
+        if let Some(cleared_at) = cleared_at {
+            let cooldown = Self::get_aml_clear_cooldown(env.clone());
+            if env.ledger().timestamp() < cleared_at.saturating_add(cooldown) {
+                return Err(RemittanceError::CooldownActive);
+            }
+        }
+
         remittance.status = symbol_short!("complete");
         env.storage().persistent().set(&remittance_id, &remittance);
 
-        Self::track_metric(&env, MetricType::Success, 1);
+        Self::track_metric(env, MetricType::Success, 1);
 
         Ok(())
     }
@@ -669,6 +2125,145 @@ impl RemittanceHubContract {
         env.storage().persistent().get(&remittance_id)
     }
 
+    /// Seconds a settlement batch stays open before `flush_batch` may settle
+    /// it early; a full batch can always be flushed. 0 allows flushing at
+    /// any time.
+    pub fn set_settlement_window(
+        env: Env,
+        caller: Address,
+        window: u64,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if caller != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::SettlementWindow, &window);
+
+        Ok(())
+    }
+
+    pub fn get_settlement_window(env: Env) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SettlementWindow)
+            .unwrap_or(0)
+    }
+
+    pub fn get_settlement_queue(env: Env) -> soroban_sdk::Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SettlementQueue)
+            .unwrap_or(soroban_sdk::Vec::new(&env))
+    }
+
+    /// Adds a pending remittance to the next settlement batch. The batch
+    /// holds at most `get_max_batch_size` remittances.
+    pub fn queue_for_batch(env: Env, remittance_id: u64) -> Result<(), RemittanceError> {
+        let remittance: RemittanceData = env
+            .storage()
+            .persistent()
+            .get(&remittance_id)
+            .ok_or(RemittanceError::NotFound)?;
+        remittance.from.require_auth();
+
+        if remittance.status != symbol_short!("pending") {
+            return Err(RemittanceError::InvalidStatus);
+        }
+
+        let mut queue = Self::get_settlement_queue(env.clone());
+        if queue.contains(remittance_id) {
+            return Err(RemittanceError::InvalidStatus);
+        }
+        if queue.len() >= Self::get_max_batch_size(env.clone()) {
+            return Err(RemittanceError::BatchTooLarge);
+        }
+        if queue.is_empty() {
+            env.storage()
+                .persistent()
+                .set(&DataKey::SettlementOpenedAt, &env.ledger().timestamp());
+        }
+        queue.push_back(remittance_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::SettlementQueue, &queue);
+
+        Ok(())
+    }
+
+    /// Settles every queued remittance that can complete now, once the batch
+    /// is full or its window has elapsed. Remittances still in their AML
+    /// cooling-off period stay queued; any other failure drops them from the
+    /// batch. Returns the ids that settled.
+    pub fn flush_batch(
+        env: Env,
+        keeper: Address,
+    ) -> Result<soroban_sdk::Vec<u64>, RemittanceError> {
+        if upgradeable::is_paused(&env) {
+            return Err(RemittanceError::ContractPaused);
+        }
+        keeper.require_auth();
+
+        let queue = Self::get_settlement_queue(env.clone());
+        let opened_at: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SettlementOpenedAt)
+            .unwrap_or(0);
+        let window = Self::get_settlement_window(env.clone());
+        let full = queue.len() >= Self::get_max_batch_size(env.clone());
+        if !full && env.ledger().timestamp() < opened_at.saturating_add(window) {
+            return Err(RemittanceError::CooldownActive);
+        }
+
+        let mut settled = soroban_sdk::Vec::new(&env);
+        let mut retained = soroban_sdk::Vec::new(&env);
+        for remittance_id in queue.iter() {
+            let remittance: Option<RemittanceData> = env.storage().persistent().get(&remittance_id);
+            let Some(remittance) = remittance else {
+                continue;
+            };
+            match Self::finish_remittance(&env, remittance_id, remittance) {
+                Ok(()) => settled.push_back(remittance_id),
+                Err(RemittanceError::CooldownActive) => retained.push_back(remittance_id),
+                Err(_) => {}
+            }
+        }
+
+        if !retained.is_empty() {
+            env.storage()
+                .persistent()
+                .set(&DataKey::SettlementOpenedAt, &env.ledger().timestamp());
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::SettlementQueue, &retained);
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("batch_fl"),
+            0,
+            &keeper,
+            settled.len() as i128,
+            symbol_short!("na"),
+            EventData::ValueChanged(
+                symbol_short!("batch_fl"),
+                settled.len() as i128,
+                retained.len() as i128,
+            ),
+        );
+
+        Ok(settled)
+    }
+
     pub fn generate_invoice(
         env: Env,
         sender: Address,
@@ -695,6 +2290,31 @@ impl RemittanceHubContract {
             return Err(RemittanceError::DueDateInPast);
         }
 
+        Ok(Self::issue_invoice(
+            &env,
+            Invoice {
+                invoice_id: 0,
+                sender,
+                recipient,
+                amount,
+                asset,
+                converted_amount: 0,
+                fees: 0,
+                total_due: 0,
+                status: InvoiceStatus::Unpaid,
+                created_at: current_time,
+                due_date,
+                paid_at: 0,
+                description,
+                escrow_id,
+                memo,
+            },
+        ))
+    }
+
+    /// Assigns the next invoice id, prices `invoice` with the oracle and the
+    /// remittance fee, and stores it.
+    fn issue_invoice(env: &Env, mut invoice: Invoice) -> u64 {
         let mut counter: u64 = env
             .storage()
             .persistent()
@@ -702,9 +2322,11 @@ impl RemittanceHubContract {
             .unwrap_or(0);
         counter = counter.checked_add(1).unwrap_or(counter);
 
-        let converted_amount = Self::convert_with_oracle(&env, amount, &asset.code);
+        let amount = invoice.amount;
+        let escrow_id = invoice.escrow_id;
+        let converted_amount = Self::convert_with_oracle(env, amount, &invoice.asset.code);
 
-        let fee_percentage = 250;
+        let fee_percentage = Self::get_remittance_fee(env.clone());
         let fees = amount
             .checked_mul(fee_percentage)
             .unwrap_or(0)
@@ -713,23 +2335,10 @@ impl RemittanceHubContract {
 
         let total_due = amount.checked_add(fees).unwrap_or(amount);
 
-        let invoice = Invoice {
-            invoice_id: counter,
-            sender: sender.clone(),
-            recipient,
-            amount,
-            asset,
-            converted_amount,
-            fees,
-            total_due,
-            status: InvoiceStatus::Unpaid,
-            created_at: current_time,
-            due_date,
-            paid_at: 0,
-            description,
-            escrow_id,
-            memo,
-        };
+        invoice.invoice_id = counter;
+        invoice.converted_amount = converted_amount;
+        invoice.fees = fees;
+        invoice.total_due = total_due;
 
         env.storage()
             .persistent()
@@ -744,13 +2353,12 @@ impl RemittanceHubContract {
                 .set(&DataKey::EscrowInvoice(escrow_id), &counter);
         }
 
-        Self::track_metric(&env, MetricType::Volume, amount);
-        Self::track_metric(&env, MetricType::Fee, fees);
+        Self::track_metric(env, MetricType::Volume, amount);
+        Self::track_metric(env, MetricType::Fee, fees);
 
-        events::emit(
-            &env,
-            symbol_short!("hub"),
-            symbol_short!("inv_gen"),
+        Self::emit_invoice_event(
+            env,
+            "inv_created",
             counter,
             &invoice.sender,
             total_due,
@@ -764,11 +2372,150 @@ impl RemittanceHubContract {
                     code: invoice.asset.code.clone(),
                     issuer: invoice.asset.issuer.clone(),
                 },
+                amount,
                 total_due,
             ),
         );
 
-        Ok(counter)
+        counter
+    }
+
+    /// Stores a template that issues `occurrences` invoices from `sender` to
+    /// `recipient`, one every `interval` seconds starting one interval from
+    /// now. Each invoice falls due one interval after it is issued.
+    pub fn create_recurring_invoice(
+        env: Env,
+        sender: Address,
+        recipient: Address,
+        amount: i128,
+        asset: Asset,
+        interval: u64,
+        occurrences: u32,
+    ) -> Result<u64, RemittanceError> {
+        if upgradeable::is_paused(&env) {
+            return Err(RemittanceError::ContractPaused);
+        }
+        sender.require_auth();
+        Self::enforce_rate_limit(&env, &sender, FunctionType::Invoice)?;
+
+        if amount <= 0 || interval == 0 || occurrences == 0 {
+            return Err(RemittanceError::InvalidAmount);
+        }
+
+        let template_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::RecurringInvoiceCounter)
+            .unwrap_or(0u64)
+            .checked_add(1)
+            .ok_or(RemittanceError::InvalidAmount)?;
+
+        let template = RecurringInvoice {
+            template_id,
+            sender: sender.clone(),
+            recipient,
+            amount,
+            asset,
+            interval,
+            remaining_occurrences: occurrences,
+            last_issued: env.ledger().timestamp(),
+            invoice_ids: soroban_sdk::Vec::new(&env),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::RecurringInvoice(template_id), &template);
+        env.storage()
+            .persistent()
+            .set(&DataKey::RecurringInvoiceCounter, &template_id);
+
+        Self::emit_invoice_event(
+            &env,
+            "recur_inv",
+            template_id,
+            &sender,
+            amount,
+            symbol_short!("created"),
+            EventData::ValueChanged(
+                symbol_short!("recur_inv"),
+                interval as i128,
+                occurrences as i128,
+            ),
+        );
+
+        Ok(template_id)
+    }
+
+    /// Issues the next invoice of a recurring template once its interval has
+    /// elapsed. Anyone may call this; the schedule was authorized by the
+    /// sender when the template was created. Returns the new invoice id.
+    pub fn mint_next_invoice(env: Env, template_id: u64) -> Result<u64, RemittanceError> {
+        if upgradeable::is_paused(&env) {
+            return Err(RemittanceError::ContractPaused);
+        }
+
+        let key = DataKey::RecurringInvoice(template_id);
+        let mut template: RecurringInvoice = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(RemittanceError::NotFound)?;
+
+        if template.remaining_occurrences == 0 {
+            return Err(RemittanceError::InvalidStatus);
+        }
+
+        let issue_at = template.last_issued.saturating_add(template.interval);
+        if env.ledger().timestamp() < issue_at {
+            return Err(RemittanceError::NotYetDue);
+        }
+
+        let invoice_id = Self::issue_invoice(
+            &env,
+            Invoice {
+                invoice_id: 0,
+                sender: template.sender.clone(),
+                recipient: template.recipient.clone(),
+                amount: template.amount,
+                asset: template.asset.clone(),
+                converted_amount: 0,
+                fees: 0,
+                total_due: 0,
+                status: InvoiceStatus::Unpaid,
+                created_at: env.ledger().timestamp(),
+                due_date: issue_at.saturating_add(template.interval),
+                paid_at: 0,
+                description: String::from_str(&env, ""),
+                escrow_id: 0,
+                memo: String::from_str(&env, ""),
+            },
+        );
+
+        template.last_issued = issue_at;
+        template.remaining_occurrences -= 1;
+        template.invoice_ids.push_back(invoice_id);
+        env.storage().persistent().set(&key, &template);
+
+        Self::emit_invoice_event(
+            &env,
+            "recur_inv",
+            template_id,
+            &template.sender,
+            template.amount,
+            symbol_short!("minted"),
+            EventData::ValueChanged(
+                symbol_short!("recur_inv"),
+                invoice_id as i128,
+                template.remaining_occurrences as i128,
+            ),
+        );
+
+        Ok(invoice_id)
+    }
+
+    pub fn get_recurring_invoice(env: Env, template_id: u64) -> Option<RecurringInvoice> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RecurringInvoice(template_id))
     }
 
     pub fn get_invoice(env: Env, invoice_id: u64) -> Option<Invoice> {
@@ -777,6 +2524,40 @@ impl RemittanceHubContract {
             .get(&DataKey::Invoice(invoice_id))
     }
 
+    pub fn get_invoice_amendments(env: Env, invoice_id: u64) -> soroban_sdk::Vec<AmendmentRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::InvoiceAmendments(invoice_id))
+            .unwrap_or(soroban_sdk::Vec::new(&env))
+    }
+
+    fn record_amendment(
+        env: &Env,
+        invoice_id: u64,
+        field: Symbol,
+        old_value: i128,
+        new_value: i128,
+        actor: &Address,
+    ) {
+        let key = DataKey::InvoiceAmendments(invoice_id);
+        let mut history: soroban_sdk::Vec<AmendmentRecord> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(soroban_sdk::Vec::new(env));
+        if history.len() >= MAX_AMENDMENT_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(AmendmentRecord {
+            field,
+            old_value,
+            new_value,
+            actor: actor.clone(),
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&key, &history);
+    }
+
     pub fn get_invoice_by_escrow(env: Env, escrow_id: u64) -> Option<u64> {
         env.storage()
             .persistent()
@@ -794,7 +2575,7 @@ impl RemittanceHubContract {
         caller.require_auth();
         Self::enforce_rate_limit(&env, &caller, FunctionType::Invoice)?;
 
-        let mut invoice: Invoice = env
+        let invoice: Invoice = env
             .storage()
             .persistent()
             .get(&DataKey::Invoice(invoice_id))
@@ -808,29 +2589,163 @@ impl RemittanceHubContract {
             return Err(RemittanceError::Unauthorized);
         }
 
+        Self::finish_invoice(&env, invoice, &caller);
+
+        Ok(())
+    }
+
+    /// Records an installment against an invoice. It stays unpaid until the
+    /// installments cover `total_due`; payments beyond that are rejected.
+    pub fn pay_invoice_partial(
+        env: Env,
+        invoice_id: u64,
+        caller: Address,
+        amount: i128,
+    ) -> Result<(), RemittanceError> {
+        if upgradeable::is_paused(&env) {
+            return Err(RemittanceError::ContractPaused);
+        }
+        caller.require_auth();
+        Self::enforce_rate_limit(&env, &caller, FunctionType::Invoice)?;
+
+        let invoice: Invoice = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Invoice(invoice_id))
+            .ok_or(RemittanceError::InvoiceNotFound)?;
+
+        if invoice.status == InvoiceStatus::Paid || invoice.status == InvoiceStatus::Cancelled {
+            return Err(RemittanceError::InvalidInvoiceStatus);
+        }
+
+        if caller != invoice.sender && caller != invoice.recipient {
+            return Err(RemittanceError::Unauthorized);
+        }
+
+        let paid_amount = Self::get_invoice_paid_amount(env.clone(), invoice_id)
+            .checked_add(amount)
+            .ok_or(RemittanceError::InvalidAmount)?;
+        if amount <= 0 || paid_amount > invoice.total_due {
+            return Err(RemittanceError::InvalidAmount);
+        }
+
+        Self::emit_invoice_event(
+            &env,
+            "inv_part",
+            invoice_id,
+            &caller,
+            amount,
+            symbol_short!("partial"),
+            EventData::ValueChanged(symbol_short!("inv_part"), amount, paid_amount),
+        );
+
+        if paid_amount >= invoice.total_due {
+            Self::finish_invoice(&env, invoice, &caller);
+        } else {
+            env.storage()
+                .persistent()
+                .set(&DataKey::InvoicePaidAmount(invoice_id), &paid_amount);
+        }
+
+        Ok(())
+    }
+
+    /// Amount paid so far against an invoice; `total_due` once it is paid.
+    pub fn get_invoice_paid_amount(env: Env, invoice_id: u64) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::InvoicePaidAmount(invoice_id))
+            .unwrap_or(0)
+    }
+
+    fn finish_invoice(env: &Env, mut invoice: Invoice, caller: &Address) {
+        let invoice_id = invoice.invoice_id;
         invoice.status = InvoiceStatus::Paid;
         invoice.paid_at = env.ledger().timestamp();
 
         env.storage()
             .persistent()
             .set(&DataKey::Invoice(invoice_id), &invoice);
+        env.storage()
+            .persistent()
+            .set(&DataKey::InvoicePaidAmount(invoice_id), &invoice.total_due);
 
-        events::emit(
-            &env,
-            symbol_short!("hub"),
-            symbol_short!("inv_paid"),
+        Self::emit_invoice_event(
+            env,
+            "inv_paid",
             invoice_id,
-            &caller,
+            caller,
             invoice.total_due,
             symbol_short!("paid"),
             EventData::InvoicePaid(invoice_id, invoice.escrow_id, invoice.total_due),
         );
 
-        Self::track_metric(&env, MetricType::Success, 1);
+        Self::track_metric(env, MetricType::Success, 1);
+    }
+
+    /// Marks an invoice paid and completes the remittance for the same
+    /// payment in one call. Both must name the same sender, recipient and
+    /// amount; otherwise nothing changes.
+    pub fn settle_payment(
+        env: Env,
+        caller: Address,
+        invoice_id: u64,
+        remittance_id: u64,
+    ) -> Result<(), RemittanceError> {
+        if upgradeable::is_paused(&env) {
+            return Err(RemittanceError::ContractPaused);
+        }
+        caller.require_auth();
+        Self::enforce_rate_limit(&env, &caller, FunctionType::Invoice)?;
+
+        let invoice: Invoice = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Invoice(invoice_id))
+            .ok_or(RemittanceError::InvoiceNotFound)?;
+        let remittance: RemittanceData = env
+            .storage()
+            .persistent()
+            .get(&remittance_id)
+            .ok_or(RemittanceError::NotFound)?;
+
+        if caller != invoice.sender && caller != invoice.recipient {
+            return Err(RemittanceError::Unauthorized);
+        }
+        if invoice.status != InvoiceStatus::Unpaid && invoice.status != InvoiceStatus::Overdue {
+            return Err(RemittanceError::InvalidInvoiceStatus);
+        }
+        if invoice.sender != remittance.from
+            || invoice.recipient != remittance.to
+            || invoice.amount != remittance.amount
+            || !Self::currency_matches(&env, &invoice.asset.code, &remittance.currency)
+        {
+            return Err(RemittanceError::PaymentMismatch);
+        }
+
+        Self::finish_remittance(&env, remittance_id, remittance)?;
+        Self::finish_invoice(&env, invoice, &caller);
 
         Ok(())
     }
 
+    /// Whether an invoice's asset code names the same currency as a
+    /// remittance's currency symbol.
+    fn currency_matches(env: &Env, code: &String, currency: &Symbol) -> bool {
+        let len = code.len() as usize;
+        if len == 0 || len > 32 {
+            return false;
+        }
+        let mut buf = [0u8; 32];
+        code.copy_into_slice(&mut buf[..len]);
+        match core::str::from_utf8(&buf[..len]) {
+            Ok(text) if text.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_') => {
+                Symbol::new(env, text) == *currency
+            }
+            _ => false,
+        }
+    }
+
     pub fn mark_invoice_overdue(env: Env, invoice_id: u64) -> Result<(), RemittanceError> {
         let mut invoice: Invoice = env
             .storage()
@@ -854,13 +2769,12 @@ impl RemittanceHubContract {
             .persistent()
             .set(&DataKey::Invoice(invoice_id), &invoice);
 
-        events::emit(
+        Self::emit_invoice_event(
             &env,
-            symbol_short!("hub"),
-            symbol_short!("inv_over"),
+            "inv_overdue",
             invoice_id,
             &env.current_contract_address(),
-            0,
+            invoice.total_due,
             symbol_short!("overdue"),
             EventData::InvoiceOverdue(invoice_id),
         );
@@ -868,6 +2782,54 @@ impl RemittanceHubContract {
         Ok(())
     }
 
+    /// Marks every past-due unpaid invoice in `invoice_ids` as overdue and
+    /// returns the ids that changed. Missing, paid, cancelled, already
+    /// overdue and not-yet-due invoices are skipped. Callable by anyone so
+    /// keepers can sweep invoices in bulk.
+    pub fn batch_mark_overdue(
+        env: Env,
+        invoice_ids: soroban_sdk::Vec<u64>,
+    ) -> Result<soroban_sdk::Vec<u64>, RemittanceError> {
+        let max_batch = Self::get_max_batch_size(env.clone());
+        if invoice_ids.len() > max_batch {
+            return Err(RemittanceError::BatchTooLarge);
+        }
+
+        let current_time = env.ledger().timestamp();
+        let mut marked = soroban_sdk::Vec::new(&env);
+        for invoice_id in invoice_ids.iter() {
+            let invoice_opt: Option<Invoice> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Invoice(invoice_id));
+            let mut invoice = match invoice_opt {
+                Some(invoice) => invoice,
+                None => continue,
+            };
+            if invoice.status != InvoiceStatus::Unpaid || current_time <= invoice.due_date {
+                continue;
+            }
+
+            invoice.status = InvoiceStatus::Overdue;
+            env.storage()
+                .persistent()
+                .set(&DataKey::Invoice(invoice_id), &invoice);
+
+            Self::emit_invoice_event(
+                &env,
+                "inv_overdue",
+                invoice_id,
+                &env.current_contract_address(),
+                invoice.total_due,
+                symbol_short!("overdue"),
+                EventData::InvoiceOverdue(invoice_id),
+            );
+            marked.push_back(invoice_id);
+        }
+
+        Ok(marked)
+    }
+
     pub fn cancel_invoice(
         env: Env,
         invoice_id: u64,
@@ -895,20 +2857,73 @@ impl RemittanceHubContract {
             .persistent()
             .set(&DataKey::Invoice(invoice_id), &invoice);
 
-        events::emit(
+        Self::emit_invoice_event(
             &env,
-            symbol_short!("hub"),
-            symbol_short!("inv_canc"),
+            "inv_cancelled",
             invoice_id,
             &caller,
-            0,
+            invoice.total_due,
             symbol_short!("cancel"),
             EventData::InvoiceCancelled(invoice_id),
         );
 
+        // The funded escrow behind the invoice is owed back to the sender;
+        // the escrow contract confirms this via `get_escrow_invoice`.
+        if invoice.escrow_id > 0 {
+            Self::emit_invoice_event(
+                &env,
+                "refund_due",
+                invoice_id,
+                &caller,
+                invoice.total_due,
+                symbol_short!("refund"),
+                EventData::ValueChanged(
+                    symbol_short!("refund"),
+                    invoice_id as i128,
+                    invoice.escrow_id as i128,
+                ),
+            );
+        }
+
         Ok(())
     }
 
+    /// The invoice linked to `escrow_id`, if any. The escrow contract checks
+    /// its parties and status before acting on a cancellation.
+    pub fn get_escrow_invoice(env: Env, escrow_id: u64) -> Option<Invoice> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EscrowInvoice(escrow_id))
+            .and_then(|invoice_id: u64| {
+                env.storage()
+                    .persistent()
+                    .get(&DataKey::Invoice(invoice_id))
+            })
+    }
+
+    /// Every invoice state transition goes through here so indexers see one
+    /// consistent `inv_*` action name with the invoice id, actor and amount.
+    fn emit_invoice_event(
+        env: &Env,
+        action: &str,
+        invoice_id: u64,
+        actor: &Address,
+        amount: i128,
+        status: Symbol,
+        data: EventData,
+    ) {
+        events::emit(
+            env,
+            symbol_short!("hub"),
+            Symbol::new(env, action),
+            invoice_id,
+            actor,
+            amount,
+            status,
+            data,
+        );
+    }
+
     pub fn update_invoice_amount(
         env: Env,
         invoice_id: u64,
@@ -935,14 +2950,21 @@ impl RemittanceHubContract {
             return Err(RemittanceError::InvalidInvoiceStatus);
         }
 
-        let fee_percentage = 250;
+        let fee_percentage = Self::get_remittance_fee(env.clone());
         let fees = new_amount
             .checked_mul(fee_percentage)
             .unwrap_or(0)
             .checked_div(10000)
             .unwrap_or(0);
 
-        let _old_amount = invoice.amount;
+        Self::record_amendment(
+            &env,
+            invoice_id,
+            symbol_short!("amount"),
+            invoice.amount,
+            new_amount,
+            &caller,
+        );
         invoice.amount = new_amount;
         invoice.fees = fees;
         invoice.total_due = new_amount.checked_add(fees).unwrap_or(new_amount);
@@ -951,10 +2973,9 @@ impl RemittanceHubContract {
             .persistent()
             .set(&DataKey::Invoice(invoice_id), &invoice);
 
-        events::emit(
+        Self::emit_invoice_event(
             &env,
-            symbol_short!("hub"),
-            symbol_short!("inv_upd"),
+            "inv_updated",
             invoice_id,
             &caller,
             invoice.total_due,
@@ -986,9 +3007,26 @@ impl RemittanceHubContract {
             return Err(RemittanceError::BatchTooLarge);
         }
 
+        let mut seen_refs: Map<String, bool> = Map::new(&env);
         let mut ids = soroban_sdk::Vec::new(&env);
         for request in requests.iter() {
+            let client_ref = request.client_ref.clone().filter(|r| !r.is_empty());
+            if let Some(client_ref) = &client_ref {
+                let key = BatchKey::ClientRef(sender.clone(), client_ref.clone());
+                if seen_refs.contains_key(client_ref.clone())
+                    || env.storage().persistent().has(&key)
+                {
+                    return Err(RemittanceError::DuplicateEscrowId);
+                }
+                seen_refs.set(client_ref.clone(), true);
+            }
+
             let id = Self::create_escrow_internal(&env, &sender, request)?;
+            if let Some(client_ref) = client_ref {
+                env.storage()
+                    .persistent()
+                    .set(&BatchKey::ClientRef(sender.clone(), client_ref), &id);
+            }
             ids.push_back(id);
         }
 
@@ -1006,6 +3044,12 @@ impl RemittanceHubContract {
         Ok(ids)
     }
 
+    pub fn get_escrow_by_ref(env: Env, sender: Address, client_ref: String) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&BatchKey::ClientRef(sender, client_ref))
+    }
+
     fn create_escrow_internal(
         env: &Env,
         sender: &Address,
@@ -1036,6 +3080,7 @@ impl RemittanceHubContract {
             asset: request.asset,
             expiration_timestamp: request.expiration_timestamp,
             status: symbol_short!("pending"),
+            oracle_address: None,
         };
 
         env.storage()
@@ -1063,7 +3108,7 @@ impl RemittanceHubContract {
 
         let mut total_amount: i128 = 0;
         let mut total_fees: i128 = 0;
-        let fee_percentage = 250;
+        let fee_percentage = Self::get_remittance_fee(env.clone());
 
         for id in escrow_ids.iter() {
             let mut escrow: EscrowData = env
@@ -1097,112 +3142,379 @@ impl RemittanceHubContract {
             env.storage()
                 .persistent()
                 .set(&DataKey::Escrow(id), &escrow);
+            env.storage()
+                .persistent()
+                .set(&DataKey::EscrowFee(id), &fees);
+            env.storage()
+                .persistent()
+                .set(&DataKey::EscrowToken(id), &token_address);
+        }
+
+        let total_transfer = total_amount
+            .checked_add(total_fees)
+            .ok_or(RemittanceError::InvalidAmount)?;
+
+        if total_transfer > 0 {
+            let token_client = soroban_sdk::token::Client::new(&env, &token_address);
+            token_client.transfer(&sender, &env.current_contract_address(), &total_transfer);
+        }
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("batch_dep"),
+            0,
+            &sender,
+            total_amount,
+            symbol_short!("na"),
+            EventData::AdminAction(symbol_short!("batch_dep")),
+        );
+
+        Self::track_metric(&env, MetricType::Volume, total_amount);
+        Self::track_metric(&env, MetricType::Fee, total_fees);
+
+        Ok(())
+    }
+
+    pub fn batch_release(
+        env: Env,
+        caller: Address,
+        escrow_ids: soroban_sdk::Vec<u64>,
+        token_address: Address,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+
+        let max_batch = Self::get_max_batch_size(env.clone());
+        if escrow_ids.len() > max_batch {
+            return Err(RemittanceError::BatchTooLarge);
+        }
+
+        let mut total_released: i128 = 0;
+        let mut total_fees: i128 = 0;
+
+        let token_client = soroban_sdk::token::Client::new(&env, &token_address);
+        let contract_address = env.current_contract_address();
+        for id in escrow_ids.iter() {
+            let mut escrow: EscrowData = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Escrow(id))
+                .ok_or(RemittanceError::NotFound)?;
+
+            if escrow.recipient != caller && escrow.sender != caller {
+                return Err(RemittanceError::Unauthorized);
+            }
+            if escrow.status != symbol_short!("funded") {
+                return Err(RemittanceError::InvalidStatus);
+            }
+
+            escrow.status = symbol_short!("release");
+            env.storage()
+                .persistent()
+                .set(&DataKey::Escrow(id), &escrow);
+
+            // batch_deposit collected amount + fee, so exactly that fee is
+            // forwarded to the admin here, whatever the current rate.
+            let fees: i128 = env
+                .storage()
+                .persistent()
+                .get(&DataKey::EscrowFee(id))
+                .unwrap_or(0);
+            env.storage().persistent().remove(&DataKey::EscrowFee(id));
+            env.storage().persistent().remove(&DataKey::EscrowToken(id));
+
+            token_client.transfer(&contract_address, &escrow.recipient, &escrow.amount);
+
+            // Left unset when the escrow could not be priced.
+            if let Some(settled) = Self::settlement_value(&env, &escrow) {
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::EscrowSettlement(id), &settled);
+            }
+
+            total_released = total_released
+                .checked_add(escrow.amount)
+                .ok_or(RemittanceError::InvalidAmount)?;
+            total_fees = total_fees
+                .checked_add(fees)
+                .ok_or(RemittanceError::InvalidAmount)?;
+        }
+
+        if total_fees > 0 {
+            let admin: Address = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Admin)
+                .ok_or(RemittanceError::Unauthorized)?;
+            token_client.transfer(&contract_address, &admin, &total_fees);
         }
 
-        let total_transfer = total_amount
-            .checked_add(total_fees)
-            .ok_or(RemittanceError::InvalidAmount)?;
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("batch_rel"),
+            0,
+            &caller,
+            total_fees,
+            symbol_short!("na"),
+            EventData::ValueChanged(symbol_short!("batch_rel"), total_released, total_fees),
+        );
+
+        Self::track_metric(&env, MetricType::Success, escrow_ids.len() as i128);
+
+        Ok(())
+    }
+
+    pub fn set_escrow_oracle(
+        env: Env,
+        sender: Address,
+        escrow_id: u64,
+        oracle_address: Option<Address>,
+    ) -> Result<(), RemittanceError> {
+        sender.require_auth();
+
+        let mut escrow: EscrowData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(RemittanceError::NotFound)?;
 
-        if total_transfer > 0 {
-            let token_client = soroban_sdk::token::Client::new(&env, &token_address);
-            token_client.transfer(&sender, &env.current_contract_address(), &total_transfer);
+        if escrow.sender != sender {
+            return Err(RemittanceError::Unauthorized);
+        }
+        // The pricing source is fixed once the escrow is funded.
+        if escrow.status != symbol_short!("pending") {
+            return Err(RemittanceError::InvalidStatus);
         }
 
+        escrow.oracle_address = oracle_address;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(escrow_id), &escrow);
+
         events::emit(
             &env,
             symbol_short!("hub"),
-            symbol_short!("batch_dep"),
-            0,
+            symbol_short!("esc_orc"),
+            escrow_id,
             &sender,
-            total_amount,
+            0,
             symbol_short!("na"),
-            EventData::AdminAction(symbol_short!("batch_dep")),
+            EventData::AdminAction(symbol_short!("esc_orc")),
         );
 
-        Self::track_metric(&env, MetricType::Volume, total_amount);
-        Self::track_metric(&env, MetricType::Fee, total_fees);
-
         Ok(())
     }
 
-    pub fn batch_release(
+    pub fn get_escrow(env: Env, escrow_id: u64) -> Option<EscrowData> {
+        env.storage().persistent().get(&DataKey::Escrow(escrow_id))
+    }
+
+    /// Converts the escrow amount into `to_asset` for settlement, using the
+    /// escrow's pinned oracle when set and the hub oracles otherwise.
+    pub fn convert_escrow_settlement(
         env: Env,
-        caller: Address,
-        escrow_ids: soroban_sdk::Vec<u64>,
-        token_address: Address,
-    ) -> Result<(), RemittanceError> {
-        caller.require_auth();
+        escrow_id: u64,
+        to_asset: String,
+    ) -> Result<oracle_mod::ConversionResult, RemittanceError> {
+        let escrow: EscrowData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(escrow_id))
+            .ok_or(RemittanceError::NotFound)?;
 
-        let max_batch = Self::get_max_batch_size(env.clone());
-        if escrow_ids.len() > max_batch {
-            return Err(RemittanceError::BatchTooLarge);
+        match escrow.oracle_address.clone() {
+            Some(oracle_address) => {
+                Self::convert_with_pinned(&env, &escrow, &oracle_address, to_asset)
+            }
+            None => Self::convert_currency(env, escrow.amount, escrow.asset.code, to_asset),
         }
+    }
 
-        let token_client = soroban_sdk::token::Client::new(&env, &token_address);
-        let contract_address = env.current_contract_address();
-        for id in escrow_ids.iter() {
-            let mut escrow: EscrowData = env
-                .storage()
-                .persistent()
-                .get(&DataKey::Escrow(id))
-                .ok_or(RemittanceError::NotFound)?;
+    /// USD value recorded for an escrow when `batch_release` settled it, or
+    /// `None` if it was never released or no rate was available at release.
+    pub fn get_escrow_settlement(env: Env, escrow_id: u64) -> Option<i128> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EscrowSettlement(escrow_id))
+    }
 
-            if escrow.recipient != caller && escrow.sender != caller {
-                return Err(RemittanceError::Unauthorized);
-            }
-            if escrow.status != symbol_short!("funded") {
-                return Err(RemittanceError::InvalidStatus);
-            }
+    fn convert_with_pinned(
+        env: &Env,
+        escrow: &EscrowData,
+        oracle_address: &Address,
+        to_asset: String,
+    ) -> Result<oracle_mod::ConversionResult, RemittanceError> {
+        let config: OracleConfig = env
+            .storage()
+            .persistent()
+            .get(&HubOracleKey::OracleConfig)
+            .ok_or(RemittanceError::OracleNotConfigured)?;
+        let max_staleness = Self::max_staleness_for(env, &config, &escrow.asset.code, &to_asset);
 
-            escrow.status = symbol_short!("release");
-            env.storage()
-                .persistent()
-                .set(&DataKey::Escrow(id), &escrow);
+        oracle_mod::convert_with_feed(
+            env,
+            &oracle_mod::feed_for(&Self::rate_decimals(env), oracle_address),
+            &escrow.asset.code,
+            &to_asset,
+            escrow.amount,
+            max_staleness,
+            None,
+        )
+        .map_err(|_| RemittanceError::ConversionFailed)
+    }
 
-            token_client.transfer(&contract_address, &escrow.recipient, &escrow.amount);
+    /// Prices a released escrow in USD, using its pinned oracle when it has
+    /// one. Returns `None` when no rate is available; the payout has already
+    /// happened, so a missing price never fails the release.
+    fn settlement_value(env: &Env, escrow: &EscrowData) -> Option<i128> {
+        let target = String::from_str(env, "USD");
+        if escrow.asset.code == target {
+            return Some(escrow.amount);
+        }
+        let conversion = match &escrow.oracle_address {
+            Some(oracle_address) => Self::convert_with_pinned(env, escrow, oracle_address, target),
+            None => {
+                Self::convert_within(env, escrow.amount, escrow.asset.code.clone(), target, None)
+            }
+        };
+        conversion
+            .ok()
+            .map(|conversion| conversion.converted_amount)
+    }
+
+    /// Fee, in basis points, added to invoices and charged on batch deposits.
+    pub fn set_remittance_fee(
+        env: Env,
+        admin: Address,
+        fee_bps: i128,
+    ) -> Result<(), RemittanceError> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+        if !(0..=10000).contains(&fee_bps) {
+            return Err(RemittanceError::InvalidAmount);
         }
 
+        env.storage()
+            .persistent()
+            .set(&DataKey::RemittanceFee, &fee_bps);
+
         events::emit(
             &env,
             symbol_short!("hub"),
-            symbol_short!("batch_rel"),
+            symbol_short!("fee_set"),
             0,
-            &caller,
-            escrow_ids.len() as i128,
+            &admin,
+            fee_bps,
             symbol_short!("na"),
-            EventData::AdminAction(symbol_short!("batch_rel")),
+            EventData::AdminAction(symbol_short!("fee_set")),
         );
 
-        Self::track_metric(&env, MetricType::Success, escrow_ids.len() as i128);
-
         Ok(())
     }
 
-    pub fn set_max_batch_size(
+    /// Registers the token contract that carries `currency`, used when
+    /// remittance funds in that currency are returned.
+    pub fn set_currency_token(
         env: Env,
-        caller: Address,
-        limit: u32,
+        admin: Address,
+        currency: Symbol,
+        token_address: Address,
     ) -> Result<(), RemittanceError> {
-        caller.require_auth();
+        admin.require_auth();
         let stored_admin: Address = env
             .storage()
             .persistent()
             .get(&DataKey::Admin)
             .ok_or(RemittanceError::Unauthorized)?;
-        if caller != stored_admin {
+        if admin != stored_admin {
             return Err(RemittanceError::Unauthorized);
         }
+
+        env.storage()
+            .persistent()
+            .set(&CurrencyKey::Token(currency.clone()), &token_address);
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("cur_tok"),
+            0,
+            &admin,
+            0,
+            currency,
+            EventData::AdminAction(symbol_short!("cur_tok")),
+        );
+
+        Ok(())
+    }
+
+    pub fn get_currency_token(env: Env, currency: Symbol) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&CurrencyKey::Token(currency))
+    }
+
+    pub fn get_remittance_fee(env: Env) -> i128 {
         env.storage()
             .persistent()
-            .set(&DataKey::MaxBatchSize, &limit);
+            .get(&DataKey::RemittanceFee)
+            .unwrap_or(DEFAULT_REMITTANCE_FEE_BPS)
+    }
+
+    /// Sets the maximum number of escrows accepted by the batch entry points.
+    /// Must be between 1 and `MAX_BATCH_LIMIT`.
+    pub fn set_batch_limit(env: Env, admin: Address, max: u32) -> Result<(), RemittanceError> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if admin != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+        if !(1..=MAX_BATCH_LIMIT).contains(&max) {
+            return Err(RemittanceError::InvalidAmount);
+        }
+
+        let old = Self::get_max_batch_size(env.clone());
+        env.storage().persistent().set(&DataKey::MaxBatchSize, &max);
+
+        events::emit(
+            &env,
+            symbol_short!("hub"),
+            symbol_short!("batch_lim"),
+            0,
+            &admin,
+            max as i128,
+            symbol_short!("na"),
+            EventData::ValueChanged(symbol_short!("batch_lim"), old as i128, max as i128),
+        );
+
         Ok(())
     }
 
+    pub fn set_max_batch_size(
+        env: Env,
+        caller: Address,
+        limit: u32,
+    ) -> Result<(), RemittanceError> {
+        Self::set_batch_limit(env, caller, limit)
+    }
+
     pub fn get_max_batch_size(env: Env) -> u32 {
         env.storage()
             .persistent()
             .get(&DataKey::MaxBatchSize)
-            .unwrap_or(10)
+            .unwrap_or(DEFAULT_BATCH_LIMIT)
     }
 
     fn convert_with_oracle(env: &Env, amount: i128, asset_code: &String) -> i128 {
@@ -1211,45 +3523,12 @@ impl RemittanceHubContract {
             return amount;
         }
 
-        let config: Option<OracleConfig> =
-            env.storage().persistent().get(&HubOracleKey::OracleConfig);
-
-        match config {
-            Some(cfg) => {
-                let cached: Option<CachedRate> = env.storage().persistent().get(
-                    &HubOracleKey::CachedRate(asset_code.clone(), target.clone()),
-                );
-
-                let result = oracle_mod::get_conversion_rate(
-                    env,
-                    &cfg.primary_oracle,
-                    asset_code,
-                    &target,
-                    amount,
-                    cfg.max_staleness,
-                    cached.clone(),
-                );
-                match result {
-                    Ok(conversion) => conversion.converted_amount,
-                    Err(_) => {
-                        let secondary_result = oracle_mod::get_conversion_rate(
-                            env,
-                            &cfg.secondary_oracle,
-                            asset_code,
-                            &target,
-                            amount,
-                            cfg.max_staleness,
-                            cached,
-                        );
-                        match secondary_result {
-                            Ok(conversion) => conversion.converted_amount,
-                            Err(_) => amount,
-                        }
-                    }
-                }
-            }
-            None => amount,
-        }
+        // Same primary -> secondary -> cache chain as `convert_currency`, so
+        // invoices also refresh the cached rate; the raw amount is kept when
+        // no rate is available at all.
+        Self::convert_within(env, amount, asset_code.clone(), target, None)
+            .map(|conversion| conversion.converted_amount)
+            .unwrap_or(amount)
     }
 
     fn enforce_rate_limit(
@@ -1270,8 +3549,62 @@ impl RemittanceHubContract {
         }
     }
 
+    /// Rejects a remittance from `from` sent within `SendCooldown` ledgers of
+    /// its previous one. Accounts exempted from rate limiting skip the check.
+    fn enforce_send_cooldown(env: &Env, from: &Address) -> Result<(), RemittanceError> {
+        let cooldown = Self::get_send_cooldown(env.clone());
+        if cooldown == 0 || rate_limit::is_exempt(env, from) {
+            return Ok(());
+        }
+
+        let key = SendLimitKey::LastSendLedger(from.clone());
+        let current = env.ledger().sequence();
+        if let Some(last) = env.storage().persistent().get::<_, u32>(&key) {
+            if current < last.saturating_add(cooldown) {
+                return Err(RemittanceError::RateLimitExceeded);
+            }
+        }
+        env.storage().persistent().set(&key, &current);
+        Ok(())
+    }
+
     // ── Rate Limit Configuration ─────────────────────────────────────
 
+    /// Sets how many ledgers a sender must wait between remittances; 0 disables
+    /// the cooldown.
+    pub fn set_send_cooldown(
+        env: Env,
+        caller: Address,
+        ledgers: u32,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if caller != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+        env.storage()
+            .persistent()
+            .set(&SendLimitKey::SendCooldown, &ledgers);
+        Ok(())
+    }
+
+    pub fn get_send_cooldown(env: Env) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&SendLimitKey::SendCooldown)
+            .unwrap_or(0)
+    }
+
+    pub fn get_last_send_ledger(env: Env, sender: Address) -> Option<u32> {
+        env.storage()
+            .persistent()
+            .get(&SendLimitKey::LastSendLedger(sender))
+    }
+
     pub fn set_rate_limit_config(
         env: Env,
         caller: Address,
@@ -1313,23 +3646,59 @@ impl RemittanceHubContract {
         if caller != stored_admin {
             return Err(RemittanceError::Unauthorized);
         }
-        rate_limit::set_global_config(&env, function_type, config);
+        rate_limit::set_global_config(&env, function_type, config);
+        Ok(())
+    }
+
+    pub fn get_global_rate_limit_config(
+        env: Env,
+        function_type: rate_limit::FunctionType,
+    ) -> Option<rate_limit::RateLimitConfig> {
+        rate_limit::get_global_config(&env, function_type)
+    }
+
+    pub fn set_rate_limit_exemption(
+        env: Env,
+        caller: Address,
+        address: Address,
+        exempt: bool,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if caller != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+        rate_limit::set_exemption(&env, &address, exempt);
+        Ok(())
+    }
+
+    pub fn is_rate_limit_exempt(env: Env, address: Address) -> bool {
+        rate_limit::is_exempt(&env, &address)
+    }
+
+    pub fn reset_rate_limit(
+        env: Env,
+        caller: Address,
+        account: Address,
+    ) -> Result<(), RemittanceError> {
+        caller.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .ok_or(RemittanceError::Unauthorized)?;
+        if caller != stored_admin {
+            return Err(RemittanceError::Unauthorized);
+        }
+        rate_limit::reset_user(&env, &account);
         Ok(())
     }
 
-    pub fn get_global_rate_limit_config(
-        env: Env,
-        function_type: rate_limit::FunctionType,
-    ) -> Option<rate_limit::RateLimitConfig> {
-        rate_limit::get_global_config(&env, function_type)
-    }
-
-    pub fn set_rate_limit_exemption(
-        env: Env,
-        caller: Address,
-        address: Address,
-        exempt: bool,
-    ) -> Result<(), RemittanceError> {
+    pub fn clear_all_rate_limits(env: Env, caller: Address) -> Result<(), RemittanceError> {
         caller.require_auth();
         let stored_admin: Address = env
             .storage()
@@ -1339,14 +3708,10 @@ impl RemittanceHubContract {
         if caller != stored_admin {
             return Err(RemittanceError::Unauthorized);
         }
-        rate_limit::set_exemption(&env, &address, exempt);
+        rate_limit::clear_all(&env);
         Ok(())
     }
 
-    pub fn is_rate_limit_exempt(env: Env, address: Address) -> bool {
-        rate_limit::is_exempt(&env, &address)
-    }
-
     // ── Upgradeable pattern ────────────────────────────────────────────
 
     /// Return the current contract version.
@@ -1646,6 +4011,72 @@ mod test {
         assert_eq!(invoice.status, InvoiceStatus::Overdue);
     }
 
+    #[test]
+    fn test_batch_mark_overdue_skips_ineligible() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let asset = Asset {
+            code: String::from_str(&env, "USDC"),
+            issuer: Address::generate(&env),
+        };
+        let desc = String::from_str(&env, "Payment");
+        let memo = String::from_str(&env, "Memo");
+
+        let past_due =
+            client.generate_invoice(&sender, &recipient, &1000, &asset, &2000, &desc, &0, &memo);
+        let not_due =
+            client.generate_invoice(&sender, &recipient, &1000, &asset, &5000, &desc, &0, &memo);
+        let cancelled =
+            client.generate_invoice(&sender, &recipient, &1000, &asset, &2000, &desc, &0, &memo);
+        let already =
+            client.generate_invoice(&sender, &recipient, &1000, &asset, &2000, &desc, &0, &memo);
+        client.cancel_invoice(&cancelled, &sender);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = 2500;
+        });
+        client.mark_invoice_overdue(&already);
+
+        let mut ids = soroban_sdk::Vec::new(&env);
+        for id in [past_due, not_due, cancelled, already, 999] {
+            ids.push_back(id);
+        }
+        let marked = client.batch_mark_overdue(&ids);
+
+        assert_eq!(marked.len(), 1);
+        assert_eq!(marked.get(0).unwrap(), past_due);
+        assert_eq!(
+            client.get_invoice(&past_due).unwrap().status,
+            InvoiceStatus::Overdue
+        );
+        assert_eq!(
+            client.get_invoice(&not_due).unwrap().status,
+            InvoiceStatus::Unpaid
+        );
+        assert_eq!(
+            client.get_invoice(&cancelled).unwrap().status,
+            InvoiceStatus::Cancelled
+        );
+
+        let mut too_many = soroban_sdk::Vec::new(&env);
+        for id in 0..(client.get_max_batch_size() as u64 + 1) {
+            too_many.push_back(id);
+        }
+        assert_eq!(
+            client.try_batch_mark_overdue(&too_many),
+            Err(Ok(RemittanceError::BatchTooLarge))
+        );
+    }
+
     #[test]
     fn test_cancel_invoice() {
         let env = Env::default();
@@ -2044,6 +4475,154 @@ mod test {
         assert_eq!(result.converted_amount, 900);
     }
 
+    #[test]
+    fn test_convert_currency_median_pricing() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let oracle_admin = Address::generate(&env);
+        let from = String::from_str(&env, "USDC");
+        let to = String::from_str(&env, "EUR");
+
+        let primary_id = env.register_contract(None, crate::oracle::MockOracleContract);
+        let primary_client = crate::oracle::MockOracleContractClient::new(&env, &primary_id);
+        primary_client.init_oracle(&oracle_admin);
+        primary_client.set_rate(&oracle_admin, &from, &to, &900000, &1000000);
+
+        let secondary_id = env.register_contract(None, crate::oracle::MockOracleContract);
+        let secondary_client = crate::oracle::MockOracleContractClient::new(&env, &secondary_id);
+        secondary_client.init_oracle(&oracle_admin);
+        secondary_client.set_rate(&oracle_admin, &from, &to, &910000, &1000000);
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.init_hub(&admin, &primary_id, &secondary_id, &3600);
+
+        // Off by default: the primary is trusted as-is
+        assert_eq!(
+            client.convert_currency(&1000, &from, &to).converted_amount,
+            900
+        );
+
+        client.set_median_pricing(&admin, &true, &200);
+        assert_eq!(
+            client.convert_currency(&1000, &from, &to).converted_amount,
+            905
+        );
+
+        secondary_client.set_rate(&oracle_admin, &from, &to, &990000, &1000000);
+        assert_eq!(
+            client.try_convert_currency(&1000, &from, &to),
+            Err(Ok(RemittanceError::RateDeviation))
+        );
+
+        assert_eq!(
+            client.try_set_median_pricing(&admin, &true, &0),
+            Err(Ok(RemittanceError::InvalidAmount))
+        );
+    }
+
+    #[test]
+    fn test_twap_over_rate_history() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let oracle = Address::generate(&env);
+        client.init_hub(&admin, &oracle, &oracle, &3600);
+
+        let from = String::from_str(&env, "USDC");
+        let to = String::from_str(&env, "EUR");
+
+        for (timestamp, rate) in [(1000u64, 900000i128), (1100, 920000), (1200, 940000)] {
+            env.ledger().with_mut(|li| {
+                li.timestamp = timestamp;
+            });
+            client.set_cached_rate(&admin, &from, &to, &rate, &1000000);
+        }
+        assert_eq!(client.get_rate_history(&from, &to).len(), 3);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1250;
+        });
+        // Window covers all three samples
+        assert_eq!(client.get_twap(&from, &to, &300), 920_000_000_000_000_000);
+        // Window covers only the last two
+        assert_eq!(client.get_twap(&from, &to, &150), 930_000_000_000_000_000);
+        // Nothing recorded in the last 10 seconds
+        assert_eq!(
+            client.try_get_twap(&from, &to, &10),
+            Err(Ok(RemittanceError::StaleRate))
+        );
+
+        // The history keeps only the most recent samples
+        for i in 0..20u64 {
+            env.ledger().with_mut(|li| {
+                li.timestamp = 2000 + i;
+            });
+            client.set_cached_rate(&admin, &from, &to, &1000000, &1000000);
+        }
+        let history = client.get_rate_history(&from, &to);
+        assert_eq!(history.len(), crate::oracle::RATE_HISTORY_SIZE);
+        assert_eq!(history.get(0).unwrap().2, 2004);
+    }
+
+    #[test]
+    fn test_invoice_conversion_uses_secondary_oracle() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let bogus_primary = Address::generate(&env);
+
+        let secondary_id = env.register_contract(None, crate::oracle::MockOracleContract);
+        let secondary_client = crate::oracle::MockOracleContractClient::new(&env, &secondary_id);
+        let oracle_admin = Address::generate(&env);
+        secondary_client.init_oracle(&oracle_admin);
+
+        let from = String::from_str(&env, "EUR");
+        let usd = String::from_str(&env, "USD");
+        secondary_client.set_rate(&oracle_admin, &from, &usd, &1100000, &1000000);
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.init_hub(&admin, &bogus_primary, &secondary_id, &3600);
+
+        let asset = Asset {
+            code: from.clone(),
+            issuer: Address::generate(&env),
+        };
+        let invoice_id = client.generate_invoice(
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &1000,
+            &asset,
+            &2000,
+            &String::from_str(&env, "Payment for services"),
+            &0,
+            &String::from_str(&env, ""),
+        );
+
+        let invoice = client.get_invoice(&invoice_id).unwrap();
+        assert_eq!(invoice.converted_amount, 1100);
+        assert_eq!(client.get_cached_rate(&from, &usd).unwrap().rate, 1100000);
+    }
+
     #[test]
     fn test_set_max_staleness() {
         let env = Env::default();
@@ -2055,39 +4634,209 @@ mod test {
         let admin = Address::generate(&env);
         let oracle = Address::generate(&env);
 
-        client.init_hub(&admin, &oracle, &oracle, &3600);
-        client.set_max_staleness(&admin, &7200);
+        client.init_hub(&admin, &oracle, &oracle, &3600);
+        client.set_max_staleness(&admin, &7200);
+
+        let config = client.get_oracle_config().unwrap();
+        assert_eq!(config.max_staleness, 7200);
+    }
+
+    #[test]
+    fn test_pair_staleness_override_rejects_rate_global_accepts() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let oracle_id = env.register_contract(None, crate::oracle::MockOracleContract);
+        let oracle_client = crate::oracle::MockOracleContractClient::new(&env, &oracle_id);
+        let oracle_admin = Address::generate(&env);
+        oracle_client.init_oracle(&oracle_admin);
+
+        let usdc = String::from_str(&env, "USDC");
+        let eur = String::from_str(&env, "EUR");
+        let btc = String::from_str(&env, "BTC");
+        oracle_client.set_rate(&oracle_admin, &usdc, &eur, &900, &1000);
+        oracle_client.set_rate(&oracle_admin, &btc, &usdc, &60000, &1);
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.init_hub(&admin, &oracle_id, &oracle_id, &3600);
+        client.set_pair_staleness(&admin, &btc, &usdc, &60);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1300;
+        });
+
+        let result = client.convert_currency(&1000, &usdc, &eur);
+        assert_eq!(result.converted_amount, 900);
+
+        let result = client.try_convert_currency(&1, &btc, &usdc);
+        assert_eq!(result, Err(Ok(RemittanceError::ConversionFailed)));
+    }
+
+    #[test]
+    fn test_convert_currency_fresh_rejects_rate_older_than_max_age() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let oracle_id = env.register_contract(None, crate::oracle::MockOracleContract);
+        let oracle_client = crate::oracle::MockOracleContractClient::new(&env, &oracle_id);
+        let oracle_admin = Address::generate(&env);
+        oracle_client.init_oracle(&oracle_admin);
+
+        let usdc = String::from_str(&env, "USDC");
+        let eur = String::from_str(&env, "EUR");
+        oracle_client.set_rate(&oracle_admin, &usdc, &eur, &900, &1000);
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.init_hub(&admin, &oracle_id, &oracle_id, &3600);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1600;
+        });
+
+        let result = client.try_convert_currency_fresh(&1000, &usdc, &eur, &300);
+        assert_eq!(result, Err(Ok(RemittanceError::StaleRate)));
+
+        let result = client.convert_currency_fresh(&1000, &usdc, &eur, &900);
+        assert_eq!(result.converted_amount, 900);
+
+        let result = client.convert_currency(&1000, &usdc, &eur);
+        assert_eq!(result.converted_amount, 900);
+    }
+
+    #[test]
+    fn test_get_conversion_rate() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let oracle_id = env.register_contract(None, crate::oracle::MockOracleContract);
+        let oracle_client = crate::oracle::MockOracleContractClient::new(&env, &oracle_id);
+        let oracle_admin = Address::generate(&env);
+        oracle_client.init_oracle(&oracle_admin);
+
+        let from = String::from_str(&env, "USDC");
+        let to = String::from_str(&env, "EUR");
+        oracle_client.set_rate(&oracle_admin, &from, &to, &850000, &1000000);
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.init_hub(&admin, &oracle_id, &oracle_id, &3600);
+
+        let result = client.get_conversion_rate(&from, &to, &10000);
+        assert_eq!(result.converted_amount, 8500);
+        assert_eq!(result.rate, 850000);
+    }
+
+    #[test]
+    fn test_mixed_precision_oracles_convert_consistently() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let from = String::from_str(&env, "USDC");
+        let to = String::from_str(&env, "EUR");
+        let oracle_admin = Address::generate(&env);
+
+        // Both feeds quote 0.85, one with 8 implied decimals, one with 6.
+        let oracle_8 = env.register_contract(None, crate::oracle::MockOracleContract);
+        let oracle_8_client = crate::oracle::MockOracleContractClient::new(&env, &oracle_8);
+        oracle_8_client.init_oracle(&oracle_admin);
+        oracle_8_client.set_rate(&oracle_admin, &from, &to, &85_000_000, &1);
+
+        let oracle_6 = env.register_contract(None, crate::oracle::MockOracleContract);
+        let oracle_6_client = crate::oracle::MockOracleContractClient::new(&env, &oracle_6);
+        oracle_6_client.init_oracle(&oracle_admin);
+        oracle_6_client.set_rate(&oracle_admin, &from, &to, &850_000, &1);
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        client.init_hub(&admin, &oracle_8, &oracle_6, &3600);
+
+        client.set_oracle_decimals(&admin, &oracle_8, &Some(8));
+        client.set_oracle_decimals(&admin, &oracle_6, &Some(6));
+        assert_eq!(client.get_oracle_decimals(&oracle_8), Some(8));
+
+        let via_8 = client.convert_currency(&10000, &from, &to);
+
+        client.set_oracle(&admin, &oracle_6, &oracle_8);
+        let via_6 = client.convert_currency(&10000, &from, &to);
+
+        assert_eq!(via_8.converted_amount, 8500);
+        assert_eq!(via_6.converted_amount, 8500);
+        assert_eq!(via_8.rate, via_6.rate);
+        assert_eq!(via_8.denominator, 1_000_000_000_000_000_000);
 
-        let config = client.get_oracle_config().unwrap();
-        assert_eq!(config.max_staleness, 7200);
+        let result = client.try_set_oracle_decimals(&admin, &oracle_8, &Some(19));
+        assert_eq!(result, Err(Ok(RemittanceError::InvalidRate)));
     }
 
     #[test]
-    fn test_get_conversion_rate() {
+    fn test_aggregated_rate_rejects_outlier_and_weights_trusted_feeds() {
         let env = Env::default();
         env.mock_all_auths();
         env.ledger().with_mut(|li| {
             li.timestamp = 1000;
         });
 
-        let oracle_id = env.register_contract(None, crate::oracle::MockOracleContract);
-        let oracle_client = crate::oracle::MockOracleContractClient::new(&env, &oracle_id);
-        let oracle_admin = Address::generate(&env);
-        oracle_client.init_oracle(&oracle_admin);
-
         let from = String::from_str(&env, "USDC");
         let to = String::from_str(&env, "EUR");
-        oracle_client.set_rate(&oracle_admin, &from, &to, &850000, &1000000);
+        let oracle_admin = Address::generate(&env);
+
+        let mut feeds = soroban_sdk::Vec::new(&env);
+        for rate in [850_000i128, 860_000, 855_000, 2_000_000] {
+            let oracle = env.register_contract(None, crate::oracle::MockOracleContract);
+            let oracle_client = crate::oracle::MockOracleContractClient::new(&env, &oracle);
+            oracle_client.init_oracle(&oracle_admin);
+            oracle_client.set_rate(&oracle_admin, &from, &to, &rate, &1_000_000);
+            feeds.push_back(oracle);
+        }
 
         let contract_id = env.register_contract(None, RemittanceHubContract);
         let client = RemittanceHubContractClient::new(&env, &contract_id);
-
         let admin = Address::generate(&env);
-        client.init_hub(&admin, &oracle_id, &oracle_id, &3600);
+        client.init_hub(
+            &admin,
+            &feeds.get(0).unwrap(),
+            &feeds.get(1).unwrap(),
+            &3600,
+        );
 
-        let result = client.get_conversion_rate(&from, &to, &10000);
-        assert_eq!(result.converted_amount, 8500);
-        assert_eq!(result.rate, 850000);
+        let result = client.try_get_aggregated_rate(&from, &to);
+        assert_eq!(result, Err(Ok(RemittanceError::OracleNotConfigured)));
+
+        // The 2.0 outlier carries the most weight but is still excluded.
+        client.set_oracle_weight(&admin, &feeds.get(0).unwrap(), &Some(1));
+        client.set_oracle_weight(&admin, &feeds.get(1).unwrap(), &Some(5));
+        client.set_oracle_weight(&admin, &feeds.get(2).unwrap(), &Some(1));
+        client.set_oracle_weight(&admin, &feeds.get(3).unwrap(), &Some(20));
+        assert_eq!(client.get_oracle_weight(&feeds.get(1).unwrap()), Some(5));
+
+        let rate = client.get_aggregated_rate(&from, &to);
+        assert_eq!(rate, 860_000_000_000_000_000);
+
+        // Without the heavily trusted feed the midpoint of the cluster wins.
+        client.set_oracle_weight(&admin, &feeds.get(1).unwrap(), &Some(1));
+        let rate = client.get_aggregated_rate(&from, &to);
+        assert_eq!(rate, 855_000_000_000_000_000);
     }
 
     #[test]
@@ -2313,6 +5062,122 @@ mod test {
         assert_eq!(flag_data.status, AmlStatus::Flagged);
     }
 
+    #[test]
+    fn test_send_remittance_aml_amount_threshold() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+            li.sequence_number = 10;
+        });
+
+        let aml_oracle_id = env.register_contract(None, MockAmlOracleContract);
+        let aml_oracle_client = MockAmlOracleContractClient::new(&env, &aml_oracle_id);
+        let admin = Address::generate(&env);
+        aml_oracle_client.initialize(&admin);
+
+        let from = Address::generate(&env);
+        let to = Address::generate(&env);
+        aml_oracle_client.set_risk_score(&admin, &from, &20);
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        client.init_hub(
+            &admin,
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &3600,
+        );
+        client.configure_aml(&admin, &aml_oracle_id, &50);
+
+        assert_eq!(
+            client.try_set_aml_amount_threshold(&admin, &-1),
+            Err(Ok(RemittanceError::InvalidAmount))
+        );
+        client.set_aml_amount_threshold(&admin, &10_000);
+        assert_eq!(client.get_aml_amount_threshold(), 10_000);
+
+        let small = client.send_remittance(&from, &to, &5000, &symbol_short!("USD"));
+        assert_eq!(
+            client.get_remittance(&small).unwrap().status,
+            symbol_short!("pending")
+        );
+
+        env.ledger().with_mut(|li| li.sequence_number = 11);
+        let large = client.send_remittance(&from, &to, &50_000, &symbol_short!("USD"));
+        assert_eq!(
+            client.get_remittance(&large).unwrap().status,
+            symbol_short!("review")
+        );
+        assert_eq!(
+            client.get_aml_flag(&large).unwrap().status,
+            AmlStatus::Reviewing
+        );
+    }
+
+    #[test]
+    fn test_aml_exempt_sender_skips_screening() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+            li.sequence_number = 10;
+        });
+
+        let aml_oracle_id = env.register_contract(None, MockAmlOracleContract);
+        let aml_oracle_client = MockAmlOracleContractClient::new(&env, &aml_oracle_id);
+        let admin = Address::generate(&env);
+        aml_oracle_client.initialize(&admin);
+
+        let partner = Address::generate(&env);
+        let other = Address::generate(&env);
+        let to = Address::generate(&env);
+        aml_oracle_client.set_risk_score(&admin, &partner, &80);
+        aml_oracle_client.set_risk_score(&admin, &other, &80);
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        client.init_hub(
+            &admin,
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &3600,
+        );
+        client.configure_aml(&admin, &aml_oracle_id, &50);
+
+        assert_eq!(
+            client.try_add_aml_exempt(&other, &other),
+            Err(Ok(RemittanceError::Unauthorized))
+        );
+        client.add_aml_exempt(&admin, &partner);
+        assert!(client.is_aml_exempt(&partner));
+
+        let exempt_id = client.send_remittance(&partner, &to, &5000, &symbol_short!("USD"));
+        assert_eq!(
+            client.get_remittance(&exempt_id).unwrap().status,
+            symbol_short!("pending")
+        );
+        assert!(client.get_aml_flag(&exempt_id).is_none());
+
+        env.ledger().with_mut(|li| li.sequence_number = 11);
+        let screened_id = client.send_remittance(&other, &to, &5000, &symbol_short!("USD"));
+        assert_eq!(
+            client.get_remittance(&screened_id).unwrap().status,
+            symbol_short!("flagged")
+        );
+
+        client.remove_aml_exempt(&admin, &partner);
+        assert!(!client.is_aml_exempt(&partner));
+        env.ledger().with_mut(|li| li.sequence_number = 12);
+        let rescreened_id = client.send_remittance(&partner, &to, &5000, &symbol_short!("USD"));
+        assert_eq!(
+            client.get_remittance(&rescreened_id).unwrap().status,
+            symbol_short!("flagged")
+        );
+    }
+
     #[test]
     fn test_send_remittance_aml_oracle_failure() {
         let env = Env::default();
@@ -2416,43 +5281,327 @@ mod test {
         let remittance = client.get_remittance(&remittance_id).unwrap();
         assert_eq!(remittance.status, symbol_short!("pending"));
 
-        client.complete_remittance(&remittance_id, &from);
+        client.complete_remittance(&remittance_id, &from);
+
+        let remittance = client.get_remittance(&remittance_id).unwrap();
+        assert_eq!(remittance.status, symbol_short!("complete"));
+    }
+
+    #[test]
+    fn test_export_aml_snapshot_returns_flags_in_range() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let aml_oracle_id = env.register_contract(None, MockAmlOracleContract);
+        let aml_oracle_client = MockAmlOracleContractClient::new(&env, &aml_oracle_id);
+        let admin = Address::generate(&env);
+        aml_oracle_client.initialize(&admin);
+
+        let risky = Address::generate(&env);
+        let clean = Address::generate(&env);
+        let to = Address::generate(&env);
+        aml_oracle_client.set_risk_score(&admin, &risky, &80);
+
+        let primary = Address::generate(&env);
+        let secondary = Address::generate(&env);
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        client.init_hub(&admin, &primary, &secondary, &3600);
+        client.configure_aml(&admin, &aml_oracle_id, &50);
+
+        let mut ids = soroban_sdk::Vec::new(&env);
+        let sends = [
+            (10u32, &risky, 100i128),
+            (11, &clean, 200),
+            (12, &risky, 300),
+            (20, &risky, 400),
+        ];
+        for (sequence, from, amount) in sends {
+            env.ledger().with_mut(|li| {
+                li.sequence_number = sequence;
+            });
+            ids.push_back(client.send_remittance(from, &to, &amount, &symbol_short!("USD")));
+        }
+        assert_eq!(ids.get(0), Some(10));
+
+        let snapshot = client.export_aml_snapshot(&10, &15);
+        assert_eq!(snapshot.len(), 2);
+        assert_eq!(snapshot.get(0).unwrap().amount, 100);
+        assert_eq!(snapshot.get(1).unwrap().amount, 300);
+
+        let empty = client.export_aml_snapshot(&15, &10);
+        assert_eq!(empty.len(), 0);
+    }
+
+    #[test]
+    fn test_complete_after_clear_blocked_during_cooldown() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let aml_oracle_id = env.register_contract(None, MockAmlOracleContract);
+        let aml_oracle_client = MockAmlOracleContractClient::new(&env, &aml_oracle_id);
+        let admin = Address::generate(&env);
+        aml_oracle_client.initialize(&admin);
+
+        let from = Address::generate(&env);
+        let to = Address::generate(&env);
+        aml_oracle_client.set_risk_score(&admin, &from, &80);
+
+        let primary = Address::generate(&env);
+        let secondary = Address::generate(&env);
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        client.init_hub(&admin, &primary, &secondary, &3600);
+        client.configure_aml(&admin, &aml_oracle_id, &50);
+        client.set_aml_clear_cooldown(&admin, &600);
+
+        let remittance_id = client.send_remittance(&from, &to, &5000, &symbol_short!("USD"));
+        client.clear_aml_flag(&admin, &remittance_id);
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1599;
+        });
+        let result = client.try_complete_remittance(&remittance_id, &from);
+        assert_eq!(result, Err(Ok(RemittanceError::CooldownActive)));
+
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1600;
+        });
+        client.complete_remittance(&remittance_id, &from);
+
+        let remittance = client.get_remittance(&remittance_id).unwrap();
+        assert_eq!(remittance.status, symbol_short!("complete"));
+    }
+
+    #[test]
+    fn test_clear_aml_flag_unauthorized() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let aml_oracle_id = env.register_contract(None, MockAmlOracleContract);
+        let aml_oracle_client = MockAmlOracleContractClient::new(&env, &aml_oracle_id);
+        let admin = Address::generate(&env);
+        aml_oracle_client.initialize(&admin);
+
+        let from = Address::generate(&env);
+        let to = Address::generate(&env);
+        aml_oracle_client.set_risk_score(&admin, &from, &80);
+
+        let primary = Address::generate(&env);
+        let secondary = Address::generate(&env);
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        client.init_hub(&admin, &primary, &secondary, &3600);
+        client.configure_aml(&admin, &aml_oracle_id, &50);
+
+        let remittance_id = client.send_remittance(&from, &to, &5000, &symbol_short!("USD"));
+
+        let other = Address::generate(&env);
+        let result = client.try_clear_aml_flag(&other, &remittance_id);
+        assert_eq!(result, Err(Ok(RemittanceError::Unauthorized)));
+    }
+
+    #[test]
+    fn test_compliance_officer_clears_flag_but_not_config() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let aml_oracle_id = env.register_contract(None, MockAmlOracleContract);
+        let aml_oracle_client = MockAmlOracleContractClient::new(&env, &aml_oracle_id);
+        let admin = Address::generate(&env);
+        aml_oracle_client.initialize(&admin);
+
+        let from = Address::generate(&env);
+        let to = Address::generate(&env);
+        aml_oracle_client.set_risk_score(&admin, &from, &80);
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+        client.init_hub(
+            &admin,
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &3600,
+        );
+        client.configure_aml(&admin, &aml_oracle_id, &50);
+
+        let officer = Address::generate(&env);
+        assert_eq!(
+            client.try_set_compliance_officer(&officer, &Some(officer.clone())),
+            Err(Ok(RemittanceError::Unauthorized))
+        );
+        client.set_compliance_officer(&admin, &Some(officer.clone()));
+        assert_eq!(client.get_compliance_officer(), Some(officer.clone()));
+
+        let remittance_id = client.send_remittance(&from, &to, &5000, &symbol_short!("USD"));
+        client.clear_aml_flag(&officer, &remittance_id);
+        assert_eq!(
+            client.get_aml_flag(&remittance_id).unwrap().status,
+            AmlStatus::Cleared
+        );
+
+        assert_eq!(
+            client.try_set_max_remittance_amount(&officer, &Some(1000)),
+            Err(Ok(RemittanceError::Unauthorized))
+        );
+        assert_eq!(
+            client.try_set_aml_threshold(&officer, &10),
+            Err(Ok(RemittanceError::Unauthorized))
+        );
+    }
+
+    #[test]
+    fn test_reject_remittance_refunds_linked_escrow() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let aml_oracle_id = env.register_contract(None, MockAmlOracleContract);
+        let aml_oracle_client = MockAmlOracleContractClient::new(&env, &aml_oracle_id);
+        let admin = Address::generate(&env);
+        aml_oracle_client.initialize(&admin);
+
+        let from = Address::generate(&env);
+        let to = Address::generate(&env);
+        aml_oracle_client.set_risk_score(&admin, &from, &80);
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+        client.init_hub(
+            &admin,
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &3600,
+        );
+        client.configure_aml(&admin, &aml_oracle_id, &50);
+
+        let token_id = env.register_stellar_asset_contract(admin.clone());
+        soroban_sdk::token::StellarAssetClient::new(&env, &token_id).mint(&from, &10000);
+        let token = soroban_sdk::token::Client::new(&env, &token_id);
+
+        let request = |code: &str, amount: i128| EscrowRequest {
+            recipient: to.clone(),
+            amount,
+            asset: Asset {
+                code: String::from_str(&env, code),
+                issuer: admin.clone(),
+            },
+            expiration_timestamp: 2000,
+            client_ref: None,
+        };
+        let mut requests = soroban_sdk::Vec::new(&env);
+        requests.push_back(request("USD", 1000));
+        requests.push_back(request("XLM", 1000));
+        requests.push_back(request("USD", 10));
+        let ids = client.batch_create_escrows(&from, &requests);
+        let escrow_id = ids.get(0).unwrap();
+        let funded = soroban_sdk::vec![&env, escrow_id];
+        client.batch_deposit(&from, &funded, &token_id);
+        assert_eq!(token.balance(&from), 10000 - 1025);
+
+        let remittance_id = client.send_remittance(&from, &to, &1000, &symbol_short!("USD"));
+        // The escrow must hold the remittance's currency and amount.
+        for mismatched in [ids.get(1).unwrap(), ids.get(2).unwrap()] {
+            assert_eq!(
+                client.try_link_remittance_escrow(&from, &remittance_id, &mismatched),
+                Err(Ok(RemittanceError::EscrowMismatch))
+            );
+        }
+        client.link_remittance_escrow(&from, &remittance_id, &escrow_id);
+        assert!(client.is_sender_flagged(&from));
+        assert_eq!(
+            client.try_link_remittance_escrow(&from, &remittance_id, &escrow_id),
+            Err(Ok(RemittanceError::InvalidStatus))
+        );
+
+        // The refund comes back in the funding token, fee included.
+        client.reject_remittance(&admin, &remittance_id);
 
         let remittance = client.get_remittance(&remittance_id).unwrap();
-        assert_eq!(remittance.status, symbol_short!("complete"));
+        assert_eq!(remittance.status, symbol_short!("rejected"));
+        assert_eq!(
+            client.get_aml_flag(&remittance_id).unwrap().status,
+            AmlStatus::Rejected
+        );
+        assert_eq!(
+            client.get_escrow(&escrow_id).unwrap().status,
+            symbol_short!("refunded")
+        );
+        assert_eq!(token.balance(&from), 10000);
+        assert_eq!(token.balance(&contract_id), 0);
+        assert!(!client.is_sender_flagged(&from));
+
+        assert_eq!(
+            client.try_clear_aml_flag(&admin, &remittance_id),
+            Err(Ok(RemittanceError::InvalidStatus))
+        );
+        assert_eq!(
+            client.try_reject_remittance(&admin, &remittance_id),
+            Err(Ok(RemittanceError::InvalidStatus))
+        );
+        assert_eq!(
+            client.try_complete_remittance(&remittance_id, &from),
+            Err(Ok(RemittanceError::InvalidStatus))
+        );
     }
 
     #[test]
-    fn test_clear_aml_flag_unauthorized() {
+    fn test_link_remittance_escrow_rejects_cancelled_remittance() {
         let env = Env::default();
         env.mock_all_auths();
-        env.ledger().with_mut(|li| {
-            li.timestamp = 1000;
-        });
 
-        let aml_oracle_id = env.register_contract(None, MockAmlOracleContract);
-        let aml_oracle_client = MockAmlOracleContractClient::new(&env, &aml_oracle_id);
         let admin = Address::generate(&env);
-        aml_oracle_client.initialize(&admin);
-
         let from = Address::generate(&env);
         let to = Address::generate(&env);
-        aml_oracle_client.set_risk_score(&admin, &from, &80);
-
-        let primary = Address::generate(&env);
-        let secondary = Address::generate(&env);
-
         let contract_id = env.register_contract(None, RemittanceHubContract);
         let client = RemittanceHubContractClient::new(&env, &contract_id);
+        client.init_hub(
+            &admin,
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &3600,
+        );
 
-        client.init_hub(&admin, &primary, &secondary, &3600);
-        client.configure_aml(&admin, &aml_oracle_id, &50);
-
-        let remittance_id = client.send_remittance(&from, &to, &5000, &symbol_short!("USD"));
-
-        let other = Address::generate(&env);
-        let result = client.try_clear_aml_flag(&other, &remittance_id);
-        assert_eq!(result, Err(Ok(RemittanceError::Unauthorized)));
+        let mut requests = soroban_sdk::Vec::new(&env);
+        requests.push_back(EscrowRequest {
+            recipient: to.clone(),
+            amount: 1000,
+            asset: Asset {
+                code: String::from_str(&env, "USDC"),
+                issuer: admin.clone(),
+            },
+            expiration_timestamp: 2000,
+            client_ref: None,
+        });
+        let escrow_id = client
+            .batch_create_escrows(&from, &requests)
+            .get(0)
+            .unwrap();
+
+        let remittance_id = client.send_remittance(&from, &to, &1000, &symbol_short!("USD"));
+        client.cancel_remittance(&remittance_id, &from);
+        assert_eq!(
+            client.try_link_remittance_escrow(&from, &remittance_id, &escrow_id),
+            Err(Ok(RemittanceError::InvalidStatus))
+        );
+        assert_eq!(client.get_remittance_escrow(&remittance_id), None);
     }
 
     #[test]
@@ -2568,12 +5717,14 @@ mod test {
             amount: 1000,
             asset: asset.clone(),
             expiration_timestamp: 2000,
+            client_ref: None,
         };
         let req2 = EscrowRequest {
             recipient: recipient2,
             amount: 2000,
             asset: asset.clone(),
             expiration_timestamp: 3000,
+            client_ref: None,
         };
 
         let mut requests = soroban_sdk::Vec::new(&env);
@@ -2584,6 +5735,190 @@ mod test {
         assert_eq!(ids.len(), 2);
     }
 
+    #[test]
+    fn test_escrow_settlement_uses_pinned_oracle() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let usdc = String::from_str(&env, "USDC");
+        let eur = String::from_str(&env, "EUR");
+        let oracle_admin = Address::generate(&env);
+
+        let global_oracle = env.register_contract(None, crate::oracle::MockOracleContract);
+        let global_client = crate::oracle::MockOracleContractClient::new(&env, &global_oracle);
+        global_client.init_oracle(&oracle_admin);
+        global_client.set_rate(&oracle_admin, &usdc, &eur, &900, &1000);
+
+        let pinned_oracle = env.register_contract(None, crate::oracle::MockOracleContract);
+        let pinned_client = crate::oracle::MockOracleContractClient::new(&env, &pinned_oracle);
+        pinned_client.init_oracle(&oracle_admin);
+        pinned_client.set_rate(&oracle_admin, &usdc, &eur, &950, &1000);
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.init_hub(&admin, &global_oracle, &global_oracle, &3600);
+
+        let sender = Address::generate(&env);
+        let asset = Asset {
+            code: usdc.clone(),
+            issuer: Address::generate(&env),
+        };
+        let mut requests = soroban_sdk::Vec::new(&env);
+        for _ in 0..2 {
+            requests.push_back(EscrowRequest {
+                recipient: Address::generate(&env),
+                amount: 1000,
+                asset: asset.clone(),
+                expiration_timestamp: 5000,
+                client_ref: None,
+            });
+        }
+        let ids = client.batch_create_escrows(&sender, &requests);
+        let pinned_id = ids.get(0).unwrap();
+        let global_id = ids.get(1).unwrap();
+
+        client.set_escrow_oracle(&sender, &pinned_id, &Some(pinned_oracle.clone()));
+        assert_eq!(
+            client.get_escrow(&pinned_id).unwrap().oracle_address,
+            Some(pinned_oracle)
+        );
+
+        let pinned = client.convert_escrow_settlement(&pinned_id, &eur);
+        assert_eq!(pinned.converted_amount, 950);
+
+        let global = client.convert_escrow_settlement(&global_id, &eur);
+        assert_eq!(global.converted_amount, 900);
+    }
+
+    #[test]
+    fn test_batch_release_settles_with_pinned_oracle() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let usdc = String::from_str(&env, "USDC");
+        let usd = String::from_str(&env, "USD");
+        let oracle_admin = Address::generate(&env);
+
+        let global_oracle = env.register_contract(None, crate::oracle::MockOracleContract);
+        let global_client = crate::oracle::MockOracleContractClient::new(&env, &global_oracle);
+        global_client.init_oracle(&oracle_admin);
+        global_client.set_rate(&oracle_admin, &usdc, &usd, &990, &1000);
+
+        let pinned_oracle = env.register_contract(None, crate::oracle::MockOracleContract);
+        let pinned_client = crate::oracle::MockOracleContractClient::new(&env, &pinned_oracle);
+        pinned_client.init_oracle(&oracle_admin);
+        pinned_client.set_rate(&oracle_admin, &usdc, &usd, &1000, &1000);
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.init_hub(&admin, &global_oracle, &global_oracle, &3600);
+
+        let token_id = env.register_stellar_asset_contract(Address::generate(&env));
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token_id).mint(&sender, &10000);
+
+        let asset = Asset {
+            code: usdc.clone(),
+            issuer: Address::generate(&env),
+        };
+        let mut requests = soroban_sdk::Vec::new(&env);
+        for _ in 0..2 {
+            requests.push_back(EscrowRequest {
+                recipient: recipient.clone(),
+                amount: 1000,
+                asset: asset.clone(),
+                expiration_timestamp: 5000,
+                client_ref: None,
+            });
+        }
+        let ids = client.batch_create_escrows(&sender, &requests);
+        let pinned_id = ids.get(0).unwrap();
+        let global_id = ids.get(1).unwrap();
+
+        client.set_escrow_oracle(&sender, &pinned_id, &Some(pinned_oracle.clone()));
+        client.batch_deposit(&sender, &ids, &token_id);
+
+        // Funded escrows keep the pricing source they were funded with.
+        assert_eq!(
+            client.try_set_escrow_oracle(&sender, &pinned_id, &None),
+            Err(Ok(RemittanceError::InvalidStatus))
+        );
+        assert_eq!(
+            client.try_set_escrow_oracle(&sender, &global_id, &Some(pinned_oracle)),
+            Err(Ok(RemittanceError::InvalidStatus))
+        );
+
+        client.batch_release(&recipient, &ids, &token_id);
+        assert_eq!(client.get_escrow_settlement(&pinned_id), Some(1000));
+        assert_eq!(client.get_escrow_settlement(&global_id), Some(990));
+    }
+
+    #[test]
+    fn test_batch_release_pays_out_when_pinned_oracle_unavailable() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let usdc = String::from_str(&env, "USDC");
+        let usd = String::from_str(&env, "USD");
+        let oracle_admin = Address::generate(&env);
+
+        let global_oracle = env.register_contract(None, crate::oracle::MockOracleContract);
+        let global_client = crate::oracle::MockOracleContractClient::new(&env, &global_oracle);
+        global_client.init_oracle(&oracle_admin);
+        global_client.set_rate(&oracle_admin, &usdc, &usd, &990, &1000);
+
+        // Pinned oracle without a USDC/USD rate.
+        let pinned_oracle = env.register_contract(None, crate::oracle::MockOracleContract);
+        let pinned_client = crate::oracle::MockOracleContractClient::new(&env, &pinned_oracle);
+        pinned_client.init_oracle(&oracle_admin);
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        client.init_hub(&admin, &global_oracle, &global_oracle, &3600);
+
+        let token_id = env.register_stellar_asset_contract(Address::generate(&env));
+        let token = soroban_sdk::token::Client::new(&env, &token_id);
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token_id).mint(&sender, &10000);
+
+        let mut requests = soroban_sdk::Vec::new(&env);
+        requests.push_back(EscrowRequest {
+            recipient: recipient.clone(),
+            amount: 1000,
+            asset: Asset {
+                code: usdc,
+                issuer: Address::generate(&env),
+            },
+            expiration_timestamp: 5000,
+            client_ref: None,
+        });
+        let ids = client.batch_create_escrows(&sender, &requests);
+        let escrow_id = ids.get(0).unwrap();
+        client.set_escrow_oracle(&sender, &escrow_id, &Some(pinned_oracle));
+        client.batch_deposit(&sender, &ids, &token_id);
+
+        client.batch_release(&recipient, &ids, &token_id);
+        assert_eq!(token.balance(&recipient), 1000);
+        assert_eq!(client.get_escrow_settlement(&escrow_id), None);
+    }
+
     #[test]
     fn test_batch_create_escrows_too_large() {
         let env = Env::default();
@@ -2606,9 +5941,128 @@ mod test {
                 amount: 100,
                 asset: asset.clone(),
                 expiration_timestamp: 2000,
+                client_ref: None,
+            });
+        }
+
+        let result = client.try_batch_create_escrows(&sender, &requests);
+        assert_eq!(result, Err(Ok(RemittanceError::BatchTooLarge)));
+    }
+
+    #[test]
+    fn test_batch_create_duplicate_client_ref() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let asset = Asset {
+            code: String::from_str(&env, "USDC"),
+            issuer: Address::generate(&env),
+        };
+
+        let mut requests = soroban_sdk::Vec::new(&env);
+        for client_ref in ["inv-1", "inv-1"] {
+            requests.push_back(EscrowRequest {
+                recipient: recipient.clone(),
+                amount: 100,
+                asset: asset.clone(),
+                expiration_timestamp: 2000,
+                client_ref: Some(String::from_str(&env, client_ref)),
+            });
+        }
+
+        let result = client.try_batch_create_escrows(&sender, &requests);
+        assert_eq!(result, Err(Ok(RemittanceError::DuplicateEscrowId)));
+        // Nothing from the batch was persisted
+        assert!(client.get_escrow(&1).is_none());
+        assert_eq!(
+            client.get_escrow_by_ref(&sender, &String::from_str(&env, "inv-1")),
+            None
+        );
+
+        // Distinct refs are accepted and can be looked up afterwards
+        requests.set(
+            1,
+            EscrowRequest {
+                recipient: recipient.clone(),
+                amount: 100,
+                asset: asset.clone(),
+                expiration_timestamp: 2000,
+                client_ref: Some(String::from_str(&env, "inv-2")),
+            },
+        );
+        let ids = client.batch_create_escrows(&sender, &requests);
+        assert_eq!(
+            client.get_escrow_by_ref(&sender, &String::from_str(&env, "inv-2")),
+            Some(ids.get(1).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_set_batch_limit() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let oracle = Address::generate(&env);
+        client.init_hub(&admin, &oracle, &oracle, &3600);
+
+        assert_eq!(
+            client.try_set_batch_limit(&admin, &0),
+            Err(Ok(RemittanceError::InvalidAmount))
+        );
+        assert_eq!(
+            client.try_set_batch_limit(&admin, &101),
+            Err(Ok(RemittanceError::InvalidAmount))
+        );
+        assert_eq!(
+            client.try_set_batch_limit(&Address::generate(&env), &25),
+            Err(Ok(RemittanceError::Unauthorized))
+        );
+
+        client.set_batch_limit(&admin, &25);
+        assert_eq!(client.get_max_batch_size(), 25);
+
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        let asset = Asset {
+            code: String::from_str(&env, "USDC"),
+            issuer: Address::generate(&env),
+        };
+
+        let mut requests = soroban_sdk::Vec::new(&env);
+        for _ in 0..25 {
+            requests.push_back(EscrowRequest {
+                recipient: recipient.clone(),
+                amount: 100,
+                asset: asset.clone(),
+                expiration_timestamp: 2000,
+                client_ref: None,
             });
         }
+        let ids = client.batch_create_escrows(&sender, &requests);
+        assert_eq!(ids.len(), 25);
 
+        requests.push_back(EscrowRequest {
+            recipient: recipient.clone(),
+            amount: 100,
+            asset: asset.clone(),
+            expiration_timestamp: 2000,
+            client_ref: None,
+        });
         let result = client.try_batch_create_escrows(&sender, &requests);
         assert_eq!(result, Err(Ok(RemittanceError::BatchTooLarge)));
     }
@@ -2624,6 +6078,10 @@ mod test {
         let contract_id = env.register_contract(None, RemittanceHubContract);
         let client = RemittanceHubContractClient::new(&env, &contract_id);
 
+        let admin = Address::generate(&env);
+        let oracle = Address::generate(&env);
+        client.init_hub(&admin, &oracle, &oracle, &3600);
+
         let token_admin = Address::generate(&env);
         let token_id = env.register_stellar_asset_contract(token_admin.clone());
         let token_client = soroban_sdk::token::StellarAssetClient::new(&env, &token_id);
@@ -2643,12 +6101,14 @@ mod test {
             amount: 1000,
             asset: asset.clone(),
             expiration_timestamp: 2000,
+            client_ref: None,
         });
         requests.push_back(EscrowRequest {
             recipient: recipient.clone(),
             amount: 2000,
             asset: asset.clone(),
             expiration_timestamp: 3000,
+            client_ref: None,
         });
 
         let ids = client.batch_create_escrows(&sender, &requests);
@@ -2660,9 +6120,58 @@ mod test {
 
         client.batch_release(&recipient, &ids, &token_id);
 
-        let recipient_balance =
-            soroban_sdk::token::Client::new(&env, &token_id).balance(&recipient);
-        assert_eq!(recipient_balance, 3000);
+        let token = soroban_sdk::token::Client::new(&env, &token_id);
+        // The recipient gets the escrowed amounts, the 2.5% fee goes to the admin
+        assert_eq!(token.balance(&recipient), 3000);
+        assert_eq!(token.balance(&admin), 75);
+        assert_eq!(token.balance(&contract_id), 0);
+
+        let escrow = client.get_escrow(&ids.get(0).unwrap()).unwrap();
+        assert_eq!(escrow.status, symbol_short!("release"));
+    }
+
+    #[test]
+    fn test_batch_release_forwards_fee_collected_at_deposit() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| {
+            li.timestamp = 1000;
+        });
+
+        let contract_id = env.register_contract(None, RemittanceHubContract);
+        let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let oracle = Address::generate(&env);
+        client.init_hub(&admin, &oracle, &oracle, &3600);
+
+        let token_id = env.register_stellar_asset_contract(Address::generate(&env));
+        let sender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+        soroban_sdk::token::StellarAssetClient::new(&env, &token_id).mint(&sender, &10000);
+
+        let mut requests = soroban_sdk::Vec::new(&env);
+        requests.push_back(EscrowRequest {
+            recipient: recipient.clone(),
+            amount: 2000,
+            asset: Asset {
+                code: String::from_str(&env, "USDC"),
+                issuer: Address::generate(&env),
+            },
+            expiration_timestamp: 3000,
+            client_ref: None,
+        });
+        let ids = client.batch_create_escrows(&sender, &requests);
+        client.batch_deposit(&sender, &ids, &token_id);
+
+        // A rate change after funding does not alter the fee already taken.
+        client.set_remittance_fee(&admin, &500);
+        client.batch_release(&recipient, &ids, &token_id);
+
+        let token = soroban_sdk::token::Client::new(&env, &token_id);
+        assert_eq!(token.balance(&recipient), 2000);
+        assert_eq!(token.balance(&admin), 50);
+        assert_eq!(token.balance(&contract_id), 0);
     }
 
     #[test]
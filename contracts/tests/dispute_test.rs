@@ -45,6 +45,7 @@ fn setup_test<'a>(
     };
 
     client.add_supported_asset(&admin, &asset);
+    client.set_asset_token(&admin, &asset, &token.0.address);
 
     (client, admin, sender, recipient, token, asset)
 }
@@ -302,7 +303,7 @@ fn test_dispute_resolved_favor_recipient() {
 
 // Test admin can resolve dispute directly
 #[test]
-fn test_admin_resolve_dispute() {
+fn test_admin_finalize_dispute() {
     let env = Env::default();
     let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
 
@@ -324,7 +325,7 @@ fn test_admin_resolve_dispute() {
     client.raise_dispute(&escrow_id, &sender, &DisputeReason::Fraud, &evidence_hash);
 
     // Admin resolves directly
-    let result = client.try_resolve_dispute(&escrow_id, &admin, &ResolutionOutcome::FavorRecipient);
+    let result = client.try_finalize_dispute(&escrow_id, &admin, &ResolutionOutcome::FavorRecipient);
 
     assert!(result.is_ok());
 
@@ -354,7 +355,7 @@ fn test_non_admin_cannot_resolve() {
     let evidence_hash = BytesN::from_array(&env, &[9u8; 32]);
     client.raise_dispute(&escrow_id, &sender, &DisputeReason::Fraud, &evidence_hash);
 
-    let result = client.try_resolve_dispute(&escrow_id, &sender, &ResolutionOutcome::FavorSender);
+    let result = client.try_finalize_dispute(&escrow_id, &sender, &ResolutionOutcome::FavorSender);
 
     assert_eq!(result, Err(Ok(Error::Unauthorized)));
 }
@@ -533,7 +534,7 @@ fn test_escrow_status_after_resolution() {
         &DisputeReason::NonDelivery,
         &evidence_hash,
     );
-    client.resolve_dispute(&escrow_id1, &admin, &ResolutionOutcome::FavorSender);
+    client.finalize_dispute(&escrow_id1, &admin, &ResolutionOutcome::FavorSender);
 
     let escrow1 = client.get_escrow(&escrow_id1).unwrap();
     assert_eq!(escrow1.status, EscrowStatus::Funded);
@@ -555,7 +556,7 @@ fn test_escrow_status_after_resolution() {
         &DisputeReason::NonDelivery,
         &evidence_hash2,
     );
-    client.resolve_dispute(&escrow_id2, &admin, &ResolutionOutcome::FavorRecipient);
+    client.finalize_dispute(&escrow_id2, &admin, &ResolutionOutcome::FavorRecipient);
 
     let escrow2 = client.get_escrow(&escrow_id2).unwrap();
     assert_eq!(escrow2.status, EscrowStatus::Approved);
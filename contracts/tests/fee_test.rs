@@ -1,9 +1,9 @@
 use gpay_remit_contracts::payment_escrow::{
-    Asset, Error, FeeBreakdown, PaymentEscrowContract, PaymentEscrowContractClient,
+    Asset, Error, FeeBreakdown, PaymentEscrowContract, PaymentEscrowContractClient, PayoutConfig,
 };
 use soroban_sdk::{
     testutils::{Address as _, Ledger},
-    token, Address, Env, String,
+    token, Address, Env, Map, String,
 };
 
 fn create_token_contract<'a>(
@@ -44,6 +44,7 @@ fn setup_test<'a>(
     };
 
     client.add_supported_asset(&admin, &asset);
+    client.set_asset_token(&admin, &asset, &token.0.address);
 
     (client, admin, sender, recipient, token, asset)
 }
@@ -83,9 +84,9 @@ fn test_fee_transfer_to_fee_wallet() {
     // Check recipient received correct amount
     assert_eq!(token.balance(&recipient), expected_recipient_amount);
 
-    // Check fee wallet received the fee (admin gets fee if no fee wallet set, but we set one)
-    // Note: Current implementation sends to admin, not fee_wallet
-    assert_eq!(token.balance(&admin), expected_fee);
+    // Fee goes to the configured fee wallet; the admin balance is untouched
+    assert_eq!(token.balance(&fee_wallet), expected_fee);
+    assert_eq!(token.balance(&admin), 0);
 }
 
 // Test fee calculation matches fee structure
@@ -263,9 +264,11 @@ fn test_fee_wallet_changes() {
     client.deposit(&escrow_id2, &sender, &amount, &token.address);
     client.release_escrow(&escrow_id2, &recipient, &token.address);
 
-    // Both should have received fees (currently goes to admin)
+    // Each release pays the wallet configured at the time
     let expected_fee = 500;
-    assert_eq!(token.balance(&admin), expected_fee * 2);
+    assert_eq!(token.balance(&fee_wallet1), expected_fee);
+    assert_eq!(token.balance(&fee_wallet2), expected_fee);
+    assert_eq!(token.balance(&admin), 0);
 }
 
 // Test zero fee configuration
@@ -393,6 +396,45 @@ fn test_processing_fee_on_refund() {
     assert_eq!(token.balance(&admin), expected_fee);
 }
 
+// Test processing fee on refund goes to the fee wallet when one is set
+#[test]
+fn test_processing_fee_on_refund_to_fee_wallet() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    let fee_wallet = Address::generate(&env);
+    let amount = 10000;
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &amount);
+
+    client.set_processing_fee(&admin, &200); // 2%
+    client.set_fee_wallet(&admin, &fee_wallet);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &amount,
+        &asset,
+        &1500,
+        &String::from_str(&env, "Test"),
+    );
+    client.deposit(&escrow_id, &sender, &amount, &token.address);
+
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+
+    use gpay_remit_contracts::payment_escrow::RefundReason;
+    client.refund_escrow(
+        &escrow_id,
+        &sender,
+        &token.address,
+        &RefundReason::Expiration,
+    );
+
+    assert_eq!(token.balance(&sender), amount - 200);
+    assert_eq!(token.balance(&fee_wallet), 200);
+    assert_eq!(token.balance(&admin), 0);
+}
+
 // Test invalid fee percentage
 #[test]
 fn test_invalid_fee_percentage() {
@@ -424,7 +466,87 @@ fn test_fee_breakdown_structure() {
     // Verify all fields are present and correct
     assert_eq!(breakdown.platform_fee, 300);
     assert_eq!(breakdown.forex_fee, 200);
+    assert_eq!(breakdown.conversion_fee, 0);
     assert_eq!(breakdown.compliance_fee, 75);
     assert_eq!(breakdown.network_fee, 0);
     assert_eq!(breakdown.total_fee, 575);
 }
+
+// Test conversion spread applies only to cross-currency payouts
+#[test]
+fn test_conversion_spread_only_on_cross_currency() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, _token, asset) = setup_test(&env);
+
+    client.set_forex_fee(&admin, &100); // 1%
+    client.set_conversion_spread(&admin, &50); // 0.5%
+    assert_eq!(client.get_conversion_spread(), 50);
+
+    let eurc = Asset {
+        code: String::from_str(&env, "EURC"),
+        issuer: admin.clone(),
+    };
+    client.add_supported_asset(&admin, &eurc);
+    client.set_payout_config(
+        &admin,
+        &Some(PayoutConfig {
+            oracle: Address::generate(&env),
+            reserve: Address::generate(&env),
+            max_staleness: 0,
+            tokens: Map::from_array(&env, [(eurc.code.clone(), Address::generate(&env))]),
+        }),
+    );
+
+    let memo = String::from_str(&env, "");
+    let same_currency = client.create_escrow(&sender, &recipient, &10000, &asset, &5000, &memo);
+    let cross_currency = client.create_escrow(&sender, &recipient, &10000, &asset, &5000, &memo);
+    client.set_payout_preference(&cross_currency, &recipient, &eurc);
+
+    let same = client.get_escrow_fee_breakdown(&same_currency);
+    assert_eq!(same.forex_fee, 100);
+    assert_eq!(same.conversion_fee, 0);
+    assert_eq!(same.total_fee, 100);
+
+    let cross = client.get_escrow_fee_breakdown(&cross_currency);
+    assert_eq!(cross.forex_fee, 100);
+    assert_eq!(cross.conversion_fee, 50);
+    assert_eq!(cross.total_fee, 150);
+
+    assert_eq!(
+        client.try_set_conversion_spread(&admin, &10001),
+        Err(Ok(Error::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_fee_percentage_cap_enforced_at_configuration() {
+    let env = Env::default();
+    let (client, admin, _sender, _recipient, _token, _asset) = setup_test(&env);
+
+    client.set_platform_fee(&admin, &1500); // 15%
+    assert_eq!(
+        client.try_set_fee_percentage_cap(&admin, &Some(1000)),
+        Err(Ok(Error::InvalidFeePercentage))
+    );
+
+    client.set_fee_percentage_cap(&admin, &Some(2000));
+    assert_eq!(client.get_fee_percentage_cap(), Some(2000));
+
+    assert_eq!(
+        client.try_set_forex_fee(&admin, &600),
+        Err(Ok(Error::InvalidFeePercentage))
+    );
+    client.set_forex_fee(&admin, &500);
+    assert_eq!(
+        client.try_set_conversion_spread(&admin, &1),
+        Err(Ok(Error::InvalidFeePercentage))
+    );
+    assert_eq!(
+        client.try_set_platform_fee(&admin, &1600),
+        Err(Ok(Error::InvalidFeePercentage))
+    );
+    assert_eq!(client.get_platform_fee(), 1500);
+
+    client.set_fee_percentage_cap(&admin, &None);
+    client.set_platform_fee(&admin, &10000);
+}
@@ -55,6 +55,7 @@ mod integration_remittance {
             issuer: admin.clone(),
         };
         client.add_supported_asset(&admin, &asset);
+        client.set_asset_token(&admin, &asset, &token.address);
 
         (client, admin, sender, recipient, token, token_admin, asset)
     }
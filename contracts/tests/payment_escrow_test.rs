@@ -1,11 +1,13 @@
+use gpay_remit_contracts::events::{EventData, GpayEvent};
+use gpay_remit_contracts::oracle::{MockOracleContract, MockOracleContractClient};
 use gpay_remit_contracts::payment_escrow::{
-    Asset, DisputeReason, Error, EscrowStatus, NotificationConfig, PaymentEscrowContract,
-    PaymentEscrowContractClient, RecurringConfig, RefundReason, ResolutionOutcome, Milestone, InsuranceConfig, EscrowInsurance, DelegationPermissions, DelegationEntry, EscrowAnalytics
+    Asset, CancellationConfig, ComplianceAction, ComplianceRule, ComplianceRuleType, Condition, ConditionOperator, ConditionType, DisputeReason, Error, EscrowStatus, NotificationConfig, PaymentEscrowContract,
+    PaymentEscrowContractClient, RecurringConfig, RefundReason, ResolutionOutcome, Milestone, InsuranceConfig, EscrowInsurance, DelegationPermissions, DelegationEntry, EscrowAnalytics, EscrowCreateRequest, FeeConversionConfig, PayoutConfig, ReleaseRecord, CategoryRules, InsuranceFund, ReleaseCallback, FeeShare, FeeSplit, EscrowStats, DisputeStatus
 };
 use soroban_sdk::{
     symbol_short,
     testutils::{Address as _, Events as _, Ledger},
-    token, vec, Address, BytesN, Env, FromVal, IntoVal, Map, String, Symbol, Vec,
+    token, vec, Address, Bytes, BytesN, Env, FromVal, IntoVal, TryFromVal, Map, String, Symbol, Vec,
 };
 
 fn create_token_contract<'a>(
@@ -46,6 +48,7 @@ fn setup_test<'a>(
     };
 
     client.add_supported_asset(&admin, &asset);
+    client.set_asset_token(&admin, &asset, &token.0.address);
 
     (client, admin, sender, recipient, token, asset)
 }
@@ -486,11 +489,12 @@ fn test_add_trusted_issuer_non_admin() {
 
     let non_admin = recipient;
     let issuer = Address::generate(&env);
-    let result = client.try_add_trusted_issuer(&non_admin, &issuer);
+    let public_key = BytesN::from_array(&env, &[1u8; 32]);
+    let result = client.try_add_trusted_issuer(&non_admin, &issuer, &public_key);
     assert_eq!(result, Err(Ok(Error::Unauthorized)));
 
     // Verify admin can add trusted issuer
-    client.add_trusted_issuer(&admin, &issuer);
+    client.add_trusted_issuer(&admin, &issuer, &public_key);
 }
 
 // Test non-admin cannot override KYC
@@ -600,7 +604,7 @@ fn test_refund_escrow_non_sender() {
 
 // Test non-admin cannot resolve dispute
 #[test]
-fn test_resolve_dispute_non_admin() {
+fn test_finalize_dispute_non_admin() {
     let env = Env::default();
     let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
 
@@ -626,16 +630,16 @@ fn test_resolve_dispute_non_admin() {
 
     // Try to resolve as non-admin
     let result =
-        client.try_resolve_dispute(&escrow_id, &sender, &ResolutionOutcome::FavorRecipient);
+        client.try_finalize_dispute(&escrow_id, &sender, &ResolutionOutcome::FavorRecipient);
     assert_eq!(result, Err(Ok(Error::Unauthorized)));
 
     // Try to resolve as recipient
     let result2 =
-        client.try_resolve_dispute(&escrow_id, &recipient, &ResolutionOutcome::FavorRecipient);
+        client.try_finalize_dispute(&escrow_id, &recipient, &ResolutionOutcome::FavorRecipient);
     assert_eq!(result2, Err(Ok(Error::Unauthorized)));
 
     // Verify admin can resolve
-    client.resolve_dispute(&escrow_id, &admin, &ResolutionOutcome::FavorRecipient);
+    client.finalize_dispute(&escrow_id, &admin, &ResolutionOutcome::FavorRecipient);
 }
 
 // Test role-based access for multi-party approvals
@@ -1080,6 +1084,7 @@ fn test_multi_asset_deposit_release_and_refund() {
         issuer: admin.clone(),
     };
     client.add_supported_asset(&admin, &asset_b);
+    client.set_asset_token(&admin, &asset_b, &token_b.address);
 
     token_a_admin.mint(&sender, &1000);
     token_b_admin.mint(&sender, &500);
@@ -1178,6 +1183,7 @@ fn test_complete_and_approve_milestone() {
         issuer: admin.clone(),
     };
     client.add_supported_asset(&admin, &asset);
+    client.set_asset_token(&admin, &asset, &token.address);
 
     let escrow_id = client.create_escrow(&sender, &recipient, &1000, &asset, &2000, &String::from_str(&env, "Test"));
     client.deposit(&escrow_id, &sender, &1000, &token.address);
@@ -1492,6 +1498,7 @@ fn test_insure_escrow() {
         issuer: admin.clone(),
     };
     client.add_supported_asset(&admin, &asset);
+    client.set_asset_token(&admin, &asset, &token.address);
 
     let config = InsuranceConfig {
         premium_rate: 500,
@@ -1536,6 +1543,7 @@ fn test_claim_insurance() {
         issuer: admin.clone(),
     };
     client.add_supported_asset(&admin, &asset);
+    client.set_asset_token(&admin, &asset, &token.address);
 
     let config = InsuranceConfig {
         premium_rate: 500,
@@ -1555,6 +1563,44 @@ fn test_claim_insurance() {
     assert_eq!(insurance.claim_reason.unwrap(), String::from_str(&env, "NonDelivery"));
 }
 
+#[test]
+fn test_insurance_reserve_accumulates_and_pays_claims() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    let reserve = Address::generate(&env);
+
+    token_admin.mint(&sender, &3000);
+    client.set_insurance_fund(
+        &admin,
+        &Some(InsuranceFund {
+            insurance_bps: 100,
+            reserve: reserve.clone(),
+        }),
+    );
+
+    let memo = String::from_str(&env, "");
+    for _ in 0..2 {
+        let escrow_id = client.create_escrow(&sender, &recipient, &1000, &asset, &2000, &memo);
+        client.deposit(&escrow_id, &sender, &1000, &token.address);
+        client.release_escrow(&escrow_id, &recipient, &token.address);
+    }
+
+    assert_eq!(client.get_insurance_reserve(&asset.code), 20);
+    assert_eq!(token.balance(&reserve), 20);
+    assert_eq!(token.balance(&recipient), 1980);
+
+    let claimed_id = client.create_escrow(&sender, &recipient, &1000, &asset, &2000, &memo);
+    assert_eq!(
+        client.try_claim_insurance_reserve(&admin, &claimed_id, &21, &token.address),
+        Err(Ok(Error::InsufficientFunds))
+    );
+    client.claim_insurance_reserve(&admin, &claimed_id, &15, &token.address);
+
+    assert_eq!(client.get_insurance_reserve(&asset.code), 5);
+    assert_eq!(token.balance(&reserve), 5);
+    assert_eq!(token.balance(&recipient), 1995);
+}
+
 #[test]
 fn test_claim_insurance_not_insured() {
     let env = Env::default();
@@ -1577,6 +1623,7 @@ fn test_claim_insurance_not_insured() {
         issuer: admin.clone(),
     };
     client.add_supported_asset(&admin, &asset);
+    client.set_asset_token(&admin, &asset, &token.address);
 
     let escrow_id = client.create_escrow(&sender, &recipient, &1000, &asset, &2000, &String::from_str(&env, "Test"));
 
@@ -1698,6 +1745,7 @@ fn test_get_success_rate() {
         issuer: admin.clone(),
     };
     client.add_supported_asset(&admin, &asset);
+    client.set_asset_token(&admin, &asset, &token.address);
 
     let e1 = client.create_escrow(&sender, &recipient, &1000, &asset, &2000, &String::from_str(&env, "Test 1"));
     client.deposit(&e1, &sender, &1000, &token.address);
@@ -1739,3 +1787,3699 @@ fn test_get_user_statistics() {
     assert_eq!(stats.total_volume, 3000);
     assert_eq!(stats.average_amount, 1500);
 }
+
+// ============================================================================
+// PARTIAL RELEASE INCREMENT TESTS
+// ============================================================================
+
+#[test]
+fn test_partial_release_below_min_increment_rejected() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.enable_partial_release(&escrow_id, &sender);
+
+    client.set_min_release_increment(&admin, &100);
+    assert_eq!(client.get_min_release_increment(), 100);
+
+    let result = client.try_release_partial(&escrow_id, &recipient, &token.address, &50);
+    assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+
+    client.release_partial(&escrow_id, &recipient, &token.address, &950);
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().released_amount, 950);
+}
+
+#[test]
+fn test_partial_release_final_clearing_amount_allowed() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.enable_partial_release(&escrow_id, &sender);
+    client.set_min_release_increment(&admin, &100);
+
+    client.release_partial(&escrow_id, &recipient, &token.address, &970);
+    client.release_partial(&escrow_id, &recipient, &token.address, &30);
+
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.released_amount, 1000);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(token.balance(&recipient), 1000);
+}
+
+#[test]
+fn test_partial_releases_are_logged_in_order() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.enable_partial_release(&escrow_id, &sender);
+    assert_eq!(client.get_release_history(&escrow_id).len(), 0);
+
+    client.release_partial(&escrow_id, &recipient, &token.address, &400);
+    env.ledger().with_mut(|li| li.timestamp = 1500);
+    client.release_partial(&escrow_id, &admin, &token.address, &600);
+
+    let fee_percentage = client.get_platform_fee();
+    let history = client.get_release_history(&escrow_id);
+    assert_eq!(history.len(), 2);
+    assert_eq!(
+        history.get(0).unwrap(),
+        ReleaseRecord {
+            amount: 400,
+            fee: 400 * fee_percentage / 10000,
+            timestamp: 1000,
+            caller: recipient.clone(),
+        }
+    );
+    assert_eq!(
+        history.get(1).unwrap(),
+        ReleaseRecord {
+            amount: 600,
+            fee: 600 * fee_percentage / 10000,
+            timestamp: 1500,
+            caller: admin,
+        }
+    );
+}
+
+// ============================================================================
+// RECIPIENT ACKNOWLEDGMENT TESTS
+// ============================================================================
+
+#[test]
+fn test_recipient_ack_condition_requires_acknowledgment() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, _, asset) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, ""),
+    );
+    client.add_condition(&escrow_id, &sender, &ConditionType::RecipientAck, &true, &0);
+
+    assert!(!client.verify_conditions(&escrow_id, &0).all_passed);
+    assert_eq!(client.get_acknowledgment(&escrow_id), None);
+
+    client.acknowledge(&escrow_id, &recipient);
+
+    assert_eq!(client.get_acknowledgment(&escrow_id), Some(1000));
+    assert!(client.verify_conditions(&escrow_id, &0).all_passed);
+}
+
+#[test]
+fn test_acknowledge_rejects_non_recipient_and_duplicates() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, _, asset) = setup_test(&env);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, ""),
+    );
+
+    let result = client.try_acknowledge(&escrow_id, &sender);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    client.acknowledge(&escrow_id, &recipient);
+    let result = client.try_acknowledge(&escrow_id, &recipient);
+    assert_eq!(result, Err(Ok(Error::AlreadyApproved)));
+}
+
+// ============================================================================
+// CONDITION TEMPLATE TESTS
+// ============================================================================
+
+#[test]
+fn test_apply_condition_template_populates_escrow() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, _, asset) = setup_test(&env);
+
+    let conditions = vec![
+        &env,
+        Condition {
+            condition_type: ConditionType::Approval,
+            required: true,
+            verified: false,
+            threshold_value: 0,
+            verified_at: 0,
+            validity_period: 0,
+        },
+        Condition {
+            condition_type: ConditionType::OraclePrice,
+            required: false,
+            verified: false,
+            threshold_value: 500,
+            verified_at: 0,
+            validity_period: 0,
+        },
+    ];
+    client.save_condition_template(
+        &admin,
+        &symbol_short!("std_pair"),
+        &conditions,
+        &ConditionOperator::Or,
+    );
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, ""),
+    );
+    client.apply_condition_template(&escrow_id, &sender, &symbol_short!("std_pair"));
+
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.release_conditions.conditions, conditions);
+    assert_eq!(escrow.release_conditions.operator, ConditionOperator::Or);
+}
+
+#[test]
+fn test_apply_unknown_condition_template_fails() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, _, asset) = setup_test(&env);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, ""),
+    );
+
+    let result = client.try_apply_condition_template(&escrow_id, &sender, &symbol_short!("missing"));
+    assert_eq!(result, Err(Ok(Error::TemplateNotFound)));
+}
+
+// ============================================================================
+// RECONCILIATION TESTS
+// ============================================================================
+
+#[test]
+fn test_reconcile_zero_drift_after_normal_operations() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &3000);
+
+    let released_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&released_id, &sender, &1000, &token.address);
+    client.approve_escrow(&released_id, &sender);
+    client.release_escrow(&released_id, &recipient, &token.address);
+
+    let open_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &2000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&open_id, &sender, &2000, &token.address);
+
+    let (actual, expected, drift) = client.reconcile(&token.address, &asset.code);
+    assert_eq!(actual, 2000);
+    assert_eq!(expected, 2000);
+    assert_eq!(drift, 0);
+}
+
+#[test]
+fn test_reconcile_detects_induced_mismatch() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    token_admin.mint(&sender, &1000);
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+
+    // Tokens sent straight to the contract bypass escrow accounting.
+    token_admin.mint(&client.address, &250);
+
+    let (actual, expected, drift) = client.reconcile(&token.address, &asset.code);
+    assert_eq!(actual, 1250);
+    assert_eq!(expected, 1000);
+    assert_eq!(drift, 250);
+}
+
+// ============================================================================
+// KYC ELIGIBILITY TESTS
+// ============================================================================
+
+#[test]
+fn test_is_kyc_eligible_depends_on_amount_and_verification() {
+    let env = Env::default();
+    let (client, admin, sender, _recipient, _, _asset) = setup_test(&env);
+
+    client.register_compliance_rule(
+        &admin,
+        &ComplianceRule {
+            rule_type: ComplianceRuleType::RegulatoryRequirement,
+            threshold: 5000,
+            action: ComplianceAction::Block,
+        },
+    );
+
+    // Without verification the account only qualifies below the threshold.
+    assert!(client.is_kyc_eligible(&sender, &1000));
+    assert!(!client.is_kyc_eligible(&sender, &10_000));
+
+    let kyc_oracle = Address::generate(&env);
+    client.configure_kyc(&admin, &kyc_oracle, &false, &86400);
+    assert!(!client.is_kyc_eligible(&sender, &1000));
+
+    client.add_to_whitelist(&admin, &sender, &0);
+    assert!(client.is_kyc_eligible(&sender, &1000));
+    assert!(client.is_kyc_eligible(&sender, &10_000));
+}
+
+// ============================================================================
+// GEOGRAPHIC RESTRICTION TESTS
+// ============================================================================
+
+#[test]
+fn test_geo_allowed_condition_rejects_disallowed_region() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, _, asset) = setup_test(&env);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, ""),
+    );
+    client.add_condition(&escrow_id, &sender, &ConditionType::GeoAllowed, &true, &0);
+    client.set_allowed_regions(
+        &escrow_id,
+        &sender,
+        &vec![&env, symbol_short!("US"), symbol_short!("NG")],
+    );
+
+    client.set_account_region(&admin, &sender, &symbol_short!("US"));
+    client.set_account_region(&admin, &recipient, &symbol_short!("KP"));
+
+    let result = client.verify_conditions(&escrow_id, &0);
+    assert!(!result.all_passed);
+    assert_eq!(result.failed_conditions, vec![&env, ConditionType::GeoAllowed]);
+
+    client.set_account_region(&admin, &recipient, &symbol_short!("NG"));
+    assert!(client.verify_conditions(&escrow_id, &0).all_passed);
+}
+
+// ============================================================================
+// FUNDING FLOOR TESTS
+// ============================================================================
+
+#[test]
+fn test_min_deposited_condition_gates_on_funding_floor() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, ""),
+    );
+    client.add_condition(&escrow_id, &sender, &ConditionType::MinDeposited, &true, &600);
+
+    client.deposit(&escrow_id, &sender, &400, &token.address);
+    let result = client.verify_conditions(&escrow_id, &0);
+    assert!(!result.all_passed);
+    assert_eq!(result.failed_conditions, vec![&env, ConditionType::MinDeposited]);
+
+    client.deposit(&escrow_id, &sender, &200, &token.address);
+    assert!(client.verify_conditions(&escrow_id, &0).all_passed);
+
+    client.deposit(&escrow_id, &sender, &400, &token.address);
+    assert!(client.verify_conditions(&escrow_id, &0).all_passed);
+}
+
+#[test]
+fn test_unmet_conditions_block_partial_and_vested_release() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, ""),
+    );
+    client.add_condition(&escrow_id, &sender, &ConditionType::MinDeposited, &true, &600);
+    client.deposit(&escrow_id, &sender, &400, &token.address);
+    client.enable_partial_release(&escrow_id, &sender);
+
+    assert_eq!(
+        client.try_release_partial(&escrow_id, &recipient, &token.address, &100),
+        Err(Ok(Error::ConditionsNotMet))
+    );
+
+    client.set_vesting_schedule(&escrow_id, &sender, &vec![&env, (1500u64, 400i128)]);
+    env.ledger().with_mut(|li| li.timestamp = 1500);
+    assert_eq!(
+        client.try_release_vested(&escrow_id, &recipient, &token.address),
+        Err(Ok(Error::ConditionsNotMet))
+    );
+
+    client.deposit(&escrow_id, &sender, &600, &token.address);
+    assert_eq!(client.release_vested(&escrow_id, &recipient, &token.address), 400);
+}
+
+#[test]
+fn test_withdraw_excess_deposit_protects_funding_floor() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, ""),
+    );
+    client.add_condition(&escrow_id, &sender, &ConditionType::MinDeposited, &true, &500);
+    client.deposit(&escrow_id, &sender, &800, &token.address);
+
+    assert_eq!(
+        client.try_withdraw_excess_deposit(&escrow_id, &recipient, &token.address, &100),
+        Err(Ok(Error::WrongSender))
+    );
+    assert_eq!(
+        client.try_withdraw_excess_deposit(&escrow_id, &sender, &token.address, &301),
+        Err(Ok(Error::InsufficientFunds))
+    );
+
+    client.withdraw_excess_deposit(&escrow_id, &sender, &token.address, &300);
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().deposited_amount, 500);
+    assert_eq!(token.balance(&sender), 500);
+
+    assert_eq!(
+        client.try_withdraw_excess_deposit(&escrow_id, &sender, &token.address, &1),
+        Err(Ok(Error::InsufficientFunds))
+    );
+
+    client.deposit(&escrow_id, &sender, &500, &token.address);
+    assert_eq!(
+        client.try_withdraw_excess_deposit(&escrow_id, &sender, &token.address, &100),
+        Err(Ok(Error::EscrowNotPending))
+    );
+}
+
+// ============================================================================
+// FUNDING SOURCE TESTS
+// ============================================================================
+
+#[test]
+fn test_deposit_from_unlisted_funding_source_rejected() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    let treasury = Address::generate(&env);
+    let other = Address::generate(&env);
+    token_admin.mint(&treasury, &1000);
+    token_admin.mint(&other, &1000);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, ""),
+    );
+    client.set_funding_sources(&escrow_id, &sender, &vec![&env, treasury.clone()]);
+
+    let result = client.try_deposit(&escrow_id, &other, &1000, &token.address);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    let result = client.try_deposit(&escrow_id, &sender, &1000, &token.address);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    client.deposit(&escrow_id, &treasury, &1000, &token.address);
+    assert_eq!(token.balance(&treasury), 0);
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().status, EscrowStatus::Funded);
+}
+
+// ============================================================================
+// KYC AUTO-REFUND TESTS
+// ============================================================================
+
+#[test]
+fn test_release_with_lapsed_recipient_kyc_auto_refunds() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let kyc_oracle = Address::generate(&env);
+    client.configure_kyc(&admin, &kyc_oracle, &false, &86400);
+    client.add_to_whitelist(&admin, &sender, &0);
+    client.add_to_whitelist(&admin, &recipient, &1500);
+
+    token_admin.mint(&sender, &1000);
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.set_kyc_auto_refund(&escrow_id, &sender, &true);
+
+    // Recipient's KYC expires before release.
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.status, EscrowStatus::Refunded);
+    assert_eq!(escrow.refunded_amount, 1000);
+    assert_eq!(token.balance(&sender), 1000);
+    assert_eq!(token.balance(&recipient), 0);
+}
+
+// ============================================================================
+// FRACTIONAL QUORUM TESTS
+// ============================================================================
+
+#[test]
+fn test_fractional_quorum_tracks_whitelist_size() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, _, asset) = setup_test(&env);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+
+    let first = Address::generate(&env);
+    client.setup_multi_party_approval(&escrow_id, &admin, &vec![&env, first.clone()], &1, &0);
+    client.set_quorum_fraction(&escrow_id, &sender, &5100);
+    assert_eq!(client.get_effective_quorum(&escrow_id), Some(1));
+
+    for _ in 0..3 {
+        client.add_approver(&escrow_id, &sender, &Address::generate(&env));
+    }
+    assert_eq!(client.get_effective_quorum(&escrow_id), Some(3));
+
+    assert!(!client.multi_party_approve(&escrow_id, &first));
+}
+
+// ============================================================================
+// AML HOLD TESTS
+// ============================================================================
+
+#[test]
+fn test_flagged_sender_escrow_created_on_hold() {
+    use gpay_remit_contracts::aml::MockAmlOracleContractClient;
+    use gpay_remit_contracts::remittance_hub::RemittanceHubContractClient;
+    use gpay_remit_contracts::{MockAmlOracleContract, RemittanceHubContract};
+
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    let aml_oracle_id = env.register_contract(None, MockAmlOracleContract);
+    let aml_oracle = MockAmlOracleContractClient::new(&env, &aml_oracle_id);
+    aml_oracle.initialize(&admin);
+    aml_oracle.set_risk_score(&admin, &sender, &90);
+
+    let hub_id = env.register_contract(None, RemittanceHubContract);
+    let hub = RemittanceHubContractClient::new(&env, &hub_id);
+    hub.init_hub(&admin, &admin, &admin, &3600);
+    hub.configure_aml(&admin, &aml_oracle_id, &50);
+    hub.send_remittance(&sender, &recipient, &500, &symbol_short!("USD"));
+    assert!(hub.is_sender_flagged(&sender));
+
+    client.set_aml_hub(&admin, &hub_id);
+
+    token_admin.mint(&sender, &1000);
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, ""),
+    );
+
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.status, EscrowStatus::Pending);
+    assert!(!escrow.compliant);
+
+    let result = client.try_deposit(&escrow_id, &sender, &1000, &token.address);
+    assert_eq!(result, Err(Ok(Error::NonCompliant)));
+}
+
+#[test]
+fn test_aml_clear_condition_fails_after_party_is_flagged() {
+    use gpay_remit_contracts::aml::{AmlConfig, MockAmlOracleContractClient};
+    use gpay_remit_contracts::MockAmlOracleContract;
+
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    let aml_oracle_id = env.register_contract(None, MockAmlOracleContract);
+    let aml_oracle = MockAmlOracleContractClient::new(&env, &aml_oracle_id);
+    aml_oracle.initialize(&admin);
+    client.set_aml_screening(
+        &admin,
+        &Some(AmlConfig {
+            admin: admin.clone(),
+            oracle_address: aml_oracle_id.clone(),
+            risk_threshold: 50,
+            enabled: true,
+        }),
+    );
+
+    token_admin.mint(&sender, &1000);
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, ""),
+    );
+    client.add_condition(&escrow_id, &sender, &ConditionType::AmlClear, &true, &0);
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    assert!(client.verify_conditions(&escrow_id, &0).all_passed);
+
+    aml_oracle.set_risk_score(&admin, &recipient, &80);
+    let result = client.verify_conditions(&escrow_id, &0);
+    assert!(!result.all_passed);
+    assert_eq!(result.failed_conditions, vec![&env, ConditionType::AmlClear]);
+}
+
+// ============================================================================
+// FEE COLLECTION EVENT TESTS
+// ============================================================================
+
+fn find_fee_collected_events(env: &Env) -> std::vec::Vec<EventData> {
+    env.events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            topics.len() > 2
+                && Symbol::try_from_val(env, &topics.get(0).unwrap()).ok()
+                    == Some(symbol_short!("gpayremit"))
+                && Symbol::try_from_val(env, &topics.get(2).unwrap()).ok()
+                    == Some(symbol_short!("fee_coll"))
+        })
+        .map(|(_, _, data)| GpayEvent::from_val(env, &data).data)
+        .collect()
+}
+
+#[test]
+fn test_release_emits_fee_collected_to_admin() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.set_platform_fee(&admin, &100);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+
+    let fees = find_fee_collected_events(&env);
+    assert_eq!(
+        fees,
+        std::vec![EventData::FeeCollected(
+            escrow_id,
+            10,
+            admin.clone(),
+            symbol_short!("platform")
+        )]
+    );
+    assert_eq!(token.balance(&admin), 10);
+}
+
+#[test]
+fn test_cancel_penalty_emits_fee_collected_to_fee_wallet() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    let fee_wallet = Address::generate(&env);
+    client.set_fee_wallet(&admin, &fee_wallet);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.set_cancellation_config(
+        &escrow_id,
+        &sender,
+        &CancellationConfig {
+            penalty_percentage: 500,
+            recipient_compensation: 0,
+        },
+    );
+    client.cancel_escrow(&escrow_id, &sender, &token.address, &String::from_str(&env, ""));
+
+    let fees = find_fee_collected_events(&env);
+    assert_eq!(
+        fees,
+        std::vec![EventData::FeeCollected(
+            escrow_id,
+            50,
+            fee_wallet.clone(),
+            symbol_short!("penalty")
+        )]
+    );
+    assert_eq!(token.balance(&fee_wallet), 50);
+}
+
+#[test]
+fn test_released_and_refunded_events_carry_fee_destination() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let fee_wallet = Address::generate(&env);
+    client.set_fee_wallet(&admin, &fee_wallet);
+    token_admin.mint(&sender, &2000);
+
+    let released_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&released_id, &sender, &1000, &token.address);
+    client.release_escrow(&released_id, &recipient, &token.address);
+
+    let refunded_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&refunded_id, &sender, &1000, &token.address);
+    env.ledger().with_mut(|li| li.timestamp = 3000);
+    client.refund_escrow(&refunded_id, &sender, &token.address, &RefundReason::Expiration);
+
+    let payloads: std::vec::Vec<EventData> = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            Symbol::try_from_val(&env, &topics.get(0).unwrap()).ok()
+                == Some(symbol_short!("gpayremit"))
+        })
+        .map(|(_, _, data)| GpayEvent::from_val(&env, &data).data)
+        .filter(|data| {
+            matches!(
+                data,
+                EventData::EscrowReleased(..) | EventData::EscrowRefunded(..)
+            )
+        })
+        .collect();
+    assert_eq!(
+        payloads,
+        std::vec![
+            EventData::EscrowReleased(released_id, 1000, fee_wallet.clone()),
+            EventData::EscrowRefunded(refunded_id, 1000, fee_wallet.clone()),
+        ]
+    );
+}
+
+// ============================================================================
+// BATCH CREATION TESTS
+// ============================================================================
+
+#[test]
+fn test_batch_create_escrows_enforces_kyc() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, _, asset) = setup_test(&env);
+    let recipient2 = Address::generate(&env);
+
+    let kyc_oracle = Address::generate(&env);
+    client.configure_kyc(&admin, &kyc_oracle, &false, &86400);
+    client.add_to_whitelist(&admin, &sender, &0);
+    client.add_to_whitelist(&admin, &recipient, &0);
+
+    let requests = vec![
+        &env,
+        EscrowCreateRequest {
+            recipient: recipient.clone(),
+            amount: 1000,
+            asset: asset.clone(),
+            expiration_timestamp: 2000,
+            memo: String::from_str(&env, "first"),
+        },
+        EscrowCreateRequest {
+            recipient: recipient2.clone(),
+            amount: 500,
+            asset: asset.clone(),
+            expiration_timestamp: 2000,
+            memo: String::from_str(&env, "second"),
+        },
+    ];
+
+    // recipient2 is not verified, so the whole batch reverts.
+    let result = client.try_batch_create_escrows(&sender, &requests);
+    assert_eq!(result, Err(Ok(Error::KycFailed)));
+    assert!(client.get_escrow(&1).is_none());
+
+    client.add_to_whitelist(&admin, &recipient2, &0);
+    let ids = client.batch_create_escrows(&sender, &requests);
+    assert_eq!(ids, vec![&env, 1u64, 2u64]);
+
+    let first = client.get_escrow(&1).unwrap();
+    assert_eq!(first.recipient, recipient);
+    assert_eq!(first.amount, 1000);
+    assert!(first.kyc_compliant);
+    let second = client.get_escrow(&2).unwrap();
+    assert_eq!(second.recipient, recipient2);
+    assert_eq!(second.amount, 500);
+    assert!(second.kyc_compliant);
+}
+
+// ============================================================================
+// ACTIONABLE ESCROW TESTS
+// ============================================================================
+
+#[test]
+fn test_get_actionable_escrows_returns_only_releasable() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    let memo = String::from_str(&env, "");
+    token_admin.mint(&sender, &3000);
+
+    let funded = client.create_escrow(&sender, &recipient, &1000, &asset, &2000, &memo);
+    client.deposit(&funded, &sender, &1000, &token.address);
+
+    let _unfunded = client.create_escrow(&sender, &recipient, &1000, &asset, &2000, &memo);
+
+    let gated = client.create_escrow(&sender, &recipient, &1000, &asset, &2000, &memo);
+    client.add_condition(&gated, &sender, &ConditionType::RecipientAck, &true, &0);
+    client.deposit(&gated, &sender, &1000, &token.address);
+
+    let other = Address::generate(&env);
+    let elsewhere = client.create_escrow(&sender, &other, &1000, &asset, &2000, &memo);
+    client.deposit(&elsewhere, &sender, &1000, &token.address);
+
+    assert_eq!(client.get_actionable_escrows(&recipient, &0, &10), (vec![&env, funded], 0));
+
+    client.acknowledge(&gated, &recipient);
+    client.verify_conditions(&gated, &0);
+    assert_eq!(
+        client.get_actionable_escrows(&recipient, &0, &10),
+        (vec![&env, funded, gated], 0)
+    );
+    assert_eq!(
+        client.get_actionable_escrows(&recipient, &(funded + 1), &10),
+        (vec![&env, gated], 0)
+    );
+    assert_eq!(
+        client.get_actionable_escrows(&recipient, &0, &1),
+        (vec![&env, funded], funded + 1)
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 2001);
+    assert!(client.get_actionable_escrows(&recipient, &0, &10).0.is_empty());
+}
+
+#[test]
+fn test_get_actionable_escrows_caps_ids_scanned_per_call() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    let memo = String::from_str(&env, "");
+    token_admin.mint(&sender, &1000);
+
+    env.budget().reset_unlimited();
+    let other = Address::generate(&env);
+    for _ in 0..55 {
+        client.create_escrow(&sender, &other, &1000, &asset, &2000, &memo);
+    }
+    let funded = client.create_escrow(&sender, &recipient, &1000, &asset, &2000, &memo);
+    client.deposit(&funded, &sender, &1000, &token.address);
+
+    // The first page stops after 50 ids and hands back where to resume.
+    assert_eq!(client.get_actionable_escrows(&recipient, &0, &100), (vec![&env], 51));
+    assert_eq!(
+        client.get_actionable_escrows(&recipient, &51, &100),
+        (vec![&env, funded], 0)
+    );
+}
+
+// ============================================================================
+// FEE CONVERSION TESTS
+// ============================================================================
+
+fn setup_fee_conversion<'a>(
+    env: &Env,
+    client: &PaymentEscrowContractClient<'a>,
+    admin: &Address,
+) -> (token::Client<'a>, Address, MockOracleContractClient<'a>) {
+    let oracle_id = env.register_contract(None, MockOracleContract);
+    let oracle = MockOracleContractClient::new(env, &oracle_id);
+    oracle.init_oracle(admin);
+
+    let (stable, stable_admin) = create_token_contract(env, admin);
+    let reserve = Address::generate(env);
+    stable_admin.mint(&reserve, &1000);
+    stable.approve(&reserve, &client.address, &1000, &1000);
+
+    client.set_fee_conversion(
+        admin,
+        &Some(FeeConversionConfig {
+            oracle: oracle_id,
+            treasury_token: stable.address.clone(),
+            treasury_asset: String::from_str(env, "USDT"),
+            reserve: reserve.clone(),
+            max_staleness: 0,
+        }),
+    );
+    (stable, reserve, oracle)
+}
+
+#[test]
+fn test_fee_converted_to_treasury_asset() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    let (stable, reserve, oracle) = setup_fee_conversion(&env, &client, &admin);
+    oracle.set_rate(
+        &admin,
+        &String::from_str(&env, "USDC"),
+        &String::from_str(&env, "USDT"),
+        &2,
+        &1,
+    );
+
+    client.set_platform_fee(&admin, &100);
+    token_admin.mint(&sender, &1000);
+    let escrow_id = client.create_escrow(&sender, &recipient, &1000, &asset, &5000, &String::from_str(&env, ""));
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+
+    assert_eq!(token.balance(&recipient), 990);
+    assert_eq!(token.balance(&admin), 0);
+    assert_eq!(token.balance(&reserve), 10);
+    assert_eq!(stable.balance(&admin), 20);
+    assert_eq!(stable.balance(&reserve), 980);
+    assert_eq!(
+        find_fee_collected_events(&env),
+        std::vec![EventData::FeeCollected(escrow_id, 20, admin.clone(), symbol_short!("platform"))]
+    );
+}
+
+#[test]
+fn test_fee_conversion_falls_back_without_rate() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    let (stable, reserve, _oracle) = setup_fee_conversion(&env, &client, &admin);
+
+    client.set_platform_fee(&admin, &100);
+    token_admin.mint(&sender, &1000);
+    let escrow_id = client.create_escrow(&sender, &recipient, &1000, &asset, &5000, &String::from_str(&env, ""));
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+
+    assert_eq!(token.balance(&admin), 10);
+    assert_eq!(stable.balance(&admin), 0);
+    assert_eq!(stable.balance(&reserve), 1000);
+}
+
+// ============================================================================
+// CONDITION EXPIRY TESTS
+// ============================================================================
+
+#[test]
+fn test_verified_condition_lapses_after_validity_period() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, _, asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.add_condition(&escrow_id, &sender, &ConditionType::OraclePrice, &true, &500);
+    client.set_condition_validity(&escrow_id, &sender, &0, &100);
+
+    assert!(client.verify_conditions(&escrow_id, &600).all_passed);
+    let condition = client.get_escrow(&escrow_id).unwrap().release_conditions.conditions.get(0).unwrap();
+    assert_eq!(condition.verified_at, 1000);
+
+    // Without fresh proof the earlier verification still holds inside the window.
+    env.ledger().with_mut(|li| li.timestamp = 1100);
+    assert!(client.verify_conditions(&escrow_id, &0).all_passed);
+
+    env.ledger().with_mut(|li| li.timestamp = 1101);
+    let result = client.verify_conditions(&escrow_id, &0);
+    assert!(!result.all_passed);
+    assert_eq!(result.failed_conditions, vec![&env, ConditionType::OraclePrice]);
+}
+
+// ============================================================================
+// COMPLIANCE OFFICER TESTS
+// ============================================================================
+
+#[test]
+fn test_compliance_officer_overrides_kyc_but_not_fees() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, _, asset) = setup_test(&env);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+
+    let officer = Address::generate(&env);
+    assert_eq!(
+        client.try_admin_override_kyc(&officer, &escrow_id),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    client.set_compliance_officer(&admin, &Some(officer.clone()));
+    assert_eq!(client.get_compliance_officer(), Some(officer.clone()));
+
+    client.admin_override_kyc(&officer, &escrow_id);
+    assert!(client.get_escrow(&escrow_id).unwrap().kyc_compliant);
+    assert_eq!(
+        client.try_set_platform_fee(&officer, &100),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    client.set_compliance_officer(&admin, &None);
+    assert_eq!(
+        client.try_admin_override_compliance(&officer, &escrow_id),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+// ============================================================================
+// SEQUENCE NUMBER TESTS
+// ============================================================================
+
+#[test]
+fn test_escrow_sequence_increments_on_each_change() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().sequence, 1);
+
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().sequence, 2);
+
+    client.add_condition(&escrow_id, &sender, &ConditionType::RecipientAck, &false, &0);
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().sequence, 3);
+
+    client.verify_conditions(&escrow_id, &0);
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().sequence, 4);
+
+    // Releasing re-verifies the conditions before paying out.
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(escrow.sequence, 6);
+}
+
+// ============================================================================
+// PAYOUT PREFERENCE TESTS
+// ============================================================================
+
+#[test]
+fn test_release_pays_recipient_in_preferred_asset() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    let oracle_id = env.register_contract(None, MockOracleContract);
+    let oracle = MockOracleContractClient::new(&env, &oracle_id);
+    oracle.init_oracle(&admin);
+    oracle.set_rate(
+        &admin,
+        &String::from_str(&env, "USDC"),
+        &String::from_str(&env, "EURC"),
+        &9,
+        &10,
+    );
+
+    let (eurc, eurc_admin) = create_token_contract(&env, &admin);
+    let eurc_asset = Asset {
+        code: String::from_str(&env, "EURC"),
+        issuer: admin.clone(),
+    };
+    client.add_supported_asset(&admin, &eurc_asset);
+
+    let reserve = Address::generate(&env);
+    eurc_admin.mint(&reserve, &5000);
+    eurc.approve(&reserve, &client.address, &5000, &1000);
+    client.set_payout_config(
+        &admin,
+        &Some(PayoutConfig {
+            oracle: oracle_id,
+            reserve: reserve.clone(),
+            max_staleness: 0,
+            tokens: Map::from_array(&env, [(eurc_asset.code.clone(), eurc.address.clone())]),
+        }),
+    );
+
+    token_admin.mint(&sender, &1000);
+    let escrow_id = client.create_escrow(&sender, &recipient, &1000, &asset, &5000, &String::from_str(&env, ""));
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_set_payout_preference(&escrow_id, &outsider, &eurc_asset),
+        Err(Ok(Error::Unauthorized))
+    );
+    let unregistered = Asset {
+        code: String::from_str(&env, "GBPC"),
+        issuer: admin.clone(),
+    };
+    client.add_supported_asset(&admin, &unregistered);
+    assert_eq!(
+        client.try_set_payout_preference(&escrow_id, &recipient, &unregistered),
+        Err(Ok(Error::InvalidAsset))
+    );
+    client.set_payout_preference(&escrow_id, &recipient, &eurc_asset);
+    assert_eq!(client.get_payout_preference(&escrow_id).unwrap().asset, eurc_asset);
+
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+
+    assert_eq!(token.balance(&recipient), 0);
+    assert_eq!(eurc.balance(&recipient), 900);
+    assert_eq!(token.balance(&reserve), 1000);
+    assert_eq!(eurc.balance(&reserve), 4100);
+}
+
+#[test]
+fn test_converted_release_charges_conversion_spread() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    let oracle_id = env.register_contract(None, MockOracleContract);
+    let oracle = MockOracleContractClient::new(&env, &oracle_id);
+    oracle.init_oracle(&admin);
+    oracle.set_rate(
+        &admin,
+        &String::from_str(&env, "USDC"),
+        &String::from_str(&env, "EURC"),
+        &9,
+        &10,
+    );
+
+    let (eurc, eurc_admin) = create_token_contract(&env, &admin);
+    let eurc_asset = Asset {
+        code: String::from_str(&env, "EURC"),
+        issuer: admin.clone(),
+    };
+    client.add_supported_asset(&admin, &eurc_asset);
+
+    let reserve = Address::generate(&env);
+    eurc_admin.mint(&reserve, &5000);
+    eurc.approve(&reserve, &client.address, &5000, &1000);
+    client.set_payout_config(
+        &admin,
+        &Some(PayoutConfig {
+            oracle: oracle_id,
+            reserve: reserve.clone(),
+            max_staleness: 0,
+            tokens: Map::from_array(&env, [(eurc_asset.code.clone(), eurc.address.clone())]),
+        }),
+    );
+    client.set_conversion_spread(&admin, &100); // 1%
+
+    token_admin.mint(&sender, &1000);
+    let escrow_id = client.create_escrow(&sender, &recipient, &1000, &asset, &5000, &String::from_str(&env, ""));
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.set_payout_preference(&escrow_id, &recipient, &eurc_asset);
+    assert_eq!(client.get_escrow_fee_breakdown(&escrow_id).conversion_fee, 10);
+
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+
+    // The quoted 10 spread goes to the fee wallet; the rest is converted.
+    assert_eq!(token.balance(&admin), 10);
+    assert_eq!(token.balance(&reserve), 990);
+    assert_eq!(eurc.balance(&recipient), 891);
+}
+
+#[test]
+fn test_refund_pays_sender_in_preferred_asset() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let oracle_id = env.register_contract(None, MockOracleContract);
+    let oracle = MockOracleContractClient::new(&env, &oracle_id);
+    oracle.init_oracle(&admin);
+    oracle.set_rate(
+        &admin,
+        &String::from_str(&env, "USDC"),
+        &String::from_str(&env, "EURC"),
+        &9,
+        &10,
+    );
+
+    let (eurc, eurc_admin) = create_token_contract(&env, &admin);
+    let eurc_asset = Asset {
+        code: String::from_str(&env, "EURC"),
+        issuer: admin.clone(),
+    };
+    client.add_supported_asset(&admin, &eurc_asset);
+
+    let reserve = Address::generate(&env);
+    eurc_admin.mint(&reserve, &5000);
+    eurc.approve(&reserve, &client.address, &5000, &1000);
+    client.set_payout_config(
+        &admin,
+        &Some(PayoutConfig {
+            oracle: oracle_id,
+            reserve: reserve.clone(),
+            max_staleness: 0,
+            tokens: Map::from_array(&env, [(eurc_asset.code.clone(), eurc.address.clone())]),
+        }),
+    );
+
+    token_admin.mint(&sender, &1000);
+    let escrow_id = client.create_escrow(&sender, &recipient, &1000, &asset, &2000, &String::from_str(&env, ""));
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+
+    assert_eq!(
+        client.try_set_refund_preference(&escrow_id, &recipient, &eurc_asset),
+        Err(Ok(Error::WrongSender))
+    );
+    let unregistered = Asset {
+        code: String::from_str(&env, "GBPC"),
+        issuer: admin.clone(),
+    };
+    client.add_supported_asset(&admin, &unregistered);
+    assert_eq!(
+        client.try_set_refund_preference(&escrow_id, &sender, &unregistered),
+        Err(Ok(Error::InvalidAsset))
+    );
+    client.set_refund_preference(&escrow_id, &sender, &eurc_asset);
+    assert_eq!(client.get_refund_preference(&escrow_id).unwrap().asset, eurc_asset);
+
+    env.ledger().with_mut(|li| li.timestamp = 3000);
+    client.refund_escrow(&escrow_id, &sender, &token.address, &RefundReason::Expiration);
+
+    assert_eq!(token.balance(&sender), 0);
+    assert_eq!(eurc.balance(&sender), 900);
+    assert_eq!(token.balance(&reserve), 1000);
+    assert_eq!(eurc.balance(&reserve), 4100);
+}
+
+// ============================================================================
+// TWO-SIDED RELEASE TESTS
+// ============================================================================
+
+#[test]
+fn test_two_sided_release_requires_both_parties() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.set_two_sided_release(&escrow_id, &sender, &true);
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+
+    assert_eq!(
+        client.try_release_escrow(&escrow_id, &recipient, &token.address),
+        Err(Ok(Error::NotApproved))
+    );
+
+    client.acknowledge(&escrow_id, &recipient);
+    assert_eq!(
+        client.try_release_escrow(&escrow_id, &recipient, &token.address),
+        Err(Ok(Error::NotApproved))
+    );
+    assert!(client.get_actionable_escrows(&recipient, &0, &10).0.is_empty());
+
+    assert_eq!(
+        client.try_authorize_release(&escrow_id, &recipient),
+        Err(Ok(Error::WrongSender))
+    );
+    client.authorize_release(&escrow_id, &sender);
+    assert!(client.get_release_authorization(&escrow_id).is_some());
+
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+    assert_eq!(token.balance(&recipient), 1000);
+}
+
+#[test]
+fn test_recipient_release_request_needs_sender_approval() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    assert_eq!(
+        client.try_request_release(&escrow_id, &recipient),
+        Err(Ok(Error::InvalidStatus))
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+
+    assert_eq!(
+        client.try_request_release(&escrow_id, &sender),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.request_release(&escrow_id, &recipient);
+    assert!(client.get_release_request(&escrow_id).unwrap().approved_at.is_none());
+
+    assert_eq!(
+        client.try_release_escrow(&escrow_id, &recipient, &token.address),
+        Err(Ok(Error::NotApproved))
+    );
+    assert_eq!(
+        client.try_approve_release_request(&escrow_id, &recipient),
+        Err(Ok(Error::WrongSender))
+    );
+
+    client.approve_release_request(&escrow_id, &sender);
+    assert_eq!(
+        client.try_approve_release_request(&escrow_id, &sender),
+        Err(Ok(Error::AlreadyApproved))
+    );
+
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+    assert_eq!(token.balance(&recipient), 1000);
+}
+
+// ============================================================================
+// ESCROW CATEGORY TESTS
+// ============================================================================
+
+#[test]
+fn test_payroll_category_applies_fee_kyc_and_expiration() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, _, asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let payroll = symbol_short!("payroll");
+    let memo = String::from_str(&env, "");
+    let request = EscrowCreateRequest {
+        recipient: recipient.clone(),
+        amount: 1000,
+        asset: asset.clone(),
+        expiration_timestamp: 0,
+        memo: memo.clone(),
+    };
+    client.set_platform_fee(&admin, &500);
+
+    assert_eq!(
+        client.try_create_escrow_in_category(&sender, &request, &payroll),
+        Err(Ok(Error::TemplateNotFound))
+    );
+
+    client.set_category_rules(
+        &admin,
+        &payroll,
+        &Some(CategoryRules {
+            platform_fee_bps: 100,
+            kyc_required: true,
+            expiration_offset: 3600,
+        }),
+    );
+    assert_eq!(client.get_category_rules(&payroll).unwrap().platform_fee_bps, 100);
+
+    // KYC is off contract-wide, but payroll requires it.
+    let plain = client.create_escrow(&sender, &recipient, &1000, &asset, &5000, &memo);
+    assert_eq!(
+        client.try_create_escrow_in_category(&sender, &request, &payroll),
+        Err(Ok(Error::KycNotConfigured))
+    );
+
+    client.configure_kyc(&admin, &Address::generate(&env), &false, &86400);
+    client.add_to_whitelist(&admin, &sender, &0);
+    client.add_to_whitelist(&admin, &recipient, &0);
+    let escrow_id = client.create_escrow_in_category(&sender, &request, &payroll);
+
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert!(escrow.kyc_compliant);
+    assert_eq!(escrow.release_conditions.expiration_timestamp, 4600);
+    assert_eq!(client.get_escrow_category(&escrow_id), Some(payroll));
+    assert_eq!(client.get_escrow_category(&plain), None);
+
+    assert_eq!(client.get_escrow_fee_breakdown(&escrow_id).platform_fee, 10);
+    assert_eq!(client.get_escrow_fee_breakdown(&plain).platform_fee, 50);
+}
+
+// ============================================================================
+// HASH-LOCKED CLAIM TESTS
+// ============================================================================
+
+#[test]
+fn test_claimable_escrow_releases_only_for_correct_preimage() {
+    let env = Env::default();
+    let (client, admin, sender, _recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let secret = Bytes::from_slice(&env, b"gift-card-secret");
+    let hash_lock: BytesN<32> = env.crypto().sha256(&secret).into();
+    let escrow_id = client.create_claimable(&sender, &1000, &asset, &hash_lock, &5000);
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    assert_eq!(client.get_hash_lock(&escrow_id), Some(hash_lock));
+
+    // Nobody can release an unclaimed escrow through the normal path.
+    assert_eq!(
+        client.try_release_escrow(&escrow_id, &admin, &token.address),
+        Err(Ok(Error::NotApproved))
+    );
+
+    let claimer = Address::generate(&env);
+    let wrong = Bytes::from_slice(&env, b"guess");
+    assert_eq!(
+        client.try_claim(&escrow_id, &claimer, &wrong, &token.address),
+        Err(Ok(Error::HashMismatch))
+    );
+
+    client.claim(&escrow_id, &claimer, &secret, &token.address);
+    assert_eq!(token.balance(&claimer), 1000);
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.recipient, claimer);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_claim(&escrow_id, &other, &secret, &token.address),
+        Err(Ok(Error::InvalidStatus))
+    );
+}
+
+#[test]
+fn test_unclaimed_escrow_refunds_after_expiry() {
+    let env = Env::default();
+    let (client, _admin, sender, _recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let secret = Bytes::from_slice(&env, b"airdrop");
+    let hash_lock: BytesN<32> = env.crypto().sha256(&secret).into();
+    let escrow_id = client.create_claimable(&sender, &1000, &asset, &hash_lock, &2000);
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+
+    env.ledger().with_mut(|li| li.timestamp = 2001);
+    assert_eq!(
+        client.try_claim(&escrow_id, &Address::generate(&env), &secret, &token.address),
+        Err(Ok(Error::Expired))
+    );
+
+    client.refund_escrow(&escrow_id, &sender, &token.address, &RefundReason::Expiration);
+    assert_eq!(token.balance(&sender), 1000);
+}
+
+#[test]
+fn test_htlc_claim_reveals_preimage() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let secret = Bytes::from_slice(&env, b"swap-secret");
+    let hash_lock: BytesN<32> = env.crypto().sha256(&secret).into();
+    let escrow_id = client.create_htlc(&sender, &recipient, &1000, &asset, &hash_lock, &5000);
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+
+    assert_eq!(
+        client.try_release_escrow(&escrow_id, &recipient, &token.address),
+        Err(Ok(Error::NotApproved))
+    );
+    assert_eq!(
+        client.try_claim(&escrow_id, &Address::generate(&env), &secret, &token.address),
+        Err(Ok(Error::UnauthorizedCaller))
+    );
+    assert_eq!(
+        client.try_refund_escrow(&escrow_id, &sender, &token.address, &RefundReason::SenderRequest),
+        Err(Ok(Error::TimestampNotReached))
+    );
+
+    client.claim(&escrow_id, &recipient, &secret, &token.address);
+    assert_eq!(token.balance(&recipient), 1000);
+    assert_eq!(client.get_revealed_preimage(&escrow_id), Some(secret.clone()));
+
+    let claimed = symbol_short!("claimed");
+    let (_, _, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            topics.len() > 2
+                && Symbol::try_from_val(&env, &topics.get(2).unwrap()).ok() == Some(claimed.clone())
+        })
+        .unwrap();
+    assert_eq!(
+        GpayEvent::from_val(&env, &data).data,
+        EventData::PreimageRevealed(escrow_id, secret)
+    );
+}
+
+#[test]
+fn test_htlc_refunds_only_after_timelock() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let secret = Bytes::from_slice(&env, b"swap-secret");
+    let hash_lock: BytesN<32> = env.crypto().sha256(&secret).into();
+    let escrow_id = client.create_htlc(&sender, &recipient, &1000, &asset, &hash_lock, &2000);
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+
+    assert_eq!(
+        client.try_refund_escrow(&escrow_id, &sender, &token.address, &RefundReason::Expiration),
+        Err(Ok(Error::TimestampNotReached))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 2001);
+    assert_eq!(
+        client.try_claim(&escrow_id, &recipient, &secret, &token.address),
+        Err(Ok(Error::Expired))
+    );
+    client.refund_escrow(&escrow_id, &sender, &token.address, &RefundReason::Expiration);
+    assert_eq!(token.balance(&sender), 1000);
+    assert_eq!(client.get_revealed_preimage(&escrow_id), None);
+}
+
+#[test]
+fn test_htlc_sender_cannot_cancel_before_timelock() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let secret = Bytes::from_slice(&env, b"swap-secret");
+    let hash_lock: BytesN<32> = env.crypto().sha256(&secret).into();
+    let escrow_id = client.create_htlc(&sender, &recipient, &1000, &asset, &hash_lock, &2000);
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+
+    assert_eq!(
+        client.try_cancel_escrow(
+            &escrow_id,
+            &sender,
+            &token.address,
+            &String::from_str(&env, "changed my mind"),
+        ),
+        Err(Ok(Error::TimestampNotReached))
+    );
+    assert_eq!(token.balance(&sender), 0);
+
+    client.claim(&escrow_id, &recipient, &secret, &token.address);
+    assert_eq!(token.balance(&recipient), 1000);
+}
+
+// ============================================================================
+// DUST THRESHOLD TESTS
+// ============================================================================
+
+#[test]
+fn test_partial_release_sweeps_dust_remainder() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    let fee_wallet = Address::generate(&env);
+    client.set_fee_wallet(&admin, &fee_wallet);
+    client.set_dust_threshold(&admin, &asset.code, &10);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.enable_partial_release(&escrow_id, &sender);
+    client.release_partial(&escrow_id, &recipient, &token.address, &995);
+
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(escrow.released_amount, 1000);
+    assert_eq!(token.balance(&recipient), 995);
+    assert_eq!(token.balance(&fee_wallet), 5);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_release_escrow_finalizes_dust_balance() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.enable_partial_release(&escrow_id, &sender);
+    client.release_partial(&escrow_id, &recipient, &token.address, &996);
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().status, EscrowStatus::Funded);
+
+    // With no fee wallet the sub-dust remainder returns to the sender.
+    client.set_dust_threshold(&admin, &asset.code, &10);
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!(token.balance(&recipient), 996);
+    assert_eq!(token.balance(&sender), 4);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_release_escrow_pays_small_escrow_below_dust_threshold() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    let fee_wallet = Address::generate(&env);
+    client.set_fee_wallet(&admin, &fee_wallet);
+    client.set_dust_threshold(&admin, &asset.code, &10);
+    token_admin.mint(&sender, &5);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &5,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &5, &token.address);
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+
+    // The release itself is never swept as dust.
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().status, EscrowStatus::Released);
+    assert_eq!(token.balance(&recipient), 5);
+    assert_eq!(token.balance(&fee_wallet), 0);
+}
+
+// ============================================================================
+// DEFAULT EXPIRATION TESTS
+// ============================================================================
+
+#[test]
+fn test_zero_expiration_uses_default_offset() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, _, asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let memo = String::from_str(&env, "");
+
+    // Without a default, 0 is stored as given.
+    let legacy = client.create_escrow(&sender, &recipient, &1000, &asset, &0, &memo);
+    assert_eq!(
+        client.get_escrow(&legacy).unwrap().release_conditions.expiration_timestamp,
+        0
+    );
+
+    client.set_default_expiration_offset(&admin, &86400);
+    assert_eq!(client.get_default_expiration_offset(), Some(86400));
+
+    let escrow_id = client.create_escrow(&sender, &recipient, &1000, &asset, &0, &memo);
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.release_conditions.expiration_timestamp, 1000 + 86400);
+
+    // An explicit expiration is still honored as given.
+    let explicit = client.create_escrow(&sender, &recipient, &1000, &asset, &5000, &memo);
+    assert_eq!(
+        client.get_escrow(&explicit).unwrap().release_conditions.expiration_timestamp,
+        5000
+    );
+}
+
+// ============================================================================
+// Keeper Sweep Tests
+// ============================================================================
+
+#[test]
+fn test_sweep_expired_pays_keeper_bounty() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    let keeper = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &3000);
+    client.set_processing_fee(&admin, &100);
+    client.set_keeper_bounty(&admin, &5, &8);
+
+    let memo = String::from_str(&env, "");
+    let first = client.create_escrow(&sender, &recipient, &1000, &asset, &2000, &memo);
+    let second = client.create_escrow(&sender, &recipient, &1000, &asset, &2000, &memo);
+    let live = client.create_escrow(&sender, &recipient, &1000, &asset, &5000, &memo);
+    client.deposit(&first, &sender, &1000, &token.address);
+    client.deposit(&second, &sender, &1000, &token.address);
+    client.deposit(&live, &sender, &1000, &token.address);
+
+    env.ledger().with_mut(|li| li.timestamp = 3000);
+
+    let ids = Vec::from_array(&env, [first, second, live]);
+    let swept = client.sweep_expired(&keeper, &ids, &token.address);
+    assert_eq!(swept, 2);
+
+    assert_eq!(client.get_escrow(&first).unwrap().status, EscrowStatus::Expired);
+    assert_eq!(client.get_escrow(&second).unwrap().status, EscrowStatus::Expired);
+    assert_eq!(client.get_escrow(&live).unwrap().status, EscrowStatus::Funded);
+
+    // 10 processing fee each; the 5 per-escrow bounty is capped at 8 in total.
+    assert_eq!(token.balance(&keeper), 8);
+    assert_eq!(token.balance(&sender), 1980);
+    assert_eq!(token.balance(&admin), 12);
+
+    assert_eq!(client.sweep_expired(&keeper, &ids, &token.address), 0);
+}
+
+#[test]
+fn test_sweep_expired_skips_ineligible_and_reports_counts() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    let keeper = Address::generate(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &3000);
+
+    let memo = String::from_str(&env, "");
+    let expired = client.create_escrow(&sender, &recipient, &1000, &asset, &2000, &memo);
+    let live = client.create_escrow(&sender, &recipient, &1000, &asset, &5000, &memo);
+    client.deposit(&expired, &sender, &1000, &token.address);
+    client.deposit(&live, &sender, &1000, &token.address);
+
+    env.ledger().with_mut(|li| li.timestamp = 3000);
+
+    // The unknown id and the live escrow are skipped, not fatal.
+    let ids = Vec::from_array(&env, [expired, live, 999]);
+    assert_eq!(client.sweep_expired(&keeper, &ids, &token.address), 1);
+
+    let events = env.events().all();
+    let summary = events.last().unwrap();
+    assert_eq!(
+        Symbol::try_from_val(&env, &summary.1.get(2).unwrap()).unwrap(),
+        symbol_short!("swept")
+    );
+    assert_eq!(
+        GpayEvent::from_val(&env, &summary.2).data,
+        EventData::ValueChanged(symbol_short!("swept"), 1, 2)
+    );
+
+    assert_eq!(client.get_escrow(&expired).unwrap().status, EscrowStatus::Expired);
+    assert_eq!(client.get_escrow(&live).unwrap().status, EscrowStatus::Funded);
+    assert_eq!(token.balance(&sender), 2000);
+}
+
+#[test]
+fn test_admin_sweep_expired_refunds_only_expired_funded_escrows() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &3000);
+    client.set_processing_fee(&admin, &100);
+    client.set_keeper_bounty(&admin, &5, &8);
+
+    let memo = String::from_str(&env, "");
+    let expired = client.create_escrow(&sender, &recipient, &1000, &asset, &2000, &memo);
+    let live = client.create_escrow(&sender, &recipient, &1000, &asset, &5000, &memo);
+    client.deposit(&expired, &sender, &1000, &token.address);
+    client.deposit(&live, &sender, &1000, &token.address);
+
+    env.ledger().with_mut(|li| li.timestamp = 3000);
+
+    let ids = Vec::from_array(&env, [expired, live]);
+    assert_eq!(
+        client.try_admin_sweep_expired(&sender, &ids, &token.address),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(client.admin_sweep_expired(&admin, &ids, &token.address), 1);
+
+    assert_eq!(client.get_escrow(&expired).unwrap().status, EscrowStatus::Expired);
+    assert_eq!(client.get_escrow(&live).unwrap().status, EscrowStatus::Funded);
+
+    // No keeper bounty on the admin sweep: the whole fee goes to the admin.
+    assert_eq!(token.balance(&sender), 1990);
+    assert_eq!(token.balance(&admin), 10);
+}
+
+// ============================================================================
+// Approval Policy Tests
+// ============================================================================
+
+#[test]
+fn test_recipient_self_approval_can_be_disabled() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, _token, asset) = setup_test(&env);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, ""),
+    );
+    assert!(client.recipient_can_self_approve(&escrow_id));
+
+    assert_eq!(
+        client.try_set_recipient_can_self_approve(&escrow_id, &recipient, &false),
+        Err(Ok(Error::WrongSender))
+    );
+    client.set_recipient_can_self_approve(&escrow_id, &sender, &false);
+
+    assert_eq!(
+        client.try_add_approval(&escrow_id, &recipient),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.add_approval(&escrow_id, &sender);
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().release_conditions.current_approvals,
+        1
+    );
+}
+
+// ============================================================================
+// Amendment History Tests
+// ============================================================================
+
+#[test]
+fn test_extend_escrow_records_amendments() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, _token, asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, ""),
+    );
+    client.extend_escrow(&escrow_id, &sender, &3000);
+    client.extend_escrow(&escrow_id, &recipient, &4000);
+
+    let history = client.get_amendment_history(&escrow_id);
+    assert_eq!(history.len(), 2);
+    let first = history.get(0).unwrap();
+    assert_eq!(
+        (first.field, first.old_value, first.new_value, first.actor),
+        (symbol_short!("expiry"), 2000, 3000, sender.clone())
+    );
+    let second = history.get(1).unwrap();
+    assert_eq!(
+        (second.old_value, second.new_value, second.actor),
+        (3000, 4000, recipient.clone())
+    );
+}
+
+// ============================================================================
+// Release Callback Tests
+// ============================================================================
+
+mod recording_callback {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Env};
+
+    #[contract]
+    pub struct RecordingCallback;
+
+    #[contractimpl]
+    impl RecordingCallback {
+        pub fn on_release(env: Env, escrow_id: u64, amount: i128) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("last"), &(escrow_id, amount));
+        }
+
+        pub fn last(env: Env) -> Option<(u64, i128)> {
+            env.storage().instance().get(&symbol_short!("last"))
+        }
+    }
+}
+
+mod reentrant_callback {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    #[contract]
+    pub struct ReentrantCallback;
+
+    #[contractimpl]
+    impl ReentrantCallback {
+        pub fn arm(env: Env, escrow: Address, recipient: Address, token: Address) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("target"), &(escrow, recipient, token));
+        }
+
+        pub fn on_release(env: Env, escrow_id: u64, _amount: i128) {
+            let (escrow, recipient, token): (Address, Address, Address) =
+                env.storage().instance().get(&symbol_short!("target")).unwrap();
+            let client = gpay_remit_contracts::payment_escrow::PaymentEscrowContractClient::new(&env, &escrow);
+            let blocked = client
+                .try_release_escrow(&escrow_id, &recipient, &token)
+                .is_err();
+            env.storage()
+                .instance()
+                .set(&symbol_short!("blocked"), &blocked);
+        }
+
+        pub fn blocked(env: Env) -> Option<bool> {
+            env.storage().instance().get(&symbol_short!("blocked"))
+        }
+    }
+}
+
+use recording_callback::{RecordingCallback, RecordingCallbackClient};
+use reentrant_callback::{ReentrantCallback, ReentrantCallbackClient};
+
+fn callback_status(env: &Env) -> Option<Symbol> {
+    env.events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| {
+            topics.len() > 2
+                && Symbol::try_from_val(env, &topics.get(2).unwrap())
+                    .map(|s| s == symbol_short!("cb_call"))
+                    .unwrap_or(false)
+        })
+        .last()
+        .map(|(_, _, data)| GpayEvent::from_val(env, &data).status)
+}
+
+#[test]
+fn test_release_callback_invoked_when_allowed() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let callback_id = env.register_contract(None, RecordingCallback);
+    let callback = RecordingCallbackClient::new(&env, &callback_id);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+
+    let config = ReleaseCallback {
+        contract: callback_id.clone(),
+        enabled: true,
+    };
+    assert_eq!(
+        client.try_set_release_callback(&escrow_id, &sender, &Some(config.clone())),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_callback_whitelisted(&sender, &callback_id, &true),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.set_callback_whitelisted(&admin, &callback_id, &true);
+    assert!(client.is_callback_whitelisted(&callback_id));
+    assert_eq!(
+        client.try_set_release_callback(&escrow_id, &recipient, &Some(config.clone())),
+        Err(Ok(Error::WrongSender))
+    );
+    client.set_release_callback(&escrow_id, &sender, &Some(config.clone()));
+    assert_eq!(client.get_release_callback(&escrow_id), Some(config));
+
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+
+    assert_eq!(callback_status(&env), Some(symbol_short!("ok")));
+    assert_eq!(callback.last(), Some((escrow_id, 1000)));
+    assert_eq!(token.balance(&recipient), 1000);
+}
+
+#[test]
+fn test_release_callback_skipped_without_opt_in() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let callback_id = env.register_contract(None, RecordingCallback);
+    let callback = RecordingCallbackClient::new(&env, &callback_id);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.set_callback_whitelisted(&admin, &callback_id, &true);
+    client.set_release_callback(
+        &escrow_id,
+        &sender,
+        &Some(ReleaseCallback {
+            contract: callback_id,
+            enabled: false,
+        }),
+    );
+
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+
+    assert_eq!(callback_status(&env), None);
+    assert_eq!(callback.last(), None);
+}
+
+#[test]
+fn test_release_callback_skipped_once_delisted() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let callback_id = env.register_contract(None, RecordingCallback);
+    let callback = RecordingCallbackClient::new(&env, &callback_id);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.set_callback_whitelisted(&admin, &callback_id, &true);
+    client.set_release_callback(
+        &escrow_id,
+        &sender,
+        &Some(ReleaseCallback {
+            contract: callback_id.clone(),
+            enabled: true,
+        }),
+    );
+    client.set_callback_whitelisted(&admin, &callback_id, &false);
+
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+
+    assert_eq!(callback_status(&env), None);
+    assert_eq!(callback.last(), None);
+    assert_eq!(token.balance(&recipient), 1000);
+}
+
+#[test]
+fn test_release_callback_cannot_reenter_release() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let callback_id = env.register_contract(None, ReentrantCallback);
+    let callback = ReentrantCallbackClient::new(&env, &callback_id);
+    callback.arm(&client.address, &recipient, &token.address);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.set_callback_whitelisted(&admin, &callback_id, &true);
+    client.set_release_callback(
+        &escrow_id,
+        &sender,
+        &Some(ReleaseCallback {
+            contract: callback_id,
+            enabled: true,
+        }),
+    );
+
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+
+    assert_eq!(callback.blocked(), Some(true));
+    assert_eq!(token.balance(&recipient), 1000);
+    assert_eq!(token.balance(&client.address), 0);
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().status,
+        EscrowStatus::Released
+    );
+}
+
+// ============================================================================
+// Fee Split Tests
+// ============================================================================
+
+#[test]
+fn test_fee_split_assigns_remainder_to_primary() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.set_platform_fee(&admin, &100);
+    token_admin.mint(&sender, &1000);
+
+    let first = Address::generate(&env);
+    let primary = Address::generate(&env);
+    let third = Address::generate(&env);
+    let split = FeeSplit {
+        shares: vec![
+            &env,
+            FeeShare { beneficiary: first.clone(), weight: 1 },
+            FeeShare { beneficiary: primary.clone(), weight: 1 },
+            FeeShare { beneficiary: third.clone(), weight: 1 },
+        ],
+        primary: 1,
+    };
+    client.set_fee_split(&admin, &Some(split.clone()));
+    assert_eq!(client.get_fee_split(), Some(split.clone()));
+    assert_eq!(client.compute_fee_split(&10, &split), vec![&env, 3, 4, 3]);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+
+    assert_eq!(token.balance(&first), 3);
+    assert_eq!(token.balance(&primary), 4);
+    assert_eq!(token.balance(&third), 3);
+    assert_eq!(token.balance(&admin), 0);
+    assert_eq!(token.balance(&recipient), 990);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_set_fee_split_rejects_invalid_primary() {
+    let env = Env::default();
+    let (client, admin, _sender, _recipient, _token, _asset) = setup_test(&env);
+
+    let split = FeeSplit {
+        shares: vec![
+            &env,
+            FeeShare { beneficiary: Address::generate(&env), weight: 1 },
+        ],
+        primary: 1,
+    };
+    assert_eq!(
+        client.try_set_fee_split(&admin, &Some(split)),
+        Err(Ok(Error::InvalidFeePercentage))
+    );
+}
+
+// ============================================================================
+// Sender Escrow Index Tests
+// ============================================================================
+
+#[test]
+fn test_get_escrows_by_sender_pages_only_owned_escrows() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, _token, asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let other_sender = Address::generate(&env);
+    let memo = String::from_str(&env, "");
+
+    client.create_escrow(&sender, &recipient, &100, &asset, &5000, &memo);
+    client.create_escrow(&other_sender, &recipient, &200, &asset, &5000, &memo);
+    let second = client.create_escrow(&sender, &recipient, &300, &asset, &5000, &memo);
+    client.create_escrow(&sender, &recipient, &400, &asset, &5000, &memo);
+
+    let amounts = |page: Vec<gpay_remit_contracts::payment_escrow::Escrow>| {
+        page.iter().map(|escrow| escrow.amount).collect::<std::vec::Vec<i128>>()
+    };
+
+    assert_eq!(
+        amounts(client.get_escrows_by_sender(&sender, &0, &2)),
+        std::vec![100, 300]
+    );
+    assert_eq!(
+        amounts(client.get_escrows_by_sender(&sender, &second, &2)),
+        std::vec![400]
+    );
+    assert_eq!(
+        amounts(client.get_escrows_by_sender(&other_sender, &0, &10)),
+        std::vec![200]
+    );
+    assert_eq!(
+        client.try_get_escrows_by_sender(&sender, &0, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+}
+
+// ============================================================================
+// Accounting Hook Tests
+// ============================================================================
+
+mod accounting_hook {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    #[contract]
+    pub struct AccountingLedger;
+
+    #[contractimpl]
+    impl AccountingLedger {
+        pub fn on_release(env: Env, escrow_id: u64, recipient: Address, amount: i128, fee: i128) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("entry"), &(escrow_id, recipient, amount, fee));
+        }
+
+        pub fn entry(env: Env) -> Option<(u64, Address, i128, i128)> {
+            env.storage().instance().get(&symbol_short!("entry"))
+        }
+    }
+}
+
+mod failing_accounting_hook {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct BrokenLedger;
+
+    #[contractimpl]
+    impl BrokenLedger {
+        pub fn on_release(_env: Env, _escrow_id: u64, _recipient: Address, _amount: i128, _fee: i128) {
+            panic!("ledger unavailable");
+        }
+    }
+}
+
+use accounting_hook::{AccountingLedger, AccountingLedgerClient};
+use failing_accounting_hook::BrokenLedger;
+
+#[test]
+fn test_accounting_hook_receives_release_details() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.set_platform_fee(&admin, &100);
+    token_admin.mint(&sender, &1000);
+
+    let hook_id = env.register_contract(None, AccountingLedger);
+    let ledger = AccountingLedgerClient::new(&env, &hook_id);
+    client.set_accounting_hook(&admin, &hook_id);
+    assert_eq!(client.get_accounting_hook(), Some(hook_id));
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+
+    assert_eq!(ledger.entry(), Some((escrow_id, recipient.clone(), 990, 10)));
+}
+
+#[test]
+fn test_failing_accounting_hook_does_not_block_release() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let hook_id = env.register_contract(None, BrokenLedger);
+    client.set_accounting_hook(&admin, &hook_id);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+
+    assert_eq!(token.balance(&recipient), 1000);
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().status,
+        EscrowStatus::Released
+    );
+}
+
+// ============================================================================
+// Recipient Rejection Tests
+// ============================================================================
+
+#[test]
+fn test_recipient_rejection_refunds_sender() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    assert_eq!(
+        client.try_reject_escrow(&escrow_id, &recipient, &token.address),
+        Err(Ok(Error::InvalidStatus))
+    );
+
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    assert_eq!(
+        client.try_reject_escrow(&escrow_id, &sender, &token.address),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.reject_escrow(&escrow_id, &recipient, &token.address);
+
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.status, EscrowStatus::Refunded);
+    assert_eq!(escrow.refunded_amount, 1000);
+    assert_eq!(token.balance(&sender), 1000);
+    assert_eq!(token.balance(&recipient), 0);
+    assert_eq!(
+        client.try_release_escrow(&escrow_id, &recipient, &token.address),
+        Err(Ok(Error::NotApproved))
+    );
+}
+
+// ============================================================================
+// Admin Transfer Tests
+// ============================================================================
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let env = Env::default();
+    let (client, admin, _sender, _recipient, _token, _asset) = setup_test(&env);
+    let new_admin = Address::generate(&env);
+
+    assert_eq!(
+        client.try_accept_admin(&new_admin),
+        Err(Ok(Error::NoPendingAdmin))
+    );
+    assert_eq!(
+        client.try_propose_admin(&new_admin, &new_admin),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    client.propose_admin(&admin, &new_admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    // The current admin keeps control until the proposal is accepted.
+    client.set_platform_fee(&admin, &100);
+
+    client.accept_admin(&new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+    client.set_platform_fee(&new_admin, &200);
+    assert_eq!(
+        client.try_set_platform_fee(&admin, &300),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_accept_admin_rejects_wrong_acceptor() {
+    let env = Env::default();
+    let (client, admin, _sender, _recipient, _token, _asset) = setup_test(&env);
+    let new_admin = Address::generate(&env);
+    let impostor = Address::generate(&env);
+
+    client.propose_admin(&admin, &new_admin);
+    assert_eq!(
+        client.try_accept_admin(&impostor),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(client.get_pending_admin(), Some(new_admin));
+}
+
+#[test]
+fn test_propose_admin_overwrites_pending_proposal() {
+    let env = Env::default();
+    let (client, admin, _sender, _recipient, _token, _asset) = setup_test(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+
+    client.propose_admin(&admin, &first);
+    client.propose_admin(&admin, &second);
+    assert_eq!(client.get_pending_admin(), Some(second.clone()));
+
+    assert_eq!(
+        client.try_accept_admin(&first),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.accept_admin(&second);
+    client.set_platform_fee(&second, &100);
+}
+
+// ============================================================================
+// Condition Removal Tests
+// ============================================================================
+
+#[test]
+fn test_remove_condition_keeps_remaining_order() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, _token, asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.add_condition(&escrow_id, &sender, &ConditionType::Timestamp, &true, &1500);
+    client.add_condition(&escrow_id, &sender, &ConditionType::RecipientAck, &true, &0);
+    client.add_condition(&escrow_id, &sender, &ConditionType::MinDeposited, &false, &500);
+
+    assert_eq!(
+        client.try_remove_condition(&escrow_id, &recipient, &1),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_remove_condition(&escrow_id, &sender, &3),
+        Err(Ok(Error::ConditionIndexOutOfRange))
+    );
+    client.remove_condition(&escrow_id, &sender, &1);
+
+    let conditions = client.get_escrow(&escrow_id).unwrap().release_conditions.conditions;
+    assert_eq!(conditions.len(), 2);
+    let first = conditions.get(0).unwrap();
+    assert_eq!((first.condition_type, first.threshold_value), (ConditionType::Timestamp, 1500));
+    let second = conditions.get(1).unwrap();
+    assert_eq!(
+        (second.condition_type, second.threshold_value),
+        (ConditionType::MinDeposited, 500)
+    );
+
+    client.clear_conditions(&escrow_id, &admin);
+    assert!(client
+        .get_escrow(&escrow_id)
+        .unwrap()
+        .release_conditions
+        .conditions
+        .is_empty());
+}
+
+// ============================================================================
+// Release Condition Enforcement Tests
+// ============================================================================
+
+#[test]
+fn test_required_oracle_condition_blocks_release_until_proven() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.add_condition(&escrow_id, &sender, &ConditionType::OraclePrice, &true, &950);
+
+    assert_eq!(
+        client.try_release_escrow(&escrow_id, &recipient, &token.address),
+        Err(Ok(Error::ConditionsNotMet))
+    );
+    assert_eq!(
+        client.try_release_escrow_with_proof(&escrow_id, &recipient, &token.address, &900),
+        Err(Ok(Error::ConditionsNotMet))
+    );
+    assert_eq!(token.balance(&recipient), 0);
+
+    client.release_escrow_with_proof(&escrow_id, &recipient, &token.address, &960);
+    assert_eq!(token.balance(&recipient), 1000);
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().status,
+        EscrowStatus::Released
+    );
+}
+
+// ============================================================================
+// Open Escrow Cap Tests
+// ============================================================================
+
+#[test]
+fn test_open_escrow_cap_blocks_creation_until_one_settles() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+    let memo = String::from_str(&env, "");
+
+    client.set_max_open_escrows(&admin, &Some(2));
+    assert_eq!(client.get_max_open_escrows(), Some(2));
+
+    let first = client.create_escrow(&sender, &recipient, &1000, &asset, &5000, &memo);
+    client.create_escrow(&sender, &recipient, &500, &asset, &5000, &memo);
+    assert_eq!(client.get_open_escrow_count(&sender), 2);
+    assert_eq!(
+        client.try_create_escrow(&sender, &recipient, &300, &asset, &5000, &memo),
+        Err(Ok(Error::RateLimitExceeded))
+    );
+
+    // Other senders are unaffected.
+    let other_sender = Address::generate(&env);
+    client.create_escrow(&other_sender, &recipient, &300, &asset, &5000, &memo);
+
+    client.deposit(&first, &sender, &1000, &token.address);
+    client.release_escrow(&first, &recipient, &token.address);
+    assert_eq!(client.get_open_escrow_count(&sender), 1);
+
+    client.create_escrow(&sender, &recipient, &300, &asset, &5000, &memo);
+    assert_eq!(client.get_open_escrow_count(&sender), 2);
+}
+
+// ============================================================================
+// Recipient Reassignment Tests
+// ============================================================================
+
+#[test]
+fn test_update_recipient_before_funding() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    let corrected = Address::generate(&env);
+
+    assert_eq!(
+        client.try_update_recipient(&escrow_id, &recipient, &corrected),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_update_recipient(&escrow_id, &sender, &sender),
+        Err(Ok(Error::SameSenderRecipient))
+    );
+    client.update_recipient(&escrow_id, &sender, &corrected);
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().recipient, corrected);
+
+    let fixed_by_admin = Address::generate(&env);
+    client.update_recipient(&escrow_id, &admin, &fixed_by_admin);
+
+    let history = client.get_amendment_history(&escrow_id);
+    assert_eq!(history.len(), 2);
+    let last = history.get(1).unwrap();
+    assert_eq!(
+        (last.field, last.actor),
+        (symbol_short!("recipient"), admin.clone())
+    );
+
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.release_escrow(&escrow_id, &fixed_by_admin, &token.address);
+    assert_eq!(token.balance(&fixed_by_admin), 1000);
+}
+
+#[test]
+fn test_update_recipient_rejected_after_partial_deposit() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &400, &token.address);
+
+    assert_eq!(
+        client.try_update_recipient(&escrow_id, &sender, &Address::generate(&env)),
+        Err(Ok(Error::AlreadyFunded))
+    );
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().recipient, recipient);
+}
+
+// ============================================================================
+// Split Settlement Tests
+// ============================================================================
+
+#[test]
+fn test_split_settle_sixty_forty() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.set_platform_fee(&admin, &100);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+
+    assert_eq!(
+        client.try_split_settle(&escrow_id, &admin, &token.address, &600, &300),
+        Err(Ok(Error::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_split_settle(&escrow_id, &sender, &token.address, &600, &400),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    client.split_settle(&escrow_id, &admin, &token.address, &600, &400);
+
+    // 1% platform fee applies to the released share only.
+    assert_eq!(token.balance(&recipient), 594);
+    assert_eq!(token.balance(&admin), 6);
+    assert_eq!(token.balance(&sender), 400);
+    assert_eq!(token.balance(&client.address), 0);
+
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.status, EscrowStatus::Released);
+    assert_eq!((escrow.released_amount, escrow.refunded_amount), (600, 400));
+    assert_eq!(
+        client.try_split_settle(&escrow_id, &admin, &token.address, &0, &0),
+        Err(Ok(Error::InvalidStatus))
+    );
+}
+
+// ============================================================================
+// Vesting Schedule Tests
+// ============================================================================
+
+#[test]
+fn test_release_vested_pays_incremental_tranches() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.set_platform_fee(&admin, &100);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &10000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+
+    assert_eq!(
+        client.try_set_vesting_schedule(&escrow_id, &sender, &vec![&env, (2000u64, 500i128), (3000, 1200)]),
+        Err(Ok(Error::InvalidAmount))
+    );
+    let schedule = vec![&env, (2000u64, 300i128), (3000, 600), (4000, 1000)];
+    client.set_vesting_schedule(&escrow_id, &sender, &schedule);
+    assert_eq!(client.get_vesting_schedule(&escrow_id), Some(schedule));
+
+    assert_eq!(
+        client.try_release_vested(&escrow_id, &recipient, &token.address),
+        Err(Ok(Error::NoFundsAvailable))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 2500);
+    assert_eq!(client.release_vested(&escrow_id, &recipient, &token.address), 297);
+    assert_eq!(token.balance(&recipient), 297);
+
+    env.ledger().with_mut(|li| li.timestamp = 3000);
+    assert_eq!(client.release_vested(&escrow_id, &recipient, &token.address), 297);
+    assert_eq!(token.balance(&recipient), 594);
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().status,
+        EscrowStatus::Funded
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 5000);
+    assert_eq!(client.release_vested(&escrow_id, &recipient, &token.address), 396);
+    assert_eq!(token.balance(&recipient), 990);
+    assert_eq!(token.balance(&admin), 10);
+
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.released_amount, 1000);
+    assert_eq!(escrow.status, EscrowStatus::Released);
+}
+
+#[test]
+fn test_vesting_escrow_blocks_other_release_paths() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &10000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.enable_partial_release(&escrow_id, &sender);
+    client.set_vesting_schedule(&escrow_id, &sender, &vec![&env, (2000u64, 1000i128)]);
+
+    assert_eq!(
+        client.try_release_escrow(&escrow_id, &recipient, &token.address),
+        Err(Ok(Error::ConditionsNotMet))
+    );
+    assert_eq!(
+        client.try_release_partial(&escrow_id, &recipient, &token.address, &500),
+        Err(Ok(Error::ConditionsNotMet))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 2500);
+    let (other_token, _) = create_token_contract(&env, &admin);
+    assert_eq!(
+        client.try_release_vested(&escrow_id, &recipient, &other_token.address),
+        Err(Ok(Error::TokenMismatch))
+    );
+    assert_eq!(client.release_vested(&escrow_id, &recipient, &token.address), 1000);
+    assert_eq!(token.balance(&recipient), 1000);
+}
+
+// ============================================================================
+// KYC Proof Signature Tests
+// ============================================================================
+
+fn sign_kyc_proof(
+    env: &Env,
+    signing_key: &ed25519_dalek::SigningKey,
+    account: &Address,
+    issuer: &Address,
+    expiry: u64,
+) -> BytesN<64> {
+    use ed25519_dalek::Signer;
+
+    let message = gpay_remit_contracts::kyc::proof_message(env, account, issuer, expiry);
+    let message: std::vec::Vec<u8> = message.iter().collect();
+    BytesN::from_array(env, &signing_key.sign(&message).to_bytes())
+}
+
+#[test]
+fn test_verify_kyc_proof_with_valid_signature() {
+    let env = Env::default();
+    let (client, admin, _sender, recipient, _token, _asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    client.configure_kyc(&admin, &Address::generate(&env), &false, &86400);
+
+    let issuer = Address::generate(&env);
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&[42u8; 32]);
+    let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    client.add_trusted_issuer(&admin, &issuer, &public_key);
+
+    let signature = sign_kyc_proof(&env, &signing_key, &recipient, &issuer, 5000);
+    assert!(client.verify_kyc_proof(&recipient, &signature, &issuer, &5000));
+    assert_eq!(
+        client.get_kyc_status(&recipient),
+        gpay_remit_contracts::kyc::KycStatus::Verified
+    );
+
+    // The expiry is part of the signed message and cannot be extended
+    let other = Address::generate(&env);
+    let signature = sign_kyc_proof(&env, &signing_key, &other, &issuer, 5000);
+    assert!(!client.verify_kyc_proof(&other, &signature, &issuer, &9000));
+}
+
+#[test]
+fn test_verify_kyc_proof_rejects_tampered_signature() {
+    let env = Env::default();
+    let (client, admin, _sender, recipient, _token, _asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    client.configure_kyc(&admin, &Address::generate(&env), &false, &86400);
+
+    let issuer = Address::generate(&env);
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&[42u8; 32]);
+    let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    client.add_trusted_issuer(&admin, &issuer, &public_key);
+
+    let mut signature = sign_kyc_proof(&env, &signing_key, &recipient, &issuer, 5000).to_array();
+    signature[10] ^= 0x01;
+    let signature = BytesN::from_array(&env, &signature);
+
+    assert!(!client.verify_kyc_proof(&recipient, &signature, &issuer, &5000));
+    assert_eq!(
+        client.get_kyc_status(&recipient),
+        gpay_remit_contracts::kyc::KycStatus::Unknown
+    );
+
+    // A proof from an issuer that was never registered is refused outright
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_verify_kyc_proof(&recipient, &signature, &stranger, &5000),
+        Err(Ok(Error::KycFailed))
+    );
+}
+
+// ============================================================================
+// KYC Tier Limit Tests
+// ============================================================================
+
+#[test]
+fn test_kyc_tier_limits_cap_escrow_amount() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, _token, asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    client.configure_kyc(&admin, &Address::generate(&env), &false, &86400);
+    let sender2 = Address::generate(&env);
+    client.add_to_whitelist(&admin, &sender, &0);
+    client.add_to_whitelist(&admin, &sender2, &0);
+    client.add_to_whitelist(&admin, &recipient, &0);
+
+    assert_eq!(
+        client.try_set_tier_limit(&admin, &1, &Some(0)),
+        Err(Ok(Error::InvalidAmount))
+    );
+    client.set_tier_limit(&admin, &1, &Some(1000));
+    assert_eq!(client.get_tier_limit(&1), Some(1000));
+    assert_eq!(client.get_tier_limit(&2), None);
+
+    client.set_kyc_tier(&admin, &sender, &1);
+    client.set_kyc_tier(&admin, &sender2, &2);
+    assert_eq!(client.get_kyc_tier(&sender), 1);
+
+    let memo = String::from_str(&env, "tiered");
+    assert_eq!(
+        client.try_create_escrow(&sender, &recipient, &1500, &asset, &5000, &memo),
+        Err(Ok(Error::KycLimitExceeded))
+    );
+    assert!(!client.is_kyc_eligible(&sender, &1500));
+    client.create_escrow(&sender, &recipient, &1000, &asset, &5000, &memo);
+
+    // Tier 2 has no limit configured
+    client.create_escrow(&sender2, &recipient, &50_000, &asset, &5000, &memo);
+
+    // Re-whitelisting keeps the assigned tier
+    client.add_to_whitelist(&admin, &sender, &0);
+    assert_eq!(client.get_kyc_tier(&sender), 1);
+}
+
+// ============================================================================
+// Batch Whitelist Tests
+// ============================================================================
+
+#[test]
+fn test_batch_whitelist_onboarding() {
+    let env = Env::default();
+    let (client, admin, sender, _recipient, _token, _asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let mut accounts = Vec::new(&env);
+    for _ in 0..5 {
+        accounts.push_back(Address::generate(&env));
+    }
+
+    assert_eq!(
+        client.try_batch_add_to_whitelist(&sender, &accounts, &0),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    assert_eq!(client.batch_add_to_whitelist(&admin, &accounts, &5000), 5);
+    let events = env.events().all();
+    let summary = events.last().unwrap();
+    assert_eq!(
+        Symbol::try_from_val(&env, &summary.1.get(2).unwrap()).unwrap(),
+        symbol_short!("kyc_badd")
+    );
+    for account in accounts.iter() {
+        assert_eq!(
+            client.get_kyc_status(&account),
+            gpay_remit_contracts::kyc::KycStatus::Verified
+        );
+    }
+
+    let mut removed = Vec::new(&env);
+    removed.push_back(accounts.get(0).unwrap());
+    removed.push_back(accounts.get(1).unwrap());
+    assert_eq!(client.batch_remove_from_whitelist(&admin, &removed), 2);
+    assert_eq!(
+        client.get_kyc_status(&accounts.get(0).unwrap()),
+        gpay_remit_contracts::kyc::KycStatus::Rejected
+    );
+    assert_eq!(
+        client.get_kyc_status(&accounts.get(2).unwrap()),
+        gpay_remit_contracts::kyc::KycStatus::Verified
+    );
+}
+
+#[test]
+fn test_batch_whitelist_rejects_oversized_batch() {
+    let env = Env::default();
+    let (client, admin, _sender, _recipient, _token, _asset) = setup_test(&env);
+
+    let mut accounts = Vec::new(&env);
+    for _ in 0..51 {
+        accounts.push_back(Address::generate(&env));
+    }
+
+    assert_eq!(
+        client.try_batch_add_to_whitelist(&admin, &accounts, &0),
+        Err(Ok(Error::RateLimitExceeded))
+    );
+    // Nothing from the rejected batch was written
+    assert_eq!(
+        client.get_kyc_status(&accounts.get(0).unwrap()),
+        gpay_remit_contracts::kyc::KycStatus::Unknown
+    );
+}
+
+// ============================================================================
+// KYC Proof Check Rate Limit Tests
+// ============================================================================
+
+#[test]
+fn test_kyc_proof_checks_are_rate_limited_per_window() {
+    let env = Env::default();
+    let (client, admin, _sender, recipient, _token, _asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    client.configure_kyc(&admin, &Address::generate(&env), &false, &86400);
+    let issuer = Address::generate(&env);
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&[42u8; 32]);
+    let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    client.add_trusted_issuer(&admin, &issuer, &public_key);
+
+    assert_eq!(
+        client.try_set_kyc_check_limit(&admin, &2, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+    client.set_kyc_check_limit(&admin, &2, &3600);
+    assert_eq!(client.get_kyc_check_limit(), Some((2, 3600)));
+
+    let signature = sign_kyc_proof(&env, &signing_key, &recipient, &issuer, 0);
+    assert!(client.verify_kyc_proof(&recipient, &signature, &issuer, &0));
+    assert!(client.verify_kyc_proof(&recipient, &signature, &issuer, &0));
+    assert_eq!(
+        client.try_verify_kyc_proof(&recipient, &signature, &issuer, &0),
+        Err(Ok(Error::KycRateLimited))
+    );
+
+    // Other accounts have their own counter
+    let other = Address::generate(&env);
+    let other_signature = sign_kyc_proof(&env, &signing_key, &other, &issuer, 0);
+    assert!(client.verify_kyc_proof(&other, &other_signature, &issuer, &0));
+
+    // Once the window has passed the counter starts over
+    env.ledger().with_mut(|li| li.timestamp = 1000 + 3600);
+    assert!(client.verify_kyc_proof(&recipient, &signature, &issuer, &0));
+
+    client.set_kyc_check_limit(&admin, &0, &0);
+    assert_eq!(client.get_kyc_check_limit(), None);
+}
+
+#[test]
+fn test_failed_kyc_proofs_count_toward_check_limit() {
+    let env = Env::default();
+    let (client, admin, _sender, recipient, _token, _asset) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    client.configure_kyc(&admin, &Address::generate(&env), &false, &86400);
+    let issuer = Address::generate(&env);
+    let signing_key = ed25519_dalek::SigningKey::from_bytes(&[42u8; 32]);
+    let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    client.add_trusted_issuer(&admin, &issuer, &public_key);
+    client.set_kyc_check_limit(&admin, &3, &3600);
+
+    let forged = BytesN::from_array(&env, &[7u8; 64]);
+    for _ in 0..3 {
+        assert!(!client.verify_kyc_proof(&recipient, &forged, &issuer, &0));
+    }
+
+    // A genuine proof is refused too until the window rolls over
+    let signature = sign_kyc_proof(&env, &signing_key, &recipient, &issuer, 0);
+    assert_eq!(
+        client.try_verify_kyc_proof(&recipient, &signature, &issuer, &0),
+        Err(Ok(Error::KycRateLimited))
+    );
+    assert_eq!(
+        client.get_kyc_status(&recipient),
+        gpay_remit_contracts::kyc::KycStatus::Unknown
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 1000 + 3600);
+    assert!(client.verify_kyc_proof(&recipient, &signature, &issuer, &0));
+}
+
+// ============================================================================
+// KYC Re-check On Release Tests
+// ============================================================================
+
+#[test]
+fn test_release_blocked_until_recipient_kyc_reverified() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.configure_kyc(&admin, &Address::generate(&env), &false, &86400);
+    client.add_to_whitelist(&admin, &sender, &0);
+    client.add_to_whitelist(&admin, &recipient, &1500);
+
+    token_admin.mint(&sender, &1000);
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.approve_escrow(&escrow_id, &admin);
+
+    assert!(!client.get_require_kyc_on_release(&escrow_id));
+    assert_eq!(
+        client.try_set_require_kyc_on_release(&sender, &escrow_id, &true),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.set_require_kyc_on_release(&admin, &escrow_id, &true);
+    assert!(client.get_require_kyc_on_release(&escrow_id));
+
+    // The recipient's verification lapses after funding
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    assert_eq!(
+        client.try_release_escrow(&escrow_id, &recipient, &token.address),
+        Err(Ok(Error::KycFailed))
+    );
+    assert_eq!(token.balance(&recipient), 0);
+
+    client.add_to_whitelist(&admin, &recipient, &0);
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().status,
+        EscrowStatus::Released
+    );
+    assert!(token.balance(&recipient) > 0);
+}
+
+#[test]
+fn test_kyc_recheck_applies_to_partial_vested_and_split_release() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.configure_kyc(&admin, &Address::generate(&env), &false, &86400);
+    client.add_to_whitelist(&admin, &sender, &0);
+    client.add_to_whitelist(&admin, &recipient, &1500);
+
+    token_admin.mint(&sender, &1000);
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.enable_partial_release(&escrow_id, &sender);
+    client.set_require_kyc_on_release(&admin, &escrow_id, &true);
+
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    assert_eq!(
+        client.try_release_partial(&escrow_id, &recipient, &token.address, &500),
+        Err(Ok(Error::KycFailed))
+    );
+    assert_eq!(
+        client.try_split_settle(&escrow_id, &admin, &token.address, &500, &500),
+        Err(Ok(Error::KycFailed))
+    );
+
+    client.set_vesting_schedule(&escrow_id, &sender, &vec![&env, (1500u64, 1000i128)]);
+    assert_eq!(
+        client.try_release_vested(&escrow_id, &recipient, &token.address),
+        Err(Ok(Error::KycFailed))
+    );
+    assert_eq!(token.balance(&recipient), 0);
+
+    // A full refund pays nothing to the recipient and needs no re-check.
+    client.split_settle(&escrow_id, &admin, &token.address, &0, &1000);
+    assert_eq!(token.balance(&sender), 1000);
+}
+
+#[test]
+fn test_release_ignores_lapsed_kyc_when_recheck_is_off() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    client.configure_kyc(&admin, &Address::generate(&env), &false, &86400);
+    client.add_to_whitelist(&admin, &sender, &0);
+    client.add_to_whitelist(&admin, &recipient, &1500);
+
+    token_admin.mint(&sender, &1000);
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.approve_escrow(&escrow_id, &admin);
+
+    env.ledger().with_mut(|li| li.timestamp = 2000);
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+    assert_eq!(
+        client.get_escrow(&escrow_id).unwrap().status,
+        EscrowStatus::Released
+    );
+}
+
+// ============================================================================
+// Accounting View Tests
+// ============================================================================
+
+#[test]
+fn test_escrow_stats_and_locked_total_track_lifecycle() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &3000);
+
+    let memo = String::from_str(&env, "");
+    let first = client.create_escrow(&sender, &recipient, &1000, &asset, &5000, &memo);
+    let second = client.create_escrow(&sender, &recipient, &500, &asset, &5000, &memo);
+    let ids = Vec::from_array(&env, [first, second, 999]);
+
+    assert_eq!(client.get_escrow_stats().pending, 2);
+    assert_eq!(client.get_locked_total(&ids), 0);
+
+    client.deposit(&first, &sender, &1000, &token.address);
+    client.deposit(&second, &sender, &500, &token.address);
+    assert_eq!(
+        client.get_escrow_stats(),
+        EscrowStats {
+            pending: 0,
+            funded: 2,
+            approved: 0,
+            released: 0,
+            refunded: 0,
+            expired: 0,
+            disputed: 0,
+            cancelled: 0,
+        }
+    );
+    assert_eq!(client.get_locked_total(&ids), 1500);
+
+    client.approve_escrow(&first, &admin);
+    client.release_escrow(&first, &recipient, &token.address);
+
+    let stats = client.get_escrow_stats();
+    assert_eq!(stats.funded, 1);
+    assert_eq!(stats.approved, 0);
+    assert_eq!(stats.released, 1);
+    assert_eq!(client.get_locked_total(&ids), 500);
+    assert_eq!(token.balance(&client.address), 500);
+}
+
+// ============================================================================
+// Escrow Token Binding Tests
+// ============================================================================
+
+#[test]
+fn test_first_deposit_must_use_registered_asset_token() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    let (foreign, foreign_admin) = create_token_contract(&env, &admin);
+    token_admin.mint(&sender, &1000);
+    foreign_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    assert_eq!(client.get_asset_token(&asset), Some(token.address.clone()));
+    assert_eq!(
+        client.try_deposit(&escrow_id, &sender, &1000, &foreign.address),
+        Err(Ok(Error::TokenMismatch))
+    );
+    assert_eq!(client.get_escrow_token(&escrow_id), None);
+
+    let unlisted = Asset {
+        code: String::from_str(&env, "EURC"),
+        issuer: admin.clone(),
+    };
+    assert_eq!(
+        client.try_set_asset_token(&admin, &unlisted, &foreign.address),
+        Err(Ok(Error::InvalidAsset))
+    );
+    client.add_supported_asset(&admin, &unlisted);
+    let unregistered = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &unlisted,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    assert_eq!(
+        client.try_deposit(&unregistered, &sender, &1000, &foreign.address),
+        Err(Ok(Error::InvalidAsset))
+    );
+    assert_eq!(foreign.balance(&sender), 1000);
+
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    assert_eq!(client.get_escrow_token(&escrow_id), Some(token.address));
+}
+
+#[test]
+fn test_escrow_rejects_foreign_token_after_first_deposit() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+    let (foreign, foreign_admin) = create_token_contract(&env, &admin);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+    foreign_admin.mint(&sender, &1000);
+    foreign_admin.mint(&client.address, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    assert_eq!(client.get_escrow_token(&escrow_id), None);
+
+    client.deposit(&escrow_id, &sender, &600, &token.address);
+    assert_eq!(client.get_escrow_token(&escrow_id), Some(token.address.clone()));
+
+    assert_eq!(
+        client.try_deposit(&escrow_id, &sender, &400, &foreign.address),
+        Err(Ok(Error::TokenMismatch))
+    );
+    assert_eq!(foreign.balance(&sender), 1000);
+    assert_eq!(
+        client.try_withdraw_excess_deposit(&escrow_id, &sender, &foreign.address, &100),
+        Err(Ok(Error::TokenMismatch))
+    );
+
+    client.deposit(&escrow_id, &sender, &400, &token.address);
+    client.approve_escrow(&escrow_id, &admin);
+
+    assert_eq!(
+        client.try_release_escrow(&escrow_id, &recipient, &foreign.address),
+        Err(Ok(Error::TokenMismatch))
+    );
+    assert_eq!(
+        client.try_refund_escrow(
+            &escrow_id,
+            &sender,
+            &foreign.address,
+            &RefundReason::SenderRequest
+        ),
+        Err(Ok(Error::TokenMismatch))
+    );
+    assert_eq!(foreign.balance(&client.address), 1000);
+
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+    assert_eq!(token.balance(&recipient), 1000);
+}
+
+// ============================================================================
+// Dispute Settlement Tests
+// ============================================================================
+
+fn setup_disputed_escrow<'a>(
+    env: &Env,
+) -> (
+    PaymentEscrowContractClient<'a>,
+    Address,
+    Address,
+    Address,
+    token::Client<'a>,
+    u64,
+) {
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.approve_escrow(&escrow_id, &admin);
+    client.raise_dispute(
+        &escrow_id,
+        &recipient,
+        &DisputeReason::NonDelivery,
+        &BytesN::from_array(env, &[0u8; 32]),
+    );
+
+    (client, admin, sender, recipient, token, escrow_id)
+}
+
+#[test]
+fn test_dispute_freezes_release_and_refund() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, token, escrow_id) = setup_disputed_escrow(&env);
+
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().status, EscrowStatus::Disputed);
+    assert_eq!(
+        client.try_release_escrow(&escrow_id, &recipient, &token.address),
+        Err(Ok(Error::NotApproved))
+    );
+    assert_eq!(
+        client.try_refund_escrow(
+            &escrow_id,
+            &sender,
+            &token.address,
+            &RefundReason::SenderRequest
+        ),
+        Err(Ok(Error::InvalidStatus))
+    );
+    assert_eq!(token.balance(&client.address), 1000);
+}
+
+#[test]
+fn test_resolve_dispute_to_recipient() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, token, escrow_id) = setup_disputed_escrow(&env);
+
+    assert_eq!(
+        client.try_resolve_dispute(&escrow_id, &sender, &false, &token.address),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    client.resolve_dispute(&escrow_id, &admin, &true, &token.address);
+
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().status, EscrowStatus::Released);
+    assert_eq!(token.balance(&recipient), 1000);
+    assert_eq!(token.balance(&sender), 0);
+    assert_eq!(
+        client.get_dispute(&escrow_id).unwrap().status,
+        DisputeStatus::Resolved
+    );
+    assert_eq!(
+        client.try_resolve_dispute(&escrow_id, &admin, &true, &token.address),
+        Err(Ok(Error::InvalidStatus))
+    );
+}
+
+#[test]
+fn test_resolve_dispute_to_sender() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, token, escrow_id) = setup_disputed_escrow(&env);
+
+    client.resolve_dispute(&escrow_id, &admin, &false, &token.address);
+
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().status, EscrowStatus::Refunded);
+    assert_eq!(token.balance(&sender), 1000);
+    assert_eq!(token.balance(&recipient), 0);
+    assert_eq!(token.balance(&client.address), 0);
+
+    let split = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| {
+            topics.len() > 2
+                && Symbol::try_from_val(&env, &topics.get(2).unwrap()).ok()
+                    == Some(symbol_short!("split"))
+        })
+        .unwrap();
+    assert_eq!(GpayEvent::from_val(&env, &split.2).status, symbol_short!("refunded"));
+}
+
+#[test]
+fn test_sender_cannot_cancel_disputed_escrow() {
+    let env = Env::default();
+    let (client, admin, sender, _recipient, token, escrow_id) = setup_disputed_escrow(&env);
+
+    assert_eq!(
+        client.try_cancel_escrow(
+            &escrow_id,
+            &sender,
+            &token.address,
+            &String::from_str(&env, "changed my mind")
+        ),
+        Err(Ok(Error::InvalidStatus))
+    );
+    assert_eq!(client.get_escrow(&escrow_id).unwrap().status, EscrowStatus::Disputed);
+    assert_eq!(token.balance(&client.address), 1000);
+
+    let officer = Address::generate(&env);
+    client.set_compliance_officer(&admin, &Some(officer.clone()));
+    assert_eq!(
+        client.try_finalize_dispute(&escrow_id, &officer, &ResolutionOutcome::FavorSender),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+// ============================================================================
+// Underfunded Cancellation Tests
+// ============================================================================
+
+#[test]
+fn test_cancel_underfunded_after_grace_period() {
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+    assert_eq!(client.get_underfunded_grace_period(), 7 * 86400);
+    assert_eq!(
+        client.try_set_underfunded_grace_period(&sender, &3600),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.set_underfunded_grace_period(&admin, &3600);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &100_000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &400, &token.address);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000 + 3600);
+    assert_eq!(
+        client.try_cancel_underfunded(&escrow_id, &sender, &token.address),
+        Err(Ok(Error::NotExpired))
+    );
+    assert_eq!(
+        client.try_cancel_underfunded(&escrow_id, &recipient, &token.address),
+        Err(Ok(Error::UnauthorizedRefund))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 1000 + 3601);
+    client.cancel_underfunded(&escrow_id, &sender, &token.address);
+
+    let escrow = client.get_escrow(&escrow_id).unwrap();
+    assert_eq!(escrow.status, EscrowStatus::Refunded);
+    assert_eq!(escrow.refunded_amount, 400);
+    assert_eq!(token.balance(&sender), 1000);
+    assert_eq!(token.balance(&client.address), 0);
+}
+
+#[test]
+fn test_cancel_underfunded_rejects_fully_funded_escrow() {
+    let env = Env::default();
+    let (client, _admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &10_000_000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000 + 8 * 86400);
+    assert_eq!(
+        client.try_cancel_underfunded(&escrow_id, &sender, &token.address),
+        Err(Ok(Error::EscrowNotPending))
+    );
+}
+
+// ============================================================================
+// Invoice Cancellation Linkage Tests
+// ============================================================================
+
+#[test]
+fn test_cancelled_invoice_blocks_linked_escrow_release() {
+    use gpay_remit_contracts::remittance_hub::{Asset as HubAsset, RemittanceHubContractClient};
+    use gpay_remit_contracts::RemittanceHubContract;
+
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    let hub_id = env.register_contract(None, RemittanceHubContract);
+    let hub = RemittanceHubContractClient::new(&env, &hub_id);
+    hub.init_hub(&admin, &admin, &admin, &3600);
+    client.set_aml_hub(&admin, &hub_id);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.approve_escrow(&escrow_id, &admin);
+
+    let invoice_id = hub.generate_invoice(
+        &sender,
+        &recipient,
+        &1000,
+        &HubAsset {
+            code: String::from_str(&env, "USDC"),
+            issuer: admin.clone(),
+        },
+        &2000,
+        &String::from_str(&env, "Linked"),
+        &escrow_id,
+        &String::from_str(&env, ""),
+    );
+
+    // Nothing to act on while the invoice is still live.
+    assert_eq!(
+        client.try_notify_invoice_cancelled(&escrow_id),
+        Err(Ok(Error::ConditionsNotMet))
+    );
+
+    hub.cancel_invoice(&invoice_id, &sender);
+    let events = env.events().all();
+    let refund_due = events.last().unwrap();
+    assert_eq!(refund_due.0, hub_id);
+    assert_eq!(
+        Symbol::try_from_val(&env, &refund_due.1.get(2).unwrap()).unwrap(),
+        Symbol::new(&env, "refund_due")
+    );
+    assert_eq!(
+        GpayEvent::from_val(&env, &refund_due.2).data,
+        EventData::ValueChanged(symbol_short!("refund"), invoice_id as i128, escrow_id as i128)
+    );
+    assert_eq!(
+        hub.get_escrow_invoice(&escrow_id).unwrap().status,
+        gpay_remit_contracts::remittance_hub::InvoiceStatus::Cancelled
+    );
+
+    client.notify_invoice_cancelled(&escrow_id);
+    assert!(client.is_invoice_cancelled(&escrow_id));
+
+    assert_eq!(
+        client.try_release_escrow(&escrow_id, &recipient, &token.address),
+        Err(Ok(Error::InvalidStatus))
+    );
+    client.cancel_escrow(&escrow_id, &sender, &token.address, &String::from_str(&env, ""));
+    assert_eq!(token.balance(&sender), 1000);
+}
+
+#[test]
+fn test_foreign_invoice_cannot_freeze_escrow() {
+    use gpay_remit_contracts::remittance_hub::{Asset as HubAsset, RemittanceHubContractClient};
+    use gpay_remit_contracts::RemittanceHubContract;
+
+    let env = Env::default();
+    let (client, admin, sender, recipient, (token, token_admin), asset) = setup_test(&env);
+
+    let hub_id = env.register_contract(None, RemittanceHubContract);
+    let hub = RemittanceHubContractClient::new(&env, &hub_id);
+    hub.init_hub(&admin, &admin, &admin, &3600);
+    client.set_aml_hub(&admin, &hub_id);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    token_admin.mint(&sender, &1000);
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &5000,
+        &String::from_str(&env, ""),
+    );
+    client.deposit(&escrow_id, &sender, &1000, &token.address);
+    client.approve_escrow(&escrow_id, &admin);
+
+    // An outsider links their own invoice to the victim's escrow id.
+    let attacker = Address::generate(&env);
+    let invoice_id = hub.generate_invoice(
+        &attacker,
+        &recipient,
+        &1000,
+        &HubAsset {
+            code: String::from_str(&env, "USDC"),
+            issuer: admin.clone(),
+        },
+        &2000,
+        &String::from_str(&env, ""),
+        &escrow_id,
+        &String::from_str(&env, ""),
+    );
+    hub.cancel_invoice(&invoice_id, &attacker);
+
+    assert_eq!(
+        client.try_notify_invoice_cancelled(&escrow_id),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert!(!client.is_invoice_cancelled(&escrow_id));
+
+    client.release_escrow(&escrow_id, &recipient, &token.address);
+    assert_eq!(token.balance(&recipient), 1000);
+}
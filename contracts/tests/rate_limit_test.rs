@@ -0,0 +1,106 @@
+// Rate limit reset tests
+//
+// Deposits are rate limited per sender through the rate_limit module.
+// These tests verify that the admin reset hooks let a limited account act again.
+
+use gpay_remit_contracts::payment_escrow::{
+    Asset, Error, PaymentEscrowContract, PaymentEscrowContractClient,
+};
+use gpay_remit_contracts::rate_limit::{FunctionType, RateLimitConfig};
+use soroban_sdk::{testutils::Address as _, token, Address, Env, String};
+
+fn create_token_contract<'a>(
+    env: &Env,
+    admin: &Address,
+) -> (token::Client<'a>, token::StellarAssetClient<'a>) {
+    let contract_address = env.register_stellar_asset_contract_v2(admin.clone());
+    (
+        token::Client::new(env, &contract_address.address()),
+        token::StellarAssetClient::new(env, &contract_address.address()),
+    )
+}
+
+fn setup_test<'a>(
+    env: &Env,
+) -> (
+    PaymentEscrowContractClient<'a>,
+    Address,
+    Address,
+    u64,
+    token::Client<'a>,
+) {
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, PaymentEscrowContract);
+    let client = PaymentEscrowContractClient::new(env, &contract_id);
+
+    let admin = Address::generate(env);
+    let sender = Address::generate(env);
+    let recipient = Address::generate(env);
+
+    client.init_escrow(&admin);
+
+    let (token, token_admin) = create_token_contract(env, &admin);
+    let asset = Asset {
+        code: String::from_str(env, "USDC"),
+        issuer: admin.clone(),
+    };
+    client.add_supported_asset(&admin, &asset);
+    client.set_asset_token(&admin, &asset, &token.address);
+    token_admin.mint(&sender, &1000);
+
+    let escrow_id = client.create_escrow(
+        &sender,
+        &recipient,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(env, ""),
+    );
+
+    client.set_rate_limit_config(
+        &admin,
+        &FunctionType::Deposit,
+        &RateLimitConfig {
+            enabled: true,
+            max_count: 1,
+            interval: 3600,
+        },
+    );
+
+    (client, admin, sender, escrow_id, token)
+}
+
+#[test]
+fn test_reset_rate_limit_unblocks_account() {
+    let env = Env::default();
+    let (client, admin, sender, escrow_id, token) = setup_test(&env);
+
+    client.deposit(&escrow_id, &sender, &100, &token.address);
+    let result = client.try_deposit(&escrow_id, &sender, &100, &token.address);
+    assert_eq!(result, Err(Ok(Error::RateLimitExceeded)));
+
+    client.reset_rate_limit(&admin, &sender);
+    client.deposit(&escrow_id, &sender, &100, &token.address);
+}
+
+#[test]
+fn test_clear_all_rate_limits_unblocks_account() {
+    let env = Env::default();
+    let (client, admin, sender, escrow_id, token) = setup_test(&env);
+
+    client.deposit(&escrow_id, &sender, &100, &token.address);
+    let result = client.try_deposit(&escrow_id, &sender, &100, &token.address);
+    assert_eq!(result, Err(Ok(Error::RateLimitExceeded)));
+
+    client.clear_all_rate_limits(&admin);
+    client.deposit(&escrow_id, &sender, &100, &token.address);
+}
+
+#[test]
+fn test_reset_rate_limit_non_admin() {
+    let env = Env::default();
+    let (client, _admin, sender, _escrow_id, _token) = setup_test(&env);
+
+    let result = client.try_reset_rate_limit(&sender, &sender);
+    assert_eq!(result, Err(Ok(Error::UnauthorizedCaller)));
+}
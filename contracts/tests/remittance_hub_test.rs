@@ -1,9 +1,12 @@
+use gpay_remit_contracts::errors::ErrorDomain;
+use gpay_remit_contracts::events::{EventData, GpayEvent};
 use gpay_remit_contracts::remittance_hub::{
     InvoiceStatus, RemittanceError, RemittanceHubContract, RemittanceHubContractClient,
 };
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    Address, Env, String, Symbol,
+    symbol_short,
+    testutils::{Address as _, Events as _, Ledger},
+    vec, Address, Env, FromVal, String, Symbol,
 };
 
 fn setup_test<'a>(env: &Env) -> (RemittanceHubContractClient<'a>, Address, Address, Address) {
@@ -360,7 +363,7 @@ fn test_send_remittance_success() {
         &soroban_sdk::Symbol::new(&env, "USD"),
     );
 
-    assert_eq!(remittance_id, 0);
+    assert_eq!(remittance_id, 1);
 
     let remittance = client.get_remittance(&remittance_id);
     assert!(remittance.is_some());
@@ -431,3 +434,872 @@ fn test_aml_screening() {
     let config = client.get_aml_config();
     assert!(config.is_some());
 }
+
+#[test]
+fn test_batch_set_cached_rates() {
+    let env = Env::default();
+    let (client, admin, _user1, _user2) = setup_test(&env);
+
+    let usd = String::from_str(&env, "USD");
+    let eur = String::from_str(&env, "EUR");
+    let ngn = String::from_str(&env, "NGN");
+    let kes = String::from_str(&env, "KES");
+
+    let rates = vec![
+        &env,
+        (usd.clone(), eur.clone(), 92, 100),
+        (usd.clone(), ngn.clone(), 1500, 1),
+        (usd.clone(), kes.clone(), 130, 1),
+    ];
+    client.batch_set_cached_rates(&admin, &rates);
+
+    // A single summary event, without the per-pair `rate_upd` events.
+    let topics: std::vec::Vec<Symbol> = env
+        .events()
+        .all()
+        .iter()
+        .filter(|(_, topics, _)| topics.len() > 2)
+        .map(|(_, topics, _)| Symbol::from_val(&env, &topics.get(2).unwrap()))
+        .collect();
+    assert!(!topics.contains(&symbol_short!("rate_upd")));
+    assert_eq!(
+        topics
+            .iter()
+            .filter(|topic| **topic == symbol_short!("rate_bat"))
+            .count(),
+        1
+    );
+
+    assert_eq!(client.get_cached_rate(&usd, &eur).unwrap().rate, 92);
+    assert_eq!(client.get_cached_rate(&usd, &ngn).unwrap().rate, 1500);
+    assert_eq!(client.get_cached_rate(&usd, &kes).unwrap().rate, 130);
+}
+
+#[test]
+fn test_rate_setters_without_admin_are_unauthorized() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceHubContract);
+    let client = RemittanceHubContractClient::new(&env, &contract_id);
+
+    let caller = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let usd = String::from_str(&env, "USD");
+    let eur = String::from_str(&env, "EUR");
+
+    assert_eq!(
+        client.try_set_cached_rate(&caller, &usd, &eur, &92, &100),
+        Err(Ok(RemittanceError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_batch_set_cached_rates(&caller, &vec![&env, (usd, eur, 92, 100)]),
+        Err(Ok(RemittanceError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_oracle_weight(&caller, &oracle, &Some(2)),
+        Err(Ok(RemittanceError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_oracle_decimals(&caller, &oracle, &Some(7)),
+        Err(Ok(RemittanceError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_batch_set_cached_rates_rejects_invalid_rate() {
+    let env = Env::default();
+    let (client, admin, _user1, _user2) = setup_test(&env);
+
+    let usd = String::from_str(&env, "USD");
+    let eur = String::from_str(&env, "EUR");
+    let rates = vec![&env, (usd.clone(), eur.clone(), 0, 100)];
+
+    let result = client.try_batch_set_cached_rates(&admin, &rates);
+    assert_eq!(result, Err(Ok(RemittanceError::InvalidRate)));
+    assert!(client.get_cached_rate(&usd, &eur).is_none());
+}
+
+#[test]
+fn test_set_aml_threshold_event_carries_old_and_new_values() {
+    let env = Env::default();
+    let (client, admin, _user1, _user2) = setup_test(&env);
+
+    let oracle = Address::generate(&env);
+    client.configure_aml(&admin, &oracle, &50);
+    client.set_aml_threshold(&admin, &75);
+
+    let (_, topics, data) = env.events().all().last().unwrap();
+    assert_eq!(
+        Symbol::from_val(&env, &topics.get(2).unwrap()),
+        symbol_short!("aml_thr")
+    );
+
+    let event = GpayEvent::from_val(&env, &data);
+    assert_eq!(event.actor, admin);
+    assert_eq!(
+        event.data,
+        EventData::ValueChanged(symbol_short!("aml_thr"), 50, 75)
+    );
+}
+
+#[test]
+fn test_send_remittance_respects_max_amount() {
+    let env = Env::default();
+    let (client, admin, user1, user2) = setup_test(&env);
+
+    assert_eq!(client.get_max_remittance_amount(), None);
+    client.set_max_remittance_amount(&admin, &Some(5000));
+
+    let result = client.try_send_remittance(&user1, &user2, &5001, &symbol_short!("USD"));
+    assert_eq!(result, Err(Ok(RemittanceError::AmountExceedsCap)));
+
+    let remittance_id = client.send_remittance(&user1, &user2, &5000, &symbol_short!("USD"));
+    assert_eq!(client.get_remittance(&remittance_id).unwrap().amount, 5000);
+
+    client.set_max_remittance_amount(&admin, &None);
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    client.send_remittance(&user1, &user2, &50_000, &symbol_short!("USD"));
+}
+
+/// Finds the `action` event emitted by the most recent contract call.
+fn invoice_event(env: &Env, action: &str) -> (u64, GpayEvent) {
+    let action = Symbol::new(env, action);
+    let (_, topics, data) = env
+        .events()
+        .all()
+        .iter()
+        .find(|(_, topics, _)| Symbol::from_val(env, &topics.get(2).unwrap()) == action)
+        .expect("invoice event not emitted");
+    (
+        u64::from_val(env, &topics.get(3).unwrap()),
+        GpayEvent::from_val(env, &data),
+    )
+}
+
+#[test]
+fn test_rate_update_flags_large_deviation() {
+    let env = Env::default();
+    let (client, admin, _user1, _user2) = setup_test(&env);
+
+    let usd = String::from_str(&env, "USD");
+    let ngn = String::from_str(&env, "NGN");
+    let rate_update = |env: &Env| -> GpayEvent {
+        let (_, _, data) = env
+            .events()
+            .all()
+            .iter()
+            .filter(|(_, topics, _)| {
+                Symbol::from_val(env, &topics.get(2).unwrap()) == symbol_short!("rate_upd")
+            })
+            .last()
+            .expect("rate update event not emitted");
+        GpayEvent::from_val(env, &data)
+    };
+
+    client.set_cached_rate(&admin, &usd, &ngn, &1000, &1);
+    assert_eq!(rate_update(&env).status, symbol_short!("na"));
+
+    // 5% stays under the default 10% threshold.
+    client.set_cached_rate(&admin, &usd, &ngn, &1050, &1);
+    let event = rate_update(&env);
+    assert_eq!(event.amount, 500);
+    assert_eq!(event.status, symbol_short!("na"));
+
+    // Same rate expressed with a different denominator is not a move.
+    client.set_cached_rate(&admin, &usd, &ngn, &105000, &100);
+    assert_eq!(rate_update(&env).amount, 0);
+
+    client.set_cached_rate(&admin, &usd, &ngn, &1575, &1);
+    let event = rate_update(&env);
+    assert_eq!(event.amount, 5000);
+    assert_eq!(event.status, symbol_short!("deviated"));
+    assert_eq!(
+        event.data,
+        EventData::ValueChanged(symbol_short!("rate_upd"), 105000, 1575)
+    );
+
+    client.set_rate_deviation_threshold(&admin, &6000);
+    client.set_cached_rate(&admin, &usd, &ngn, &1000, &1);
+    assert_eq!(rate_update(&env).status, symbol_short!("na"));
+}
+
+#[test]
+fn test_invoice_amount_changes_are_logged_in_order() {
+    let env = Env::default();
+    let (client, admin, user1, user2) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let asset = gpay_remit_contracts::remittance_hub::Asset {
+        code: String::from_str(&env, "USDC"),
+        issuer: admin.clone(),
+    };
+    let invoice_id = client.generate_invoice(
+        &user1,
+        &user2,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, "Invoice"),
+        &0,
+        &String::from_str(&env, ""),
+    );
+    assert!(client.get_invoice_amendments(&invoice_id).is_empty());
+
+    client.update_invoice_amount(&invoice_id, &user1, &1500);
+    env.ledger().with_mut(|li| li.timestamp = 1100);
+    client.update_invoice_amount(&invoice_id, &user1, &1200);
+
+    let amendments = client.get_invoice_amendments(&invoice_id);
+    assert_eq!(amendments.len(), 2);
+    let first = amendments.get(0).unwrap();
+    assert_eq!(
+        (
+            first.field,
+            first.old_value,
+            first.new_value,
+            first.timestamp
+        ),
+        (symbol_short!("amount"), 1000, 1500, 1000)
+    );
+    let second = amendments.get(1).unwrap();
+    assert_eq!(
+        (
+            second.old_value,
+            second.new_value,
+            second.actor,
+            second.timestamp
+        ),
+        (1500, 1200, user1.clone(), 1100)
+    );
+}
+
+#[test]
+fn test_invoice_lifecycle_emits_standard_events() {
+    let env = Env::default();
+    let (client, admin, user1, user2) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let asset = gpay_remit_contracts::remittance_hub::Asset {
+        code: String::from_str(&env, "USDC"),
+        issuer: admin.clone(),
+    };
+    let description = String::from_str(&env, "Invoice");
+    let memo = String::from_str(&env, "");
+
+    let invoice_id = client.generate_invoice(
+        &user1,
+        &user2,
+        &1000,
+        &asset,
+        &2000,
+        &description,
+        &0,
+        &memo,
+    );
+    let (id, event) = invoice_event(&env, "inv_created");
+    assert_eq!(
+        (id, event.actor, event.amount),
+        (invoice_id, user1.clone(), 1025)
+    );
+
+    client.update_invoice_amount(&invoice_id, &user1, &2000);
+    let (id, event) = invoice_event(&env, "inv_updated");
+    assert_eq!((id, event.amount), (invoice_id, 2050));
+
+    env.ledger().with_mut(|li| li.timestamp = 3000);
+    client.mark_invoice_overdue(&invoice_id);
+    let (id, event) = invoice_event(&env, "inv_overdue");
+    assert_eq!((id, event.amount), (invoice_id, 2050));
+
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    client.mark_invoice_paid(&invoice_id, &user2);
+    let (id, event) = invoice_event(&env, "inv_paid");
+    assert_eq!(
+        (id, event.actor, event.amount),
+        (invoice_id, user2.clone(), 2050)
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    let second =
+        client.generate_invoice(&user1, &user2, &500, &asset, &5000, &description, &0, &memo);
+    client.cancel_invoice(&second, &user1);
+    let (id, event) = invoice_event(&env, "inv_cancelled");
+    assert_eq!((id, event.actor, event.amount), (second, user1, 512));
+}
+
+#[test]
+fn test_settle_payment_requires_matching_invoice_and_remittance() {
+    let env = Env::default();
+    let (client, admin, user1, user2) = setup_test(&env);
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1000;
+        li.sequence_number = 1;
+    });
+
+    let asset = gpay_remit_contracts::remittance_hub::Asset {
+        code: String::from_str(&env, "USD"),
+        issuer: admin.clone(),
+    };
+    let invoice_id = client.generate_invoice(
+        &user1,
+        &user2,
+        &1000,
+        &asset,
+        &2000,
+        &String::from_str(&env, "Invoice"),
+        &0,
+        &String::from_str(&env, ""),
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    let euros = client.send_remittance(&user1, &user2, &1000, &symbol_short!("EUR"));
+    assert_eq!(
+        client.try_settle_payment(&user1, &invoice_id, &euros),
+        Err(Ok(RemittanceError::PaymentMismatch))
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    let user3 = Address::generate(&env);
+    let other = client.send_remittance(&user1, &user3, &1000, &symbol_short!("USD"));
+    assert_eq!(
+        client.try_settle_payment(&user1, &invoice_id, &other),
+        Err(Ok(RemittanceError::PaymentMismatch))
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    let short = client.send_remittance(&user1, &user2, &999, &symbol_short!("USD"));
+    assert_eq!(
+        client.try_settle_payment(&user1, &invoice_id, &short),
+        Err(Ok(RemittanceError::PaymentMismatch))
+    );
+    assert_eq!(
+        client.get_invoice(&invoice_id).unwrap().status,
+        InvoiceStatus::Unpaid
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    let matching = client.send_remittance(&user1, &user2, &1000, &symbol_short!("USD"));
+    assert_eq!(
+        client.try_settle_payment(&user3, &invoice_id, &matching),
+        Err(Ok(RemittanceError::Unauthorized))
+    );
+
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    client.settle_payment(&user1, &invoice_id, &matching);
+    assert_eq!(
+        client.get_invoice(&invoice_id).unwrap().status,
+        InvoiceStatus::Paid
+    );
+    assert_eq!(
+        client.get_remittance(&matching).unwrap().status,
+        symbol_short!("complete")
+    );
+    assert_eq!(
+        client.get_remittance(&short).unwrap().status,
+        symbol_short!("pending")
+    );
+}
+
+// ============================================================================
+// SUPPORTED PAIR TESTS
+// ============================================================================
+
+#[test]
+fn test_get_supported_pairs_lists_registered_pairs() {
+    let env = Env::default();
+    let (client, admin, user1, _user2) = setup_test(&env);
+
+    let usd = String::from_str(&env, "USD");
+    let ngn = String::from_str(&env, "NGN");
+    let kes = String::from_str(&env, "KES");
+
+    assert!(client.get_supported_pairs(&0, &10).is_empty());
+
+    client.add_supported_pair(&admin, &usd, &ngn);
+    client.add_supported_pair(&admin, &usd, &kes);
+    client.add_supported_pair(&admin, &ngn, &kes);
+
+    assert_eq!(
+        client.try_add_supported_pair(&admin, &usd, &ngn),
+        Err(Ok(RemittanceError::PairAlreadySupported))
+    );
+    assert_eq!(
+        client.try_add_supported_pair(&user1, &kes, &usd),
+        Err(Ok(RemittanceError::Unauthorized))
+    );
+
+    assert_eq!(
+        client.get_supported_pairs(&0, &10),
+        vec![
+            &env,
+            (usd.clone(), ngn.clone()),
+            (usd.clone(), kes.clone()),
+            (ngn.clone(), kes.clone())
+        ]
+    );
+    assert_eq!(
+        client.get_supported_pairs(&1, &1),
+        vec![&env, (usd.clone(), kes.clone())]
+    );
+    assert!(client.get_supported_pairs(&3, &10).is_empty());
+}
+
+#[test]
+fn test_find_conversion_path_direct_and_two_hop() {
+    let env = Env::default();
+    let (client, admin, _user1, _user2) = setup_test(&env);
+
+    let usd = String::from_str(&env, "USD");
+    let ngn = String::from_str(&env, "NGN");
+    let kes = String::from_str(&env, "KES");
+    let eur = String::from_str(&env, "EUR");
+
+    client.add_supported_pair(&admin, &usd, &ngn);
+    client.add_supported_pair(&admin, &kes, &usd);
+    client.add_supported_pair(&admin, &eur, &ngn);
+
+    assert_eq!(
+        client.find_conversion_path(&usd, &ngn),
+        vec![&env, usd.clone(), ngn.clone()]
+    );
+    assert_eq!(
+        client.find_conversion_path(&kes, &ngn),
+        vec![&env, kes.clone(), usd.clone(), ngn.clone()]
+    );
+    assert!(client.find_conversion_path(&ngn, &kes).is_empty());
+}
+
+#[test]
+fn test_describe_error_covers_each_error_enum() {
+    let env = Env::default();
+    let (client, _admin, _user1, _user2) = setup_test(&env);
+
+    let cases = [
+        (
+            ErrorDomain::Escrow,
+            34,
+            "Multi-party quorum has not been met.",
+        ),
+        (
+            ErrorDomain::Remittance,
+            26,
+            "Invoice and remittance do not describe the same payment.",
+        ),
+        (ErrorDomain::Oracle, 5, "Cached oracle rate is stale."),
+        (
+            ErrorDomain::Aml,
+            3,
+            "Screening result exceeded configured risk threshold.",
+        ),
+        (ErrorDomain::Kyc, 7, "Proof has expired."),
+        (ErrorDomain::Kyc, 99, "Unknown error code."),
+    ];
+    for (domain, code, expected) in cases {
+        assert_eq!(
+            client.describe_error(&domain, &code),
+            String::from_str(&env, expected)
+        );
+    }
+}
+
+// ============================================================================
+// Settlement Batch Tests
+// ============================================================================
+
+#[test]
+fn test_flush_batch_settles_queued_remittances() {
+    let env = Env::default();
+    let (client, admin, user1, user2) = setup_test(&env);
+    let keeper = Address::generate(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    let usd = Symbol::new(&env, "USD");
+    let first = client.send_remittance(&user1, &user2, &1000, &usd);
+    env.ledger().with_mut(|li| li.sequence_number += 1);
+    let second = client.send_remittance(&user2, &user1, &500, &usd);
+
+    client.set_settlement_window(&admin, &600);
+    client.queue_for_batch(&first);
+    client.queue_for_batch(&second);
+    assert_eq!(
+        client.try_queue_for_batch(&first),
+        Err(Ok(RemittanceError::InvalidStatus))
+    );
+
+    assert_eq!(
+        client.try_flush_batch(&keeper),
+        Err(Ok(RemittanceError::CooldownActive))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 1600);
+    let settled = client.flush_batch(&keeper);
+    assert_eq!(settled, vec![&env, first, second]);
+    assert_eq!(
+        client.get_remittance(&first).unwrap().status,
+        symbol_short!("complete")
+    );
+    assert_eq!(
+        client.get_remittance(&second).unwrap().status,
+        symbol_short!("complete")
+    );
+    assert!(client.get_settlement_queue().is_empty());
+}
+
+// ============================================================================
+// Remittance Fee Tests
+// ============================================================================
+
+#[test]
+fn test_configured_remittance_fee_applies_to_invoices() {
+    let env = Env::default();
+    let (client, admin, user1, user2) = setup_test(&env);
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+
+    assert_eq!(client.get_remittance_fee(), 250);
+    assert_eq!(
+        client.try_set_remittance_fee(&user1, &500),
+        Err(Ok(RemittanceError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_remittance_fee(&admin, &10001),
+        Err(Ok(RemittanceError::InvalidAmount))
+    );
+    client.set_remittance_fee(&admin, &500);
+    assert_eq!(client.get_remittance_fee(), 500);
+
+    let invoice_id = client.generate_invoice(
+        &user1,
+        &user2,
+        &1000,
+        &gpay_remit_contracts::remittance_hub::Asset {
+            code: String::from_str(&env, "USD"),
+            issuer: admin.clone(),
+        },
+        &2000,
+        &String::from_str(&env, "Consulting"),
+        &0,
+        &String::from_str(&env, ""),
+    );
+
+    let invoice = client.get_invoice(&invoice_id).unwrap();
+    assert_eq!(invoice.fees, 50);
+    assert_eq!(invoice.total_due, 1050);
+}
+
+// ============================================================================
+// SEND COOLDOWN TESTS
+// ============================================================================
+
+#[test]
+fn test_send_remittance_cooldown() {
+    let env = Env::default();
+    let (client, admin, user1, user2) = setup_test(&env);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1000;
+        li.sequence_number = 100;
+    });
+
+    assert_eq!(
+        client.try_set_send_cooldown(&user1, &10),
+        Err(Ok(RemittanceError::Unauthorized))
+    );
+    client.set_send_cooldown(&admin, &10);
+    assert_eq!(client.get_send_cooldown(), 10);
+
+    let usd = Symbol::new(&env, "USD");
+    client.send_remittance(&user1, &user2, &1000, &usd);
+    assert_eq!(client.get_last_send_ledger(&user1), Some(100));
+
+    // Same ledger: throttled
+    assert_eq!(
+        client.try_send_remittance(&user1, &user2, &1000, &usd),
+        Err(Ok(RemittanceError::RateLimitExceeded))
+    );
+
+    // Still inside the window
+    env.ledger().with_mut(|li| li.sequence_number = 109);
+    assert_eq!(
+        client.try_send_remittance(&user1, &user2, &1000, &usd),
+        Err(Ok(RemittanceError::RateLimitExceeded))
+    );
+
+    // Other senders are tracked separately
+    client.send_remittance(&user2, &user1, &1000, &usd);
+
+    env.ledger().with_mut(|li| li.sequence_number = 110);
+    let remittance_id = client.send_remittance(&user1, &user2, &1000, &usd);
+    assert_eq!(remittance_id, 110);
+    assert_eq!(client.get_last_send_ledger(&user1), Some(110));
+}
+
+// ============================================================================
+// AML VELOCITY TESTS
+// ============================================================================
+
+#[test]
+fn test_velocity_threshold_trips_on_structured_transfers() {
+    let env = Env::default();
+    let (client, admin, user1, user2) = setup_test(&env);
+    let usd = Symbol::new(&env, "USD");
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 1000;
+        li.sequence_number = 1;
+    });
+
+    assert_eq!(
+        client.try_set_velocity_threshold(&user1, &10_000),
+        Err(Ok(RemittanceError::Unauthorized))
+    );
+    client.set_velocity_threshold(&admin, &10_000);
+    assert_eq!(client.get_velocity_threshold(), 10_000);
+
+    // Three transfers of 4,000 each stay under any per-transfer limit, but the
+    // third pushes the day's total to 12,000.
+    let mut ids = [0u64; 3];
+    for (i, id) in ids.iter_mut().enumerate() {
+        env.ledger().with_mut(|li| {
+            li.sequence_number = 1 + i as u32;
+            li.timestamp = 1000 + 3600 * i as u64;
+        });
+        *id = client.send_remittance(&user1, &user2, &4000, &usd);
+    }
+
+    assert_eq!(
+        client.get_remittance(&ids[0]).unwrap().status,
+        symbol_short!("pending")
+    );
+    assert_eq!(
+        client.get_remittance(&ids[1]).unwrap().status,
+        symbol_short!("pending")
+    );
+    assert_eq!(
+        client.get_remittance(&ids[2]).unwrap().status,
+        symbol_short!("review")
+    );
+    assert!(client.get_aml_flag(&ids[2]).is_some());
+    assert_eq!(client.get_sender_volume(&user1), Some((1000, 12_000)));
+
+    // Another sender has an independent window
+    env.ledger().with_mut(|li| li.sequence_number = 10);
+    let other = client.send_remittance(&user2, &user1, &4000, &usd);
+    assert_eq!(
+        client.get_remittance(&other).unwrap().status,
+        symbol_short!("pending")
+    );
+
+    // Once the 24h window has passed the total starts over
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 11;
+        li.timestamp = 1000 + 86401;
+    });
+    let next = client.send_remittance(&user1, &user2, &4000, &usd);
+    assert_eq!(
+        client.get_remittance(&next).unwrap().status,
+        symbol_short!("pending")
+    );
+    assert_eq!(client.get_sender_volume(&user1), Some((87401, 4000)));
+}
+
+// ============================================================================
+// RECURRING INVOICE TESTS
+// ============================================================================
+
+#[test]
+fn test_recurring_invoice_mints_on_schedule() {
+    let env = Env::default();
+    let (client, admin, user1, user2) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let asset = gpay_remit_contracts::remittance_hub::Asset {
+        code: String::from_str(&env, "USDC"),
+        issuer: admin.clone(),
+    };
+    assert_eq!(
+        client.try_create_recurring_invoice(&user1, &user2, &500, &asset, &0, &2),
+        Err(Ok(RemittanceError::InvalidAmount))
+    );
+    let template_id = client.create_recurring_invoice(&user1, &user2, &500, &asset, &3600, &2);
+
+    assert_eq!(
+        client.try_mint_next_invoice(&template_id),
+        Err(Ok(RemittanceError::NotYetDue))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 1000 + 3600);
+    let first = client.mint_next_invoice(&template_id);
+    assert_eq!(
+        client.try_mint_next_invoice(&template_id),
+        Err(Ok(RemittanceError::NotYetDue))
+    );
+
+    // A late call still issues on the original schedule.
+    env.ledger().with_mut(|li| li.timestamp = 1000 + 7300);
+    let second = client.mint_next_invoice(&template_id);
+
+    let first_invoice = client.get_invoice(&first).unwrap();
+    let second_invoice = client.get_invoice(&second).unwrap();
+    assert_eq!(first_invoice.amount, 500);
+    assert_eq!(first_invoice.recipient, user2);
+    assert_eq!(first_invoice.due_date, 1000 + 7200);
+    assert_eq!(second_invoice.due_date, 1000 + 10800);
+    assert_eq!(second_invoice.status, InvoiceStatus::Unpaid);
+
+    let template = client.get_recurring_invoice(&template_id).unwrap();
+    assert_eq!(template.remaining_occurrences, 0);
+    assert_eq!(template.invoice_ids, vec![&env, first, second]);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000 + 20000);
+    assert_eq!(
+        client.try_mint_next_invoice(&template_id),
+        Err(Ok(RemittanceError::InvalidStatus))
+    );
+}
+
+// ============================================================================
+// PARTIAL INVOICE PAYMENT TESTS
+// ============================================================================
+
+#[test]
+fn test_partial_payments_settle_invoice() {
+    let env = Env::default();
+    let (client, admin, user1, user2) = setup_test(&env);
+
+    env.ledger().with_mut(|li| li.timestamp = 1000);
+    let invoice_id = client.generate_invoice(
+        &user1,
+        &user2,
+        &1000,
+        &gpay_remit_contracts::remittance_hub::Asset {
+            code: String::from_str(&env, "USDC"),
+            issuer: admin.clone(),
+        },
+        &2000,
+        &String::from_str(&env, "Rent"),
+        &0,
+        &String::from_str(&env, ""),
+    );
+    // 250 bps remittance fee on top of the 1000 principal.
+    assert_eq!(client.get_invoice(&invoice_id).unwrap().total_due, 1025);
+
+    env.ledger().with_mut(|li| li.timestamp = 1500);
+    client.pay_invoice_partial(&invoice_id, &user1, &600);
+    let invoice = client.get_invoice(&invoice_id).unwrap();
+    assert_eq!(client.get_invoice_paid_amount(&invoice_id), 600);
+    assert_eq!(invoice.status, InvoiceStatus::Unpaid);
+    assert_eq!(invoice.paid_at, 0);
+
+    assert_eq!(
+        client.try_pay_invoice_partial(&invoice_id, &user1, &426),
+        Err(Ok(RemittanceError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_pay_invoice_partial(&invoice_id, &admin, &100),
+        Err(Ok(RemittanceError::Unauthorized))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 1800);
+    client.pay_invoice_partial(&invoice_id, &user1, &425);
+    let invoice = client.get_invoice(&invoice_id).unwrap();
+    assert_eq!(client.get_invoice_paid_amount(&invoice_id), 1025);
+    assert_eq!(invoice.status, InvoiceStatus::Paid);
+    assert_eq!(invoice.paid_at, 1800);
+
+    assert_eq!(
+        client.try_pay_invoice_partial(&invoice_id, &user1, &1),
+        Err(Ok(RemittanceError::InvalidInvoiceStatus))
+    );
+}
+
+// ============================================================================
+// REMITTANCE CANCELLATION TESTS
+// ============================================================================
+
+#[test]
+fn test_cancel_pending_remittance() {
+    let env = Env::default();
+    let (client, _admin, user1, user2) = setup_test(&env);
+
+    let remittance_id = client.send_remittance(&user1, &user2, &1000, &symbol_short!("USD"));
+
+    assert_eq!(
+        client.try_cancel_remittance(&remittance_id, &user2),
+        Err(Ok(RemittanceError::Unauthorized))
+    );
+
+    client.cancel_remittance(&remittance_id, &user1);
+    let events = env.events().all();
+    let cancelled = events.last().unwrap();
+    assert_eq!(
+        Symbol::from_val(&env, &cancelled.1.get(2).unwrap()),
+        symbol_short!("rem_canc")
+    );
+    assert_eq!(GpayEvent::from_val(&env, &cancelled.2).amount, 1000);
+
+    assert_eq!(
+        client.get_remittance(&remittance_id).unwrap().status,
+        symbol_short!("cancelled")
+    );
+    assert_eq!(
+        client.try_complete_remittance(&remittance_id, &user1),
+        Err(Ok(RemittanceError::InvalidStatus))
+    );
+    assert_eq!(
+        client.try_cancel_remittance(&remittance_id, &user1),
+        Err(Ok(RemittanceError::InvalidStatus))
+    );
+}
+
+#[test]
+fn test_cancel_flagged_remittance_then_clear_keeps_it_cancelled() {
+    let env = Env::default();
+    let (client, admin, user1, user2) = setup_test(&env);
+
+    // An unreachable AML oracle sends every remittance to review.
+    client.configure_aml(&admin, &Address::generate(&env), &50);
+    let remittance_id = client.send_remittance(&user1, &user2, &1000, &symbol_short!("USD"));
+    assert_eq!(
+        client.get_remittance(&remittance_id).unwrap().status,
+        symbol_short!("review")
+    );
+    assert!(client.is_sender_flagged(&user1));
+
+    client.cancel_remittance(&remittance_id, &user1);
+    assert!(!client.is_sender_flagged(&user1));
+
+    assert_eq!(
+        client.try_clear_aml_flag(&admin, &remittance_id),
+        Err(Ok(RemittanceError::InvalidStatus))
+    );
+    assert_eq!(
+        client.try_reject_remittance(&admin, &remittance_id),
+        Err(Ok(RemittanceError::InvalidStatus))
+    );
+    assert_eq!(
+        client.get_remittance(&remittance_id).unwrap().status,
+        symbol_short!("cancelled")
+    );
+    assert_eq!(
+        client.try_complete_remittance(&remittance_id, &user1),
+        Err(Ok(RemittanceError::InvalidStatus))
+    );
+}
+
+#[test]
+fn test_cancel_completed_remittance_rejected() {
+    let env = Env::default();
+    let (client, _admin, user1, user2) = setup_test(&env);
+
+    let remittance_id = client.send_remittance(&user1, &user2, &1000, &symbol_short!("USD"));
+    client.complete_remittance(&remittance_id, &user1);
+
+    assert_eq!(
+        client.try_cancel_remittance(&remittance_id, &user1),
+        Err(Ok(RemittanceError::InvalidStatus))
+    );
+    assert_eq!(
+        client.get_remittance(&remittance_id).unwrap().status,
+        symbol_short!("complete")
+    );
+}
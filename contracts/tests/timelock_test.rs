@@ -44,6 +44,7 @@ fn setup_test<'a>(
     };
 
     client.add_supported_asset(&admin, &asset);
+    client.set_asset_token(&admin, &asset, &token.0.address);
 
     (client, admin, sender, recipient, token, asset)
 }